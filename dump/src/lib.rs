@@ -104,6 +104,10 @@ pub enum KindDump {
     DocumentDeletionByFilter {
         filter: serde_json::Value,
     },
+    DocumentEditionByFilter {
+        filter: serde_json::Value,
+        edit: serde_json::Map<String, serde_json::Value>,
+    },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
         is_deletion: bool,
@@ -172,6 +176,9 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 KindDump::DocumentDeletionByFilter { filter: filter_expr }
             }
+            KindWithContent::DocumentEditionByFilter { filter_expr, edit, .. } => {
+                KindDump::DocumentEditionByFilter { filter: filter_expr, edit }
+            }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -273,8 +280,19 @@ pub(crate) mod test {
                 sort_facet_values_by: Setting::Set(
                     btreemap! { S("age") => FacetValuesSort::Count },
                 ),
+                hierarchical_facets: Setting::NotSet,
             }),
             pagination: Setting::NotSet,
+            attribute_weights: Setting::NotSet,
+            vector_spaces: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_locales: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            synonym_rules_patch: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
+            code_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            suggestions: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -297,6 +315,7 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        rejected_documents: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -320,6 +339,7 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        rejected_documents: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
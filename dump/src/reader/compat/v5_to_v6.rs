@@ -132,6 +132,7 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                rejected_documents: Vec::new(),
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -358,6 +359,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    disable_on_numbers: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -366,6 +368,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
                     sort_facet_values_by: v6::Setting::NotSet,
+                    hierarchical_facets: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -377,6 +380,16 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
+            attribute_weights: v6::Setting::NotSet,
+            vector_spaces: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            stemming_locales: v6::Setting::NotSet,
+            synonym_rules: v6::Setting::NotSet,
+            synonym_rules_patch: v6::Setting::NotSet,
+            phonetic_attributes: v6::Setting::NotSet,
+            code_attributes: v6::Setting::NotSet,
+            query_rules: v6::Setting::NotSet,
+            suggestions: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -61,6 +61,9 @@ pub enum ErrorKind<'a> {
     GeoBoundingBox,
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
+    ReservedVector(&'a str),
+    VectorSimilarity,
+    MisusedVectorSimilarity,
     InvalidPrimary,
     InvalidEscapedNumber,
     ExpectedEof,
@@ -169,6 +172,15 @@ impl<'a> Display for Error<'a> {
             ErrorKind::MisusedGeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter is an operation and can't be used as a value.")?
             }
+            ErrorKind::VectorSimilarity => {
+                writeln!(f, "The `_vectorSimilarity` filter expects a vector space, a vector and a comparison with a similarity: `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity`.")?
+            }
+            ErrorKind::ReservedVector(name) => {
+                writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity` built-in rule to filter on vectors.", name.escape_debug())?
+            }
+            ErrorKind::MisusedVectorSimilarity => {
+                writeln!(f, "The `_vectorSimilarity` filter is an operation and can't be used as a value.")?
+            }
             ErrorKind::ReservedKeyword(word) => {
                 writeln!(f, "`{word}` is a reserved keyword and thus cannot be used as a field name unless it is put inside quotes. Use \"{word}\" or \'{word}\' instead.")?
             }
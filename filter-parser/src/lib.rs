@@ -6,7 +6,7 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | vectorSimilarity | in | condition | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
@@ -19,11 +19,13 @@
 //! word           = (alphanumeric | _ | - | .)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! vectorSimilarity = "_vectorSimilarity(" WS* word WS* "," WS* "[" WS* float (WS* "," WS* float)* WS* "]" WS* ")" WS* (">" | ">=" | "<" | "<=") WS* float
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
 //! ```text
 //! geoPoint       = WS* "_geoPoint(" (float ",")* ")"
+//! vectorDistance = WS* "_vectorDistance(" .* ")"
 //! ```
 //!
 //! Specific errors:
@@ -54,16 +56,16 @@ use condition::{
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
 use nom::branch::alt;
-use nom::bytes::complete::tag;
+use nom::bytes::complete::{tag, take_till, take_while1};
 use nom::character::complete::{char, multispace0};
 use nom::combinator::{cut, eof, map, opt};
 use nom::multi::{many0, separated_list1};
 use nom::number::complete::recognize_float;
-use nom::sequence::{delimited, preceded, terminated, tuple};
+use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::Finish;
 use nom_locate::LocatedSpan;
 pub(crate) use value::parse_value;
-use value::word_exact;
+use value::{is_value_component, word_exact};
 
 pub type Span<'a> = LocatedSpan<&'a str, &'a str>;
 
@@ -145,6 +147,7 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    VectorSimilarity { space: Token<'a>, vector: Vec<Token<'a>>, op: Condition<'a> },
 }
 
 impl<'a> FilterCondition<'a> {
@@ -171,6 +174,7 @@ impl<'a> FilterCondition<'a> {
                 None
             }
             FilterCondition::GeoLowerThan { point: [point, _], .. } if depth == 0 => Some(point),
+            FilterCondition::VectorSimilarity { space, .. } if depth == 0 => Some(space),
             _ => None,
         }
     }
@@ -371,6 +375,52 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// vectorSimilarity = WS* "_vectorSimilarity(word WS* "," WS* "[" float ("," WS* float)* "]")" WS* (">" | ">=" | "<" | "<=") WS* float
+/// If we parse `_vectorSimilarity` we MUST parse the rest of the expression.
+fn parse_vector_similarity(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _vectorSimilarity but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_vectorSimilarity"))),
+        // if we were able to parse `_vectorSimilarity` and can't parse the rest of the input we return a failure
+        cut(tuple((
+            delimited(
+                char('('),
+                separated_pair(
+                    ws(take_while1(is_value_component)),
+                    tag(","),
+                    ws(delimited(
+                        char('['),
+                        separated_list1(tag(","), ws(recognize_float)),
+                        char(']'),
+                    )),
+                ),
+                char(')'),
+            ),
+            ws(alt((tag("<="), tag(">="), tag("<"), tag(">")))),
+            recognize_float,
+        ))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::VectorSimilarity)));
+
+    let (input, ((space, vector), op, similarity)) = parsed?;
+
+    let similarity = Token::from(similarity);
+    let op = match *op.fragment() {
+        "<=" => Condition::LowerThanOrEqual(similarity),
+        ">=" => Condition::GreaterThanOrEqual(similarity),
+        "<" => Condition::LowerThan(similarity),
+        ">" => Condition::GreaterThan(similarity),
+        _ => unreachable!(),
+    };
+
+    let res = FilterCondition::VectorSimilarity {
+        space: space.into(),
+        vector: vector.into_iter().map(Token::from).collect(),
+        op,
+    };
+    Ok((input, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -413,6 +463,25 @@ fn parse_geo(input: Span) -> IResult<FilterCondition> {
     Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::ReservedGeo("_geo"))))
 }
 
+/// vectorDistance = WS* "_vectorDistance(" .* ")"
+fn parse_vector_distance(input: Span) -> IResult<FilterCondition> {
+    // we want to forbid space BEFORE the _vectorDistance but not after
+    tuple((
+        multispace0,
+        tag("_vectorDistance"),
+        // if we were able to parse `_vectorDistance` we are going to return a Failure whatever happens next.
+        cut(delimited(char('('), take_till(|c| c == ')'), char(')'))),
+    ))(input)
+    .map_err(|e| {
+        e.map(|_| Error::new_from_kind(input, ErrorKind::ReservedVector("_vectorDistance")))
+    })?;
+    // if we succeeded we still return a `Failure` because `_vectorDistance` filters are not allowed
+    Err(nom::Err::Failure(Error::new_from_kind(
+        input,
+        ErrorKind::ReservedVector("_vectorDistance"),
+    )))
+}
+
 fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     match parse_condition(input) {
         Ok(result) => Ok(result),
@@ -426,7 +495,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | vectorSimilarity | condition | exists | not_exists | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -442,6 +511,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        parse_vector_similarity,
         parse_in,
         parse_not_in,
         parse_condition,
@@ -456,6 +526,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_geo,
         parse_geo_distance,
         parse_geo_point,
+        parse_vector_distance,
         parse_error_reserved_keyword,
     ))(input)
     // if the inner parsers did not match enough information to return an accurate error
@@ -518,6 +589,13 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::VectorSimilarity { space, vector, op } => {
+                write!(f, "_vectorSimilarity({space}, [")?;
+                for el in vector {
+                    write!(f, "{el}, ")?;
+                }
+                write!(f, "]) {op}")
+            }
         }
     }
 }
@@ -653,6 +731,11 @@ pub mod tests {
         insta::assert_display_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_display_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test vector similarity
+        insta::assert_display_snapshot!(p("_vectorSimilarity(title, [0.1, 0.2]) > 0.8"), @"_vectorSimilarity({title}, [{0.1}, {0.2}, ]) > {0.8}");
+        insta::assert_display_snapshot!(p("NOT _vectorSimilarity(title,[1,2,3])<=0.5"), @"NOT (_vectorSimilarity({title}, [{1}, {2}, {3}, ]) <= {0.5})");
+        insta::assert_display_snapshot!(p("_vectorSimilarity(default, [1]) >= 0.5 AND genre = horror"), @"AND[_vectorSimilarity({default}, [{1}, ]) >= {0.5}, {genre} = {horror}, ]");
+
         // Test OR + AND
         insta::assert_display_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_display_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -754,6 +837,31 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_display_snapshot!(p("_vectorSimilarity"), @r###"
+        The `_vectorSimilarity` filter expects a vector space, a vector and a comparison with a similarity: `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity`.
+        1:18 _vectorSimilarity
+        "###);
+
+        insta::assert_display_snapshot!(p("_vectorSimilarity(title, [1, 2])"), @r###"
+        The `_vectorSimilarity` filter expects a vector space, a vector and a comparison with a similarity: `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity`.
+        1:33 _vectorSimilarity(title, [1, 2])
+        "###);
+
+        insta::assert_display_snapshot!(p("_vectorSimilarity(title, [1, 2]) = 0.5"), @r###"
+        The `_vectorSimilarity` filter expects a vector space, a vector and a comparison with a similarity: `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity`.
+        1:39 _vectorSimilarity(title, [1, 2]) = 0.5
+        "###);
+
+        insta::assert_display_snapshot!(p("similarity > _vectorSimilarity(title, [1]) > 0.5"), @r###"
+        The `_vectorSimilarity` filter is an operation and can't be used as a value.
+        14:49 similarity > _vectorSimilarity(title, [1]) > 0.5
+        "###);
+
+        insta::assert_display_snapshot!(p("_vectorDistance(title, [1, 2])"), @r###"
+        `_vectorDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_vectorSimilarity(vectorSpace, [x, y, ...]) > similarity` built-in rule to filter on vectors.
+        1:31 _vectorDistance(title, [1, 2])
+        "###);
+
         insta::assert_display_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
         `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
@@ -8,7 +8,7 @@ use nom::{InputIter, InputLength, InputTake, Slice};
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
     parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_vector_distance, parse_vector_similarity, Error, ErrorKind, IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -88,7 +88,8 @@ pub fn parse_value(input: Span) -> IResult<Token> {
     // then, we want to check if the user is misusing a geo expression
     // This expression can’t finish without error.
     // We want to return an error in case of failure.
-    let geo_reserved_parse_functions = [parse_geo_point, parse_geo_distance, parse_geo];
+    let geo_reserved_parse_functions =
+        [parse_geo_point, parse_geo_distance, parse_geo, parse_vector_distance];
 
     for parser in geo_reserved_parse_functions {
         if let Err(err) = parser(input) {
@@ -128,6 +129,24 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_vector_similarity(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedVectorSimilarity,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _vectorSimilarity filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedVectorSimilarity,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -191,7 +210,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
     }
 }
 
-fn is_value_component(c: char) -> bool {
+pub(crate) fn is_value_component(c: char) -> bool {
     c.is_alphanumeric() || ['_', '-', '.'].contains(&c)
 }
 
@@ -213,6 +232,7 @@ fn is_keyword(s: &str) -> bool {
             | "EMPTY"
             | "_geoRadius"
             | "_geoBoundingBox"
+            | "_vectorSimilarity"
     )
 }
 
@@ -8,7 +8,7 @@ The main function of the autobatcher is [`next_autobatch`].
 use std::ops::ControlFlow::{self, Break, Continue};
 
 use meilisearch_types::milli::update::IndexDocumentsMethod::{
-    self, ReplaceDocuments, UpdateDocuments,
+    self, EditDocuments, ReplaceDocuments, UpdateDocuments,
 };
 use meilisearch_types::tasks::TaskId;
 
@@ -26,6 +26,7 @@ enum AutobatchKind {
     },
     DocumentDeletion,
     DocumentDeletionByFilter,
+    DocumentEditionByFilter,
     DocumentClear,
     Settings {
         allow_index_creation: bool,
@@ -68,6 +69,9 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::DocumentDeletionByFilter { .. } => {
                 AutobatchKind::DocumentDeletionByFilter
             }
+            KindWithContent::DocumentEditionByFilter { .. } => {
+                AutobatchKind::DocumentEditionByFilter
+            }
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
                     allow_index_creation: allow_index_creation && !is_deletion,
@@ -104,6 +108,9 @@ pub enum BatchKind {
     DocumentDeletionByFilter {
         id: TaskId,
     },
+    DocumentEditionByFilter {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -205,6 +212,9 @@ impl BatchKind {
             K::DocumentDeletionByFilter => {
                 (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
             }
+            K::DocumentEditionByFilter => {
+                (Break(BatchKind::DocumentEditionByFilter { id: task_id }), false)
+            }
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -222,7 +232,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter | K::DocumentEditionByFilter) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -308,13 +318,14 @@ impl BatchKind {
                     primary_key: pk,
                 })
             }
+            // the document editions are merged like the updates and can be batched with them
             (
-                BatchKind::DocumentOperation { method: UpdateDocuments, allow_index_creation, primary_key: _, mut operation_ids },
-                K::DocumentImport { method: UpdateDocuments, primary_key: pk, .. },
+                BatchKind::DocumentOperation { method: this_method @ (UpdateDocuments | EditDocuments), allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: kind_method @ (UpdateDocuments | EditDocuments), primary_key: pk, .. },
             ) => {
                 operation_ids.push(id);
                 Continue(BatchKind::DocumentOperation {
-                    method: UpdateDocuments,
+                    method: merge_update_methods(this_method, kind_method),
                     allow_index_creation,
                     primary_key: pk,
                     operation_ids,
@@ -483,13 +494,13 @@ impl BatchKind {
                 })
             }
             (
-                BatchKind::SettingsAndDocumentOperation { settings_ids, method: UpdateDocuments, allow_index_creation, primary_key: _, mut operation_ids },
-                K::DocumentImport { method: UpdateDocuments, primary_key: pk2, .. },
+                BatchKind::SettingsAndDocumentOperation { settings_ids, method: this_method @ (UpdateDocuments | EditDocuments), allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: kind_method @ (UpdateDocuments | EditDocuments), primary_key: pk2, .. },
             ) => {
                 operation_ids.push(id);
                 Continue(BatchKind::SettingsAndDocumentOperation {
                     settings_ids,
-                    method: UpdateDocuments,
+                    method: merge_update_methods(this_method, kind_method),
                     allow_index_creation,
                     primary_key: pk2,
                     operation_ids,
@@ -519,7 +530,8 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentDeletionByFilter { .. },
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentEditionByFilter { .. },
                 _,
             ) => {
                 unreachable!()
@@ -528,6 +540,19 @@ impl BatchKind {
     }
 }
 
+/// Returns the method of a batch of document updates and editions,
+/// the field operations are applied as soon as one task is an edition.
+fn merge_update_methods(
+    this: IndexDocumentsMethod,
+    other: IndexDocumentsMethod,
+) -> IndexDocumentsMethod {
+    if this == EditDocuments || other == EditDocuments {
+        EditDocuments
+    } else {
+        UpdateDocuments
+    }
+}
+
 /// Create a batch from an ordered list of tasks.
 ///
 /// ## Preconditions
@@ -736,6 +761,19 @@ mod tests {
         debug_snapshot!(autobatch_from(true, None, [doc_del(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
     }
 
+    #[test]
+    fn document_edition_batch_with_updates() {
+        // editions batch together and with the updates, the whole batch becomes an edition
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(EditDocuments, true, None), doc_imp(EditDocuments, true, None)]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), doc_imp(EditDocuments, true, None), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(EditDocuments, true, None), doc_del(), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(EditDocuments, true, None), settings(true), doc_imp(UpdateDocuments, true, None)]), @"Some((SettingsAndDocumentOperation { settings_ids: [1], method: EditDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 2] }, true))");
+
+        // but they can't batch with the additions
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(EditDocuments, true, None), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp(EditDocuments, true, None)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+    }
+
     #[test]
     fn document_addition_batch_with_settings() {
         // simple case
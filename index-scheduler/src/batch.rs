@@ -21,21 +21,25 @@ use std::collections::{BTreeSet, HashSet};
 use std::ffi::OsStr;
 use std::fmt;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{BufWriter, Seek};
 
 use dump::IndexMetadata;
 use log::{debug, error, info};
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
     DeleteDocuments, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    IndexerConfig, Settings as MilliSettings,
 };
 use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, Kind, KindWithContent, RejectedDocument, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
@@ -44,7 +48,7 @@ use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
 use crate::utils::{self, swap_index_uid_in_task};
-use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
+use crate::{Error, IndexScheduler, MustStopProcessing, ProcessingTasks, Result, TaskId};
 
 /// Represents a combination of tasks that can all be processed at the same time.
 ///
@@ -115,6 +119,10 @@ pub(crate) enum IndexOperation {
         index_uid: String,
         task: Task,
     },
+    IndexDocumentEditionByFilter {
+        index_uid: String,
+        task: Task,
+    },
     DocumentClear {
         index_uid: String,
         tasks: Vec<Task>,
@@ -167,7 +175,8 @@ impl Batch {
                 | IndexOperation::DocumentClear { tasks, .. } => {
                     tasks.iter().map(|task| task.uid).collect()
                 }
-                IndexOperation::IndexDocumentDeletionByFilter { task, .. } => vec![task.uid],
+                IndexOperation::IndexDocumentDeletionByFilter { task, .. }
+                | IndexOperation::IndexDocumentEditionByFilter { task, .. } => vec![task.uid],
                 IndexOperation::SettingsAndDocumentOperation {
                     document_import_tasks: tasks,
                     settings_tasks: other,
@@ -229,6 +238,7 @@ impl IndexOperation {
             IndexOperation::DocumentOperation { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
             | IndexOperation::IndexDocumentDeletionByFilter { index_uid, .. }
+            | IndexOperation::IndexDocumentEditionByFilter { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
             | IndexOperation::DocumentClearAndSetting { index_uid, .. }
@@ -249,6 +259,9 @@ impl fmt::Display for IndexOperation {
             IndexOperation::IndexDocumentDeletionByFilter { .. } => {
                 f.write_str("IndexOperation::IndexDocumentDeletionByFilter")
             }
+            IndexOperation::IndexDocumentEditionByFilter { .. } => {
+                f.write_str("IndexOperation::IndexDocumentEditionByFilter")
+            }
             IndexOperation::DocumentClear { .. } => f.write_str("IndexOperation::DocumentClear"),
             IndexOperation::Settings { .. } => f.write_str("IndexOperation::Settings"),
             IndexOperation::DocumentClearAndSetting { .. } => {
@@ -298,6 +311,21 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::DocumentEditionByFilter { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                match &task.kind {
+                    KindWithContent::DocumentEditionByFilter { index_uid, .. } => {
+                        Ok(Some(Batch::IndexOperation {
+                            op: IndexOperation::IndexDocumentEditionByFilter {
+                                index_uid: index_uid.clone(),
+                                task,
+                            },
+                            must_create_index: false,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentOperation { method, operation_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, operation_ids)?;
                 let primary_key = tasks
@@ -1195,14 +1223,38 @@ impl IndexScheduler {
                     || must_stop_processing.get(),
                 )?;
 
-                for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
+                // the position of the task of each call to `add_documents` or `edit_documents`.
+                let mut additions_tasks = Vec::new();
+                for (position, (operation, task)) in
+                    operations.into_iter().zip(tasks.iter_mut()).enumerate()
+                {
                     match operation {
                         DocumentOperation::Add(content_uuid) => {
+                            additions_tasks.push(position);
                             let content_file = self.file_store.get_update(content_uuid)?;
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
-                            let (new_builder, user_result) = builder.add_documents(reader)?;
+                            let edit_documents = matches!(
+                                task.kind,
+                                KindWithContent::DocumentAdditionOrUpdate {
+                                    method: IndexDocumentsMethod::EditDocuments,
+                                    ..
+                                }
+                            );
+                            let (new_builder, user_result) = if edit_documents {
+                                builder.edit_documents(reader)?
+                            } else {
+                                builder.add_documents(reader)?
+                            };
                             builder = new_builder;
+                            let rejected_documents = builder
+                                .take_rejected_documents()
+                                .into_iter()
+                                .map(|rejected| RejectedDocument {
+                                    document_id: rejected.document_id,
+                                    error: milli::Error::from(rejected.error).into(),
+                                })
+                                .collect();
 
                             let received_documents =
                                 if let Some(Details::DocumentAdditionOrUpdate {
@@ -1222,6 +1274,7 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        rejected_documents,
                                     })
                                 }
                                 Err(e) => {
@@ -1229,6 +1282,7 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        rejected_documents,
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1271,8 +1325,25 @@ impl IndexScheduler {
                 }
 
                 if !tasks.iter().all(|res| res.error.is_some()) {
-                    let addition = builder.execute()?;
+                    let (addition, rejected_editions) = builder.execute_editions()?;
                     info!("document addition done: {:?}", addition);
+
+                    // the editions conflicting with another edition of the batch are only
+                    // known once all the versions of the documents are merged.
+                    for (addition, rejected) in rejected_editions {
+                        let task = &mut tasks[additions_tasks[addition]];
+                        if let Some(Details::DocumentAdditionOrUpdate {
+                            rejected_documents, ..
+                        }) = &mut task.details
+                        {
+                            if rejected_documents.len() < milli::update::MAX_REJECTED_DOCUMENTS {
+                                rejected_documents.push(RejectedDocument {
+                                    document_id: rejected.document_id,
+                                    error: milli::Error::from(rejected.error).into(),
+                                });
+                            }
+                        }
+                    }
                 } else if primary_key_has_been_set {
                     // Everything failed but we've set a primary key.
                     // We need to remove it.
@@ -1346,6 +1417,60 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            IndexOperation::IndexDocumentEditionByFilter { mut task, index_uid: _ } => {
+                let (filter, edit) =
+                    if let KindWithContent::DocumentEditionByFilter { filter_expr, edit, .. } =
+                        &task.kind
+                    {
+                        (filter_expr, edit)
+                    } else {
+                        unreachable!()
+                    };
+                let result = edit_documents_by_filter(
+                    index_wtxn,
+                    filter,
+                    edit,
+                    self.index_mapper.indexer_config(),
+                    self.must_stop_processing.clone(),
+                    index,
+                );
+                let (original_filter, edit) =
+                    if let Some(Details::DocumentEditionByFilter {
+                        original_filter, edit, ..
+                    }) = task.details
+                    {
+                        (original_filter, edit)
+                    } else {
+                        // In the case of a `documentEditionByFilter` the details MUST be set
+                        unreachable!();
+                    };
+
+                match result {
+                    Ok((matched_documents, edited_documents, rejected_documents)) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentEditionByFilter {
+                            original_filter,
+                            edit,
+                            matched_documents: Some(matched_documents),
+                            edited_documents: Some(edited_documents),
+                            rejected_documents,
+                        });
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentEditionByFilter {
+                            original_filter,
+                            edit,
+                            matched_documents: Some(0),
+                            edited_documents: Some(0),
+                            rejected_documents: Vec::new(),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
@@ -1565,3 +1690,96 @@ fn delete_document_by_filter<'a>(
         0
     })
 }
+
+/// Applies the edit on all the documents matching the filter by sending them through the
+/// document edition pipeline, the fields of the edit can either be plain values that replace
+/// the current ones or field operations like `{ "$unset": true }`.
+///
+/// Returns the number of documents that matched the filter, the number of edited documents
+/// and the documents that were rejected because the edit couldn't be applied on them.
+fn edit_documents_by_filter<'a>(
+    wtxn: &mut RwTxn<'a, '_>,
+    filter: &serde_json::Value,
+    edit: &milli::Object,
+    indexer_config: &IndexerConfig,
+    must_stop_processing: MustStopProcessing,
+    index: &'a Index,
+) -> Result<(u64, u64, Vec<RejectedDocument>)> {
+    // An index without primary key doesn't contain any document.
+    let primary_key = match index.primary_key(wtxn)? {
+        Some(primary_key) => primary_key.to_string(),
+        None => return Ok((0, 0, Vec::new())),
+    };
+    // the edit is invalid whether or not documents match the filter.
+    if edit.contains_key(&primary_key) {
+        return Err(Error::DocumentEditionOfPrimaryKey(primary_key));
+    }
+
+    let filter = match Filter::from_json(filter)? {
+        Some(filter) => filter,
+        None => return Ok((0, 0, Vec::new())),
+    };
+    let candidates = filter.evaluate(wtxn, index).map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
+        }
+        e => e.into(),
+    })?;
+    if candidates.is_empty() {
+        return Ok((0, 0, Vec::new()));
+    }
+
+    let fields_ids_map = index.fields_ids_map(wtxn)?;
+    let primary_key_id = fields_ids_map
+        .id(&primary_key)
+        .ok_or_else(|| milli::FieldIdMapMissingEntry::FieldName {
+            field_name: primary_key.clone(),
+            process: "edit_documents_by_filter",
+        })
+        .map_err(milli::Error::from)?;
+
+    // We generate one partial document per matching document, containing its
+    // primary key and the edit, that will be merged with the stored version.
+    let mut documents = DocumentsBatchBuilder::new(tempfile::tempfile()?);
+    for result in index.iter_documents(wtxn, &candidates)? {
+        let (_docid, obkv) = result?;
+        let document_id = match obkv.get(primary_key_id) {
+            Some(value) => serde_json::from_slice(value)
+                .map_err(|e| milli::Error::from(milli::InternalError::SerdeJson(e)))?,
+            None => continue,
+        };
+        let mut document = edit.clone();
+        document.insert(primary_key.clone(), document_id);
+        documents.append_json_object(&document)?;
+    }
+    let matched_documents = documents.documents_count() as u64;
+    let mut documents = documents.into_inner()?;
+    documents.rewind()?;
+
+    let config = IndexDocumentsConfig {
+        update_method: IndexDocumentsMethod::EditDocuments,
+        ..Default::default()
+    };
+    let builder = milli::update::IndexDocuments::new(
+        wtxn,
+        index,
+        indexer_config,
+        config,
+        |indexing_step| debug!("update: {:?}", indexing_step),
+        || must_stop_processing.get(),
+    )?;
+    let reader = DocumentsBatchReader::from_reader(documents).map_err(milli::Error::from)?;
+    let (mut builder, user_result) = builder.edit_documents(reader)?;
+    let edited_documents = user_result.map_err(milli::Error::from)?;
+    let rejected_documents = builder
+        .take_rejected_documents()
+        .into_iter()
+        .map(|rejected| RejectedDocument {
+            document_id: rejected.document_id,
+            error: milli::Error::from(rejected.error).into(),
+        })
+        .collect();
+    builder.execute()?;
+
+    Ok((matched_documents, edited_documents, rejected_documents))
+}
@@ -173,8 +173,14 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            rejected_documents,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            if rejected_documents.is_empty() {
+                format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            } else {
+                let rejected_documents: Vec<_> = rejected_documents.iter().map(|rejected| &rejected.document_id).collect();
+                format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}, rejected_documents: {rejected_documents:?} }}")
+            }
         }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
@@ -189,6 +195,15 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
+        Details::DocumentEditionByFilter { original_filter, edit, matched_documents, edited_documents, rejected_documents } => {
+            let edit = serde_json::to_string(edit).unwrap();
+            if rejected_documents.is_empty() {
+                format!("{{ original_filter: {original_filter}, edit: {edit}, matched_documents: {matched_documents:?}, edited_documents: {edited_documents:?} }}")
+            } else {
+                let rejected_documents: Vec<_> = rejected_documents.iter().map(|rejected| &rejected.document_id).collect();
+                format!("{{ original_filter: {original_filter}, edit: {edit}, matched_documents: {matched_documents:?}, edited_documents: {edited_documents:?}, rejected_documents: {rejected_documents:?} }}")
+            }
+        }
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
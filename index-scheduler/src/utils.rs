@@ -240,6 +240,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEditionByFilter { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
@@ -402,7 +403,11 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        rejected_documents: _,
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -488,6 +493,36 @@ impl IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentEditionByFilter {
+                        original_filter: _,
+                        edit: _,
+                        matched_documents,
+                        edited_documents,
+                        rejected_documents: _,
+                    } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        let index_uid = if let KindWithContent::DocumentEditionByFilter {
+                            ref index_uid,
+                            ..
+                        } = kind
+                        {
+                            index_uid
+                        } else {
+                            unreachable!()
+                        };
+                        assert_eq!(&task_index_uid.unwrap(), index_uid);
+
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => {
+                                assert!(matched_documents.is_some());
+                                assert!(edited_documents <= matched_documents);
+                            }
+                            Status::Failed | Status::Canceled => {
+                                assert!(edited_documents == Some(0));
+                            }
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
@@ -151,6 +151,7 @@ make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(MissingDocumentEdition, missing_document_edition);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
     missing_facet_search_facet_name
@@ -188,3 +189,25 @@ merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
+merge_with_error_impl_take_error_message!(milli::CriterionError);
+
+impl<Format> MergeWithError<InvalidSearchSemanticRatio>
+    for DeserrError<Format, InvalidSearchSemanticRatio>
+where
+    DeserrError<Format, InvalidSearchSemanticRatio>: deserr::DeserializeError,
+{
+    fn merge(
+        _self_: Option<Self>,
+        _other: InvalidSearchSemanticRatio,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        DeserrError::error::<Infallible>(
+            None,
+            deserr::ErrorKind::Unexpected {
+                msg: "the value of `semanticRatio` is invalid, expected a float between `0.0` and `1.0`."
+                    .to_string(),
+            },
+            merge_location,
+        )
+    }
+}
@@ -214,6 +214,9 @@ InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentFieldOperation         , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentEdition                , InvalidRequest       , BAD_REQUEST ;
+MissingDocumentEdition                , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
@@ -226,48 +229,79 @@ InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
+InvalidQueryRuleId                    , InvalidRequest       , BAD_REQUEST ;
+InvalidQueryRulesLimit                , InvalidRequest       , BAD_REQUEST ;
+InvalidQueryRulesOffset               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchAggregations             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToRetrieve     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDidYouMean               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetBuckets             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetFilters             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetStats               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchQuerySyntax              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRankingRules             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchVectorSpace              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchTypoTolerance            , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsAttributeWeights       , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryRules             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsStemmingLocales        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPhoneticAttributes     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsCodeAttributes         , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSuggestions            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSynonymRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsVectorSpaces           , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
+InvalidSuggestHighlightPostTag        , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestHighlightPreTag         , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestLimit                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestQ                       , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSynonymRuleId                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSynonymRulesLimit              , InvalidRequest       , BAD_REQUEST ;
+InvalidSynonymRulesOffset             , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterFinishedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterStartedAt             , InvalidRequest       , BAD_REQUEST ;
@@ -298,6 +332,8 @@ MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
+QueryRuleNotFound                     , InvalidRequest       , NOT_FOUND ;
+SynonymRuleNotFound                   , InvalidRequest       , NOT_FOUND ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
 TooManyOpenFiles                      , System               , UNPROCESSABLE_ENTITY ;
 UnretrievableDocument                 , Internal             , BAD_REQUEST ;
@@ -344,6 +380,9 @@ impl ErrorCode for milli::Error {
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
+                    UserError::InvalidSearchRankingRuleAttribute { .. } => {
+                        Code::InvalidSearchRankingRules
+                    }
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
@@ -352,12 +391,42 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidDocumentFieldOperation { .. } => {
+                        Code::InvalidDocumentFieldOperation
+                    }
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
-                    UserError::InvalidVectorsType { .. } => Code::InvalidVectorsType,
+                    UserError::InvalidVectorsType { .. } | UserError::UnknownVectorSpace { .. } => {
+                        Code::InvalidVectorsType
+                    }
+                    UserError::InvalidVectorSpaceDimensions { .. } => Code::InvalidVectorDimensions,
+                    UserError::InvalidSearchVectorSpace { .. } => Code::InvalidSearchVectorSpace,
+                    UserError::InvalidVectorSpaceName(_)
+                    | UserError::InvalidVectorSpaceDimensionsSetting(_) => {
+                        Code::InvalidSettingsVectorSpaces
+                    }
+                    UserError::InvalidAttributeWeight { .. } => {
+                        Code::InvalidSettingsAttributeWeights
+                    }
+                    UserError::InvalidLocalizedAttributesLocale(_) => {
+                        Code::InvalidSettingsLocalizedAttributes
+                    }
+                    UserError::InvalidSearchLocale(_) => Code::InvalidSearchLocales,
+                    UserError::InvalidStemmingLocale(_) => Code::InvalidSettingsStemmingLocales,
+                    UserError::InvalidHierarchicalFacetSeparator(_) => {
+                        Code::InvalidSettingsFaceting
+                    }
+                    UserError::InvalidSynonymRule { .. } => Code::InvalidSettingsSynonymRules,
+                    UserError::InvalidQueryRule { .. } => Code::InvalidSettingsQueryRules,
+                    UserError::AggregationsTooDeep(_) | UserError::TooManyAggregationBuckets(_) => {
+                        Code::InvalidSearchAggregations
+                    }
                     UserError::SortError(_) => Code::InvalidSearchSort,
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidSearchMinTypoWordLen(_, _) => {
+                        Code::InvalidSearchTypoTolerance
+                    }
                 }
             }
         }
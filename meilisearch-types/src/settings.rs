@@ -3,13 +3,12 @@ use std::convert::Infallible;
 use std::fmt;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
-use std::ops::ControlFlow;
 use std::str::FromStr;
 
-use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{Criterion, CriterionError, FunctionScore, Index, DEFAULT_VALUES_PER_FACET};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
@@ -94,6 +93,13 @@ pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    /// Whether the words containing digits, e.g. `15` or `A-1234`, are only matched exactly.
+    ///
+    /// Disabled by default, as the typos are allowed on any other word, unless the index has
+    /// `codeAttributes`, in which case it is enabled until it is explicitly set to `false`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub disable_on_numbers: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -106,6 +112,10 @@ pub struct FacetingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    /// The facets whose values are paths, along with the separator of their levels.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub hierarchical_facets: Setting<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -117,17 +127,283 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
-impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
-    fn merge(
-        _self_: Option<Self>,
-        other: milli::CriterionError,
-        merge_location: ValuePointerRef,
-    ) -> ControlFlow<Self, Self> {
-        Self::error::<Infallible>(
-            None,
-            ErrorKind::Unexpected { msg: other.to_string() },
-            merge_location,
-        )
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestionsSettings {
+    /// The attributes whose values are suggested as completions of the queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub attributes: Setting<Vec<String>>,
+    /// The filterable attributes whose most frequent values are suggested.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub facet_attributes: Setting<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct VectorSpaceSettings {
+    /// The number of dimensions of the vectors stored in this vector space.
+    pub dimensions: usize,
+    #[serde(default)]
+    #[deserr(default)]
+    pub distance: VectorDistance,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum VectorDistance {
+    /// The vectors are compared by their angle, they are normalized before being stored.
+    #[default]
+    Cosine,
+    /// The vectors are compared by their raw dot product.
+    Dot,
+    /// The vectors are compared by their euclidean distance.
+    Euclidean,
+}
+
+impl From<VectorDistance> for milli::Distance {
+    fn from(val: VectorDistance) -> Self {
+        match val {
+            VectorDistance::Cosine => milli::Distance::Cosine,
+            VectorDistance::Dot => milli::Distance::Dot,
+            VectorDistance::Euclidean => milli::Distance::Euclidean,
+        }
+    }
+}
+
+impl From<milli::Distance> for VectorDistance {
+    fn from(val: milli::Distance) -> Self {
+        match val {
+            milli::Distance::Cosine => VectorDistance::Cosine,
+            milli::Distance::Dot => VectorDistance::Dot,
+            milli::Distance::Euclidean => VectorDistance::Euclidean,
+        }
+    }
+}
+
+impl From<VectorSpaceSettings> for milli::VectorSpace {
+    fn from(val: VectorSpaceSettings) -> Self {
+        milli::VectorSpace { dimensions: val.dimensions, distance: val.distance.into() }
+    }
+}
+
+impl From<milli::VectorSpace> for VectorSpaceSettings {
+    fn from(val: milli::VectorSpace) -> Self {
+        VectorSpaceSettings { dimensions: val.dimensions, distance: val.distance.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRuleView {
+    /// The patterns of the attributes whose locales are pinned, e.g. `title_ja` or `*_ja`.
+    pub attribute_patterns: Vec<String>,
+    /// The ISO 639-3 codes of the locales of the attributes, e.g. `jpn`.
+    pub locales: Vec<String>,
+}
+
+impl From<LocalizedAttributesRuleView> for milli::LocalizedAttributesRule {
+    fn from(val: LocalizedAttributesRuleView) -> Self {
+        milli::LocalizedAttributesRule {
+            attribute_patterns: val.attribute_patterns,
+            locales: val.locales,
+        }
+    }
+}
+
+impl From<milli::LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(val: milli::LocalizedAttributesRule) -> Self {
+        LocalizedAttributesRuleView {
+            attribute_patterns: val.attribute_patterns,
+            locales: val.locales,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum SynonymRuleKindView {
+    /// The input is rewritten into the synonyms, but not the other way around.
+    OneWay,
+    /// All the synonyms of the group are rewritten into each other.
+    Equivalent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SynonymRuleView {
+    #[serde(rename = "type")]
+    #[deserr(rename = "type")]
+    pub kind: SynonymRuleKindView,
+    /// The words rewritten into the synonyms, only used by the `oneWay` rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub input: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+impl From<SynonymRuleView> for milli::SynonymRule {
+    fn from(val: SynonymRuleView) -> Self {
+        milli::SynonymRule {
+            kind: match val.kind {
+                SynonymRuleKindView::OneWay => milli::SynonymRuleKind::OneWay,
+                SynonymRuleKindView::Equivalent => milli::SynonymRuleKind::Equivalent,
+            },
+            input: val.input,
+            synonyms: val.synonyms,
+        }
+    }
+}
+
+impl From<milli::SynonymRule> for SynonymRuleView {
+    fn from(val: milli::SynonymRule) -> Self {
+        SynonymRuleView {
+            kind: match val.kind {
+                milli::SynonymRuleKind::OneWay => SynonymRuleKindView::OneWay,
+                milli::SynonymRuleKind::Equivalent => SynonymRuleKindView::Equivalent,
+            },
+            input: val.input,
+            synonyms: val.synonyms,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum AnchoringView {
+    Is,
+    StartsWith,
+    EndsWith,
+    #[default]
+    Contains,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleConditionView {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: AnchoringView,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocumentView {
+    pub id: String,
+    /// The position of the document in the results, starting at zero.
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleConsequenceView {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocumentView>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub boost: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleView {
+    /// The rule is applied when one of its conditions holds.
+    pub conditions: Vec<QueryRuleConditionView>,
+    pub consequence: QueryRuleConsequenceView,
+}
+
+impl From<QueryRuleView> for milli::QueryRule {
+    fn from(val: QueryRuleView) -> Self {
+        use milli::query_rules::{
+            Anchoring, PinnedDocument, QueryRuleCondition, QueryRuleConsequence,
+        };
+
+        let QueryRuleView { conditions, consequence } = val;
+        let QueryRuleConsequenceView { pin, hide, boost, filter } = consequence;
+        milli::QueryRule {
+            conditions: conditions
+                .into_iter()
+                .map(|QueryRuleConditionView { pattern, anchoring, filter }| QueryRuleCondition {
+                    pattern,
+                    anchoring: match anchoring {
+                        AnchoringView::Is => Anchoring::Is,
+                        AnchoringView::StartsWith => Anchoring::StartsWith,
+                        AnchoringView::EndsWith => Anchoring::EndsWith,
+                        AnchoringView::Contains => Anchoring::Contains,
+                    },
+                    filter,
+                })
+                .collect(),
+            consequence: QueryRuleConsequence {
+                pin: pin
+                    .into_iter()
+                    .map(|PinnedDocumentView { id, position }| PinnedDocument { id, position })
+                    .collect(),
+                hide,
+                boost,
+                filter,
+            },
+        }
+    }
+}
+
+impl From<milli::QueryRule> for QueryRuleView {
+    fn from(val: milli::QueryRule) -> Self {
+        use milli::query_rules::{
+            Anchoring, PinnedDocument, QueryRuleCondition, QueryRuleConsequence,
+        };
+
+        let milli::QueryRule { conditions, consequence } = val;
+        let QueryRuleConsequence { pin, hide, boost, filter } = consequence;
+        QueryRuleView {
+            conditions: conditions
+                .into_iter()
+                .map(|QueryRuleCondition { pattern, anchoring, filter }| QueryRuleConditionView {
+                    pattern,
+                    anchoring: match anchoring {
+                        Anchoring::Is => AnchoringView::Is,
+                        Anchoring::StartsWith => AnchoringView::StartsWith,
+                        Anchoring::EndsWith => AnchoringView::EndsWith,
+                        Anchoring::Contains => AnchoringView::Contains,
+                    },
+                    filter,
+                })
+                .collect(),
+            consequence: QueryRuleConsequenceView {
+                pin: pin
+                    .into_iter()
+                    .map(|PinnedDocument { id, position }| PinnedDocumentView { id, position })
+                    .collect(),
+                hide,
+                boost,
+                filter,
+            },
+        }
     }
 }
 
@@ -157,6 +433,9 @@ pub struct Settings<T> {
     )]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchableAttributes>)]
     pub searchable_attributes: Setting<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsAttributeWeights>)]
+    pub attribute_weights: Setting<BTreeMap<String, u16>>,
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsFilterableAttributes>)]
@@ -182,6 +461,15 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    /// The synonym rules identified by their id, they replace all the rules of the index.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonymRules>)]
+    pub synonym_rules: Setting<BTreeMap<String, SynonymRuleView>>,
+    /// The synonym rules added or removed, a rule set to `null` is removed and the rules
+    /// which are not mentioned are kept. Only the synonyms routes can send it.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(skip)]
+    pub synonym_rules_patch: Setting<BTreeMap<String, Option<SynonymRuleView>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
@@ -194,6 +482,31 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVectorSpaces>)]
+    pub vector_spaces: Setting<BTreeMap<String, VectorSpaceSettings>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsStemmingLocales>)]
+    pub stemming_locales: Setting<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPhoneticAttributes>)]
+    pub phonetic_attributes: Setting<Vec<String>>,
+    /// The attributes whose alphanumeric identifiers, e.g. `A-1234`, are kept whole.
+    /// The typos are disabled on numbers by default once they are set.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsCodeAttributes>)]
+    pub code_attributes: Setting<Vec<String>>,
+    /// The query rules identified by their id, a rule set to `null` is removed
+    /// and the rules which are not mentioned are kept.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    pub query_rules: Setting<BTreeMap<String, Option<QueryRuleView>>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSuggestions>)]
+    pub suggestions: Setting<SuggestionsSettings>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -205,11 +518,14 @@ impl Settings<Checked> {
         Settings {
             displayed_attributes: Setting::Reset,
             searchable_attributes: Setting::Reset,
+            attribute_weights: Setting::Reset,
             filterable_attributes: Setting::Reset,
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
+            synonym_rules: Setting::Reset,
+            synonym_rules_patch: Setting::NotSet,
             non_separator_tokens: Setting::Reset,
             separator_tokens: Setting::Reset,
             dictionary: Setting::Reset,
@@ -217,6 +533,13 @@ impl Settings<Checked> {
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            vector_spaces: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            stemming_locales: Setting::Reset,
+            phonetic_attributes: Setting::Reset,
+            code_attributes: Setting::Reset,
+            query_rules: Setting::Reset,
+            suggestions: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -225,6 +548,7 @@ impl Settings<Checked> {
         let Self {
             displayed_attributes,
             searchable_attributes,
+            attribute_weights,
             filterable_attributes,
             sortable_attributes,
             ranking_rules,
@@ -233,16 +557,26 @@ impl Settings<Checked> {
             separator_tokens,
             dictionary,
             synonyms,
+            synonym_rules,
+            synonym_rules_patch,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            vector_spaces,
+            localized_attributes,
+            stemming_locales,
+            phonetic_attributes,
+            code_attributes,
+            query_rules,
+            suggestions,
             ..
         } = self;
 
         Settings {
             displayed_attributes,
             searchable_attributes,
+            attribute_weights,
             filterable_attributes,
             sortable_attributes,
             ranking_rules,
@@ -251,10 +585,19 @@ impl Settings<Checked> {
             separator_tokens,
             dictionary,
             synonyms,
+            synonym_rules,
+            synonym_rules_patch,
             distinct_attribute,
             typo_tolerance,
             faceting,
             pagination,
+            vector_spaces,
+            localized_attributes,
+            stemming_locales,
+            phonetic_attributes,
+            code_attributes,
+            query_rules,
+            suggestions,
             _kind: PhantomData,
         }
     }
@@ -287,11 +630,14 @@ impl Settings<Unchecked> {
         Settings {
             displayed_attributes,
             searchable_attributes,
+            attribute_weights: self.attribute_weights,
             filterable_attributes: self.filterable_attributes,
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
             synonyms: self.synonyms,
+            synonym_rules: self.synonym_rules,
+            synonym_rules_patch: self.synonym_rules_patch,
             non_separator_tokens: self.non_separator_tokens,
             separator_tokens: self.separator_tokens,
             dictionary: self.dictionary,
@@ -299,6 +645,13 @@ impl Settings<Unchecked> {
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            vector_spaces: self.vector_spaces,
+            localized_attributes: self.localized_attributes,
+            stemming_locales: self.stemming_locales,
+            phonetic_attributes: self.phonetic_attributes,
+            code_attributes: self.code_attributes,
+            query_rules: self.query_rules,
+            suggestions: self.suggestions,
             _kind: PhantomData,
         }
     }
@@ -322,6 +675,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.attribute_weights {
+        Setting::Set(ref weights) => builder.set_attribute_weights(weights.clone()),
+        Setting::Reset => builder.reset_attribute_weights(),
+        Setting::NotSet => (),
+    }
+
     match settings.displayed_attributes {
         Setting::Set(ref names) => builder.set_displayed_fields(names.clone()),
         Setting::Reset => builder.reset_displayed_fields(),
@@ -384,6 +743,22 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.synonym_rules {
+        Setting::Set(ref rules) => builder.set_synonym_rules(
+            rules.iter().map(|(id, rule)| (id.clone(), rule.clone().into())).collect(),
+        ),
+        Setting::Reset => builder.reset_synonym_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.synonym_rules_patch {
+        Setting::Set(ref patch) => builder.patch_synonym_rules(
+            patch.iter().map(|(id, rule)| (id.clone(), rule.clone().map(Into::into))).collect(),
+        ),
+        Setting::Reset => builder.reset_synonym_rules(),
+        Setting::NotSet => (),
+    }
+
     match settings.distinct_attribute {
         Setting::Set(ref attr) => builder.set_distinct_field(attr.clone()),
         Setting::Reset => builder.reset_distinct_field(),
@@ -433,6 +808,12 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -441,12 +822,17 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_disable_typos_on_numbers();
         }
         Setting::NotSet => (),
     }
 
     match &settings.faceting {
-        Setting::Set(FacetingSettings { max_values_per_facet, sort_facet_values_by }) => {
+        Setting::Set(FacetingSettings {
+            max_values_per_facet,
+            sort_facet_values_by,
+            hierarchical_facets,
+        }) => {
             match max_values_per_facet {
                 Setting::Set(val) => builder.set_max_values_per_facet(*val),
                 Setting::Reset => builder.reset_max_values_per_facet(),
@@ -459,10 +845,16 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_sort_facet_values_by(),
                 Setting::NotSet => (),
             }
+            match hierarchical_facets {
+                Setting::Set(val) => builder.set_hierarchical_facets(val.clone()),
+                Setting::Reset => builder.reset_hierarchical_facets(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             builder.reset_max_values_per_facet();
             builder.reset_sort_facet_values_by();
+            builder.reset_hierarchical_facets();
         }
         Setting::NotSet => (),
     }
@@ -476,6 +868,69 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.vector_spaces {
+        Setting::Set(ref spaces) => builder.set_vector_spaces(
+            spaces.iter().map(|(name, space)| (name.clone(), space.clone().into())).collect(),
+        ),
+        Setting::Reset => builder.reset_vector_spaces(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => builder
+            .set_localized_attributes_rules(rules.iter().map(|rule| rule.clone().into()).collect()),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming_locales {
+        Setting::Set(ref locales) => builder.set_stemming_locales(locales.clone()),
+        Setting::Reset => builder.reset_stemming_locales(),
+        Setting::NotSet => (),
+    }
+
+    match settings.phonetic_attributes {
+        Setting::Set(ref attributes) => builder.set_phonetic_attributes(attributes.clone()),
+        Setting::Reset => builder.reset_phonetic_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match settings.code_attributes {
+        Setting::Set(ref attributes) => builder.set_code_attributes(attributes.clone()),
+        Setting::Reset => builder.reset_code_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match settings.query_rules {
+        Setting::Set(ref patch) => builder.patch_query_rules(
+            patch.iter().map(|(id, rule)| (id.clone(), rule.clone().map(Into::into))).collect(),
+        ),
+        Setting::Reset => builder.reset_query_rules(),
+        Setting::NotSet => (),
+    }
+
+    match &settings.suggestions {
+        Setting::Set(SuggestionsSettings { attributes, facet_attributes }) => {
+            match attributes {
+                Setting::Set(attributes) => builder.set_suggestion_attributes(attributes.clone()),
+                Setting::Reset => builder.reset_suggestion_attributes(),
+                Setting::NotSet => (),
+            }
+            match facet_attributes {
+                Setting::Set(attributes) => {
+                    builder.set_suggestion_facet_attributes(attributes.clone())
+                }
+                Setting::Reset => builder.reset_suggestion_facet_attributes(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_suggestion_attributes();
+            builder.reset_suggestion_facet_attributes();
+        }
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -511,6 +966,10 @@ pub fn settings(
 
     let synonyms = index.user_defined_synonyms(rtxn)?;
 
+    // the synonym rules are only displayed once defined.
+    let synonym_rules: BTreeMap<_, _> =
+        index.synonym_rules(rtxn)?.into_iter().map(|(id, rule)| (id, rule.into())).collect();
+
     let min_typo_word_len = MinWordSizeTyposSetting {
         one_typo: Setting::Set(index.min_word_len_one_typo(rtxn)?),
         two_typos: Setting::Set(index.min_word_len_two_typos(rtxn)?),
@@ -528,6 +987,13 @@ pub fn settings(
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        // the typos are only displayed as disabled on numbers once they are,
+        // or when they are enabled again on the numbers of the code attributes.
+        disable_on_numbers: match index.disable_typos_on_numbers(rtxn)? {
+            true => Setting::Set(true),
+            false if !index.code_attributes(rtxn)?.is_empty() => Setting::Set(false),
+            false => Setting::NotSet,
+        },
     };
 
     let faceting = FacetingSettings {
@@ -541,6 +1007,11 @@ pub fn settings(
                 .map(|(name, sort)| (name, sort.into()))
                 .collect(),
         ),
+        // the hierarchical facets are only displayed once defined.
+        hierarchical_facets: match index.hierarchical_facets(rtxn)? {
+            facets if facets.is_empty() => Setting::NotSet,
+            facets => Setting::Set(facets),
+        },
     };
 
     let pagination = PaginationSettings {
@@ -549,6 +1020,42 @@ pub fn settings(
         ),
     };
 
+    // the attribute weights are only displayed once defined.
+    let attribute_weights = index.attribute_weights(rtxn)?;
+
+    // the vector spaces are only displayed once declared.
+    let vector_spaces: BTreeMap<_, _> =
+        index.vector_spaces(rtxn)?.into_iter().map(|(name, space)| (name, space.into())).collect();
+
+    // the localized attributes are only displayed once defined.
+    let localized_attributes: Vec<_> =
+        index.localized_attributes_rules(rtxn)?.into_iter().map(Into::into).collect();
+
+    // the stemming locales are only displayed once the stemming is enabled.
+    let stemming_locales = index.stemming_locales(rtxn)?;
+
+    // the phonetic attributes are only displayed once defined.
+    let phonetic_attributes = index.phonetic_attributes(rtxn)?;
+
+    // the code attributes are only displayed once defined.
+    let code_attributes = index.code_attributes(rtxn)?;
+
+    // the suggestions are only displayed once configured.
+    let suggestion_attributes = index.suggestion_attributes(rtxn)?;
+    let suggestion_facet_attributes = index.suggestion_facet_attributes(rtxn)?;
+    let suggestions =
+        match suggestion_attributes.is_empty() && suggestion_facet_attributes.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(SuggestionsSettings {
+                attributes: Setting::Set(suggestion_attributes),
+                facet_attributes: Setting::Set(suggestion_facet_attributes),
+            }),
+        };
+
+    // the query rules are only displayed once defined.
+    let query_rules: BTreeMap<_, _> =
+        index.query_rules(rtxn)?.into_iter().map(|(id, rule)| (id, Some(rule.into()))).collect();
+
     Ok(Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -558,6 +1065,10 @@ pub fn settings(
             Some(attrs) => Setting::Set(attrs),
             None => Setting::Reset,
         },
+        attribute_weights: match attribute_weights.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(attribute_weights),
+        },
         filterable_attributes: Setting::Set(filterable_attributes),
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
@@ -570,9 +1081,39 @@ pub fn settings(
             None => Setting::Reset,
         },
         synonyms: Setting::Set(synonyms),
+        synonym_rules: match synonym_rules.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(synonym_rules),
+        },
+        synonym_rules_patch: Setting::NotSet,
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        vector_spaces: match vector_spaces.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(vector_spaces),
+        },
+        localized_attributes: match localized_attributes.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(localized_attributes),
+        },
+        stemming_locales: match stemming_locales.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(stemming_locales),
+        },
+        phonetic_attributes: match phonetic_attributes.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(phonetic_attributes),
+        },
+        code_attributes: match code_attributes.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(code_attributes),
+        },
+        query_rules: match query_rules.is_empty() {
+            true => Setting::NotSet,
+            false => Setting::Set(query_rules),
+        },
+        suggestions,
         _kind: PhantomData,
     })
 }
@@ -599,6 +1140,8 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score computed from the numeric value of the field specified.
+    FunctionScore(FunctionScore),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -655,6 +1198,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::FunctionScore(x) => RankingRuleView::FunctionScore(x),
         }
     }
 }
@@ -669,6 +1213,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::FunctionScore(x) => Criterion::FunctionScore(x),
         }
     }
 }
@@ -683,6 +1228,7 @@ pub(crate) mod test {
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("hello")]),
             searchable_attributes: Setting::Set(vec![String::from("hello")]),
+            attribute_weights: Setting::NotSet,
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
@@ -691,10 +1237,19 @@ pub(crate) mod test {
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            synonym_rules_patch: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            vector_spaces: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_locales: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
+            code_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            suggestions: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -707,6 +1262,7 @@ pub(crate) mod test {
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("*")]),
             searchable_attributes: Setting::Set(vec![String::from("hello"), String::from("*")]),
+            attribute_weights: Setting::NotSet,
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
@@ -715,10 +1271,19 @@ pub(crate) mod test {
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            synonym_rules_patch: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            vector_spaces: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming_locales: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
+            code_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            suggestions: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
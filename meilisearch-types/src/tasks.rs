@@ -50,6 +50,7 @@ impl Task {
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEditionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -69,6 +70,7 @@ impl Task {
             KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentEditionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
@@ -102,6 +104,11 @@ pub enum KindWithContent {
         index_uid: String,
         filter_expr: serde_json::Value,
     },
+    DocumentEditionByFilter {
+        index_uid: String,
+        filter_expr: serde_json::Value,
+        edit: milli::Object,
+    },
     DocumentClear {
         index_uid: String,
     },
@@ -152,6 +159,7 @@ impl KindWithContent {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentEditionByFilter { .. } => Kind::DocumentEdition,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
@@ -176,6 +184,7 @@ impl KindWithContent {
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEditionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -200,6 +209,7 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    rejected_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -214,6 +224,15 @@ impl KindWithContent {
                     deleted_documents: None,
                 })
             }
+            KindWithContent::DocumentEditionByFilter { index_uid: _, filter_expr, edit } => {
+                Some(Details::DocumentEditionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    edit: edit.clone(),
+                    matched_documents: None,
+                    edited_documents: None,
+                    rejected_documents: Vec::new(),
+                })
+            }
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -248,6 +267,7 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    rejected_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -262,6 +282,15 @@ impl KindWithContent {
                     deleted_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentEditionByFilter { index_uid: _, filter_expr, edit } => {
+                Some(Details::DocumentEditionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    edit: edit.clone(),
+                    matched_documents: Some(0),
+                    edited_documents: Some(0),
+                    rejected_documents: Vec::new(),
+                })
+            }
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -299,10 +328,12 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    rejected_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
+            KindWithContent::DocumentEditionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
@@ -395,6 +426,7 @@ impl std::error::Error for ParseTaskStatusError {}
 pub enum Kind {
     DocumentAdditionOrUpdate,
     DocumentDeletion,
+    DocumentEdition,
     SettingsUpdate,
     IndexCreation,
     IndexDeletion,
@@ -411,6 +443,7 @@ impl Kind {
         match self {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentDeletion
+            | Kind::DocumentEdition
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
@@ -428,6 +461,7 @@ impl Display for Kind {
         match self {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
+            Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
@@ -456,6 +490,8 @@ impl FromStr for Kind {
             Ok(Kind::DocumentAdditionOrUpdate)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentEdition") {
+            Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
@@ -495,16 +531,61 @@ impl std::error::Error for ParseTaskKindError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        #[serde(default)]
+        rejected_documents: Vec<RejectedDocument>,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+    },
+    IndexInfo {
+        primary_key: Option<String>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    DocumentEditionByFilter {
+        original_filter: String,
+        edit: milli::Object,
+        matched_documents: Option<u64>,
+        edited_documents: Option<u64>,
+        #[serde(default)]
+        rejected_documents: Vec<RejectedDocument>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
+}
+
+/// A document of a document edition that could not be indexed.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedDocument {
+    pub document_id: String,
+    pub error: ResponseError,
 }
 
 impl Details {
@@ -518,6 +599,7 @@ impl Details {
             Self::DocumentDeletionByFilter { deleted_documents, .. } => {
                 *deleted_documents = Some(0)
             }
+            Self::DocumentEditionByFilter { edited_documents, .. } => *edited_documents = Some(0),
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use actix_web::HttpRequest;
 use meilisearch_types::InstanceUid;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use super::{find_user_id, Analytics, DocumentDeletionKind, DocumentFetchKind};
 use crate::routes::indexes::documents::UpdateDocumentsQuery;
@@ -84,6 +84,14 @@ impl Analytics for MockAnalytics {
         _request: &HttpRequest,
     ) {
     }
+    fn edit_documents(
+        &self,
+        _documents_query: &UpdateDocumentsQuery,
+        _index_creation: bool,
+        _request: &HttpRequest,
+    ) {
+    }
+    fn edit_documents_by_filter(&self, _edit: &Map<String, Value>, _request: &HttpRequest) {}
     fn get_fetch_documents(&self, _documents_query: &DocumentFetchKind, _request: &HttpRequest) {}
     fn post_fetch_documents(&self, _documents_query: &DocumentFetchKind, _request: &HttpRequest) {}
     fn get_tasks(&self, _query: &TasksFilterQuery, _request: &HttpRequest) {}
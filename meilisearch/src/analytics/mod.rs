@@ -11,7 +11,7 @@ use meilisearch_types::InstanceUid;
 pub use mock_analytics::MockAnalytics;
 use once_cell::sync::Lazy;
 use platform_dirs::AppDirs;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::routes::indexes::documents::UpdateDocumentsQuery;
 use crate::routes::tasks::TasksFilterQuery;
@@ -118,6 +118,17 @@ pub trait Analytics: Sync + Send {
         request: &HttpRequest,
     );
 
+    // this method should be called to batch an edit documents request
+    fn edit_documents(
+        &self,
+        documents_query: &UpdateDocumentsQuery,
+        index_creation: bool,
+        request: &HttpRequest,
+    );
+
+    // this method should be called to batch an edit documents by filter request
+    fn edit_documents_by_filter(&self, edit: &Map<String, Value>, request: &HttpRequest);
+
     // this method should be called to aggregate the get tasks requests.
     fn get_tasks(&self, query: &TasksFilterQuery, request: &HttpRequest);
 
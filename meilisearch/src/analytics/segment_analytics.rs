@@ -1,4 +1,4 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::mem::take;
 use std::path::{Path, PathBuf};
@@ -11,13 +11,14 @@ use byte_unit::Byte;
 use http::header::CONTENT_TYPE;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::milli::update::FieldOperation;
 use meilisearch_types::InstanceUid;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use segment::message::{Identify, Track, User};
 use segment::{AutoBatcher, Batcher, HttpClient};
 use serde::Serialize;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use sysinfo::{DiskExt, System, SystemExt};
 use time::OffsetDateTime;
 use tokio::select;
@@ -34,8 +35,8 @@ use crate::routes::indexes::facet_search::FacetSearchQuery;
 use crate::routes::tasks::TasksFilterQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    FacetSearchResult, MatchingStrategy, SearchQuery, SearchQueryWithIndex, SearchResult,
-    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    FacetSearchResult, MatchingStrategy, QuerySyntax, SearchQuery, SearchQueryWithIndex,
+    SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
     DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
 };
 use crate::Opt;
@@ -77,6 +78,8 @@ pub enum AnalyticsMsg {
     AggregateAddDocuments(DocumentsAggregator),
     AggregateDeleteDocuments(DocumentsDeletionAggregator),
     AggregateUpdateDocuments(DocumentsAggregator),
+    AggregateEditDocuments(DocumentsAggregator),
+    AggregateEditDocumentsByFilter(DocumentsEditionByFilterAggregator),
     AggregateGetFetchDocuments(DocumentsFetchAggregator),
     AggregatePostFetchDocuments(DocumentsFetchAggregator),
     AggregateTasks(TasksAggregator),
@@ -148,6 +151,8 @@ impl SegmentAnalytics {
             add_documents_aggregator: DocumentsAggregator::default(),
             delete_documents_aggregator: DocumentsDeletionAggregator::default(),
             update_documents_aggregator: DocumentsAggregator::default(),
+            edit_documents_aggregator: DocumentsAggregator::default(),
+            edit_documents_by_filter_aggregator: DocumentsEditionByFilterAggregator::default(),
             get_fetch_documents_aggregator: DocumentsFetchAggregator::default(),
             post_fetch_documents_aggregator: DocumentsFetchAggregator::default(),
             get_tasks_aggregator: TasksAggregator::default(),
@@ -220,6 +225,21 @@ impl super::Analytics for SegmentAnalytics {
         let _ = self.sender.try_send(AnalyticsMsg::AggregateUpdateDocuments(aggregate));
     }
 
+    fn edit_documents(
+        &self,
+        documents_query: &UpdateDocumentsQuery,
+        index_creation: bool,
+        request: &HttpRequest,
+    ) {
+        let aggregate = DocumentsAggregator::from_query(documents_query, index_creation, request);
+        let _ = self.sender.try_send(AnalyticsMsg::AggregateEditDocuments(aggregate));
+    }
+
+    fn edit_documents_by_filter(&self, edit: &Map<String, Value>, request: &HttpRequest) {
+        let aggregate = DocumentsEditionByFilterAggregator::from_query(edit, request);
+        let _ = self.sender.try_send(AnalyticsMsg::AggregateEditDocumentsByFilter(aggregate));
+    }
+
     fn get_fetch_documents(&self, documents_query: &DocumentFetchKind, request: &HttpRequest) {
         let aggregate = DocumentsFetchAggregator::from_query(documents_query, request);
         let _ = self.sender.try_send(AnalyticsMsg::AggregateGetFetchDocuments(aggregate));
@@ -367,6 +387,8 @@ pub struct Segment {
     add_documents_aggregator: DocumentsAggregator,
     delete_documents_aggregator: DocumentsDeletionAggregator,
     update_documents_aggregator: DocumentsAggregator,
+    edit_documents_aggregator: DocumentsAggregator,
+    edit_documents_by_filter_aggregator: DocumentsEditionByFilterAggregator,
     get_fetch_documents_aggregator: DocumentsFetchAggregator,
     post_fetch_documents_aggregator: DocumentsFetchAggregator,
     get_tasks_aggregator: TasksAggregator,
@@ -430,6 +452,8 @@ impl Segment {
                         Some(AnalyticsMsg::AggregateAddDocuments(agreg)) => self.add_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateDeleteDocuments(agreg)) => self.delete_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateUpdateDocuments(agreg)) => self.update_documents_aggregator.aggregate(agreg),
+                        Some(AnalyticsMsg::AggregateEditDocuments(agreg)) => self.edit_documents_aggregator.aggregate(agreg),
+                        Some(AnalyticsMsg::AggregateEditDocumentsByFilter(agreg)) => self.edit_documents_by_filter_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateGetFetchDocuments(agreg)) => self.get_fetch_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregatePostFetchDocuments(agreg)) => self.post_fetch_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateTasks(agreg)) => self.get_tasks_aggregator.aggregate(agreg),
@@ -483,6 +507,8 @@ impl Segment {
             add_documents_aggregator,
             delete_documents_aggregator,
             update_documents_aggregator,
+            edit_documents_aggregator,
+            edit_documents_by_filter_aggregator,
             get_fetch_documents_aggregator,
             post_fetch_documents_aggregator,
             get_tasks_aggregator,
@@ -524,6 +550,16 @@ impl Segment {
         {
             let _ = self.batcher.push(update_documents).await;
         }
+        if let Some(edit_documents) =
+            take(edit_documents_aggregator).into_event(user, "Documents Edited")
+        {
+            let _ = self.batcher.push(edit_documents).await;
+        }
+        if let Some(edit_documents_by_filter) =
+            take(edit_documents_by_filter_aggregator).into_event(user, "Documents Edited By Filter")
+        {
+            let _ = self.batcher.push(edit_documents_by_filter).await;
+        }
         if let Some(get_fetch_documents) =
             take(get_fetch_documents_aggregator).into_event(user, "Documents Fetched GET")
         {
@@ -563,6 +599,24 @@ pub struct SearchAggregator {
     // every time a request has a filter, this field must be incremented by one
     sort_total_number_of_criteria: usize,
 
+    // ranking rules
+    // Whether the ranking rules of the index have been overridden at search time
+    query_ranking_rules: bool,
+
+    // typo tolerance
+    // Whether the typo tolerance settings of the index have been overridden at search time
+    query_typo_tolerance: bool,
+
+    // did you mean
+    // Whether a correction of the query has been requested
+    query_did_you_mean: bool,
+    // The number of searches whose results are those of the corrected query
+    total_auto_corrected: usize,
+
+    // locales
+    // The locales forced at search time
+    locales: BTreeSet<String>,
+
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
@@ -572,6 +626,22 @@ pub struct SearchAggregator {
     filter_total_number_of_criteria: usize,
     used_syntax: HashMap<String, usize>,
 
+    // facet_filters
+    // every time a search is done using facet-scoped filters
+    facet_filters_total_number_of_uses: usize,
+
+    // facet_buckets
+    // every time a search is done computing facet buckets
+    facet_buckets_total_number_of_uses: usize,
+
+    // facet_stats
+    // every time a search is done computing facet statistics
+    facet_stats_total_number_of_uses: usize,
+
+    // aggregations
+    // every time a search is done computing aggregations
+    aggregations_total_number_of_uses: usize,
+
     // attributes_to_search_on
     // every time a search is done using attributes_to_search_on
     attributes_to_search_on_total_number_of_uses: usize,
@@ -583,9 +653,14 @@ pub struct SearchAggregator {
     // vector
     // The maximum number of floats in a vector request
     max_vector_size: usize,
+    // Whether a named vector space has been searched
+    vector_space: bool,
+    // Whether a hybrid search has been performed
+    hybrid: bool,
 
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
+    query_syntax: HashMap<String, usize>,
 
     // pagination
     max_limit: usize,
@@ -617,6 +692,8 @@ impl SearchAggregator {
         let SearchQuery {
             q,
             vector,
+            vector_space,
+            hybrid,
             offset,
             limit,
             page,
@@ -629,12 +706,21 @@ impl SearchAggregator {
             show_ranking_score,
             show_ranking_score_details,
             filter,
+            facet_filters,
             sort,
+            ranking_rules,
+            locales,
+            typo_tolerance,
+            did_you_mean,
             facets: _,
+            facet_buckets,
+            facet_stats,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
         } = query;
 
@@ -650,6 +736,14 @@ impl SearchAggregator {
             ret.sort_sum_of_criteria_terms = sort.len();
         }
 
+        ret.query_ranking_rules = ranking_rules.is_some();
+        ret.query_typo_tolerance = typo_tolerance.is_some();
+        ret.query_did_you_mean = did_you_mean.is_some();
+
+        if let Some(locales) = locales {
+            ret.locales = locales.iter().cloned().collect();
+        }
+
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
             ret.filter_total_number_of_criteria = 1;
@@ -674,6 +768,26 @@ impl SearchAggregator {
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
+        // facet_filters
+        if facet_filters.is_some() {
+            ret.facet_filters_total_number_of_uses = 1;
+        }
+
+        // facet_buckets
+        if facet_buckets.is_some() {
+            ret.facet_buckets_total_number_of_uses = 1;
+        }
+
+        // facet_stats
+        if facet_stats.is_some() {
+            ret.facet_stats_total_number_of_uses = 1;
+        }
+
+        // aggregations
+        if aggregations.is_some() {
+            ret.aggregations_total_number_of_uses = 1;
+        }
+
         // attributes_to_search_on
         if attributes_to_search_on.is_some() {
             ret.attributes_to_search_on_total_number_of_uses = 1;
@@ -686,6 +800,8 @@ impl SearchAggregator {
         if let Some(ref vector) = vector {
             ret.max_vector_size = vector.len();
         }
+        ret.vector_space = vector_space.is_some();
+        ret.hybrid = hybrid.is_some();
 
         if query.is_finite_pagination() {
             let limit = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -699,6 +815,7 @@ impl SearchAggregator {
         }
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
+        ret.query_syntax.insert(format!("{:?}", query_syntax), 1);
 
         ret.highlight_pre_tag = *highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = *highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
@@ -721,9 +838,17 @@ impl SearchAggregator {
             hits_info: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_hierarchy: _,
+            facet_buckets: _,
+            aggregations: _,
+            did_you_mean: _,
+            auto_corrected,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
+        if *auto_corrected {
+            self.total_auto_corrected = self.total_auto_corrected.saturating_add(1);
+        }
         self.time_spent.push(*processing_time_ms as usize);
     }
 
@@ -738,15 +863,27 @@ impl SearchAggregator {
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
+            query_ranking_rules,
+            query_typo_tolerance,
+            query_did_you_mean,
+            total_auto_corrected,
+            locales,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            facet_filters_total_number_of_uses,
+            facet_buckets_total_number_of_uses,
+            facet_stats_total_number_of_uses,
+            aggregations_total_number_of_uses,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
             max_vector_size,
+            vector_space,
+            hybrid,
             matching_strategy,
+            query_syntax,
             max_limit,
             max_offset,
             finite_pagination,
@@ -785,6 +922,19 @@ impl SearchAggregator {
         self.sort_total_number_of_criteria =
             self.sort_total_number_of_criteria.saturating_add(sort_total_number_of_criteria);
 
+        // ranking rules
+        self.query_ranking_rules |= query_ranking_rules;
+
+        // typo tolerance
+        self.query_typo_tolerance |= query_typo_tolerance;
+
+        // did you mean
+        self.query_did_you_mean |= query_did_you_mean;
+        self.total_auto_corrected = self.total_auto_corrected.saturating_add(total_auto_corrected);
+
+        // locales
+        self.locales.extend(locales);
+
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
@@ -797,6 +947,25 @@ impl SearchAggregator {
             *used_syntax = used_syntax.saturating_add(value);
         }
 
+        // facet_filters
+        self.facet_filters_total_number_of_uses = self
+            .facet_filters_total_number_of_uses
+            .saturating_add(facet_filters_total_number_of_uses);
+
+        // facet_buckets
+        self.facet_buckets_total_number_of_uses = self
+            .facet_buckets_total_number_of_uses
+            .saturating_add(facet_buckets_total_number_of_uses);
+
+        // facet_stats
+        self.facet_stats_total_number_of_uses =
+            self.facet_stats_total_number_of_uses.saturating_add(facet_stats_total_number_of_uses);
+
+        // aggregations
+        self.aggregations_total_number_of_uses = self
+            .aggregations_total_number_of_uses
+            .saturating_add(aggregations_total_number_of_uses);
+
         // attributes_to_search_on
         self.attributes_to_search_on_total_number_of_uses = self
             .attributes_to_search_on_total_number_of_uses
@@ -807,6 +976,8 @@ impl SearchAggregator {
 
         // vector
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
+        self.vector_space |= vector_space;
+        self.hybrid |= hybrid;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -836,6 +1007,12 @@ impl SearchAggregator {
             *matching_strategy = matching_strategy.saturating_add(value);
         }
 
+        // query syntax
+        for (key, value) in query_syntax.into_iter() {
+            let query_syntax = self.query_syntax.entry(key).or_insert(0);
+            *query_syntax = query_syntax.saturating_add(value);
+        }
+
         // scoring
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
@@ -851,15 +1028,27 @@ impl SearchAggregator {
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
+            query_ranking_rules,
+            query_typo_tolerance,
+            query_did_you_mean,
+            total_auto_corrected,
+            locales,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            facet_filters_total_number_of_uses,
+            facet_buckets_total_number_of_uses,
+            facet_stats_total_number_of_uses,
+            aggregations_total_number_of_uses,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
             max_vector_size,
+            vector_space,
+            hybrid,
             matching_strategy,
+            query_syntax,
             max_limit,
             max_offset,
             finite_pagination,
@@ -899,12 +1088,35 @@ impl SearchAggregator {
                     "with_geoPoint": sort_with_geo_point,
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
+                "ranking_rules": {
+                    "with_query_ranking_rules": query_ranking_rules,
+                },
+                "typo_tolerance": {
+                    "with_query_typo_tolerance": query_typo_tolerance,
+                },
+                "did_you_mean": {
+                    "with_query_did_you_mean": query_did_you_mean,
+                    "total_auto_corrected": total_auto_corrected,
+                },
+                "locales": locales,
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
+                "facet_filters": {
+                   "total_number_of_uses": facet_filters_total_number_of_uses,
+                },
+                "facet_buckets": {
+                   "total_number_of_uses": facet_buckets_total_number_of_uses,
+                },
+                "facet_stats": {
+                   "total_number_of_uses": facet_stats_total_number_of_uses,
+                },
+                "aggregations": {
+                   "total_number_of_uses": aggregations_total_number_of_uses,
+                },
                 "attributes_to_search_on": {
                    "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
                 },
@@ -913,6 +1125,10 @@ impl SearchAggregator {
                 },
                 "vector": {
                     "max_vector_size": max_vector_size,
+                    "with_vector_space": vector_space,
+                },
+                "hybrid": {
+                    "enabled": hybrid,
                 },
                 "pagination": {
                    "max_limit": max_limit,
@@ -935,6 +1151,9 @@ impl SearchAggregator {
                 "matching_strategy": {
                     "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
+                "query_syntax": {
+                    "most_used_syntax": query_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
+                },
                 "scoring": {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
@@ -990,6 +1209,8 @@ impl MultiSearchAggregator {
                     index_uid,
                     q: _,
                     vector: _,
+                    vector_space: _,
+                    hybrid: _,
                     offset: _,
                     limit: _,
                     page: _,
@@ -1002,12 +1223,21 @@ impl MultiSearchAggregator {
                     show_ranking_score_details: _,
                     show_matches_position: _,
                     filter: _,
+                    facet_filters: _,
                     sort: _,
+                    ranking_rules: _,
+                    locales: _,
+                    typo_tolerance: _,
+                    did_you_mean: _,
                     facets: _,
+                    facet_buckets: _,
+                    facet_stats: _,
+                    aggregations: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
                     matching_strategy: _,
+                    query_syntax: _,
                     attributes_to_search_on: _,
                 } = query;
 
@@ -1151,9 +1381,11 @@ impl FacetSearchAggregator {
             facet_query: _,
             facet_name,
             vector,
+            vector_space,
             q,
             filter,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
         } = query;
 
@@ -1166,8 +1398,10 @@ impl FacetSearchAggregator {
 
         ret.additional_search_parameters_provided = q.is_some()
             || vector.is_some()
+            || vector_space.is_some()
             || filter.is_some()
             || *matching_strategy != MatchingStrategy::default()
+            || *query_syntax != QuerySyntax::default()
             || attributes_to_search_on.is_some();
 
         ret
@@ -1427,6 +1661,79 @@ impl DocumentsDeletionAggregator {
     }
 }
 
+#[derive(Default, Serialize)]
+pub struct DocumentsEditionByFilterAggregator {
+    #[serde(skip)]
+    timestamp: Option<OffsetDateTime>,
+
+    // context
+    #[serde(rename = "user-agent")]
+    user_agents: HashSet<String>,
+
+    #[serde(rename = "requests.total_received")]
+    total_received: usize,
+
+    // edit
+    #[serde(rename = "edit.plain_values")]
+    plain_values: bool,
+    #[serde(rename = "edit.field_operations")]
+    field_operations: HashSet<String>,
+}
+
+impl DocumentsEditionByFilterAggregator {
+    pub fn from_query(edit: &Map<String, Value>, request: &HttpRequest) -> Self {
+        let mut plain_values = false;
+        let mut field_operations = HashSet::new();
+        for value in edit.values() {
+            match FieldOperation::from_value(value) {
+                Ok(Some(operation)) => {
+                    field_operations.insert(operation.operator().to_string());
+                }
+                _ => plain_values = true,
+            }
+        }
+
+        Self {
+            timestamp: Some(OffsetDateTime::now_utc()),
+            user_agents: extract_user_agents(request).into_iter().collect(),
+            total_received: 1,
+            plain_values,
+            field_operations,
+        }
+    }
+
+    /// Aggregate one [DocumentsEditionByFilterAggregator] into another.
+    pub fn aggregate(&mut self, other: Self) {
+        let Self { timestamp, user_agents, total_received, plain_values, field_operations } = other;
+
+        if self.timestamp.is_none() {
+            self.timestamp = timestamp;
+        }
+
+        // we can't create a union because there is no `into_union` method
+        for user_agent in user_agents {
+            self.user_agents.insert(user_agent);
+        }
+        self.total_received = self.total_received.saturating_add(total_received);
+        self.plain_values |= plain_values;
+        self.field_operations.extend(field_operations);
+    }
+
+    pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
+        // if we had no timestamp it means we never encountered any events and
+        // thus we don't need to send this event.
+        let timestamp = self.timestamp?;
+
+        Some(Track {
+            timestamp: Some(timestamp),
+            user: user.clone(),
+            event: event_name.to_string(),
+            properties: serde_json::to_value(self).ok()?,
+            ..Default::default()
+        })
+    }
+}
+
 #[derive(Default, Serialize)]
 pub struct TasksAggregator {
     #[serde(skip)]
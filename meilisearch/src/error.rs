@@ -21,10 +21,24 @@ pub enum MeilisearchHttpError {
     InvalidContentType(String, Vec<String>),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
+    #[error("Synonym rule `{0}` not found.")]
+    SynonymRuleNotFound(String),
+    #[error("Query rule `{0}` not found.")]
+    QueryRuleNotFound(String),
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidExpression(&'static [&'static str], Value),
+    #[error("A `vector` must be provided when performing a `hybrid` search.")]
+    MissingSearchHybridVector,
+    #[error("The buckets of the `{0}` facet must be defined by exactly one of `interval`, `ranges` or `calendarInterval`.")]
+    InvalidSearchFacetBuckets(String),
+    #[error("The `interval` of the buckets of the `{0}` facet must be a positive number, but found `{1}`.")]
+    InvalidSearchFacetBucketsInterval(String, f64),
+    #[error("The percentiles of the `{0}` facet must be between 0 and 100, but found `{1}`.")]
+    InvalidSearchFacetStatsPercentile(String, f64),
+    #[error("The `{0}` aggregation is invalid: {1}.")]
+    InvalidSearchAggregation(String, String),
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("The provided payload reached the size limit. The maximum accepted payload size is {}.",  Byte::from_bytes(*.0 as u64).get_appropriate_unit(true))]
@@ -61,8 +75,19 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
+            MeilisearchHttpError::SynonymRuleNotFound(_) => Code::SynonymRuleNotFound,
+            MeilisearchHttpError::QueryRuleNotFound(_) => Code::QueryRuleNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
+            MeilisearchHttpError::MissingSearchHybridVector => Code::InvalidSearchHybridQuery,
+            MeilisearchHttpError::InvalidSearchFacetBuckets(_)
+            | MeilisearchHttpError::InvalidSearchFacetBucketsInterval(_, _) => {
+                Code::InvalidSearchFacetBuckets
+            }
+            MeilisearchHttpError::InvalidSearchFacetStatsPercentile(_, _) => {
+                Code::InvalidSearchFacetStats
+            }
+            MeilisearchHttpError::InvalidSearchAggregation(_, _) => Code::InvalidSearchAggregations,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
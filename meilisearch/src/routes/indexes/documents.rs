@@ -16,7 +16,7 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{FieldOperation, IndexDocumentsMethod};
 use meilisearch_types::milli::DocumentId;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
@@ -71,6 +71,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(SeqHandler(get_documents)))
             .route(web::post().to(SeqHandler(replace_documents)))
             .route(web::put().to(SeqHandler(update_documents)))
+            .route(web::patch().to(SeqHandler(edit_documents)))
             .route(web::delete().to(SeqHandler(clear_all_documents))),
     )
     // these routes need to be before the /documents/{document_id} to match properly
@@ -78,6 +79,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents_batch))),
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_filter))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
     .service(
         web::resource("/{document_id}")
@@ -323,6 +325,37 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+pub async fn edit_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    debug!("called with params: {:?}", params);
+    let params = params.into_inner();
+
+    analytics.edit_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = document_addition(
+        extract_mime_type(&req)?,
+        index_scheduler,
+        index_uid,
+        params.primary_key,
+        params.csv_delimiter,
+        body,
+        IndexDocumentsMethod::EditDocuments,
+        allow_index_creation,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn document_addition(
     mime_type: Option<Mime>,
@@ -519,6 +552,64 @@ pub async fn delete_documents_by_filter(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentEditionByFilter {
+    #[deserr(error = DeserrJsonError<InvalidDocumentFilter>, missing_field_error = DeserrJsonError::missing_document_filter)]
+    filter: Value,
+    #[deserr(error = DeserrJsonError<InvalidDocumentEdition>, missing_field_error = DeserrJsonError::missing_document_edition)]
+    edit: Value,
+}
+
+pub async fn edit_documents_by_filter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<DocumentEditionByFilter, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let DocumentEditionByFilter { filter, edit } = body.into_inner();
+
+    // we ensure the filter is well formed before enqueuing it
+    || -> Result<_, ResponseError> {
+        Ok(crate::search::parse_filter(&filter)?.ok_or(MeilisearchHttpError::EmptyFilter)?)
+    }()
+    // and whatever was the error, the error code should always be an InvalidDocumentFilter
+    .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentFilter))?;
+
+    // and that the edit is made of valid values and field operations
+    let edit = match edit {
+        Value::Object(edit) if !edit.is_empty() => edit,
+        _ => {
+            return Err(ResponseError::from_msg(
+                format!("The edit must be a non-empty object, but received `{edit}`."),
+                Code::InvalidDocumentEdition,
+            ))
+        }
+    };
+    for (field, value) in &edit {
+        if let Err(error) = FieldOperation::from_value(value) {
+            return Err(ResponseError::from_msg(
+                format!("Invalid edit of the `{field}` field. {error}"),
+                Code::InvalidDocumentEdition,
+            ));
+        }
+    }
+
+    analytics.edit_documents_by_filter(&edit, &req);
+
+    let task = KindWithContent::DocumentEditionByFilter { index_uid, filter_expr: filter, edit };
+
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
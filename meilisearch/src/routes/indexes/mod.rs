@@ -25,8 +25,11 @@ use crate::extractors::sequential_extractor::SeqHandler;
 
 pub mod documents;
 pub mod facet_search;
+pub mod rules;
 pub mod search;
 pub mod settings;
+pub mod suggest;
+pub mod synonyms;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -46,7 +49,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
-            .service(web::scope("/settings").configure(settings::configure)),
+            .service(web::scope("/settings").configure(settings::configure))
+            .service(web::scope("/synonyms").configure(synonyms::configure))
+            .service(web::scope("/rules").configure(rules::configure))
+            .service(web::scope("/suggest").configure(suggest::configure)),
     );
 }
 
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::settings::{
+    QueryRuleConditionView, QueryRuleConsequenceView, QueryRuleView, Settings,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::synonyms::register_settings_update;
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::{Pagination, PAGINATION_DEFAULT_LIMIT};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(list_query_rules)))
+            .route(web::post().to(SeqHandler(add_query_rules)))
+            .route(web::delete().to(SeqHandler(clear_query_rules))),
+    )
+    .service(
+        web::resource("/{rule_id}")
+            .route(web::get().to(SeqHandler(get_query_rule)))
+            .route(web::delete().to(SeqHandler(delete_query_rule))),
+    );
+}
+
+#[derive(Deserialize)]
+pub struct QueryRuleParam {
+    index_uid: String,
+    rule_id: String,
+}
+
+/// A query rule along with its id, as returned and accepted by the rules routes.
+#[derive(Debug, Clone, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleWithId {
+    #[deserr(error = DeserrJsonError<InvalidQueryRuleId>)]
+    pub id: String,
+    #[deserr(error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    pub conditions: Vec<QueryRuleConditionView>,
+    #[deserr(error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    pub consequence: QueryRuleConsequenceView,
+}
+
+impl QueryRuleWithId {
+    fn new(id: String, rule: QueryRuleView) -> Self {
+        let QueryRuleView { conditions, consequence } = rule;
+        QueryRuleWithId { id, conditions, consequence }
+    }
+
+    fn into_id_and_rule(self) -> (String, QueryRuleView) {
+        let QueryRuleWithId { id, conditions, consequence } = self;
+        (id, QueryRuleView { conditions, consequence })
+    }
+}
+
+#[derive(Deserr, Debug, Clone, Copy)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ListQueryRules {
+    #[deserr(default, error = DeserrQueryParamError<InvalidQueryRulesOffset>)]
+    pub offset: Param<usize>,
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidQueryRulesLimit>)]
+    pub limit: Param<usize>,
+}
+
+pub async fn list_query_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<ListQueryRules, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let ListQueryRules { offset, limit } = params.into_inner();
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let rules = index
+        .query_rules(&rtxn)?
+        .into_iter()
+        .map(|(id, rule)| QueryRuleWithId::new(id, rule.into()));
+
+    let ret = Pagination { offset: offset.0, limit: limit.0 }.auto_paginate_sized(rules);
+
+    debug!("returns: {:?}", ret);
+    Ok(HttpResponse::Ok().json(ret))
+}
+
+pub async fn get_query_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    path: web::Path<QueryRuleParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let QueryRuleParam { index_uid, rule_id } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let rule = index
+        .query_rules(&rtxn)?
+        .remove(&rule_id)
+        .ok_or_else(|| MeilisearchHttpError::QueryRuleNotFound(rule_id.clone()))?;
+    let rule = QueryRuleWithId::new(rule_id, rule.into());
+
+    debug!("returns: {:?}", rule);
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+pub async fn add_query_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Vec<QueryRuleWithId>, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let rules = body.into_inner();
+
+    analytics.publish(
+        "Query Rules Added".to_string(),
+        json!({
+            "query_rules": {
+                "total": rules.len(),
+                "pin": rules.iter().filter(|rule| !rule.consequence.pin.is_empty()).count(),
+                "hide": rules.iter().filter(|rule| !rule.consequence.hide.is_empty()).count(),
+                "boost": rules.iter().filter(|rule| !rule.consequence.boost.is_empty()).count(),
+                "filter": rules.iter().filter(|rule| rule.consequence.filter.is_some()).count(),
+            },
+        }),
+        Some(&req),
+    );
+
+    let patch = rules
+        .into_iter()
+        .map(|rule| {
+            let (id, rule) = rule.into_id_and_rule();
+            (id, Some(rule))
+        })
+        .collect();
+    let new_settings = Settings { query_rules: Setting::Set(patch), ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, false).await
+}
+
+pub async fn delete_query_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    path: web::Path<QueryRuleParam>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let QueryRuleParam { index_uid, rule_id } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    analytics.publish("Query Rule Deleted".to_string(), json!({}), Some(&req));
+
+    let patch = BTreeMap::from([(rule_id, None)]);
+    let new_settings = Settings { query_rules: Setting::Set(patch), ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, true).await
+}
+
+pub async fn clear_query_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    analytics.publish("Query Rules Cleared".to_string(), json!({}), Some(&req));
+
+    let new_settings = Settings { query_rules: Setting::Reset, ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, true).await
+}
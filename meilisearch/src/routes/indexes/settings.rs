@@ -7,6 +7,7 @@ use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::Setting;
 use meilisearch_types::settings::{settings, RankingRuleView, Settings, Unchecked};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
@@ -88,6 +89,11 @@ macro_rules! make_setting_route {
                     ..Default::default()
                 };
 
+                let new_settings = $crate::routes::indexes::settings::validate_settings(
+                    new_settings,
+                    &index_scheduler,
+                )?;
+
                 let allow_index_creation =
                     index_scheduler.filters().allow_index_creation(&index_uid);
 
@@ -235,6 +241,10 @@ make_setting_route!(
                     "disable_on_attributes": setting
                         .as_ref()
                         .and_then(|s| s.disable_on_attributes.as_ref().set().map(|m| !m.is_empty())),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set())
+                        .copied(),
                     "disable_on_words": setting
                         .as_ref()
                         .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
@@ -285,6 +295,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/attribute-weights",
+    put,
+    std::collections::BTreeMap<String, u16>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsAttributeWeights,
+    >,
+    attribute_weights,
+    "attributeWeights",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, u16>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "AttributeWeights Updated".to_string(),
+            json!({
+                "attribute_weights": {
+                    "total": setting.as_ref().map(|weights| weights.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/stop-words",
     put,
@@ -488,6 +523,7 @@ make_setting_route!(
                         s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
                     }),
                     "sort_facet_values_by_total": setting.as_ref().and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                    "hierarchical_facets_total": setting.as_ref().and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
                 },
             }),
             Some(req),
@@ -520,6 +556,157 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/vector-spaces",
+    put,
+    std::collections::BTreeMap<String, meilisearch_types::settings::VectorSpaceSettings>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVectorSpaces,
+    >,
+    vector_spaces,
+    "vectorSpaces",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, meilisearch_types::settings::VectorSpaceSettings>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "VectorSpaces Updated".to_string(),
+            json!({
+                "vector_spaces": {
+                    "total": setting.as_ref().map(|spaces| spaces.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::settings::LocalizedAttributesRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |rules: &Option<Vec<meilisearch_types::settings::LocalizedAttributesRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": rules.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming-locales",
+    put,
+    Vec<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemmingLocales,
+    >,
+    stemming_locales,
+    "stemmingLocales",
+    analytics,
+    |locales: &Option<Vec<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "StemmingLocales Updated".to_string(),
+            json!({
+                "stemming_locales": {
+                    "locales": locales,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/phonetic-attributes",
+    put,
+    Vec<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPhoneticAttributes,
+    >,
+    phonetic_attributes,
+    "phoneticAttributes",
+    analytics,
+    |attributes: &Option<Vec<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PhoneticAttributes Updated".to_string(),
+            json!({
+                "phonetic_attributes": {
+                    "total": attributes.as_ref().map(|attributes| attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/code-attributes",
+    put,
+    Vec<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsCodeAttributes,
+    >,
+    code_attributes,
+    "codeAttributes",
+    analytics,
+    |attributes: &Option<Vec<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "CodeAttributes Updated".to_string(),
+            json!({
+                "code_attributes": {
+                    "total": attributes.as_ref().map(|attributes| attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/suggestions",
+    patch,
+    meilisearch_types::settings::SuggestionsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSuggestions,
+    >,
+    suggestions,
+    "suggestions",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::SuggestionsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Suggestions Updated".to_string(),
+            json!({
+                "suggestions": {
+                    "attributes_total": setting.as_ref().and_then(|s| s.attributes.as_ref().set().map(|a| a.len())),
+                    "facet_attributes_total": setting.as_ref().and_then(|s| s.facet_attributes.as_ref().set().map(|a| a.len())),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -539,6 +726,7 @@ generate_configure!(
     sortable_attributes,
     displayed_attributes,
     searchable_attributes,
+    attribute_weights,
     distinct_attribute,
     stop_words,
     separator_tokens,
@@ -548,7 +736,13 @@ generate_configure!(
     ranking_rules,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    vector_spaces,
+    localized_attributes,
+    stemming_locales,
+    phonetic_attributes,
+    code_attributes,
+    suggestions
 );
 
 pub async fn update_all(
@@ -578,6 +772,9 @@ pub async fn update_all(
                 "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
                 "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
             },
+            "attribute_weights": {
+                "total": new_settings.attribute_weights.as_ref().set().map(|weights| weights.len()),
+            },
             "displayed_attributes": {
                 "total": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.len()),
                 "with_wildcard": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.iter().any(|displayed| displayed == "*")),
@@ -603,6 +800,11 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.disable_on_attributes.as_ref().set().map(|m| !m.is_empty())),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set())
+                    .copied(),
                 "disable_on_words": new_settings.typo_tolerance
                     .as_ref()
                     .set()
@@ -639,6 +841,10 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                "hierarchical_facets_total": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
             },
             "pagination": {
                 "max_total_hits": new_settings.pagination
@@ -652,10 +858,36 @@ pub async fn update_all(
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
+            "synonym_rules": {
+                "total": new_settings.synonym_rules.as_ref().set().map(|rules| rules.len()),
+            },
+            "vector_spaces": {
+                "total": new_settings.vector_spaces.as_ref().set().map(|spaces| spaces.len()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
+            "stemming_locales": {
+                "locales": new_settings.stemming_locales.as_ref().set(),
+            },
+            "phonetic_attributes": {
+                "total": new_settings.phonetic_attributes.as_ref().set().map(|attributes| attributes.len()),
+            },
+            "code_attributes": {
+                "total": new_settings.code_attributes.as_ref().set().map(|attributes| attributes.len()),
+            },
+            "query_rules": {
+                "total": new_settings.query_rules.as_ref().set().map(|rules| rules.len()),
+            },
+            "suggestions": {
+                "attributes_total": new_settings.suggestions.as_ref().set().and_then(|s| s.attributes.as_ref().set().map(|a| a.len())),
+                "facet_attributes_total": new_settings.suggestions.as_ref().set().and_then(|s| s.facet_attributes.as_ref().set().map(|a| a.len())),
+            },
         }),
         Some(&req),
     );
 
+    let new_settings = validate_settings(new_settings, &index_scheduler)?;
     let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
     let index_uid = IndexUid::try_from(index_uid.into_inner())?.into_inner();
     let task = KindWithContent::SettingsUpdate {
@@ -706,3 +938,14 @@ pub async fn delete_all(
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
+
+/// Ensures the settings only use the experimental features that are enabled.
+pub fn validate_settings(
+    settings: Settings<Unchecked>,
+    index_scheduler: &IndexScheduler,
+) -> Result<Settings<Unchecked>, ResponseError> {
+    if matches!(settings.vector_spaces, Setting::Set(_)) {
+        index_scheduler.features()?.check_vector("Passing `vectorSpaces` as a setting")?;
+    }
+    Ok(settings)
+}
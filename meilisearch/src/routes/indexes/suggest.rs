@@ -0,0 +1,61 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::DEFAULT_SUGGESTIONS_LIMIT;
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::search::{perform_suggest, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(suggest)));
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestQ>)]
+    pub q: Option<String>,
+    #[deserr(default = DEFAULT_SUGGESTIONS_LIMIT, error = DeserrJsonError<InvalidSuggestLimit>)]
+    pub limit: usize,
+    #[deserr(default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrJsonError<InvalidSuggestHighlightPreTag>)]
+    pub highlight_pre_tag: String,
+    #[deserr(default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrJsonError<InvalidSuggestHighlightPostTag>)]
+    pub highlight_post_tag: String,
+}
+
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SuggestQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let SuggestQuery { q, limit, highlight_pre_tag, highlight_post_tag } = params.into_inner();
+    debug!("suggest called with params: {:?} {:?}", q, limit);
+
+    analytics.publish(
+        "Suggestions Requested".to_string(),
+        json!({ "limit": limit, "query_length": q.as_ref().map(|q| q.len()) }),
+        Some(&req),
+    );
+
+    let index = index_scheduler.index(&index_uid)?;
+    let suggest_result = tokio::task::spawn_blocking(move || {
+        perform_suggest(&index, q, limit, &highlight_pre_tag, &highlight_post_tag)
+    })
+    .await??;
+
+    debug!("returns: {:?}", suggest_result);
+    Ok(HttpResponse::Ok().json(suggest_result))
+}
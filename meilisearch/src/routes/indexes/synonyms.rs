@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::settings::{Settings, SynonymRuleKindView, SynonymRuleView, Unchecked};
+use meilisearch_types::tasks::KindWithContent;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::{Pagination, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(list_synonym_rules)))
+            .route(web::post().to(SeqHandler(add_synonym_rules)))
+            .route(web::delete().to(SeqHandler(clear_synonym_rules))),
+    )
+    .service(
+        web::resource("/{rule_id}")
+            .route(web::get().to(SeqHandler(get_synonym_rule)))
+            .route(web::delete().to(SeqHandler(delete_synonym_rule))),
+    );
+}
+
+#[derive(Deserialize)]
+pub struct SynonymRuleParam {
+    index_uid: String,
+    rule_id: String,
+}
+
+/// A synonym rule along with its id, as returned and accepted by the synonyms routes.
+#[derive(Debug, Clone, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SynonymRuleWithId {
+    #[deserr(error = DeserrJsonError<InvalidSynonymRuleId>)]
+    pub id: String,
+    #[serde(rename = "type")]
+    #[deserr(rename = "type", error = DeserrJsonError<InvalidSettingsSynonymRules>)]
+    pub kind: SynonymRuleKindView,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonymRules>)]
+    pub input: Option<String>,
+    #[deserr(error = DeserrJsonError<InvalidSettingsSynonymRules>)]
+    pub synonyms: Vec<String>,
+}
+
+impl SynonymRuleWithId {
+    fn new(id: String, rule: SynonymRuleView) -> Self {
+        let SynonymRuleView { kind, input, synonyms } = rule;
+        SynonymRuleWithId { id, kind, input, synonyms }
+    }
+
+    fn into_id_and_rule(self) -> (String, SynonymRuleView) {
+        let SynonymRuleWithId { id, kind, input, synonyms } = self;
+        (id, SynonymRuleView { kind, input, synonyms })
+    }
+}
+
+#[derive(Deserr, Debug, Clone, Copy)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct ListSynonymRules {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSynonymRulesOffset>)]
+    pub offset: Param<usize>,
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidSynonymRulesLimit>)]
+    pub limit: Param<usize>,
+}
+
+pub async fn list_synonym_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<ListSynonymRules, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let ListSynonymRules { offset, limit } = params.into_inner();
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let rules = index
+        .synonym_rules(&rtxn)?
+        .into_iter()
+        .map(|(id, rule)| SynonymRuleWithId::new(id, rule.into()));
+
+    let ret = Pagination { offset: offset.0, limit: limit.0 }.auto_paginate_sized(rules);
+
+    debug!("returns: {:?}", ret);
+    Ok(HttpResponse::Ok().json(ret))
+}
+
+pub async fn get_synonym_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    path: web::Path<SynonymRuleParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let SynonymRuleParam { index_uid, rule_id } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let rule = index
+        .synonym_rule(&rtxn, &rule_id)?
+        .ok_or_else(|| MeilisearchHttpError::SynonymRuleNotFound(rule_id.clone()))?;
+    let rule = SynonymRuleWithId::new(rule_id, rule.into());
+
+    debug!("returns: {:?}", rule);
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+pub async fn add_synonym_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Vec<SynonymRuleWithId>, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let rules = body.into_inner();
+
+    analytics.publish(
+        "Synonym Rules Added".to_string(),
+        json!({
+            "synonym_rules": {
+                "total": rules.len(),
+                "one_way": rules.iter().filter(|rule| rule.kind == SynonymRuleKindView::OneWay).count(),
+                "equivalent": rules.iter().filter(|rule| rule.kind == SynonymRuleKindView::Equivalent).count(),
+            },
+        }),
+        Some(&req),
+    );
+
+    let patch = rules
+        .into_iter()
+        .map(|rule| {
+            let (id, rule) = rule.into_id_and_rule();
+            (id, Some(rule))
+        })
+        .collect();
+    let new_settings = Settings { synonym_rules_patch: Setting::Set(patch), ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, false).await
+}
+
+pub async fn delete_synonym_rule(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    path: web::Path<SynonymRuleParam>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let SynonymRuleParam { index_uid, rule_id } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    analytics.publish("Synonym Rule Deleted".to_string(), json!({}), Some(&req));
+
+    let patch = BTreeMap::from([(rule_id, None)]);
+    let new_settings = Settings { synonym_rules_patch: Setting::Set(patch), ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, true).await
+}
+
+pub async fn clear_synonym_rules(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    analytics.publish("Synonym Rules Cleared".to_string(), json!({}), Some(&req));
+
+    let new_settings = Settings { synonym_rules: Setting::Reset, ..Default::default() };
+
+    register_settings_update(index_scheduler, index_uid, new_settings, true).await
+}
+
+pub(super) async fn register_settings_update(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    new_settings: Settings<Unchecked>,
+    is_deletion: bool,
+) -> Result<HttpResponse, ResponseError> {
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: index_uid.to_string(),
+        new_settings: Box::new(new_settings),
+        is_deletion,
+        allow_index_creation,
+    };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
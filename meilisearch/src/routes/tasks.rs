@@ -8,10 +8,11 @@ use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::Object;
 use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
 use meilisearch_types::tasks::{
-    serialize_duration, Details, IndexSwap, Kind, KindWithContent, Status, Task,
+    serialize_duration, Details, IndexSwap, Kind, KindWithContent, RejectedDocument, Status, Task,
 };
 use serde::Serialize;
 use serde_json::json;
@@ -87,12 +88,20 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_documents: Option<Vec<RejectedDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edit: Option<Object>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_tasks: Option<Option<u64>>,
@@ -112,13 +121,17 @@ pub struct DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                rejected_documents,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                // only the document editions can reject documents
+                rejected_documents: (!rejected_documents.is_empty()).then_some(rejected_documents),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { settings } => {
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
             }
@@ -142,6 +155,20 @@ impl From<Details> for DetailsView {
                     ..DetailsView::default()
                 }
             }
+            Details::DocumentEditionByFilter {
+                original_filter,
+                edit,
+                matched_documents,
+                edited_documents,
+                rejected_documents,
+            } => DetailsView {
+                original_filter: Some(Some(original_filter)),
+                edit: Some(edit),
+                matched_documents: Some(matched_documents),
+                edited_documents: Some(edited_documents),
+                rejected_documents: (!rejected_documents.is_empty()).then_some(rejected_documents),
+                ..DetailsView::default()
+            },
             Details::ClearAll { deleted_documents } => {
                 DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
             }
@@ -725,7 +752,7 @@ mod tests {
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
             snapshot!(meili_snap::json_string!(err), @r###"
             {
-              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
+              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `documentEdition`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
               "code": "invalid_task_types",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_task_types"
@@ -15,9 +15,10 @@ use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::{
-    dot_product_similarity, FacetValueHit, InternalError, OrderBy, SearchForFacetValues,
+    Distance, FacetBucket, FacetHierarchyNode, FacetValueHit, InternalError, OrderBy,
+    SearchForFacetValues, SearchForSuggestions, Suggestion, DEFAULT_VECTOR_SPACE,
 };
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::settings::{RankingRuleView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::TokenizerBuilder;
 use milli::{
@@ -47,6 +48,10 @@ pub struct SearchQuery {
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
     pub vector: Option<Vec<f32>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVectorSpace>)]
+    pub vector_space: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
+    pub hybrid: Option<HybridQuery>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
     pub offset: usize,
     #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
@@ -71,10 +76,26 @@ pub struct SearchQuery {
     pub show_ranking_score_details: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetFilters>)]
+    pub facet_filters: Option<BTreeMap<String, Value>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+    pub typo_tolerance: Option<TypoToleranceQuery>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDidYouMean>)]
+    pub did_you_mean: Option<DidYouMeanQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetBuckets>)]
+    pub facet_buckets: Option<BTreeMap<String, FacetBucketsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetStats>)]
+    pub facet_stats: Option<BTreeMap<String, FacetStatsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregations>)]
+    pub aggregations: Option<BTreeMap<String, AggregationQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -83,6 +104,8 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>, default)]
+    pub query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
 }
@@ -93,6 +116,129 @@ impl SearchQuery {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchHybridQuery>, rename_all = camelCase, deny_unknown_fields)]
+pub struct HybridQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSemanticRatio>)]
+    pub semantic_ratio: SemanticRatio,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
+    pub fusion: FusionMethod,
+}
+
+/// The weight of the semantic results in a hybrid search, between `0.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f32) = TryFrom::try_from -> InvalidSearchSemanticRatio)]
+pub struct SemanticRatio(f32);
+
+impl Default for SemanticRatio {
+    fn default() -> Self {
+        SemanticRatio(0.5)
+    }
+}
+
+impl std::convert::TryFrom<f32> for SemanticRatio {
+    type Error = InvalidSearchSemanticRatio;
+
+    fn try_from(f: f32) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&f) {
+            Ok(SemanticRatio(f))
+        } else {
+            Err(InvalidSearchSemanticRatio)
+        }
+    }
+}
+
+impl std::ops::Deref for SemanticRatio {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum FusionMethod {
+    /// Merge the keyword and semantic results according to their ranks only
+    #[default]
+    ReciprocalRankFusion,
+    /// Merge the keyword and semantic results according to their scores
+    NormalizedScore,
+}
+
+impl From<FusionMethod> for milli::FusionMethod {
+    fn from(other: FusionMethod) -> Self {
+        match other {
+            FusionMethod::ReciprocalRankFusion => Self::ReciprocalRankFusion,
+            FusionMethod::NormalizedScore => Self::NormalizedScore,
+        }
+    }
+}
+
+/// Overrides the typo tolerance settings of the index for a single search.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchTypoTolerance>, rename_all = camelCase, deny_unknown_fields)]
+pub struct TypoToleranceQuery {
+    #[deserr(default)]
+    pub enabled: Option<bool>,
+    #[deserr(default)]
+    pub min_word_size_for_typos: Option<MinWordSizeForTyposQuery>,
+    #[deserr(default)]
+    pub disable_on_words: Option<BTreeSet<String>>,
+    #[deserr(default)]
+    pub disable_on_attributes: Option<BTreeSet<String>>,
+    #[deserr(default)]
+    pub disable_on_numbers: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchTypoTolerance>, rename_all = camelCase, deny_unknown_fields)]
+pub struct MinWordSizeForTyposQuery {
+    #[deserr(default)]
+    pub one_typo: Option<u8>,
+    #[deserr(default)]
+    pub two_typos: Option<u8>,
+}
+
+/// Suggests a correction of the query when it matches few documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchDidYouMean>, rename_all = camelCase, deny_unknown_fields)]
+pub struct DidYouMeanQuery {
+    #[deserr(default = milli::DEFAULT_DID_YOU_MEAN_MAX_HITS)]
+    pub max_hits: u64,
+    #[deserr(default)]
+    pub auto_correct: bool,
+}
+
+impl From<DidYouMeanQuery> for milli::DidYouMean {
+    fn from(DidYouMeanQuery { max_hits, auto_correct }: DidYouMeanQuery) -> Self {
+        milli::DidYouMean { max_hits, auto_correct }
+    }
+}
+
+impl From<TypoToleranceQuery> for milli::TypoToleranceOverride {
+    fn from(other: TypoToleranceQuery) -> Self {
+        let TypoToleranceQuery {
+            enabled,
+            min_word_size_for_typos,
+            disable_on_words,
+            disable_on_attributes,
+            disable_on_numbers,
+        } = other;
+        let MinWordSizeForTyposQuery { one_typo, two_typos } =
+            min_word_size_for_typos.unwrap_or_default();
+
+        Self {
+            enabled,
+            min_word_len_one_typo: one_typo,
+            min_word_len_two_typos: two_typos,
+            disable_on_words,
+            disable_on_attributes,
+            disable_on_numbers,
+        }
+    }
+}
+
 /// A `SearchQuery` + an index UID.
 // This struct contains the fields of `SearchQuery` inline.
 // This is because neither deserr nor serde support `flatten` when using `deny_unknown_fields.
@@ -106,6 +252,10 @@ pub struct SearchQueryWithIndex {
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub vector: Option<Vec<f32>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVectorSpace>)]
+    pub vector_space: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
+    pub hybrid: Option<HybridQuery>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
     pub offset: usize,
     #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
@@ -130,10 +280,26 @@ pub struct SearchQueryWithIndex {
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetFilters>)]
+    pub facet_filters: Option<BTreeMap<String, Value>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchTypoTolerance>)]
+    pub typo_tolerance: Option<TypoToleranceQuery>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDidYouMean>)]
+    pub did_you_mean: Option<DidYouMeanQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetBuckets>)]
+    pub facet_buckets: Option<BTreeMap<String, FacetBucketsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetStats>)]
+    pub facet_stats: Option<BTreeMap<String, FacetStatsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregations>)]
+    pub aggregations: Option<BTreeMap<String, AggregationQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -142,6 +308,8 @@ pub struct SearchQueryWithIndex {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>, default)]
+    pub query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
 }
@@ -152,6 +320,8 @@ impl SearchQueryWithIndex {
             index_uid,
             q,
             vector,
+            vector_space,
+            hybrid,
             offset,
             limit,
             page,
@@ -164,12 +334,21 @@ impl SearchQueryWithIndex {
             show_ranking_score_details,
             show_matches_position,
             filter,
+            facet_filters,
             sort,
+            ranking_rules,
+            locales,
+            typo_tolerance,
+            did_you_mean,
             facets,
+            facet_buckets,
+            facet_stats,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
         } = self;
         (
@@ -177,6 +356,8 @@ impl SearchQueryWithIndex {
             SearchQuery {
                 q,
                 vector,
+                vector_space,
+                hybrid,
                 offset,
                 limit,
                 page,
@@ -189,12 +370,21 @@ impl SearchQueryWithIndex {
                 show_ranking_score_details,
                 show_matches_position,
                 filter,
+                facet_filters,
                 sort,
+                ranking_rules,
+                locales,
+                typo_tolerance,
+                did_you_mean,
                 facets,
+                facet_buckets,
+                facet_stats,
+                aggregations,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
+                query_syntax,
                 attributes_to_search_on,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
@@ -227,6 +417,240 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum QuerySyntax {
+    /// The query is made of words and quoted phrases.
+    #[default]
+    Plain,
+    /// The words, phrases and groups in parentheses of the query can be required with `+`,
+    /// excluded with `-` and joined with the `OR` operator.
+    Boolean,
+}
+
+impl From<QuerySyntax> for milli::QuerySyntax {
+    fn from(other: QuerySyntax) -> Self {
+        match other {
+            QuerySyntax::Plain => Self::Plain,
+            QuerySyntax::Boolean => Self::Boolean,
+        }
+    }
+}
+
+/// How the values of a numeric facet are grouped into buckets,
+/// exactly one of the fields must be set.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetBuckets>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetBucketsQuery {
+    /// The interval of a histogram.
+    #[deserr(default)]
+    pub interval: Option<f64>,
+    #[deserr(default)]
+    pub ranges: Option<Vec<FacetRangeQuery>>,
+    /// The calendar interval of a date histogram, the values being timestamps in seconds.
+    #[deserr(default)]
+    pub calendar_interval: Option<CalendarInterval>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetBuckets>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum CalendarInterval {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl From<CalendarInterval> for milli::CalendarInterval {
+    fn from(other: CalendarInterval) -> Self {
+        match other {
+            CalendarInterval::Minute => Self::Minute,
+            CalendarInterval::Hour => Self::Hour,
+            CalendarInterval::Day => Self::Day,
+            CalendarInterval::Week => Self::Week,
+            CalendarInterval::Month => Self::Month,
+            CalendarInterval::Quarter => Self::Quarter,
+            CalendarInterval::Year => Self::Year,
+        }
+    }
+}
+
+impl FacetBucketsQuery {
+    fn into_facet_buckets(self, facet: &str) -> Result<milli::FacetBuckets, MeilisearchHttpError> {
+        match self {
+            FacetBucketsQuery {
+                interval: Some(interval),
+                ranges: None,
+                calendar_interval: None,
+            } => {
+                if interval.is_finite() && interval > 0.0 {
+                    Ok(milli::FacetBuckets::Histogram { interval })
+                } else {
+                    Err(MeilisearchHttpError::InvalidSearchFacetBucketsInterval(
+                        facet.to_string(),
+                        interval,
+                    ))
+                }
+            }
+            FacetBucketsQuery { interval: None, ranges: Some(ranges), calendar_interval: None } => {
+                Ok(milli::FacetBuckets::Ranges(
+                    ranges
+                        .into_iter()
+                        .map(|FacetRangeQuery { from, to }| milli::FacetRange { from, to })
+                        .collect(),
+                ))
+            }
+            FacetBucketsQuery {
+                interval: None,
+                ranges: None,
+                calendar_interval: Some(interval),
+            } => Ok(milli::FacetBuckets::DateHistogram { interval: interval.into() }),
+            _ => Err(MeilisearchHttpError::InvalidSearchFacetBuckets(facet.to_string())),
+        }
+    }
+}
+
+/// The aggregations computed on the values of a facet in addition to its bounds.
+#[derive(Debug, Clone, Default, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetStats>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetStatsQuery {
+    /// The percentiles to compute, between 0 and 100.
+    #[deserr(default)]
+    pub percentiles: Vec<f64>,
+}
+
+impl FacetStatsQuery {
+    fn into_facet_stats_options(
+        self,
+        facet: &str,
+    ) -> Result<milli::FacetStatsOptions, MeilisearchHttpError> {
+        let FacetStatsQuery { percentiles } = self;
+        match percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+            Some(&percentile) => Err(MeilisearchHttpError::InvalidSearchFacetStatsPercentile(
+                facet.to_string(),
+                percentile,
+            )),
+            None => Ok(milli::FacetStatsOptions { percentiles }),
+        }
+    }
+}
+
+/// An aggregation of the values of a facet, exactly one of `terms`, `ranges` and `stats`
+/// must be set and the buckets of the first two are aggregated by the `aggregations`.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+pub struct AggregationQuery {
+    #[deserr(default)]
+    pub terms: Option<TermsAggregationQuery>,
+    #[deserr(default)]
+    pub ranges: Option<RangesAggregationQuery>,
+    #[deserr(default)]
+    pub stats: Option<StatsAggregationQuery>,
+    #[deserr(default)]
+    pub aggregations: BTreeMap<String, AggregationQuery>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+pub struct TermsAggregationQuery {
+    pub facet: String,
+    /// The maximum number of buckets, `maxValuesPerFacet` by default.
+    #[deserr(default)]
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+pub struct RangesAggregationQuery {
+    pub facet: String,
+    pub ranges: Vec<AggregationRangeQuery>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+pub struct AggregationRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+pub struct StatsAggregationQuery {
+    pub facet: String,
+    /// The percentiles to compute, between 0 and 100.
+    #[deserr(default)]
+    pub percentiles: Vec<f64>,
+}
+
+impl AggregationQuery {
+    fn into_aggregations(
+        aggregations: BTreeMap<String, AggregationQuery>,
+    ) -> Result<BTreeMap<String, milli::Aggregation>, MeilisearchHttpError> {
+        aggregations
+            .into_iter()
+            .map(|(name, aggregation)| {
+                let aggregation = aggregation.into_aggregation(&name)?;
+                Ok((name, aggregation))
+            })
+            .collect()
+    }
+
+    fn into_aggregation(self, name: &str) -> Result<milli::Aggregation, MeilisearchHttpError> {
+        let invalid = |reason: String| {
+            MeilisearchHttpError::InvalidSearchAggregation(name.to_string(), reason)
+        };
+
+        let AggregationQuery { terms, ranges, stats, aggregations } = self;
+        match (terms, ranges, stats) {
+            (Some(TermsAggregationQuery { facet, size }), None, None) => {
+                let aggregations = Self::into_aggregations(aggregations)?;
+                Ok(milli::Aggregation::Terms { facet, size, aggregations })
+            }
+            (None, Some(RangesAggregationQuery { facet, ranges }), None) => {
+                let ranges = ranges
+                    .into_iter()
+                    .map(|AggregationRangeQuery { from, to }| milli::FacetRange { from, to })
+                    .collect();
+                let aggregations = Self::into_aggregations(aggregations)?;
+                Ok(milli::Aggregation::Ranges { facet, ranges, aggregations })
+            }
+            (None, None, Some(StatsAggregationQuery { facet, percentiles })) => {
+                if !aggregations.is_empty() {
+                    return Err(invalid(String::from(
+                        "a `stats` aggregation cannot have sub-aggregations",
+                    )));
+                }
+                match percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+                    Some(percentile) => Err(invalid(format!(
+                        "the percentiles must be between 0 and 100, but found `{percentile}`"
+                    ))),
+                    None => Ok(milli::Aggregation::Stats {
+                        facet,
+                        options: milli::FacetStatsOptions { percentiles },
+                    }),
+                }
+            }
+            _ => Err(invalid(String::from(
+                "exactly one of `terms`, `ranges` or `stats` must be defined",
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum FacetValuesSort {
@@ -277,6 +701,16 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_hierarchy: Option<BTreeMap<String, Vec<FacetHierarchyNode>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_buckets: Option<BTreeMap<String, Vec<FacetBucket>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregations: Option<BTreeMap<String, AggregationResult>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_you_mean: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub auto_corrected: bool,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -296,10 +730,104 @@ pub enum HitsInfo {
     OffsetLimit { limit: usize, offset: usize, estimated_total_hits: usize },
 }
 
+/// The statistics of a facet, only its bounds unless it is part of the `facetStats` parameter.
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct FacetStats {
-    pub min: f64,
-    pub max: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentiles: Option<IndexMap<String, f64>>,
+}
+
+impl FacetStats {
+    fn from_bounds(min: f64, max: f64) -> Self {
+        FacetStats {
+            min: Some(min),
+            max: Some(max),
+            count: None,
+            sum: None,
+            avg: None,
+            distinct: None,
+            percentiles: None,
+        }
+    }
+}
+
+impl From<milli::FacetValuesStats> for FacetStats {
+    fn from(stats: milli::FacetValuesStats) -> Self {
+        let avg = stats.avg();
+        let milli::FacetValuesStats { min, max, count, sum, distinct, percentiles } = stats;
+        FacetStats {
+            min,
+            max,
+            count: Some(count),
+            sum: Some(sum),
+            avg,
+            distinct: Some(distinct),
+            percentiles: Some(
+                percentiles.into_iter().map(|(p, value)| (p.to_string(), value)).collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AggregationResult {
+    Buckets { buckets: Vec<AggregationBucket> },
+    Stats(FacetStats),
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AggregationBucket {
+    #[serde(flatten)]
+    pub key: BucketKey,
+    pub count: u64,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aggregations: BTreeMap<String, AggregationResult>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum BucketKey {
+    Term { value: String },
+    Range { from: Option<f64>, to: Option<f64> },
+}
+
+impl From<milli::AggregationResult> for AggregationResult {
+    fn from(result: milli::AggregationResult) -> Self {
+        match result {
+            milli::AggregationResult::Buckets(buckets) => AggregationResult::Buckets {
+                buckets: buckets
+                    .into_iter()
+                    .map(|milli::AggregationBucket { key, count, aggregations }| {
+                        let key = match key {
+                            milli::BucketKey::Term(value) => BucketKey::Term { value },
+                            milli::BucketKey::Range(milli::FacetRange { from, to }) => {
+                                BucketKey::Range { from, to }
+                            }
+                        };
+                        let aggregations = aggregations
+                            .into_iter()
+                            .map(|(name, result)| (name, result.into()))
+                            .collect();
+                        AggregationBucket { key, count, aggregations }
+                    })
+                    .collect(),
+            },
+            milli::AggregationResult::Stats(stats) => AggregationResult::Stats(stats.into()),
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -310,6 +838,25 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionHit {
+    /// The normalized phrase completing the query.
+    pub suggestion: String,
+    /// The number of documents in which the phrase appears.
+    pub frequency: u64,
+    /// The phrase with the part matching the query between the highlight tags.
+    pub highlighted: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<SuggestionHit>,
+    pub query: Option<String>,
+    pub processing_time_ms: u128,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
     query.filter = match (query.filter.take(), rules.filter) {
@@ -338,7 +885,7 @@ fn prepare_search<'t>(
 ) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
     let mut search = index.search(rtxn);
 
-    if query.vector.is_some() && query.q.is_some() {
+    if query.vector.is_some() && query.q.is_some() && query.hybrid.is_none() {
         warn!("Ignoring the query string `q` when used with the `vector` parameter.");
     }
 
@@ -346,6 +893,10 @@ fn prepare_search<'t>(
         search.vector(vector.clone());
     }
 
+    if let Some(ref vector_space) = query.vector_space {
+        search.vector_space(vector_space);
+    }
+
     if let Some(ref query) = query.q {
         search.query(query);
     }
@@ -356,6 +907,7 @@ fn prepare_search<'t>(
 
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    search.query_syntax(query.query_syntax.into());
 
     let max_total_hits = index
         .pagination_max_total_hits(rtxn)
@@ -373,8 +925,15 @@ fn prepare_search<'t>(
         features.check_score_details()?;
     }
 
-    if query.vector.is_some() {
-        features.check_vector()?;
+    if query.vector.is_some() || query.vector_space.is_some() || query.hybrid.is_some() {
+        features.check_vector("Passing `vector` as a query parameter")?;
+    }
+
+    if let Some(ref hybrid) = query.hybrid {
+        if query.vector.is_none() {
+            return Err(MeilisearchHttpError::MissingSearchHybridVector);
+        }
+        search.hybrid(*hybrid.semantic_ratio, hybrid.fusion.into());
     }
 
     // compute the offset on the limit depending on the pagination mode.
@@ -402,6 +961,15 @@ fn prepare_search<'t>(
         }
     }
 
+    // the facet-scoped filters are ignored by the distribution of their facet.
+    if let Some(ref facet_filters) = query.facet_filters {
+        for (facet, filter) in facet_filters {
+            if let Some(filter) = parse_filter(filter)? {
+                search.facet_filter(facet, filter);
+            }
+        }
+    }
+
     if let Some(ref sort) = query.sort {
         let sort = match sort.iter().map(|s| AscDesc::from_str(s)).collect() {
             Ok(sorts) => sorts,
@@ -413,6 +981,22 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
+    if let Some(ref ranking_rules) = query.ranking_rules {
+        search.ranking_rules(ranking_rules.iter().cloned().map(Into::into).collect());
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.clone());
+    }
+
+    if let Some(ref typo_tolerance) = query.typo_tolerance {
+        search.typo_tolerance(typo_tolerance.clone().into());
+    }
+
+    if let Some(did_you_mean) = query.did_you_mean {
+        search.did_you_mean(did_you_mean.into());
+    }
+
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
@@ -427,11 +1011,55 @@ pub fn perform_search(
     let (search, is_finite_pagination, max_total_hits, offset) =
         prepare_search(index, &rtxn, &query, features)?;
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, document_scores, .. } =
-        search.execute()?;
+    let facet_buckets = match query.facet_buckets.clone() {
+        Some(facet_buckets) => Some(
+            facet_buckets
+                .into_iter()
+                .map(|(facet, buckets)| {
+                    let buckets = buckets.into_facet_buckets(&facet)?;
+                    Ok((facet, buckets))
+                })
+                .collect::<Result<BTreeMap<_, _>, MeilisearchHttpError>>()?,
+        ),
+        None => None,
+    };
+
+    let facet_stats = match query.facet_stats.clone() {
+        Some(facet_stats) => Some(
+            facet_stats
+                .into_iter()
+                .map(|(facet, stats)| {
+                    let options = stats.into_facet_stats_options(&facet)?;
+                    Ok((facet, options))
+                })
+                .collect::<Result<BTreeMap<_, _>, MeilisearchHttpError>>()?,
+        ),
+        None => None,
+    };
+
+    let aggregations = match query.aggregations.clone() {
+        Some(aggregations) => Some(AggregationQuery::into_aggregations(aggregations)?),
+        None => None,
+    };
+
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        candidates,
+        facet_candidates,
+        document_scores,
+        did_you_mean,
+        auto_corrected,
+    } = search.execute()?;
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
+    let vector_space = query.vector_space.as_deref().unwrap_or(DEFAULT_VECTOR_SPACE);
+    let vector_distance = index
+        .vector_spaces(&rtxn)?
+        .get(vector_space)
+        .map_or(Distance::default(), |space| space.distance);
+
     let displayed_ids = index
         .displayed_fields_ids(&rtxn)?
         .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
@@ -539,7 +1167,9 @@ pub fn perform_search(
 
         let semantic_score = match query.vector.as_ref() {
             Some(vector) => match extract_field("_vectors", &fields_ids_map, obkv)? {
-                Some(vectors) => compute_semantic_score(vector, vectors)?,
+                Some(vectors) => {
+                    compute_semantic_score(vector, vector_space, vector_distance, vectors)?
+                }
                 None => None,
             },
             None => None,
@@ -579,49 +1209,87 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit: query.limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = match query.facets {
-        Some(ref fields) => {
+    let compute_facets = query.facets.is_some()
+        || facet_buckets.is_some()
+        || facet_stats.is_some()
+        || aggregations.is_some();
+    let (facet_distribution, facet_stats, facet_hierarchy, facet_buckets, aggregations) =
+        if compute_facets {
             let mut facet_distribution = index.facets_distribution(&rtxn);
 
             let max_values_by_facet = index
                 .max_values_per_facet(&rtxn)
                 .map_err(milli::Error::from)?
                 .unwrap_or(DEFAULT_VALUES_PER_FACET);
-            facet_distribution.max_values_per_facet(max_values_by_facet);
-
-            let sort_facet_values_by =
-                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
-            let default_sort_facet_values_by =
-                sort_facet_values_by.get("*").copied().unwrap_or_default();
-
-            if fields.iter().all(|f| f != "*") {
-                let fields: Vec<_> = fields
-                    .iter()
-                    .map(|n| {
-                        (
-                            n,
-                            sort_facet_values_by
-                                .get(n)
-                                .copied()
-                                .unwrap_or(default_sort_facet_values_by),
-                        )
-                    })
-                    .collect();
-                facet_distribution.facets(fields);
-            }
-            let distribution = facet_distribution
+            facet_distribution
+                .max_values_per_facet(max_values_by_facet)
                 .candidates(candidates)
-                .default_order_by(default_sort_facet_values_by)
-                .execute()?;
-            let stats = facet_distribution.compute_stats()?;
-            (Some(distribution), Some(stats))
-        }
-        None => (None, None),
-    };
+                .facet_candidates(facet_candidates);
+
+            let (distribution, mut stats, hierarchy) = match query.facets {
+                Some(ref fields) => {
+                    let sort_facet_values_by =
+                        index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
+                    let default_sort_facet_values_by =
+                        sort_facet_values_by.get("*").copied().unwrap_or_default();
+
+                    if fields.iter().all(|f| f != "*") {
+                        let fields: Vec<_> = fields
+                            .iter()
+                            .map(|n| {
+                                (
+                                    n,
+                                    sort_facet_values_by
+                                        .get(n)
+                                        .copied()
+                                        .unwrap_or(default_sort_facet_values_by),
+                                )
+                            })
+                            .collect();
+                        facet_distribution.facets(fields);
+                    }
+                    let distribution = facet_distribution
+                        .default_order_by(default_sort_facet_values_by)
+                        .execute()?;
+                    let stats: BTreeMap<_, _> = facet_distribution
+                        .compute_stats()?
+                        .into_iter()
+                        .map(|(facet, (min, max))| (facet, FacetStats::from_bounds(min, max)))
+                        .collect();
+                    // the hierarchies are only returned when a hierarchical facet is requested.
+                    let hierarchy = facet_distribution.compute_hierarchies()?;
+                    let hierarchy = Some(hierarchy).filter(|hierarchy| !hierarchy.is_empty());
+                    (Some(distribution), Some(stats), hierarchy)
+                }
+                None => (None, None, None),
+            };
 
-    let facet_stats = facet_stats.map(|stats| {
-        stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
-    });
+            // the statistics requested with `facetStats` replace the bounds of their facet.
+            if let Some(ref facet_stats) = facet_stats {
+                let values_stats = facet_distribution.compute_values_stats(facet_stats)?;
+                stats
+                    .get_or_insert_with(BTreeMap::new)
+                    .extend(values_stats.into_iter().map(|(facet, values)| (facet, values.into())));
+            }
+
+            let buckets = match facet_buckets {
+                Some(ref facet_buckets) => Some(facet_distribution.compute_buckets(facet_buckets)?),
+                None => None,
+            };
+            let aggregations = match aggregations {
+                Some(ref aggregations) => Some(
+                    facet_distribution
+                        .compute_aggregations(aggregations)?
+                        .into_iter()
+                        .map(|(name, result)| (name, result.into()))
+                        .collect(),
+                ),
+                None => None,
+            };
+            (distribution, stats, hierarchy, buckets, aggregations)
+        } else {
+            (None, None, None, None, None)
+        };
 
     let result = SearchResult {
         hits: documents,
@@ -631,6 +1299,11 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_hierarchy,
+        facet_buckets,
+        aggregations,
+        did_you_mean,
+        auto_corrected,
     };
     Ok(result)
 }
@@ -658,6 +1331,44 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    query: Option<String>,
+    limit: usize,
+    highlight_pre_tag: &str,
+    highlight_post_tag: &str,
+) -> Result<SuggestResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let mut search = SearchForSuggestions::new(index, &rtxn);
+    search.limit(limit);
+    if let Some(query) = &query {
+        search.query(query);
+    }
+
+    let suggestions = search
+        .execute()?
+        .into_iter()
+        .map(|Suggestion { phrase, frequency, matched_len, .. }| {
+            let highlighted = match matched_len {
+                0 => phrase.clone(),
+                len => {
+                    let (matched, rest) = phrase.split_at(len);
+                    format!("{highlight_pre_tag}{matched}{highlight_post_tag}{rest}")
+                }
+            };
+            SuggestionHit { suggestion: phrase, frequency, highlighted }
+        })
+        .collect();
+
+    Ok(SuggestResult {
+        suggestions,
+        query,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -674,14 +1385,25 @@ fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     }
 }
 
-fn compute_semantic_score(query: &[f32], vectors: Value) -> milli::Result<Option<f32>> {
+fn compute_semantic_score(
+    query: &[f32],
+    vector_space: &str,
+    distance: Distance,
+    vectors: Value,
+) -> milli::Result<Option<f32>> {
+    // the vectors are either those of the default vector space or an object of named vector spaces.
+    let vectors = match vectors {
+        Value::Object(mut spaces) => spaces.remove(vector_space).unwrap_or(Value::Null),
+        vectors if vector_space == DEFAULT_VECTOR_SPACE => vectors,
+        _ => Value::Null,
+    };
     let vectors = serde_json::from_value(vectors)
         .map(VectorOrArrayOfVectors::into_array_of_vectors)
         .map_err(InternalError::SerdeJson)?;
     Ok(vectors
         .into_iter()
         .flatten()
-        .map(|v| OrderedFloat(dot_product_similarity(query, &v)))
+        .map(|v| OrderedFloat(distance.vectors_similarity(query, &v)))
         .max()
         .map(OrderedFloat::into_inner))
 }
@@ -15,12 +15,15 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
+            ("PATCH",   "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/edit") =>                 hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
             ("GET",     "/indexes/products/documents/0") =>                    hashset!{"documents.get", "documents.*", "*"},
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete-batch") =>         hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete") =>               hashset!{"documents.delete", "documents.*", "*"},
+            ("POST",    "/indexes/products/suggest") =>                        hashset!{"search", "*"},
             ("GET",     "/tasks") =>                                           hashset!{"tasks.get", "tasks.*", "*"},
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
@@ -51,6 +54,16 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PUT",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.update", "settings.*", "*"},
+            ("GET",     "/indexes/products/synonyms") =>                       hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/synonyms/rule-id") =>               hashset!{"settings.get", "settings.*", "*"},
+            ("POST",    "/indexes/products/synonyms") =>                       hashset!{"settings.update", "settings.*", "*"},
+            ("DELETE",  "/indexes/products/synonyms") =>                       hashset!{"settings.update", "settings.*", "*"},
+            ("DELETE",  "/indexes/products/synonyms/rule-id") =>               hashset!{"settings.update", "settings.*", "*"},
+            ("GET",     "/indexes/products/rules") =>                          hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/rules/rule-id") =>                  hashset!{"settings.get", "settings.*", "*"},
+            ("POST",    "/indexes/products/rules") =>                          hashset!{"settings.update", "settings.*", "*"},
+            ("DELETE",  "/indexes/products/rules") =>                          hashset!{"settings.update", "settings.*", "*"},
+            ("DELETE",  "/indexes/products/rules/rule-id") =>                  hashset!{"settings.update", "settings.*", "*"},
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
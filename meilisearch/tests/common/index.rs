@@ -236,6 +236,25 @@ impl Index<'_> {
         self.service.post_encoded(url, body, self.encoder).await
     }
 
+    pub async fn edit_documents(
+        &self,
+        documents: Value,
+        primary_key: Option<&str>,
+    ) -> (Value, StatusCode) {
+        let url = match primary_key {
+            Some(key) => {
+                format!("/indexes/{}/documents?primaryKey={}", urlencode(self.uid.as_ref()), key)
+            }
+            None => format!("/indexes/{}/documents", urlencode(self.uid.as_ref())),
+        };
+        self.service.patch_encoded(url, documents, self.encoder).await
+    }
+
+    pub async fn edit_document_by_filter(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn clear_all_documents(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -385,6 +404,61 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn suggest(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/suggest", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    pub async fn synonym_rules(&self, options: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/synonyms?{}", urlencode(self.uid.as_ref()), options);
+        self.service.get(url).await
+    }
+
+    pub async fn synonym_rule(&self, rule_id: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/synonyms/{}", urlencode(self.uid.as_ref()), rule_id);
+        self.service.get(url).await
+    }
+
+    pub async fn add_synonym_rules(&self, rules: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/synonyms", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, rules, self.encoder).await
+    }
+
+    pub async fn delete_synonym_rule(&self, rule_id: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/synonyms/{}", urlencode(self.uid.as_ref()), rule_id);
+        self.service.delete(url).await
+    }
+
+    pub async fn clear_synonym_rules(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/synonyms", urlencode(self.uid.as_ref()));
+        self.service.delete(url).await
+    }
+
+    pub async fn query_rules(&self, options: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules?{}", urlencode(self.uid.as_ref()), options);
+        self.service.get(url).await
+    }
+
+    pub async fn query_rule(&self, rule_id: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules/{}", urlencode(self.uid.as_ref()), rule_id);
+        self.service.get(url).await
+    }
+
+    pub async fn add_query_rules(&self, rules: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, rules, self.encoder).await
+    }
+
+    pub async fn delete_query_rule(&self, rule_id: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules/{}", urlencode(self.uid.as_ref()), rule_id);
+        self.service.delete(url).await
+    }
+
+    pub async fn clear_query_rules(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rules", urlencode(self.uid.as_ref()));
+        self.service.delete(url).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
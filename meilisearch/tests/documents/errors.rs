@@ -784,3 +784,57 @@ async fn fetch_document_by_filter() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn edit_document_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    // do not send any edit
+    let (response, code) =
+        index.edit_document_by_filter(json!({ "filter": "doggo = bernese" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `edit`",
+      "code": "missing_document_edition",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_document_edition"
+    }
+    "###);
+
+    // send an empty edit
+    let (response, code) =
+        index.edit_document_by_filter(json!({ "filter": "doggo = bernese", "edit": {} })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The edit must be a non-empty object, but received `{}`.",
+      "code": "invalid_document_edition",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition"
+    }
+    "###);
+
+    // send an unknown operator
+    let (response, code) = index
+        .edit_document_by_filter(
+            json!({ "filter": "doggo = bernese", "edit": { "age": { "$mul": 2 } } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid edit of the `age` field. Unknown operator `$mul`. Available operators are `$inc`, `$push`, `$pull`, `$unset` and `$setIfMissing`.",
+      "code": "invalid_document_edition",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_edition"
+    }
+    "###);
+
+    // send a bad filter
+    let (response, code) =
+        index.edit_document_by_filter(json!({ "filter": "hello", "edit": { "age": 2 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_filter""###);
+}
@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 
 use crate::common::encoder::Encoder;
 use crate::common::{GetAllDocumentsOptions, Server};
@@ -209,3 +209,208 @@ async fn error_update_documents_missing_document_id() {
         "https://docs.meilisearch.com/errors#missing_document_id"
     );
 }
+
+#[actix_rt::test]
+async fn edit_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "views": 10, "tags": ["a", "b"], "draft": true },
+                { "id": 2, "views": "many" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .edit_documents(
+            json!([
+                { "id": 1, "views": { "$inc": 2 }, "tags": { "$push": "c" }, "draft": { "$unset": true } },
+                { "id": 2, "views": { "$inc": 1 } },
+                { "id": 3, "title": { "$setIfMissing": "hello" } },
+            ]),
+            None,
+        )
+        .await;
+    assert_eq!(code, 202);
+    assert_eq!(response["type"], "documentAdditionOrUpdate");
+
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["receivedDocuments"], 3);
+    assert_eq!(response["details"]["indexedDocuments"], 2);
+    assert_eq!(response["details"]["rejectedDocuments"][0]["documentId"], "2");
+    assert_eq!(
+        response["details"]["rejectedDocuments"][0]["error"]["code"],
+        "invalid_document_field_operation"
+    );
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 2,
+        "views": "many"
+      },
+      {
+        "id": 1,
+        "views": 12,
+        "tags": [
+          "a",
+          "b",
+          "c"
+        ]
+      },
+      {
+        "id": 3,
+        "title": "hello"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn edit_document_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "color": "red", "status": "draft", "draft": true },
+                { "id": 1, "color": "blue", "status": "draft", "draft": true, "views": 1 },
+                { "id": 2, "color": "blue" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_document_by_filter(json!({
+            "filter": "color = blue",
+            "edit": { "status": "archived", "draft": { "$unset": true }, "views": { "$inc": 1 } },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
+    {
+      "taskUid": 2,
+      "indexUid": "doggo",
+      "status": "enqueued",
+      "type": "documentEdition",
+      "enqueuedAt": "[date]"
+    }
+    "###);
+
+    let response = index.wait_task(2).await;
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]", ".duration" => "[duration]" }), @r###"
+    {
+      "uid": 2,
+      "indexUid": "doggo",
+      "status": "succeeded",
+      "type": "documentEdition",
+      "canceledBy": null,
+      "details": {
+        "edit": {
+          "status": "archived",
+          "draft": {
+            "$unset": true
+          },
+          "views": {
+            "$inc": 1
+          }
+        },
+        "matchedDocuments": 2,
+        "editedDocuments": 2,
+        "originalFilter": "\"color = blue\""
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    let (document, _) = index.get_document(0, None).await;
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 0,
+      "color": "red",
+      "status": "draft",
+      "draft": true
+    }
+    "###);
+    let (document, _) = index.get_document(1, None).await;
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "color": "blue",
+      "status": "archived",
+      "views": 2
+    }
+    "###);
+    let (document, _) = index.get_document(2, None).await;
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 2,
+      "color": "blue",
+      "status": "archived",
+      "views": 1
+    }
+    "###);
+
+    // the primary key can't be edited
+    let (response, _) = index
+        .edit_document_by_filter(json!({ "filter": "color = red", "edit": { "id": 12 } }))
+        .await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_edition""###);
+
+    // even when no document matches the filter
+    let (response, _) = index
+        .edit_document_by_filter(json!({ "filter": "color = green", "edit": { "id": 12 } }))
+        .await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_document_edition""###);
+
+    // the documents on which the operations can't be applied are rejected
+    let (response, _) = index
+        .edit_document_by_filter(
+            json!({ "filter": "color = blue", "edit": { "status": { "$inc": 1 } } }),
+        )
+        .await;
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"]["matchedDocuments"], @"2");
+    snapshot!(response["details"]["editedDocuments"], @"0");
+    snapshot!(json_string!(response["details"]["rejectedDocuments"], { "[].error.message" => "[message]" }), @r###"
+    [
+      {
+        "documentId": "1",
+        "error": {
+          "message": "[message]",
+          "code": "invalid_document_field_operation",
+          "type": "invalid_request",
+          "link": "https://docs.meilisearch.com/errors#invalid_document_field_operation"
+        }
+      },
+      {
+        "documentId": "2",
+        "error": {
+          "message": "[message]",
+          "code": "invalid_document_field_operation",
+          "type": "invalid_request",
+          "link": "https://docs.meilisearch.com/errors#invalid_document_field_operation"
+        }
+      }
+    ]
+    "###);
+}
@@ -5,11 +5,13 @@ mod documents;
 mod dumps;
 mod features;
 mod index;
+mod rules;
 mod search;
 mod settings;
 mod snapshot;
 mod stats;
 mod swap_indexes;
+mod synonyms;
 mod tasks;
 
 // Tests are isolated by features in different modules to allow better readability, test
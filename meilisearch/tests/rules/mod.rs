@@ -0,0 +1,240 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn add_get_and_list_query_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_response, code) = index
+        .add_query_rules(json!([
+            {
+                "id": "tv",
+                "conditions": [{ "pattern": "tv", "anchoring": "is" }],
+                "consequence": { "pin": [{ "id": "4", "position": 0 }] },
+            },
+            {
+                "id": "black-friday",
+                "conditions": [{ "filter": "sale = true" }],
+                "consequence": { "boost": ["brand = acme"] },
+            },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.query_rules("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": "black-friday",
+          "conditions": [
+            {
+              "anchoring": "contains",
+              "filter": "sale = true"
+            }
+          ],
+          "consequence": {
+            "boost": [
+              "brand = acme"
+            ]
+          }
+        },
+        {
+          "id": "tv",
+          "conditions": [
+            {
+              "pattern": "tv",
+              "anchoring": "is"
+            }
+          ],
+          "consequence": {
+            "pin": [
+              {
+                "id": "4",
+                "position": 0
+              }
+            ]
+          }
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    let (response, code) = index.query_rules("offset=1&limit=1").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["results"][0]["id"], "tv");
+    assert_eq!(response["total"], 2);
+
+    let (response, code) = index.query_rule("tv").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": "tv",
+      "conditions": [
+        {
+          "pattern": "tv",
+          "anchoring": "is"
+        }
+      ],
+      "consequence": {
+        "pin": [
+          {
+            "id": "4",
+            "position": 0
+          }
+        ]
+      }
+    }
+    "###);
+
+    let (response, code) = index.query_rule("unknown").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Query rule `unknown` not found.",
+      "code": "query_rule_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#query_rule_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn query_rules_are_applied_to_the_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "tv" },
+                { "id": 1, "name": "tv" },
+                { "id": 2, "name": "tv" },
+                { "id": 3, "name": "radio" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    index
+        .add_query_rules(json!([
+            {
+                "id": "pin",
+                "conditions": [{ "pattern": "tv" }],
+                "consequence": { "pin": [{ "id": "3", "position": 1 }] },
+            },
+            {
+                "id": "hide",
+                "conditions": [{ "pattern": "tv" }],
+                "consequence": { "hide": ["0"] },
+            },
+        ]))
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "tv", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (_response, code) = index.delete_query_rule("hide").await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(2).await;
+
+    let (response, _code) = index.query_rules("").await;
+    assert_eq!(response["total"], 1);
+    index
+        .search(json!({ "q": "tv", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (_response, code) = index.clear_query_rules().await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(3).await;
+
+    let (response, _code) = index.query_rules("").await;
+    assert_eq!(response["total"], 0);
+    index
+        .search(json!({ "q": "tv", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 3);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn add_invalid_query_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .add_query_rules(json!([{
+            "id": "tv",
+            "conditions": [{ "pattern": "tv", "anchoring": "everywhere" }],
+            "consequence": { "hide": ["0"] },
+        }]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_query_rules""###);
+
+    index
+        .add_query_rules(
+            json!([{ "id": "tv", "conditions": [], "consequence": { "hide": ["0"] } }]),
+        )
+        .await;
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The query rule `tv` is invalid: a rule must have at least one condition.",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+
+    let (response, code) = index.query_rules("limit=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_query_rules_limit""###);
+}
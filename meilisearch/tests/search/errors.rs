@@ -275,6 +275,90 @@ async fn search_bad_sort() {
     // Can't make the `sort` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"rankingRules": "words"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rankingRules`: expected an array, but found a string: `\"words\"`",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["words", "doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[1]`: `doggo` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_typo_tolerance() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"typoTolerance": {"enabled": "doggo"}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.typoTolerance.enabled`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_typo_tolerance",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_typo_tolerance"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"typoTolerance": {"doggo": true}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `.typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`",
+      "code": "invalid_search_typo_tolerance",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_typo_tolerance"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_did_you_mean() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"didYouMean": {"maxHits": -1}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.didYouMean.maxHits`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_search_did_you_mean",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_did_you_mean"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"didYouMean": {"doggo": true}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `.didYouMean`: expected one of `maxHits`, `autoCorrect`",
+      "code": "invalid_search_did_you_mean",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_did_you_mean"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_show_matches_position() {
     let server = Server::new().await;
@@ -321,6 +405,197 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_facet_buckets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({"facetBuckets": {"price": {"interval": "doggo"}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetBuckets.price.interval`: expected a number, but found a string: `\"doggo\"`",
+      "code": "invalid_search_facet_buckets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_buckets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetBuckets": {"date": {"calendarInterval": "decade"}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `decade` at `.facetBuckets.date.calendarInterval`: expected one of `minute`, `hour`, `day`, `week`, `month`, `quarter`, `year`",
+      "code": "invalid_search_facet_buckets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_buckets"
+    }
+    "###);
+
+    // the buckets are checked once the index is found.
+    let (_, code) = index.create(None).await;
+    server.wait_task(0).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) =
+        index.search_post(json!({"facetBuckets": {"price": {"interval": 10, "ranges": []}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The buckets of the `price` facet must be defined by exactly one of `interval`, `ranges` or `calendarInterval`.",
+      "code": "invalid_search_facet_buckets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_buckets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetBuckets": {"price": {"interval": 0}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `interval` of the buckets of the `price` facet must be a positive number, but found `0`.",
+      "code": "invalid_search_facet_buckets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_buckets"
+    }
+    "###);
+    // The facet buckets can't be sent with a get search.
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({"facetStats": {"price": {"percentiles": "doggo"}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetStats.price.percentiles`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_facet_stats",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_stats"
+    }
+    "###);
+
+    // the percentiles are checked once the index is found.
+    let (_, code) = index.create(None).await;
+    server.wait_task(0).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) =
+        index.search_post(json!({"facetStats": {"price": {"percentiles": [50, 101]}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The percentiles of the `price` facet must be between 0 and 100, but found `101`.",
+      "code": "invalid_search_facet_stats",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_stats"
+    }
+    "###);
+    // The facet stats can't be sent with a get search.
+}
+
+#[actix_rt::test]
+async fn search_bad_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({"aggregations": {"genres": {"doggo": {}}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `.aggregations.genres`: expected one of `terms`, `ranges`, `stats`, `aggregations`",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+
+    // the kind of the aggregations is checked once the index is found.
+    let (_, code) = index.create(None).await;
+    server.wait_task(0).await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) = index
+        .search_post(json!({"aggregations": {"genres": {
+            "terms": { "facet": "genre" },
+            "stats": { "facet": "price" },
+        }}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `genres` aggregation is invalid: exactly one of `terms`, `ranges` or `stats` must be defined.",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"aggregations": {"genres": {
+            "terms": { "facet": "genre" },
+            "aggregations": { "prices": { "stats": { "facet": "price", "percentiles": [101] } } },
+        }}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `prices` aggregation is invalid: the percentiles must be between 0 and 100, but found `101`.",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+
+    // the aggregations are limited in depth.
+    index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    index.wait_task(1).await;
+
+    let mut aggregation = json!({ "stats": { "facet": "genre" } });
+    for _ in 0..5 {
+        aggregation =
+            json!({ "terms": { "facet": "genre" }, "aggregations": { "nested": aggregation } });
+    }
+    let (response, code) =
+        index.search_post(json!({"aggregations": {"genres": aggregation}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The aggregations cannot be nested more than 5 levels deep.",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+    // The aggregations can't be sent with a get search.
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_filters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"facetFilters": ["doggo = bork"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetFilters`: expected an object, but found an array: `[\"doggo = bork\"]`",
+      "code": "invalid_search_facet_filters",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_filters"
+    }
+    "###);
+    // The facet-scoped filters can't be sent with a get search.
+}
+
 #[actix_rt::test]
 async fn search_non_filterable_facets() {
     let server = Server::new().await;
@@ -535,6 +810,34 @@ async fn search_bad_matching_strategy() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_query_syntax() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"querySyntax": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.querySyntax`: expected one of `plain`, `boolean`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+
+    let (response, code) = index.search_get("querySyntax=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `querySyntax`: expected one of `plain`, `boolean`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn filter_invalid_syntax_object() {
     let server = Server::new().await;
@@ -1038,3 +1341,74 @@ async fn search_on_unknown_field_plus_joker() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn search_bad_hybrid_query() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.search_post(json!({"vector": [1.0], "hybrid": {"semanticRatio": 1.5}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.hybrid.semanticRatio`: the value of `semanticRatio` is invalid, expected a float between `0.0` and `1.0`.",
+      "code": "invalid_search_semantic_ratio",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_semantic_ratio"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"vector": [1.0], "hybrid": {"fusion": "doggo"}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.hybrid.fusion`: expected one of `reciprocalRankFusion`, `normalizedScore`",
+      "code": "invalid_search_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_query"
+    }
+    "###);
+
+    let (response, code) = index.search_get("hybridSemanticRatio=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `hybridSemanticRatio`: the value of `semanticRatio` is invalid, expected a float between `0.0` and `1.0`.",
+      "code": "invalid_search_semantic_ratio",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_semantic_ratio"
+    }
+    "###);
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({"q": "Captain", "hybrid": {}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Passing `vector` as a query parameter requires enabling the `vector store` experimental feature. See https://github.com/meilisearch/product/discussions/677",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (response, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["vectorStore"], @"true");
+
+    let (response, code) = index.search_post(json!({"q": "Captain", "hybrid": {}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A `vector` must be provided when performing a `hybrid` search.",
+      "code": "invalid_search_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_query"
+    }
+    "###);
+}
@@ -0,0 +1,128 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+async fn index_with_documents<'a>(server: &'a Server, documents: &Value) -> Index<'a> {
+    let index = server.index("test");
+
+    let (response, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["vectorStore"], @"true");
+
+    index.add_documents(documents.clone(), None).await;
+    index.wait_task(0).await;
+    index
+}
+
+static SIMPLE_SEARCH_DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+    {
+        "title": "Shazam!",
+        "desc": "a Captain Marvel ersatz",
+        "id": "1",
+        "_vectors": [1.0, 0.0, 0.0],
+    },
+    {
+        "title": "Captain Planet",
+        "desc": "He's not part of the Marvel Cinematic Universe",
+        "id": "2",
+        "_vectors": [0.0, 1.0, 0.0],
+    },
+    {
+        "title": "Captain Marvel",
+        "desc": "a Shazam ersatz",
+        "id": "3",
+        "_vectors": [0.9, 0.1, 0.0],
+    }])
+});
+
+fn hit_ids(response: &Value) -> Vec<serde_json::Value> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect()
+}
+
+#[actix_rt::test]
+async fn simple_search() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Planet", "vector": [1.0, 0.0, 0.0], "hybrid": {"semanticRatio": 0.0}}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(hit_ids(&response)), @r###"
+    [
+      "2"
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Planet", "vector": [1.0, 0.0, 0.0], "hybrid": {"semanticRatio": 1.0}}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(hit_ids(&response)), @r###"
+    [
+      "1",
+      "3",
+      "2"
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"q": "Planet", "vector": [1.0, 0.0, 0.0], "hybrid": {}})).await;
+    snapshot!(code, @"200 OK");
+    // "2" is the only keyword match, "1" is the nearest semantic match
+    snapshot!(json_string!(hit_ids(&response)), @r###"
+    [
+      "2",
+      "1",
+      "3"
+    ]
+    "###);
+    snapshot!(response["hits"][1]["_semanticScore"], @"1.0");
+}
+
+#[actix_rt::test]
+async fn ranking_score_details() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    let (response, code) = server.set_features(json!({"scoreDetails": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["scoreDetails"], @"true");
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "Captain Marvel",
+            "vector": [1.0, 0.0, 0.0],
+            "hybrid": {"semanticRatio": 0.5, "fusion": "normalizedScore"},
+            "showRankingScore": true,
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let first_hit = &response["hits"][0];
+    snapshot!(first_hit["_rankingScoreDetails"]["hybrid"]["order"], @"0");
+    snapshot!(json_string!(first_hit["_rankingScoreDetails"]["hybrid"]["fusion"]), @r###""normalizedScore""###);
+    snapshot!(first_hit["_rankingScore"] == first_hit["_rankingScoreDetails"]["hybrid"]["score"], @"true");
+}
+
+#[actix_rt::test]
+async fn estimated_total_hits() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    let (response, code) = index
+        .search_post(json!({"q": "Planet", "vector": [1.0, 0.0, 0.0], "hybrid": {}, "limit": 1}))
+        .await;
+    snapshot!(code, @"200 OK");
+    // the keyword match "2" and the semantic hit "1", the semantic search doesn't
+    // make every document a candidate.
+    snapshot!(response["estimatedTotalHits"], @"2");
+}
@@ -6,9 +6,12 @@ mod errors;
 mod facet_search;
 mod formatted;
 mod geo;
+mod hybrid;
 mod multi;
 mod pagination;
 mod restrict_searchable;
+mod suggest;
+mod vector_spaces;
 
 use once_cell::sync::Lazy;
 
@@ -567,6 +570,340 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_filters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["brand", "colour"]})).await;
+
+    let documents = json!([
+        { "id": 0, "brand": "acme", "colour": "red" },
+        { "id": 1, "brand": "globex", "colour": "blue" },
+        { "id": 2, "brand": "acme", "colour": "blue" },
+        { "id": 3, "brand": "initech", "colour": "red" },
+        { "id": 4, "brand": "globex", "colour": "red" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // the distribution of each facet ignores its own filters but not the other ones.
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["brand", "colour"],
+            "facetFilters": {
+                "brand": "brand = acme OR brand = globex",
+                "colour": ["colour = red"],
+            },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(0), &json!(4)]);
+    assert_eq!(response["estimatedTotalHits"], json!(2));
+    assert_eq!(
+        response["facetDistribution"],
+        json!({
+            "brand": { "acme": 1, "globex": 1, "initech": 1 },
+            "colour": { "blue": 2, "red": 2 },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn search_facet_buckets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["price", "date"]})).await;
+
+    // 2023-01-15, 2023-02-20, 2023-02-28 and 2023-07-04
+    let documents = json!([
+        { "id": 0, "price": 5, "date": 1673740800 },
+        { "id": 1, "price": 20, "date": 1676851200 },
+        { "id": 2, "price": 45, "date": 1677542400 },
+        { "id": 3, "price": 60, "date": 1688428800 },
+        { "id": 4, "price": 150 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetBuckets": {
+                "price": { "ranges": [{ "to": 50 }, { "from": 50, "to": 100 }, { "from": 100 }] },
+                "date": { "calendarInterval": "month" },
+            },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetBuckets"],
+        json!({
+            "price": [
+                { "from": null, "to": 50.0, "count": 3 },
+                { "from": 50.0, "to": 100.0, "count": 1 },
+                { "from": 100.0, "to": null, "count": 1 },
+            ],
+            "date": [
+                { "from": 1672531200.0, "to": 1675209600.0, "count": 1 },
+                { "from": 1675209600.0, "to": 1677628800.0, "count": 2 },
+                { "from": 1688169600.0, "to": 1690848000.0, "count": 1 },
+            ],
+        })
+    );
+
+    // the histograms only return the non-empty buckets of the candidates.
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "price > 10",
+            "facetBuckets": { "price": { "interval": 50 } },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetBuckets"],
+        json!({
+            "price": [
+                { "from": 0.0, "to": 50.0, "count": 2 },
+                { "from": 50.0, "to": 100.0, "count": 1 },
+                { "from": 150.0, "to": 200.0, "count": 1 },
+            ],
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn search_facet_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["price", "brand"]})).await;
+
+    let documents = json!([
+        { "id": 0, "price": 5, "brand": "Acme" },
+        { "id": 1, "price": 20, "brand": "Acme" },
+        { "id": 2, "price": 45, "brand": "Globex" },
+        { "id": 3, "price": [10, 20], "brand": "Initech" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["price", "brand"],
+            "facetStats": { "price": { "percentiles": [50, 90] }, "brand": {} },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetStats"],
+        json!({
+            "price": {
+                "min": 5.0,
+                "max": 45.0,
+                "count": 5,
+                "sum": 100.0,
+                "avg": 20.0,
+                "distinct": 4,
+                "percentiles": { "50": 20.0, "90": 45.0 },
+            },
+            "brand": { "count": 0, "sum": 0.0, "distinct": 3, "percentiles": {} },
+        })
+    );
+
+    // the statistics are computed over the filtered documents only.
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "brand = Acme",
+            "facetStats": { "brand": {}, "price": {} },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetStats"],
+        json!({
+            "price": {
+                "min": 5.0,
+                "max": 20.0,
+                "count": 2,
+                "sum": 25.0,
+                "avg": 12.5,
+                "distinct": 2,
+                "percentiles": {},
+            },
+            "brand": { "count": 0, "sum": 0.0, "distinct": 1, "percentiles": {} },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn search_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["genre", "price"]})).await;
+
+    let documents = json!([
+        { "id": 0, "genre": "rock", "price": 5 },
+        { "id": 1, "genre": "rock", "price": 20 },
+        { "id": 2, "genre": "jazz", "price": 45 },
+        { "id": 3, "genre": "pop", "price": 12 },
+        { "id": 4, "genre": "rock", "price": 30 },
+        { "id": 5, "genre": "pop", "price": 50 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "genre != jazz",
+            "aggregations": {
+                "genres": {
+                    "terms": { "facet": "genre", "size": 1 },
+                    "aggregations": {
+                        "prices": {
+                            "ranges": { "facet": "price", "ranges": [{ "to": 10 }, { "from": 10 }] },
+                            "aggregations": { "stats": { "stats": { "facet": "price" } } },
+                        },
+                    },
+                },
+                "prices": { "stats": { "facet": "price", "percentiles": [50] } },
+            },
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["aggregations"],
+        json!({
+            "genres": {
+                "buckets": [
+                    {
+                        "value": "rock",
+                        "count": 3,
+                        "aggregations": {
+                            "prices": {
+                                "buckets": [
+                                    {
+                                        "from": null,
+                                        "to": 10.0,
+                                        "count": 1,
+                                        "aggregations": {
+                                            "stats": {
+                                                "min": 5.0,
+                                                "max": 5.0,
+                                                "count": 1,
+                                                "sum": 5.0,
+                                                "avg": 5.0,
+                                                "distinct": 1,
+                                                "percentiles": {},
+                                            },
+                                        },
+                                    },
+                                    {
+                                        "from": 10.0,
+                                        "to": null,
+                                        "count": 2,
+                                        "aggregations": {
+                                            "stats": {
+                                                "min": 20.0,
+                                                "max": 30.0,
+                                                "count": 2,
+                                                "sum": 50.0,
+                                                "avg": 25.0,
+                                                "distinct": 2,
+                                                "percentiles": {},
+                                            },
+                                        },
+                                    },
+                                ],
+                            },
+                        },
+                    },
+                ],
+            },
+            "prices": {
+                "min": 5.0,
+                "max": 50.0,
+                "count": 5,
+                "sum": 117.0,
+                "avg": 23.4,
+                "distinct": 5,
+                "percentiles": { "50": 20.0 },
+            },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn search_facet_hierarchy() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["categories"],
+            "faceting": { "hierarchicalFacets": { "categories": " > " } },
+        }))
+        .await;
+
+    let documents = json!([
+        { "id": 0, "categories": "Electronics > Phones > Android" },
+        { "id": 1, "categories": "Electronics > Phones > iOS" },
+        { "id": 2, "categories": ["Electronics > TV", "Garden"] },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "facets": ["categories"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetHierarchy"],
+        json!({
+            "categories": [
+                {
+                    "value": "Electronics",
+                    "count": 3,
+                    "children": [
+                        {
+                            "value": "Electronics > Phones",
+                            "count": 2,
+                            "children": [
+                                { "value": "Electronics > Phones > Android", "count": 1 },
+                                { "value": "Electronics > Phones > iOS", "count": 1 },
+                            ],
+                        },
+                        { "value": "Electronics > TV", "count": 1 },
+                    ],
+                },
+                { "value": "Garden", "count": 1 },
+            ],
+        })
+    );
+    // while the flat distribution only contains the values of the documents.
+    assert_eq!(
+        response["facetDistribution"],
+        json!({
+            "categories": {
+                "Electronics > Phones > Android": 1,
+                "Electronics > Phones > iOS": 1,
+                "Electronics > TV": 1,
+                "Garden": 1,
+            },
+        })
+    );
+
+    // filtering on a level matches all its descendants.
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "categories = 'Electronics > Phones'",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 0 }, { "id": 1 }]));
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -0,0 +1,141 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+pub(self) static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "The Lord of the Rings", "genre": "Fantasy" },
+        { "id": 1, "title": "The Lord of the Rings", "genre": "Fantasy" },
+        { "id": 2, "title": "The Lord of the Flies", "genre": "Novel" },
+        { "id": 3, "title": "Lords of Dogtown", "genre": "Drama" },
+    ])
+});
+
+#[actix_rt::test]
+async fn suggest_attributes_and_facet_values() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["genre"],
+            "suggestions": { "attributes": ["title"], "facetAttributes": ["genre"] },
+        }))
+        .await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.suggest(json!({ "q": "the lord of" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["query"], @r###""the lord of""###);
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "the lord of the rings",
+        "frequency": 2,
+        "highlighted": "<em>the lord of</em> the rings"
+      },
+      {
+        "suggestion": "the lord of the flies",
+        "frequency": 1,
+        "highlighted": "<em>the lord of</em> the flies"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .suggest(json!({
+            "q": "fant",
+            "highlightPreTag": "<mark>",
+            "highlightPostTag": "</mark>",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "fantasy",
+        "frequency": 2,
+        "highlighted": "<mark>fant</mark>asy"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "the lord", "limit": 1 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "the lord of the rings",
+        "frequency": 2,
+        "highlighted": "<em>the lord</em> of the rings"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggestions_follow_the_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "suggestions": { "attributes": ["title"] } })).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    index.delete_document(0).await;
+    index.wait_task(2).await;
+    index.update_documents(json!([{ "id": 2, "title": "Lord of War" }]), None).await;
+    index.wait_task(3).await;
+
+    let (response, code) = index.suggest(json!({ "q": "lord" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "lord of war",
+        "frequency": 1,
+        "highlighted": "<em>lord</em> of war"
+      },
+      {
+        "suggestion": "lords of dogtown",
+        "frequency": 1,
+        "highlighted": "<em>lord</em>s of dogtown"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "the lord" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "suggestion": "the lord of the rings",
+        "frequency": 1,
+        "highlighted": "<em>the lord</em> of the rings"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_invalid_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.suggest(json!({ "q": 1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_q""###);
+
+    let (response, code) = index.suggest(json!({ "limit": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_limit""###);
+
+    let (response, code) = index.suggest(json!({ "highlightPreTag": 1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_highlight_pre_tag""###);
+}
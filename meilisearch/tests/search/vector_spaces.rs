@@ -0,0 +1,97 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_named_vector_spaces() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["vectorStore"], @"true");
+
+    let (_, code) = index
+        .update_settings(json!({
+            "vectorSpaces": {
+                "title": { "dimensions": 3 },
+                "image": { "dimensions": 2, "distance": "euclidean" },
+            }
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["vectorSpaces"]), @r###"
+    {
+      "image": {
+        "dimensions": 2,
+        "distance": "euclidean"
+      },
+      "title": {
+        "dimensions": 3,
+        "distance": "cosine"
+      }
+    }
+    "###);
+
+    let documents = json!([
+        { "id": 1, "_vectors": { "title": [1.0, 0.0, 0.0], "image": [0.0, 0.0] } },
+        { "id": 2, "_vectors": { "title": [0.0, 1.0, 0.0], "image": [1.0, 1.0] } },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.search_post(json!({"vector": [0.0, 1.0, 0.0], "vectorSpace": "title"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"2");
+
+    let (response, code) =
+        index.search_post(json!({"vector": [0.1, 0.1], "vectorSpace": "image"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"1");
+
+    let (response, code) =
+        index.search_post(json!({"vector": [0.1, 0.1], "vectorSpace": "sound"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Vector space `sound` is not declared. Available vector spaces are: `image, title`.",
+      "code": "invalid_search_vector_space",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector_space"
+    }
+    "###);
+
+    // the dimensions of the vectors are checked against the declared vector space
+    index.add_documents(json!([{ "id": 3, "_vectors": { "image": [1.0, 1.0, 1.0] } }]), None).await;
+    let response = index.wait_task(2).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_vector_dimensions""###);
+}
+
+#[actix_rt::test]
+async fn vector_spaces_require_the_vector_store() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "vectorSpaces": { "title": { "dimensions": 3 } } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Passing `vectorSpaces` as a setting requires enabling the `vector store` experimental feature. See https://github.com/meilisearch/product/discussions/677",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    // resetting the vector spaces is always allowed
+    let (_, code) = index.update_settings(json!({ "vectorSpaces": null })).await;
+    snapshot!(code, @"202 Accepted");
+}
@@ -0,0 +1,242 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn add_get_and_list_synonym_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_response, code) = index
+        .add_synonym_rules(json!([
+            { "id": "wolverine", "type": "oneWay", "input": "wolverine", "synonyms": ["logan"] },
+            { "id": "phones", "type": "equivalent", "synonyms": ["phone", "mobile"] },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.synonym_rules("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": "phones",
+          "type": "equivalent",
+          "synonyms": [
+            "phone",
+            "mobile"
+          ]
+        },
+        {
+          "id": "wolverine",
+          "type": "oneWay",
+          "input": "wolverine",
+          "synonyms": [
+            "logan"
+          ]
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    let (response, code) = index.synonym_rules("offset=1&limit=1").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["results"][0]["id"], "wolverine");
+    assert_eq!(response["total"], 2);
+
+    let (response, code) = index.synonym_rule("phones").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": "phones",
+      "type": "equivalent",
+      "synonyms": [
+        "phone",
+        "mobile"
+      ]
+    }
+    "###);
+
+    let (response, code) = index.synonym_rule("unknown").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Synonym rule `unknown` not found.",
+      "code": "synonym_rule_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#synonym_rule_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn synonym_rules_are_used_by_the_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "logan" },
+                { "id": 1, "name": "wolverine" },
+                { "id": 2, "name": "phone" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(0).await;
+
+    index
+        .add_synonym_rules(json!([
+            { "id": "wolverine", "type": "oneWay", "input": "wolverine", "synonyms": ["logan"] },
+            { "id": "phones", "type": "equivalent", "synonyms": ["phone", "mobile"] },
+        ]))
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "wolverine" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+        })
+        .await;
+    index
+        .search(json!({ "q": "logan" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+    index
+        .search(json!({ "q": "mobile" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"][0]["id"], 2);
+        })
+        .await;
+
+    let (_response, code) = index.delete_synonym_rule("phones").await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(2).await;
+
+    let (response, _code) = index.synonym_rules("").await;
+    assert_eq!(response["total"], 1);
+    index
+        .search(json!({ "q": "mobile" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 0);
+        })
+        .await;
+
+    let (_response, code) = index.clear_synonym_rules().await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(3).await;
+
+    let (response, _code) = index.synonym_rules("").await;
+    assert_eq!(response["total"], 0);
+    index
+        .search(json!({ "q": "wolverine" }), |response, code| {
+            snapshot!(code, @"200 OK");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn synonym_rules_setting_replaces_the_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .add_synonym_rules(json!([
+            { "id": "phones", "type": "equivalent", "synonyms": ["phone", "mobile"] },
+        ]))
+        .await;
+    index.wait_task(0).await;
+
+    // the synonyms routes only add the given rules
+    index
+        .add_synonym_rules(json!([
+            { "id": "wolverine", "type": "oneWay", "input": "wolverine", "synonyms": ["logan"] },
+        ]))
+        .await;
+    index.wait_task(1).await;
+
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["synonymRules"]), @r###"
+    {
+      "phones": {
+        "type": "equivalent",
+        "synonyms": [
+          "phone",
+          "mobile"
+        ]
+      },
+      "wolverine": {
+        "type": "oneWay",
+        "input": "wolverine",
+        "synonyms": [
+          "logan"
+        ]
+      }
+    }
+    "###);
+
+    // while the setting replaces all of them
+    index
+        .update_settings(json!({
+            "synonymRules": {
+                "colors": { "type": "equivalent", "synonyms": ["colour", "color"] },
+            }
+        }))
+        .await;
+    index.wait_task(2).await;
+
+    let (response, _code) = index.settings().await;
+    snapshot!(json_string!(response["synonymRules"]), @r###"
+    {
+      "colors": {
+        "type": "equivalent",
+        "synonyms": [
+          "colour",
+          "color"
+        ]
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_invalid_synonym_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .add_synonym_rules(json!([{ "id": "phones", "type": "sameAs", "synonyms": ["phone"] }]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_synonym_rules""###);
+
+    index
+        .add_synonym_rules(json!([{ "id": "phones", "type": "equivalent", "synonyms": ["phone"] }]))
+        .await;
+    let response = index.wait_task(0).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The synonym rule `phones` is invalid: an equivalent rule must have at least two synonyms.",
+      "code": "invalid_settings_synonym_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_rules"
+    }
+    "###);
+
+    let (response, code) = index.synonym_rules("limit=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_synonym_rules_limit""###);
+}
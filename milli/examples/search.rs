@@ -5,8 +5,8 @@ use std::time::Instant;
 
 use heed::EnvOpenOptions;
 use milli::{
-    execute_search, DefaultSearchLogger, GeoSortStrategy, Index, SearchContext, SearchLogger,
-    TermsMatchingStrategy,
+    execute_search, DefaultSearchLogger, GeoSortStrategy, Index, QuerySyntax, SearchContext,
+    SearchLogger, TermsMatchingStrategy, VectorSearchStrategy,
 };
 
 #[global_allocator]
@@ -53,12 +53,18 @@ fn main() -> Result<(), Box<dyn Error>> {
                 &mut ctx,
                 &(!query.trim().is_empty()).then(|| query.trim().to_owned()),
                 &None,
+                None,
                 TermsMatchingStrategy::Last,
+                QuerySyntax::Plain,
                 milli::score_details::ScoringStrategy::Skip,
                 false,
                 &None,
+                &[],
+                &None,
                 &None,
+                None,
                 GeoSortStrategy::default(),
+                VectorSearchStrategy::default(),
                 0,
                 20,
                 None,
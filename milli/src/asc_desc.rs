@@ -44,6 +44,12 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 CriterionError::ReservedNameForFilter { name: "_geoBoundingBox".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_vectorDistance") => {
+                CriterionError::ReservedNameForSort { name: "_vectorDistance".to_string() }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_vectorSimilarity") => {
+                CriterionError::ReservedNameForFilter { name: "_vectorSimilarity".to_string() }
+            }
             AscDescError::ReservedKeyword { name } => CriterionError::ReservedName { name },
         }
     }
@@ -53,12 +59,20 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    Vector { space: String, vector: Vec<f32> },
 }
 
 impl FromStr for Member {
     type Err = AscDescError;
 
     fn from_str(text: &str) -> Result<Member, Self::Err> {
+        if let Some(params) =
+            text.strip_prefix("_vectorDistance(").and_then(|text| text.strip_suffix(')'))
+        {
+            return parse_vector_distance(params)
+                .ok_or_else(|| AscDescError::ReservedKeyword { name: text.to_string() });
+        }
+
         match text.strip_prefix("_geoPoint(").and_then(|text| text.strip_suffix(')')) {
             Some(point) => {
                 let (lat, lng) = point
@@ -83,6 +97,7 @@ impl FromStr for Member {
                     || text.starts_with("_geoBoundingBox(")
                     || text.starts_with("_geo(")
                     || text.starts_with("_geoDistance(")
+                    || text.starts_with("_vectorSimilarity(")
                 {
                     return Err(AscDescError::ReservedKeyword { name: text.to_string() })?;
                 }
@@ -92,11 +107,32 @@ impl FromStr for Member {
     }
 }
 
+/// Parses the `space, [x, y, ...]` parameters of a `_vectorDistance` sort expression.
+fn parse_vector_distance(params: &str) -> Option<Member> {
+    let (space, vector) = params.split_once(',')?;
+    let space = space.trim();
+    if space.is_empty() {
+        return None;
+    }
+
+    let vector = vector.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let vector = vector
+        .split(',')
+        .map(|x| x.trim().parse::<f32>().ok().filter(|x| x.is_finite()))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Member::Vector { space: space.to_string(), vector })
+}
+
 impl fmt::Display for Member {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::Vector { space, vector } => {
+                let vector = vector.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+                write!(f, "_vectorDistance({}, [{}])", space, vector.join(", "))
+            }
         }
     }
 }
@@ -105,14 +141,14 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::Vector { .. } => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
-            Member::Field(_) => None,
+            Member::Field(_) | Member::Vector { .. } => None,
         }
     }
 }
@@ -155,6 +191,9 @@ pub enum SortError {
     #[error("Invalid syntax for the geo parameter: expected expression formated like \
                     `_geoPoint(latitude, longitude)` and ending by `:asc` or `:desc`, found `{name}`.")]
     BadGeoPointUsage { name: String },
+    #[error("Invalid syntax for the vector parameter: expected expression formated like \
+                    `_vectorDistance(space, [x, y, ...])` and ending by `:asc` or `:desc`, found `{name}`.")]
+    BadVectorDistanceUsage { name: String },
     #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `{name}`.")]
     InvalidName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression.")]
@@ -184,6 +223,12 @@ impl From<AscDescError> for SortError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 SortError::ReservedNameForFilter { name: String::from("_geoBoundingBox") }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_vectorDistance") => {
+                SortError::BadVectorDistanceUsage { name }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_vectorSimilarity") => {
+                SortError::ReservedNameForFilter { name: String::from("_vectorSimilarity") }
+            }
             AscDescError::ReservedKeyword { name } => SortError::ReservedName { name },
         }
     }
@@ -222,6 +267,14 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            (
+                "_vectorDistance(default, [0.5, -1]):asc",
+                Asc(Vector { space: S("default"), vector: vec![0.5, -1.] }),
+            ),
+            (
+                "_vectorDistance( images , [1.,2,3.25]):desc",
+                Desc(Vector { space: S("images"), vector: vec![1., 2., 3.25] }),
+            ),
         ];
 
         for (req, expected) in valid_req {
@@ -274,6 +327,20 @@ mod tests {
                 "_geoDistance(12, -2021):desc",
                 ReservedKeyword { name: S("_geoDistance(12, -2021)") },
             ),
+            ("_vectorDistance:asc", ReservedKeyword { name: S("_vectorDistance") }),
+            ("_vectorDistance([1, 2]):asc", ReservedKeyword { name: S("_vectorDistance([1, 2])") }),
+            (
+                "_vectorDistance(default, 1, 2):asc",
+                ReservedKeyword { name: S("_vectorDistance(default, 1, 2)") },
+            ),
+            (
+                "_vectorDistance(default, [1, a]):desc",
+                ReservedKeyword { name: S("_vectorDistance(default, [1, a])") },
+            ),
+            (
+                "_vectorSimilarity(default, [1, 2]):asc",
+                ReservedKeyword { name: S("_vectorSimilarity(default, [1, 2])") },
+            ),
         ];
 
         for (req, expected_error) in invalid_req {
@@ -0,0 +1,82 @@
+//! The detection of the alphanumeric identifiers, e.g. `A-1234` or `SKU_12.B`,
+//! kept whole by the tokenization of the code attributes.
+
+use std::ops::Range;
+
+/// The characters joining the parts of an identifier, they are separators for the tokenizer.
+pub const CODE_JOINERS: [char; 4] = ['-', '_', '.', '/'];
+
+/// Returns whether the word contains at least one ASCII digit.
+pub fn contains_digit(word: &str) -> bool {
+    word.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// Returns whether the word is an identifier: alphanumeric parts containing
+/// at least one digit and joined by at least one of the [`CODE_JOINERS`].
+pub fn is_code_identifier(word: &str) -> bool {
+    word.chars().all(|c| c.is_alphanumeric() || CODE_JOINERS.contains(&c))
+        && word.contains(CODE_JOINERS)
+        && contains_digit(word)
+}
+
+/// Returns the byte ranges of the identifiers of the text, in the order they appear.
+///
+/// The identifiers are searched in the chunks of text delimited by whitespaces,
+/// trimmed of their leading and trailing punctuation.
+pub fn code_identifier_spans(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    text.split_whitespace()
+        .map(|chunk| chunk.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|chunk| is_code_identifier(chunk))
+        .map(move |chunk| {
+            // the chunks are subslices of the text, their offset is that of their pointer.
+            let start = chunk.as_ptr() as usize - text.as_ptr() as usize;
+            start..start + chunk.len()
+        })
+}
+
+/// Returns the distinct identifiers of the text, as they are written in it.
+pub fn code_identifiers(text: &str) -> Vec<&str> {
+    let mut identifiers: Vec<_> = code_identifier_spans(text).map(|span| &text[span]).collect();
+    identifiers.sort_unstable();
+    identifiers.dedup();
+    identifiers
+}
+
+/// Returns the alphanumeric parts of the identifier, as the separators would split them.
+pub fn code_identifier_parts(identifier: &str) -> impl Iterator<Item = &str> {
+    identifier.split(CODE_JOINERS).filter(|part| !part.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_of_text() {
+        assert_eq!(
+            code_identifiers("Replace part (A-1234), not a-1234 or SKU_12.B/x."),
+            vec!["A-1234", "SKU_12.B/x", "a-1234"]
+        );
+        assert!(code_identifiers("a well-known part: 1234, v2").is_empty());
+        assert!(code_identifiers("--- 12- -").is_empty());
+    }
+
+    #[test]
+    fn spans_of_identifiers() {
+        let text = "part (A-1234), or B_5.";
+        let spans: Vec<_> = code_identifier_spans(text).collect();
+        assert_eq!(spans, [6..12, 18..21]);
+        assert_eq!(&text[6..12], "A-1234");
+        assert_eq!(&text[18..21], "B_5");
+    }
+
+    #[test]
+    fn parts_of_identifier() {
+        assert_eq!(
+            code_identifier_parts("SKU_12.B//x").collect::<Vec<_>>(),
+            ["SKU", "12", "B", "x"]
+        );
+        assert!(contains_digit("iphone15"));
+        assert!(!contains_digit("iphone"));
+    }
+}
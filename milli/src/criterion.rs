@@ -22,6 +22,17 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. A function score ranking rule must be written \
+`<attribute>:log`, `<attribute>:linear(<origin>, <scale>)` or `<attribute>:gauss(<origin>, <scale>)` \
+with a positive scale, optionally followed by a number of buckets between {} and {}, \
+e.g. `<attribute>:log({})` or `<attribute>:gauss(<origin>, <scale>, {})`.",
+        FunctionScore::MIN_BUCKETS,
+        FunctionScore::MAX_BUCKETS,
+        FunctionScore::DEFAULT_BUCKETS,
+        FunctionScore::DEFAULT_BUCKETS
+    )]
+    InvalidFunctionScore { name: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +56,154 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score computed from the numeric value of the field specified.
+    FunctionScore(FunctionScore),
+}
+
+/// A ranking rule that boosts the documents based on the numeric value of a field.
+///
+/// The score of a document is computed by the [`ScoreFunction`] and then bucketed coarsely,
+/// the documents of the same bucket are ranked equally and are sorted by the next ranking rules.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionScore {
+    pub field: String,
+    pub function: ScoreFunction,
+    pub buckets: u32,
+}
+
+// The parameters of the functions are always finite numbers.
+impl Eq for FunctionScore {}
+
+impl FunctionScore {
+    pub const DEFAULT_BUCKETS: u32 = 10;
+    pub const MIN_BUCKETS: u32 = 2;
+    pub const MAX_BUCKETS: u32 = 1000;
+
+    /// Returns the bucket of a document with the given value, from `0` to `buckets - 1`,
+    /// `max_value` is the highest value of the field in the index.
+    pub fn bucket(&self, value: f64, max_value: f64) -> u32 {
+        let score = self.function.score(value, max_value).clamp(0.0, 1.0);
+        ((score * self.buckets as f64) as u32).min(self.buckets - 1)
+    }
+
+    /// Parses the function part of a function score ranking rule, e.g. `gauss(10, 5)`,
+    /// returns `None` if it is not a function score.
+    fn parse(text: &str, field: &str, function: &str) -> Option<Result<Self, CriterionError>> {
+        let (name, params) = match function.split_once('(') {
+            Some((name, params)) => (name, Some(params)),
+            None => (function, None),
+        };
+        if !matches!(name, "log" | "linear" | "gauss") {
+            return None;
+        }
+
+        let error = || CriterionError::InvalidFunctionScore { name: text.to_string() };
+        if field.is_empty() {
+            return Some(Err(error()));
+        }
+        if crate::is_reserved_keyword(field) {
+            return Some(Err(CriterionError::ReservedName { name: field.to_string() }));
+        }
+
+        let params: Vec<&str> = match params {
+            Some(params) => match params.strip_suffix(')') {
+                Some(params) => params.split(',').map(str::trim).collect(),
+                None => return Some(Err(error())),
+            },
+            None => Vec::new(),
+        };
+
+        let parse_buckets = |param: Option<&&str>| match param {
+            Some(buckets) => buckets
+                .parse::<u32>()
+                .ok()
+                .filter(|b| (Self::MIN_BUCKETS..=Self::MAX_BUCKETS).contains(b)),
+            None => Some(Self::DEFAULT_BUCKETS),
+        };
+        let parse_finite = |param: &str| param.parse::<f64>().ok().filter(|f| f.is_finite());
+
+        let function_score = match (name, params.as_slice()) {
+            ("log", [] | [_]) => {
+                parse_buckets(params.first()).map(|buckets| (ScoreFunction::Log, buckets))
+            }
+            ("linear" | "gauss", [origin, scale] | [origin, scale, _]) => {
+                let origin = parse_finite(origin);
+                let scale = parse_finite(scale).filter(|scale| *scale > 0.0);
+                let buckets = parse_buckets(params.get(2));
+                match (origin, scale, buckets) {
+                    (Some(origin), Some(scale), Some(buckets)) if name == "linear" => {
+                        Some((ScoreFunction::Linear { origin, scale }, buckets))
+                    }
+                    (Some(origin), Some(scale), Some(buckets)) => {
+                        Some((ScoreFunction::Gauss { origin, scale }, buckets))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        Some(match function_score {
+            Some((function, buckets)) => {
+                Ok(FunctionScore { field: field.to_string(), function, buckets })
+            }
+            None => Err(error()),
+        })
+    }
+}
+
+/// The functions used to compute a score between `0` and `1` from a numeric value.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ScoreFunction {
+    /// The logarithm of the value relative to the logarithm of the highest value of the field,
+    /// negative values have a score of zero. Useful to boost popular documents.
+    Log,
+    /// Decreases linearly with the distance to the origin, the score is halved
+    /// at `scale` from the origin and is zero at twice the scale.
+    Linear { origin: f64, scale: f64 },
+    /// Decreases following a gaussian curve with the distance to the origin,
+    /// the score is halved at `scale` from the origin.
+    Gauss { origin: f64, scale: f64 },
+}
+
+impl ScoreFunction {
+    pub fn score(&self, value: f64, max_value: f64) -> f64 {
+        match *self {
+            ScoreFunction::Log if value <= 0.0 || max_value <= 0.0 => 0.0,
+            ScoreFunction::Log => value.ln_1p() / max_value.ln_1p(),
+            ScoreFunction::Linear { origin, scale } => {
+                let distance = (value - origin).abs();
+                (1.0 - distance / (2.0 * scale)).max(0.0)
+            }
+            ScoreFunction::Gauss { origin, scale } => {
+                let distance = (value - origin).abs() / scale;
+                (-std::f64::consts::LN_2 * distance * distance).exp()
+            }
+        }
+    }
+}
+
+impl fmt::Display for FunctionScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FunctionScore { field, function, buckets } = self;
+        let buckets = (*buckets != Self::DEFAULT_BUCKETS).then_some(buckets);
+        match (function, buckets) {
+            (ScoreFunction::Log, None) => write!(f, "{field}:log"),
+            (ScoreFunction::Log, Some(buckets)) => write!(f, "{field}:log({buckets})"),
+            (ScoreFunction::Linear { origin, scale }, None) => {
+                write!(f, "{field}:linear({origin}, {scale})")
+            }
+            (ScoreFunction::Linear { origin, scale }, Some(buckets)) => {
+                write!(f, "{field}:linear({origin}, {scale}, {buckets})")
+            }
+            (ScoreFunction::Gauss { origin, scale }, None) => {
+                write!(f, "{field}:gauss({origin}, {scale})")
+            }
+            (ScoreFunction::Gauss { origin, scale }, Some(buckets)) => {
+                write!(f, "{field}:gauss({origin}, {scale}, {buckets})")
+            }
+        }
+    }
 }
 
 impl Criterion {
@@ -52,6 +211,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::FunctionScore(function_score) => Some(&function_score.field),
             _otherwise => None,
         }
     }
@@ -68,13 +228,26 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
-            text => match AscDesc::from_str(text)? {
-                AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
-                AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
-                    Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
+            text => {
+                if let Some(function_score) = text
+                    .rsplit_once(':')
+                    .and_then(|(field, function)| FunctionScore::parse(text, field, function))
+                {
+                    return function_score.map(Criterion::FunctionScore);
                 }
-            },
+                match AscDesc::from_str(text)? {
+                    AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
+                    AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
+                    AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+                        Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
+                    }
+                    AscDesc::Asc(Member::Vector { .. }) | AscDesc::Desc(Member::Vector { .. }) => {
+                        Err(CriterionError::ReservedNameForSort {
+                            name: "_vectorDistance".to_string(),
+                        })?
+                    }
+                }
+            }
         }
     }
 }
@@ -103,6 +276,7 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            FunctionScore(function_score) => function_score.fmt(f),
         }
     }
 }
@@ -129,6 +303,38 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "popularity:log",
+                Criterion::FunctionScore(FunctionScore {
+                    field: S("popularity"),
+                    function: ScoreFunction::Log,
+                    buckets: FunctionScore::DEFAULT_BUCKETS,
+                }),
+            ),
+            (
+                "popularity:log(5)",
+                Criterion::FunctionScore(FunctionScore {
+                    field: S("popularity"),
+                    function: ScoreFunction::Log,
+                    buckets: 5,
+                }),
+            ),
+            (
+                "release_date:gauss(1700000000, 86400.5)",
+                Criterion::FunctionScore(FunctionScore {
+                    field: S("release_date"),
+                    function: ScoreFunction::Gauss { origin: 1700000000.0, scale: 86400.5 },
+                    buckets: FunctionScore::DEFAULT_BUCKETS,
+                }),
+            ),
+            (
+                "truc:price:linear(-20,10,4)",
+                Criterion::FunctionScore(FunctionScore {
+                    field: S("truc:price"),
+                    function: ScoreFunction::Linear { origin: -20.0, scale: 10.0 },
+                    buckets: 4,
+                }),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -164,6 +370,25 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_vectorDistance:asc", ReservedNameForSort { name: S("_vectorDistance") }),
+            (
+                "_vectorDistance(default, [0.5, 1]):asc",
+                ReservedNameForSort { name: S("_vectorDistance") },
+            ),
+            (
+                "_vectorSimilarity(default, [0.5, 1]):desc",
+                ReservedNameForFilter { name: S("_vectorSimilarity") },
+            ),
+            (":log", InvalidFunctionScore { name: S(":log") }),
+            ("_geo:log", ReservedName { name: S("_geo") }),
+            ("popularity:log()", InvalidFunctionScore { name: S("popularity:log()") }),
+            ("popularity:log(1)", InvalidFunctionScore { name: S("popularity:log(1)") }),
+            ("popularity:log(5", InvalidFunctionScore { name: S("popularity:log(5") }),
+            ("price:linear(20)", InvalidFunctionScore { name: S("price:linear(20)") }),
+            ("price:gauss(20, 0)", InvalidFunctionScore { name: S("price:gauss(20, 0)") }),
+            ("price:gauss(20, -1, 5)", InvalidFunctionScore { name: S("price:gauss(20, -1, 5)") }),
+            ("price:gauss(inf, 1)", InvalidFunctionScore { name: S("price:gauss(inf, 1)") }),
+            ("price:logs", InvalidName { name: S("price:logs") }),
         ];
 
         for (input, expected) in invalid_criteria {
@@ -185,4 +410,48 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn display_function_score() {
+        let criteria = [
+            "popularity:log",
+            "popularity:log(5)",
+            "price:linear(-20, 10.5)",
+            "release_date:gauss(1700000000, 86400, 100)",
+        ];
+
+        for criterion in criteria {
+            let parsed: Criterion = criterion.parse().unwrap();
+            assert_eq!(parsed.to_string(), criterion);
+        }
+    }
+
+    #[test]
+    fn function_score_buckets() {
+        let log =
+            FunctionScore { field: S("popularity"), function: ScoreFunction::Log, buckets: 4 };
+        assert_eq!(log.bucket(-5.0, 1000.0), 0);
+        assert_eq!(log.bucket(0.0, 1000.0), 0);
+        assert_eq!(log.bucket(5.0, 1000.0), 1);
+        assert_eq!(log.bucket(100.0, 1000.0), 2);
+        assert_eq!(log.bucket(1000.0, 1000.0), 3);
+
+        let gauss = FunctionScore {
+            field: S("price"),
+            function: ScoreFunction::Gauss { origin: 100.0, scale: 10.0 },
+            buckets: 2,
+        };
+        assert_eq!(gauss.bucket(100.0, 1000.0), 1);
+        assert_eq!(gauss.bucket(95.0, 1000.0), 1);
+        assert_eq!(gauss.bucket(111.0, 1000.0), 0);
+
+        let linear = FunctionScore {
+            field: S("price"),
+            function: ScoreFunction::Linear { origin: 100.0, scale: 10.0 },
+            buckets: 10,
+        };
+        assert_eq!(linear.bucket(100.0, 1000.0), 9);
+        assert_eq!(linear.bucket(90.0, 1000.0), 5);
+        assert_eq!(linear.bucket(120.0, 1000.0), 0);
+    }
 }
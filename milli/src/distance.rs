@@ -1,41 +1,109 @@
 use std::ops;
 
-use instant_distance::Point;
 use serde::{Deserialize, Serialize};
 
 use crate::normalize_vector;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
-pub struct NDotProductPoint(Vec<f32>);
+/// The name of the vector space used when the `_vectors` field of a document
+/// is directly an array of vectors instead of an object of named vector spaces.
+pub const DEFAULT_VECTOR_SPACE: &str = "default";
+
+/// The metric used to compute the distance between two vectors of a vector space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Distance {
+    /// The angle between the vectors, they are normalized before being stored.
+    #[default]
+    Cosine,
+    /// The raw dot product of the vectors, the magnitudes of the vectors are kept.
+    Dot,
+    /// The euclidean (L2) distance between the vectors.
+    Euclidean,
+}
 
-impl NDotProductPoint {
-    pub fn new(point: Vec<f32>) -> Self {
-        NDotProductPoint(normalize_vector(point))
+impl Distance {
+    /// Converts a distance computed with this metric into a similarity score,
+    /// the higher the more similar the vectors are.
+    ///
+    /// The score is between `0.0` and `1.0` for the cosine and euclidean metrics,
+    /// and for the dot product metric when the vectors are normalized.
+    pub fn similarity(&self, distance: f32) -> f32 {
+        match self {
+            Distance::Cosine | Distance::Dot => 1.0 - distance,
+            Distance::Euclidean => 1.0 / (1.0 + distance),
+        }
     }
 
-    pub fn into_inner(self) -> Vec<f32> {
-        self.0
+    /// Computes the similarity score between two raw vectors with this metric.
+    pub fn vectors_similarity(&self, a: &[f32], b: &[f32]) -> f32 {
+        let a = VectorPoint::new(*self, a.to_vec());
+        let b = VectorPoint::new(*self, b.to_vec());
+        self.similarity(a.distance_to(&b))
     }
 }
 
-impl ops::Deref for NDotProductPoint {
-    type Target = [f32];
+/// The declaration of a named vector space, all the vectors it contains
+/// must have the same number of dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorSpace {
+    pub dimensions: usize,
+    #[serde(default)]
+    pub distance: Distance,
+}
 
-    fn deref(&self) -> &Self::Target {
-        self.0.as_slice()
-    }
+/// A vector stored in an HNSW along with the metric used to compare it with the other vectors.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct VectorPoint {
+    distance: Distance,
+    vector: Vec<f32>,
 }
 
-impl Point for NDotProductPoint {
-    fn distance(&self, other: &Self) -> f32 {
-        let dist = 1.0 - dot_product_similarity(&self.0, &other.0);
+impl VectorPoint {
+    pub fn new(distance: Distance, vector: Vec<f32>) -> Self {
+        let vector = match distance {
+            Distance::Cosine => normalize_vector(vector),
+            Distance::Dot | Distance::Euclidean => vector,
+        };
+        VectorPoint { distance, vector }
+    }
+
+    pub fn distance_metric(&self) -> Distance {
+        self.distance
+    }
+
+    pub fn into_inner(self) -> Vec<f32> {
+        self.vector
+    }
+
+    /// Computes the distance between this point and another one, using the metric of this point.
+    pub fn distance_to(&self, other: &Self) -> f32 {
+        let dist = match self.distance {
+            Distance::Cosine | Distance::Dot => {
+                1.0 - dot_product_similarity(&self.vector, &other.vector)
+            }
+            Distance::Euclidean => euclidean_distance(&self.vector, &other.vector),
+        };
         debug_assert!(!dist.is_nan());
         dist
     }
 }
 
+impl ops::Deref for VectorPoint {
+    type Target = [f32];
+
+    fn deref(&self) -> &Self::Target {
+        self.vector.as_slice()
+    }
+}
+
 /// Returns the dot product similarity score that will between 0.0 and 1.0
 /// if both vectors are normalized. The higher the more similar the vectors are.
 pub fn dot_product_similarity(a: &[f32], b: &[f32]) -> f32 {
     a.iter().zip(b).map(|(a, b)| a * b).sum()
 }
+
+/// Returns the euclidean distance between the two vectors. The lower the more similar the vectors are.
+pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f32>().sqrt()
+}
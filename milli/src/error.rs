@@ -9,10 +9,20 @@ use serde_json::Value;
 use thiserror::Error;
 
 use crate::documents::{self, DocumentsBatchCursorError};
+use crate::update::FieldOperationError;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"].contains(&keyword)
+    [
+        "_geo",
+        "_geoDistance",
+        "_geoPoint",
+        "_geoRadius",
+        "_geoBoundingBox",
+        "_vectorDistance",
+        "_vectorSimilarity",
+    ]
+    .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -61,6 +71,11 @@ pub enum InternalError {
     AbortedIndexation,
     #[error("The matching words list contains at least one invalid member.")]
     InvalidMatchingWords,
+    #[error("The vectors of the index are stored with {}, but the version {expected} of the layout is expected.", match .found {
+        Some(version) => format!("the version {version} of the layout"),
+        None => "an unversioned layout".to_string(),
+    })]
+    VectorLayoutMismatch { found: Option<u32>, expected: u32 },
 }
 
 #[derive(Error, Debug)]
@@ -103,6 +118,10 @@ A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error(
+        "Cannot edit the `{field}` field of the document with the id: `{document_id}`. {error}"
+    )]
+    InvalidDocumentFieldOperation { document_id: Value, field: String, error: FieldOperationError },
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
@@ -114,6 +133,52 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidVectorDimensions { expected: usize, found: usize },
     #[error("The `_vectors` field in the document with the id: `{document_id}` is not an array. Was expecting an array of floats or an array of arrays of floats but instead got `{value}`.")]
     InvalidVectorsType { document_id: Value, value: Value },
+    #[error("Invalid vector dimensions in the `{space}` vector space of the document with the id: `{document_id}`: expected: `{expected}`, found: `{found}`.")]
+    InvalidVectorSpaceDimensions {
+        document_id: Value,
+        space: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("The `_vectors` field in the document with the id: `{document_id}` references the `{space}` vector space which is not declared. {}",
+        match .available.is_empty() {
+            true => "This index does not have declared vector spaces.".to_string(),
+            false => format!("Available vector spaces are: `{}`.", available.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")),
+        }
+    )]
+    UnknownVectorSpace { document_id: Value, space: String, available: BTreeSet<String> },
+    #[error("Vector space `{space}` is not declared. {}",
+        match .available.is_empty() {
+            true => "This index does not have declared vector spaces.".to_string(),
+            false => format!("Available vector spaces are: `{}`.", available.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")),
+        }
+    )]
+    InvalidSearchVectorSpace { space: String, available: BTreeSet<String> },
+    #[error("Vector space name `{0}` is invalid. A vector space name must only be composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).")]
+    InvalidVectorSpaceName(String),
+    #[error("Vector space `{0}` is invalid. The number of dimensions of a vector space must be greater than zero.")]
+    InvalidVectorSpaceDimensionsSetting(String),
+    #[error("Attribute `{}` cannot be weighted as it is not searchable. Available searchable attributes are: `{}`.",
+        .attribute,
+        .searchable_attributes.join(", "),
+    )]
+    InvalidAttributeWeight { attribute: String, searchable_attributes: Vec<String> },
+    #[error("Locale `{0}` is invalid. A locale must be a supported ISO 639-3 language code, e.g. `jpn`, `cmn` or `swe`.")]
+    InvalidLocalizedAttributesLocale(String),
+    #[error("Locale `{0}` is invalid. A locale must be a supported ISO 639-3 language code, e.g. `jpn`, `cmn` or `swe`.")]
+    InvalidSearchLocale(String),
+    #[error("Locale `{0}` does not support stemming. Stemming is supported for the ISO 639-3 codes `ara`, `dan`, `deu`, `ell`, `eng`, `fin`, `fra`, `hun`, `ita`, `nld`, `nob`, `por`, `ron`, `rus`, `spa`, `swe`, `tam` and `tur`.")]
+    InvalidStemmingLocale(String),
+    #[error("The hierarchical facet `{0}` is invalid: its separator must contain a non-whitespace character.")]
+    InvalidHierarchicalFacetSeparator(String),
+    #[error("The synonym rule `{id}` is invalid: {reason}.")]
+    InvalidSynonymRule { id: String, reason: &'static str },
+    #[error("The query rule `{id}` is invalid: {reason}.")]
+    InvalidQueryRule { id: String, reason: String },
+    #[error("The aggregations cannot be nested more than {0} levels deep.")]
+    AggregationsTooDeep(usize),
+    #[error("The aggregations cannot return more than {0} buckets.")]
+    TooManyAggregationBuckets(usize),
     #[error("{0}")]
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: expected: {}, found: {1}.", .0.join(", "))]
@@ -129,6 +194,23 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` cannot be used by the `{}` ranking rule given at search time. {}",
+        .field,
+        .rule,
+        match .valid_fields.is_empty() {
+            true => "This index does not have attributes that can be ranked at search time.".to_string(),
+            false => format!("Available attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidSearchRankingRuleAttribute {
+        rule: String,
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -182,6 +264,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     UnknownInternalDocumentId { document_id: DocumentId },
     #[error("`minWordSizeForTypos` setting is invalid. `oneTypo` and `twoTypos` fields should be between `0` and `255`, and `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and twoTypos: {1}`.")]
     InvalidMinTypoWordLenSetting(u8, u8),
+    #[error("`typoTolerance.minWordSizeForTypos` parameter is invalid. `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and `twoTypos: {1}`.")]
+    InvalidSearchMinTypoWordLen(u8, u8),
 }
 
 #[derive(Error, Debug)]
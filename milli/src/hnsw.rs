@@ -0,0 +1,673 @@
+//! An HNSW (Hierarchical Navigable Small World) graph stored directly in LMDB.
+//!
+//! Every node of the graph is stored in its own entry of the `vector_hnsw_nodes` database,
+//! keyed by the name of its vector space and its point id. This way inserting or deleting
+//! a point only reads and rewrites the handful of nodes it is connected to, and the graph
+//! never has to be entirely deserialized in memory, neither to be updated nor to be searched.
+//!
+//! The links are directed, a node also knows the nodes linking to it so that all
+//! the links to a deleted point can be repaired and not only the symmetric ones.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use heed::types::ByteSlice;
+use heed::{RoTxn, RwTxn};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use crate::distance::VectorPoint;
+use crate::error::InternalError;
+use crate::heed_codec::StrBEU32Codec;
+use crate::index::db_name::VECTOR_HNSW_NODES;
+use crate::{Index, Result};
+
+/// The maximum number of neighbours of a node on the upper layers.
+const M: usize = 16;
+/// The maximum number of neighbours of a node on the bottom layer.
+const M0: usize = M * 2;
+/// The maximum layer a node can be inserted into.
+const MAX_LEVEL: usize = 16;
+/// The number of candidates explored when inserting a point.
+const EF_CONSTRUCTION: usize = 100;
+/// The minimum number of candidates explored when searching for the nearest neighbours.
+pub const EF_SEARCH: usize = 100;
+
+/// A point of the graph along with its neighbours on every layer it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswNode {
+    pub point: VectorPoint,
+    /// The neighbours of the node, indexed by layer, starting from the bottom one.
+    pub links: Vec<Vec<u32>>,
+    /// The nodes that have this node as a neighbour, indexed by layer.
+    pub incoming: Vec<Vec<u32>>,
+}
+
+impl HnswNode {
+    fn level(&self) -> usize {
+        self.links.len().saturating_sub(1)
+    }
+}
+
+/// The global information about the graph of a vector space.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct HnswMetadata {
+    /// The node from which the searches start, it belongs to the highest layer.
+    pub entry_point: Option<u32>,
+    /// The point id that will be given to the next inserted point.
+    pub next_pid: u32,
+    /// The number of points in the graph.
+    pub len: u64,
+}
+
+/// A handle on the HNSW graph of a vector space.
+pub struct Hnsw<'i> {
+    index: &'i Index,
+    space: &'i str,
+}
+
+impl<'i> Hnsw<'i> {
+    pub fn new(index: &'i Index, space: &'i str) -> Hnsw<'i> {
+        Hnsw { index, space }
+    }
+
+    /// Returns the number of points in the graph.
+    pub fn len(&self, rtxn: &RoTxn) -> Result<u64> {
+        Ok(self.metadata(rtxn)?.len)
+    }
+
+    pub fn is_empty(&self, rtxn: &RoTxn) -> Result<bool> {
+        self.len(rtxn).map(|len| len == 0)
+    }
+
+    /// Returns the number of dimensions of the points of the graph, if there is any.
+    pub fn dimensions(&self, rtxn: &RoTxn) -> Result<Option<usize>> {
+        match self.metadata(rtxn)?.entry_point {
+            Some(pid) => Ok(Some(self.entry_node(rtxn, pid)?.point.len())),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the point ids and distances of the points that are the nearest to the query,
+    /// sorted by increasing distance. At least `ef` candidates are explored.
+    pub fn search(&self, rtxn: &RoTxn, query: &VectorPoint, ef: usize) -> Result<Vec<(u32, f32)>> {
+        self.search_filtered(rtxn, query, ef, &mut |_| Ok(true))
+    }
+
+    /// Returns the point ids and distances of the points whose distance to the query is
+    /// accepted, the accepted distances must be the ones lower than a given bound.
+    ///
+    /// The number of explored candidates is doubled until the furthest point found is
+    /// out of range, the work done is then proportional to the number of points returned.
+    pub fn search_within(
+        &self,
+        rtxn: &RoTxn,
+        query: &VectorPoint,
+        within: impl Fn(f32) -> bool,
+    ) -> Result<Vec<(u32, f32)>> {
+        let len = self.len(rtxn)? as usize;
+        let mut ef = EF_SEARCH;
+        loop {
+            let mut neighbours = self.search(rtxn, query, ef)?;
+            let exhausted = neighbours.len() < ef || neighbours.len() >= len;
+            if exhausted || neighbours.last().map_or(true, |(_, distance)| !within(*distance)) {
+                neighbours.retain(|(_, distance)| within(*distance));
+                return Ok(neighbours);
+            }
+            ef = ef.saturating_mul(2);
+        }
+    }
+
+    /// Returns the point ids and distances of the points that are the nearest to the query
+    /// and accepted by the filter, sorted by increasing distance.
+    ///
+    /// The rejected points are still used to navigate the graph, this way
+    /// the accepted points that are only reachable through them are found.
+    pub fn search_filtered(
+        &self,
+        rtxn: &RoTxn,
+        query: &VectorPoint,
+        ef: usize,
+        filter: &mut dyn FnMut(u32) -> Result<bool>,
+    ) -> Result<Vec<(u32, f32)>> {
+        let ef = ef.max(EF_SEARCH);
+        let entry_point = match self.metadata(rtxn)?.entry_point {
+            Some(pid) => pid,
+            None => return Ok(Vec::new()),
+        };
+
+        let entry_node = self.entry_node(rtxn, entry_point)?;
+        let mut entry_points = vec![(query.distance_to(&entry_node.point), entry_point)];
+        for level in (1..=entry_node.level()).rev() {
+            entry_points =
+                self.search_layer(rtxn, query, &entry_points, 1, level, &mut |_| Ok(true))?;
+        }
+        let neighbours = self.search_layer(rtxn, query, &entry_points, ef, 0, filter)?;
+
+        Ok(neighbours.into_iter().map(|(distance, pid)| (pid, distance)).collect())
+    }
+
+    /// Returns an iterator over the point ids and the nodes of all the points of the graph.
+    pub fn iter<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+    ) -> Result<impl Iterator<Item = Result<(u32, HnswNode)>> + 't>
+    where
+        'i: 't,
+    {
+        let space = self.space;
+        let nodes = self.index.vector_hnsw_nodes.remap_key_type::<ByteSlice>();
+        let iter = nodes.prefix_iter(rtxn, space.as_bytes())?.remap_key_type::<StrBEU32Codec>();
+        // the prefix also matches the spaces whose name starts with the name of this space
+        Ok(iter.filter_map(move |result| match result {
+            Ok(((node_space, pid), node)) if node_space == space => Some(Ok((pid, node))),
+            Ok(_) => None,
+            Err(e) => Some(Err(e.into())),
+        }))
+    }
+
+    /// Inserts a new point in the graph and returns its point id.
+    pub(crate) fn insert(&self, wtxn: &mut RwTxn, point: VectorPoint) -> Result<u32> {
+        let mut metadata = self.metadata(wtxn)?;
+        let pid = metadata.next_pid;
+        metadata.next_pid += 1;
+        metadata.len += 1;
+
+        let level = random_level(pid);
+        let mut node = HnswNode {
+            point,
+            links: vec![Vec::new(); level + 1],
+            incoming: vec![Vec::new(); level + 1],
+        };
+
+        let entry_point = match metadata.entry_point {
+            Some(entry_point) => entry_point,
+            None => {
+                self.put_node(wtxn, pid, &node)?;
+                metadata.entry_point = Some(pid);
+                self.put_metadata(wtxn, &metadata)?;
+                return Ok(pid);
+            }
+        };
+
+        let entry_node = self.entry_node(wtxn, entry_point)?;
+        let top_level = entry_node.level();
+        let mut entry_points = vec![(node.point.distance_to(&entry_node.point), entry_point)];
+        for level in (level + 1..=top_level).rev() {
+            entry_points =
+                self.search_layer(wtxn, &node.point, &entry_points, 1, level, &mut |_| Ok(true))?;
+        }
+        for level in (0..=level.min(top_level)).rev() {
+            entry_points = self.search_layer(
+                wtxn,
+                &node.point,
+                &entry_points,
+                EF_CONSTRUCTION,
+                level,
+                &mut |_| Ok(true),
+            )?;
+            let max_links = max_links(level);
+            let links = entry_points.iter().take(max_links).map(|(_, pid)| *pid).collect();
+            self.set_links(wtxn, pid, &mut node, level, links)?;
+        }
+
+        // The node must be written before the reverse links are created
+        // as the neighbours must be able to compute their distance to it.
+        self.put_node(wtxn, pid, &node)?;
+        for (level, links) in node.links.iter().enumerate() {
+            for &neighbour in links {
+                self.link(wtxn, neighbour, pid, level)?;
+            }
+        }
+
+        if level > top_level {
+            metadata.entry_point = Some(pid);
+        }
+        self.put_metadata(wtxn, &metadata)?;
+
+        Ok(pid)
+    }
+
+    /// Removes a point from the graph, the nodes that were linked to the removed point
+    /// are reconnected to its neighbours to keep the graph navigable.
+    ///
+    /// Returns `false` if the point was not in the graph.
+    pub(crate) fn delete(&self, wtxn: &mut RwTxn, pid: u32) -> Result<bool> {
+        let node = match self.node(wtxn, pid)? {
+            Some(node) => node,
+            None => return Ok(false),
+        };
+        self.index.vector_hnsw_nodes.delete(wtxn, &(self.space, pid))?;
+
+        for (level, links) in node.links.iter().enumerate() {
+            for &neighbour in links {
+                self.update_node(wtxn, neighbour, |node| {
+                    remove_link(&mut node.incoming, level, pid)
+                })?;
+            }
+        }
+        for (level, incoming) in node.incoming.iter().enumerate() {
+            for &from in incoming {
+                self.unlink(wtxn, from, pid, &node.links[level], level)?;
+            }
+        }
+
+        let mut metadata = self.metadata(wtxn)?;
+        metadata.len = metadata.len.saturating_sub(1);
+        if metadata.len == 0 {
+            self.index.delete_vector_hnsw_metadata(wtxn, self.space)?;
+            return Ok(true);
+        }
+
+        if metadata.entry_point == Some(pid) {
+            metadata.entry_point = self.highest_node(wtxn, &node)?;
+        }
+        self.put_metadata(wtxn, &metadata)?;
+
+        Ok(true)
+    }
+
+    /// Explores the layer, starting from the entry points, and returns at most `ef`
+    /// `(distance, point id)` of the points nearest to the query and accepted by the filter,
+    /// sorted by increasing distance.
+    fn search_layer(
+        &self,
+        rtxn: &RoTxn,
+        query: &VectorPoint,
+        entry_points: &[(f32, u32)],
+        ef: usize,
+        level: usize,
+        filter: &mut dyn FnMut(u32) -> Result<bool>,
+    ) -> Result<Vec<(f32, u32)>> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|(_, pid)| *pid).collect();
+        let mut candidates: BinaryHeap<_> =
+            entry_points.iter().map(|(d, pid)| Reverse((OrderedFloat(*d), *pid))).collect();
+        let mut results = BinaryHeap::new();
+        for &(distance, pid) in entry_points {
+            if filter(pid)? {
+                results.push((OrderedFloat(distance), pid));
+            }
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse((distance, pid))) = candidates.pop() {
+            let furthest = results.peek().map_or(OrderedFloat(f32::INFINITY), |(d, _)| *d);
+            if results.len() >= ef && distance > furthest {
+                break;
+            }
+
+            // The links to the deleted points that could not be repaired are ignored.
+            let node = match self.node(rtxn, pid)? {
+                Some(node) => node,
+                None => continue,
+            };
+
+            for &neighbour in node.links.get(level).into_iter().flatten() {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let neighbour_node = match self.node(rtxn, neighbour)? {
+                    Some(node) => node,
+                    None => continue,
+                };
+
+                let distance = OrderedFloat(query.distance_to(&neighbour_node.point));
+                let furthest = results.peek().map_or(OrderedFloat(f32::INFINITY), |(d, _)| *d);
+                if results.len() < ef || distance < furthest {
+                    candidates.push(Reverse((distance, neighbour)));
+                    if filter(neighbour)? {
+                        results.push((distance, neighbour));
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(results.into_sorted_vec().into_iter().map(|(d, pid)| (d.0, pid)).collect())
+    }
+
+    /// Adds a link from the `from` node to the `to` node on the given layer,
+    /// the furthest links are dropped if the node has too many of them.
+    fn link(&self, wtxn: &mut RwTxn, from: u32, to: u32, level: usize) -> Result<()> {
+        let mut node = match self.node(wtxn, from)? {
+            Some(node) => node,
+            None => return Ok(()),
+        };
+
+        match node.links.get_mut(level) {
+            Some(links) if !links.contains(&to) => links.push(to),
+            _ => return Ok(()),
+        }
+
+        if node.links[level].len() > max_links(level) {
+            let links = node.links[level].clone();
+            let links = self.select_neighbours(wtxn, &node.point, links, level)?;
+            self.set_links(wtxn, from, &mut node, level, links)?;
+        }
+        // the new link was pushed directly, the incoming links must be updated if it was kept
+        if node.links[level].contains(&to) {
+            self.update_node(wtxn, to, |node| add_link(&mut node.incoming, level, from))?;
+        }
+
+        self.put_node(wtxn, from, &node)
+    }
+
+    /// Removes the link from the `from` node to the deleted node on the given layer and
+    /// replaces it by links to the closest neighbours of the deleted node.
+    fn unlink(
+        &self,
+        wtxn: &mut RwTxn,
+        from: u32,
+        deleted: u32,
+        deleted_links: &[u32],
+        level: usize,
+    ) -> Result<()> {
+        let mut node = match self.node(wtxn, from)? {
+            Some(node) => node,
+            None => return Ok(()),
+        };
+
+        let mut candidates = match node.links.get(level) {
+            Some(links) if links.contains(&deleted) => links.clone(),
+            _ => return Ok(()),
+        };
+        candidates.retain(|pid| *pid != deleted);
+        for &pid in deleted_links {
+            if pid != from && !candidates.contains(&pid) {
+                candidates.push(pid);
+            }
+        }
+
+        let links = self.select_neighbours(wtxn, &node.point, candidates, level)?;
+        self.set_links(wtxn, from, &mut node, level, links)?;
+        self.put_node(wtxn, from, &node)
+    }
+
+    /// Replaces the links of the node on the given layer and updates the incoming links
+    /// of the nodes that were added or removed, the node itself must be written by the caller.
+    fn set_links(
+        &self,
+        wtxn: &mut RwTxn,
+        pid: u32,
+        node: &mut HnswNode,
+        level: usize,
+        links: Vec<u32>,
+    ) -> Result<()> {
+        let previous = std::mem::replace(&mut node.links[level], links);
+        for &removed in previous.iter().filter(|p| !node.links[level].contains(p)) {
+            self.update_node(wtxn, removed, |node| remove_link(&mut node.incoming, level, pid))?;
+        }
+        for &added in node.links[level].iter().filter(|p| !previous.contains(p)) {
+            self.update_node(wtxn, added, |node| add_link(&mut node.incoming, level, pid))?;
+        }
+        Ok(())
+    }
+
+    /// Keeps the candidates that are the nearest to the point, at most as many as the layer allows.
+    fn select_neighbours(
+        &self,
+        rtxn: &RoTxn,
+        point: &VectorPoint,
+        candidates: Vec<u32>,
+        level: usize,
+    ) -> Result<Vec<u32>> {
+        let mut neighbours = Vec::with_capacity(candidates.len());
+        for pid in candidates {
+            if let Some(node) = self.node(rtxn, pid)? {
+                neighbours.push((OrderedFloat(point.distance_to(&node.point)), pid));
+            }
+        }
+        neighbours.sort_unstable();
+        neighbours.truncate(max_links(level));
+        Ok(neighbours.into_iter().map(|(_, pid)| pid).collect())
+    }
+
+    /// Finds the node that must become the new entry point once the `deleted` node is removed.
+    fn highest_node(&self, rtxn: &RoTxn, deleted: &HnswNode) -> Result<Option<u32>> {
+        // The neighbours of the deleted node on its highest layers are the best candidates.
+        for links in deleted.links.iter().rev() {
+            let mut highest: Option<(usize, u32)> = None;
+            for &pid in links {
+                if let Some(node) = self.node(rtxn, pid)? {
+                    if highest.map_or(true, |(level, _)| node.level() > level) {
+                        highest = Some((node.level(), pid));
+                    }
+                }
+            }
+            if let Some((_, pid)) = highest {
+                return Ok(Some(pid));
+            }
+        }
+
+        // The deleted node was isolated, we must look at all the nodes of the space.
+        let mut highest: Option<(usize, u32)> = None;
+        for result in self.iter(rtxn)? {
+            let (pid, node) = result?;
+            if highest.map_or(true, |(level, _)| node.level() > level) {
+                highest = Some((node.level(), pid));
+            }
+        }
+        Ok(highest.map(|(_, pid)| pid))
+    }
+
+    fn metadata(&self, rtxn: &RoTxn) -> Result<HnswMetadata> {
+        Ok(self.index.vector_hnsw_metadata(rtxn, self.space)?.unwrap_or_default())
+    }
+
+    fn put_metadata(&self, wtxn: &mut RwTxn, metadata: &HnswMetadata) -> Result<()> {
+        Ok(self.index.put_vector_hnsw_metadata(wtxn, self.space, metadata)?)
+    }
+
+    fn node(&self, rtxn: &RoTxn, pid: u32) -> Result<Option<HnswNode>> {
+        Ok(self.index.vector_hnsw_nodes.get(rtxn, &(self.space, pid))?)
+    }
+
+    fn entry_node(&self, rtxn: &RoTxn, pid: u32) -> Result<HnswNode> {
+        self.node(rtxn, pid)?.ok_or_else(|| {
+            InternalError::DatabaseMissingEntry { db_name: VECTOR_HNSW_NODES, key: None }.into()
+        })
+    }
+
+    fn put_node(&self, wtxn: &mut RwTxn, pid: u32, node: &HnswNode) -> Result<()> {
+        Ok(self.index.vector_hnsw_nodes.put(wtxn, &(self.space, pid), node)?)
+    }
+
+    /// Applies the change on the node if it is still in the graph.
+    fn update_node(
+        &self,
+        wtxn: &mut RwTxn,
+        pid: u32,
+        change: impl FnOnce(&mut HnswNode),
+    ) -> Result<()> {
+        match self.node(wtxn, pid)? {
+            Some(mut node) => {
+                change(&mut node);
+                self.put_node(wtxn, pid, &node)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn add_link(links: &mut [Vec<u32>], level: usize, pid: u32) {
+    if let Some(links) = links.get_mut(level) {
+        if !links.contains(&pid) {
+            links.push(pid);
+        }
+    }
+}
+
+fn remove_link(links: &mut [Vec<u32>], level: usize, pid: u32) {
+    if let Some(links) = links.get_mut(level) {
+        links.retain(|link| *link != pid);
+    }
+}
+
+/// Returns the maximum number of neighbours a node can have on the given layer.
+fn max_links(level: usize) -> usize {
+    if level == 0 {
+        M0
+    } else {
+        M
+    }
+}
+
+/// Draws the highest layer a point belongs to from an exponentially decaying distribution.
+///
+/// The draw is derived from the point id to keep the indexation deterministic.
+fn random_level(pid: u32) -> usize {
+    let hash = fxhash::hash64(&pid);
+    // keep the 53 bits that fit in the mantissa of a f64 to get a uniform number in [0, 1)
+    let uniform = (hash >> 11) as f64 / (1u64 << 53) as f64;
+    let level_multiplier = 1.0 / (M as f64).ln();
+    let level = (-(1.0 - uniform).ln() * level_multiplier).floor() as usize;
+    level.min(MAX_LEVEL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::Distance;
+    use crate::index::tests::TempIndex;
+
+    /// Generates deterministic pseudo-random vectors.
+    fn vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                (0..dimensions)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        (state >> 40) as f32 / (1u64 << 24) as f32
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn insert_search_and_delete() {
+        let index = TempIndex::new();
+        let vectors = vectors(300, 8);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let hnsw = Hnsw::new(&index, "default");
+        let mut pids = Vec::new();
+        for vector in &vectors {
+            let point = VectorPoint::new(Distance::Euclidean, vector.clone());
+            pids.push(hnsw.insert(&mut wtxn, point).unwrap());
+        }
+        assert_eq!(hnsw.len(&wtxn).unwrap(), 300);
+        assert_eq!(hnsw.dimensions(&wtxn).unwrap(), Some(8));
+
+        // every point is its own nearest neighbour
+        for (pid, vector) in pids.iter().zip(&vectors) {
+            let query = VectorPoint::new(Distance::Euclidean, vector.clone());
+            let neighbours = hnsw.search(&wtxn, &query, 10).unwrap();
+            assert_eq!(neighbours[0], (*pid, 0.0));
+        }
+
+        // the deleted points are never returned and the others are still reachable
+        for pid in pids.iter().step_by(2) {
+            assert!(hnsw.delete(&mut wtxn, *pid).unwrap());
+        }
+        assert!(!hnsw.delete(&mut wtxn, pids[0]).unwrap());
+        assert_eq!(hnsw.len(&wtxn).unwrap(), 150);
+
+        for (pid, vector) in pids.iter().zip(&vectors) {
+            let query = VectorPoint::new(Distance::Euclidean, vector.clone());
+            let neighbours = hnsw.search(&wtxn, &query, 10).unwrap();
+            assert!(neighbours.iter().all(|(pid, _)| pid % 2 == 1));
+            if pid % 2 == 1 {
+                assert_eq!(neighbours[0], (*pid, 0.0));
+            }
+        }
+
+        // a graph without points doesn't have metadata anymore
+        for pid in pids.iter().skip(1).step_by(2) {
+            assert!(hnsw.delete(&mut wtxn, *pid).unwrap());
+        }
+        assert!(hnsw.is_empty(&wtxn).unwrap());
+        assert!(index.vector_hnsw_metadata(&wtxn, "default").unwrap().is_none());
+        assert!(index.vector_hnsw_nodes.is_empty(&wtxn).unwrap());
+        assert!(hnsw.search(&wtxn, &VectorPoint::default(), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn recall_after_deletions() {
+        let index = TempIndex::new();
+        let vectors = vectors(600, 8);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let hnsw = Hnsw::new(&index, "default");
+        let mut pids = Vec::new();
+        for vector in &vectors {
+            let point = VectorPoint::new(Distance::Euclidean, vector.clone());
+            pids.push(hnsw.insert(&mut wtxn, point).unwrap());
+        }
+
+        // delete two thirds of the points
+        for pid in pids.iter().filter(|pid| *pid % 3 != 0) {
+            assert!(hnsw.delete(&mut wtxn, *pid).unwrap());
+        }
+
+        // no node is linked to a deleted point anymore, in both directions
+        for result in hnsw.iter(&wtxn).unwrap() {
+            let (pid, node) = result.unwrap();
+            for links in node.links.iter().chain(&node.incoming) {
+                assert!(links.iter().all(|link| link % 3 == 0), "{pid}: {links:?}");
+            }
+        }
+
+        // the nearest neighbours of the remaining points are still found
+        let remaining: Vec<_> =
+            pids.iter().zip(&vectors).filter(|(pid, _)| *pid % 3 == 0).collect();
+        let (mut found, mut expected) = (0, 0);
+        for (_, vector) in &remaining {
+            let query = VectorPoint::new(Distance::Euclidean, (*vector).clone());
+            let mut exact: Vec<_> = remaining
+                .iter()
+                .map(|(pid, other)| {
+                    let other = VectorPoint::new(Distance::Euclidean, (*other).clone());
+                    (OrderedFloat(query.distance_to(&other)), **pid)
+                })
+                .collect();
+            exact.sort_unstable();
+            let exact: HashSet<_> = exact.into_iter().take(10).map(|(_, pid)| pid).collect();
+
+            let neighbours = hnsw.search(&wtxn, &query, 10).unwrap();
+            found += neighbours.iter().take(10).filter(|(pid, _)| exact.contains(pid)).count();
+            expected += exact.len();
+        }
+        let recall = found as f64 / expected as f64;
+        assert!(recall >= 0.95, "recall: {recall}");
+    }
+
+    #[test]
+    fn vector_spaces_are_independent() {
+        let index = TempIndex::new();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let title = Hnsw::new(&index, "title");
+        let image = Hnsw::new(&index, "image");
+        let a =
+            title.insert(&mut wtxn, VectorPoint::new(Distance::Cosine, vec![1.0, 0.0])).unwrap();
+        let b =
+            image.insert(&mut wtxn, VectorPoint::new(Distance::Dot, vec![0.0, 1.0, 0.0])).unwrap();
+        // point ids are given per vector space
+        assert_eq!((a, b), (0, 0));
+
+        assert!(title.delete(&mut wtxn, a).unwrap());
+        assert!(title.is_empty(&wtxn).unwrap());
+        assert_eq!(image.len(&wtxn).unwrap(), 1);
+        assert_eq!(image.dimensions(&wtxn).unwrap(), Some(3));
+        assert_eq!(
+            index.vector_hnsw_spaces(&wtxn).unwrap().into_iter().collect::<Vec<_>>(),
+            ["image"]
+        );
+    }
+}
@@ -12,7 +12,7 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use time::OffsetDateTime;
 
-use crate::distance::NDotProductPoint;
+use crate::distance::VectorSpace;
 use crate::error::{InternalError, UserError};
 use crate::facet::FacetType;
 use crate::fields_ids_map::FieldsIdsMap;
@@ -21,9 +21,12 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{
-    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
+    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrBEU32Codec, StrRefCodec,
 };
-use crate::readable_slices::ReadableSlices;
+use crate::hnsw::{HnswMetadata, HnswNode};
+use crate::localized_attributes_rules::{localized_fields_ids, LocalizedAttributesRule};
+use crate::query_rules::QueryRule;
+use crate::synonyms::{synonyms_from_fst, synonyms_fst, words_synonyms_from_fst, SynonymRule};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdWordCountCodec, GeoPoint, ObkvCodec,
@@ -31,13 +34,15 @@ use crate::{
     BEU32,
 };
 
-/// The HNSW data-structure that we serialize, fill and search in.
-pub type Hnsw = instant_distance::Hnsw<NDotProductPoint>;
-
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// The version of the layout of the HNSW of the vector spaces and of the vector ids,
+/// an index storing its vectors with another layout can't be opened.
+pub const VECTOR_LAYOUT_VERSION: u32 = 1;
+
 pub mod main_key {
+    pub const ATTRIBUTE_WEIGHTS_KEY: &str = "attribute-weights";
     pub const CRITERIA_KEY: &str = "criteria";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
     pub const DISTINCT_FIELD_KEY: &str = "distinct-field-key";
@@ -50,10 +55,13 @@ pub mod main_key {
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
-    /// The prefix of the key that is used to store the, potential big, HNSW structure.
-    /// It is concatenated with a big-endian encoded number (non-human readable).
-    /// e.g. vector-hnsw0x0032.
+    /// The prefix of the key that is used to store the metadata of the HNSW structures.
+    /// It is concatenated with the name of the vector space and a nul byte.
+    /// e.g. vector-hnswdefault\0.
     pub const VECTOR_HNSW_KEY_PREFIX: &str = "vector-hnsw";
+    pub const VECTOR_SPACES_KEY: &str = "vector-spaces";
+    pub const VECTOR_LAYOUT_VERSION_KEY: &str = "vector-layout-version";
+    pub const LOCALIZED_ATTRIBUTES_RULES_KEY: &str = "localized-attributes-rules";
     pub const HARD_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "hard-external-documents-ids";
     pub const NUMBER_FACETED_DOCUMENTS_IDS_PREFIX: &str = "number-faceted-documents-ids";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
@@ -61,11 +69,23 @@ pub mod main_key {
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const SOFT_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "soft-external-documents-ids";
     pub const STOP_WORDS_KEY: &str = "stop-words";
+    pub const STEMMING_LOCALES_KEY: &str = "stemming-locales";
+    pub const STEMS_FST_KEY: &str = "stems-fst";
+    pub const PHONETIC_ATTRIBUTES_KEY: &str = "phonetic-attributes";
+    pub const PHONETIC_FST_KEY: &str = "phonetic-fst";
+    pub const CODE_ATTRIBUTES_KEY: &str = "code-attributes";
+    pub const SUGGESTION_ATTRIBUTES_KEY: &str = "suggestion-attributes";
+    pub const SUGGESTION_FACET_ATTRIBUTES_KEY: &str = "suggestion-facet-attributes";
+    pub const SUGGESTIONS_FST_KEY: &str = "suggestions-fst";
+    pub const SUGGESTION_PHRASES_FST_KEY: &str = "suggestion-phrases-fst";
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
+    /// The normalized synonyms of the indexes created before the synonyms FST.
     pub const SYNONYMS_KEY: &str = "synonyms";
+    pub const SYNONYMS_FST_KEY: &str = "synonyms-fst";
+    pub const QUERY_RULES_KEY: &str = "query-rules";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
@@ -76,8 +96,10 @@ pub mod main_key {
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const HIERARCHICAL_FACETS: &str = "hierarchical-facets";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
 }
 
@@ -106,6 +128,9 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_ID_DOCID: &str = "vector-id-docids";
+    pub const DOCID_VECTOR_IDS: &str = "docid-vector-ids";
+    pub const VECTOR_HNSW_NODES: &str = "vector-hnsw-nodes";
+    pub const SYNONYM_RULES: &str = "synonym-rules";
     pub const DOCUMENTS: &str = "documents";
     pub const SCRIPT_LANGUAGE_DOCIDS: &str = "script_language_docids";
 }
@@ -173,8 +198,15 @@ pub struct Index {
     /// Maps the document id, the facet field id and the strings.
     pub field_id_docid_facet_strings: Database<FieldDocIdFacetStringCodec, Str>,
 
-    /// Maps a vector id to the document id that have it.
-    pub vector_id_docid: Database<OwnedType<BEU32>, OwnedType<BEU32>>,
+    /// Maps a vector space name and a vector id to the document id that have it.
+    pub vector_id_docid: Database<StrBEU32Codec, OwnedType<BEU32>>,
+    /// Maps a document id to the vector space names and vector ids of its vectors.
+    pub docid_vector_ids: Database<OwnedType<BEU32>, SerdeBincode<Vec<(String, u32)>>>,
+    /// Maps a vector space name and a vector id to the HNSW node of this vector.
+    pub vector_hnsw_nodes: Database<StrBEU32Codec, SerdeBincode<HnswNode>>,
+
+    /// Maps the id of a synonym rule defined by the user to this rule.
+    pub synonym_rules: Database<Str, SerdeJson<SynonymRule>>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<OwnedType<BEU32>, ObkvCodec>,
@@ -189,7 +221,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(28);
         unsafe { options.flag(Flags::MdbAlwaysFreePages) };
 
         let env = options.open(path)?;
@@ -233,10 +265,14 @@ impl Index {
         let field_id_docid_facet_strings =
             env.create_database(&mut wtxn, Some(FIELD_ID_DOCID_FACET_STRINGS))?;
         let vector_id_docid = env.create_database(&mut wtxn, Some(VECTOR_ID_DOCID))?;
+        let docid_vector_ids = env.create_database(&mut wtxn, Some(DOCID_VECTOR_IDS))?;
+        let vector_hnsw_nodes = env.create_database(&mut wtxn, Some(VECTOR_HNSW_NODES))?;
+        let synonym_rules = env.create_database(&mut wtxn, Some(SYNONYM_RULES))?;
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
         wtxn.commit()?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
+        Index::check_vector_layout(&env, main, vector_id_docid)?;
 
         Ok(Index {
             env,
@@ -264,6 +300,9 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_id_docid,
+            docid_vector_ids,
+            vector_hnsw_nodes,
+            synonym_rules,
             documents,
         })
     }
@@ -298,6 +337,53 @@ impl Index {
         Ok(())
     }
 
+    /// Writes the version of the layout of the vectors in a new index, and makes sure that
+    /// the vectors of an existing index were not written with a layout that can't be read.
+    ///
+    /// The indexes written before the layout was versioned stored a single chunked HNSW
+    /// and the vector ids without their vector space.
+    fn check_vector_layout(
+        env: &heed::Env,
+        main: PolyDatabase,
+        vector_id_docid: Database<StrBEU32Codec, OwnedType<BEU32>>,
+    ) -> Result<()> {
+        let mut txn = env.write_txn()?;
+        let found =
+            main.get::<_, Str, OwnedType<u32>>(&txn, main_key::VECTOR_LAYOUT_VERSION_KEY)?;
+        match found {
+            Some(version) if version == VECTOR_LAYOUT_VERSION => Ok(()),
+            Some(version) => Err(InternalError::VectorLayoutMismatch {
+                found: Some(version),
+                expected: VECTOR_LAYOUT_VERSION,
+            }
+            .into()),
+            None => {
+                let has_hnsw = main
+                    .prefix_iter::<_, ByteSlice, DecodeIgnore>(
+                        &txn,
+                        main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes(),
+                    )?
+                    .next()
+                    .is_some();
+                if has_hnsw || !vector_id_docid.is_empty(&txn)? {
+                    return Err(InternalError::VectorLayoutMismatch {
+                        found: None,
+                        expected: VECTOR_LAYOUT_VERSION,
+                    }
+                    .into());
+                }
+
+                main.put::<_, Str, OwnedType<u32>>(
+                    &mut txn,
+                    main_key::VECTOR_LAYOUT_VERSION_KEY,
+                    &VECTOR_LAYOUT_VERSION,
+                )?;
+                txn.commit()?;
+                Ok(())
+            }
+        }
+    }
+
     /// Create a write transaction to be able to write into the index.
     pub fn write_txn(&self) -> heed::Result<RwTxn> {
         self.env.write_txn()
@@ -537,24 +623,29 @@ impl Index {
 
     /* vector HNSW */
 
-    /// Writes the provided `hnsw`.
-    pub(crate) fn put_vector_hnsw(&self, wtxn: &mut RwTxn, hnsw: &Hnsw) -> heed::Result<()> {
-        // We must delete all the chunks before we write the new HNSW chunks.
-        self.delete_vector_hnsw(wtxn)?;
-
-        let chunk_size = 1024 * 1024 * (1024 + 512); // 1.5 GiB
-        let bytes = bincode::serialize(hnsw).map_err(|_| heed::Error::Encoding)?;
-        for (i, chunk) in bytes.chunks(chunk_size).enumerate() {
-            let i = i as u32;
-            let mut key = main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes().to_vec();
-            key.extend_from_slice(&i.to_be_bytes());
-            self.main.put::<_, ByteSlice, ByteSlice>(wtxn, &key, chunk)?;
-        }
-        Ok(())
+    /// Writes the metadata of the `hnsw` of the given vector space.
+    pub(crate) fn put_vector_hnsw_metadata(
+        &self,
+        wtxn: &mut RwTxn,
+        space: &str,
+        metadata: &HnswMetadata,
+    ) -> heed::Result<()> {
+        let key = vector_hnsw_key(space);
+        self.main.put::<_, ByteSlice, SerdeBincode<HnswMetadata>>(wtxn, &key, metadata)
+    }
+
+    /// Delete the metadata of the `hnsw` of the given vector space.
+    pub(crate) fn delete_vector_hnsw_metadata(
+        &self,
+        wtxn: &mut RwTxn,
+        space: &str,
+    ) -> heed::Result<bool> {
+        let key = vector_hnsw_key(space);
+        self.main.delete::<_, ByteSlice>(wtxn, &key)
     }
 
-    /// Delete the `hnsw`.
-    pub(crate) fn delete_vector_hnsw(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+    /// Delete the metadata of the `hnsw` of every vector space, the nodes must be cleared separately.
+    pub(crate) fn delete_all_vector_hnsw(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
         let mut iter = self.main.prefix_iter_mut::<_, ByteSlice, DecodeIgnore>(
             wtxn,
             main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes(),
@@ -567,22 +658,56 @@ impl Index {
         Ok(deleted)
     }
 
-    /// Returns the `hnsw`.
-    pub fn vector_hnsw(&self, rtxn: &RoTxn) -> Result<Option<Hnsw>> {
-        let mut slices = Vec::new();
-        for result in
-            self.main.prefix_iter::<_, Str, ByteSlice>(rtxn, main_key::VECTOR_HNSW_KEY_PREFIX)?
-        {
-            let (_, slice) = result?;
-            slices.push(slice);
-        }
+    /// Returns the metadata of the `hnsw` of the given vector space.
+    pub fn vector_hnsw_metadata(
+        &self,
+        rtxn: &RoTxn,
+        space: &str,
+    ) -> heed::Result<Option<HnswMetadata>> {
+        let key = vector_hnsw_key(space);
+        self.main.get::<_, ByteSlice, SerdeBincode<HnswMetadata>>(rtxn, &key)
+    }
 
-        if slices.is_empty() {
-            Ok(None)
-        } else {
-            let readable_slices: ReadableSlices<_> = slices.into_iter().collect();
-            Ok(Some(bincode::deserialize_from(readable_slices).map_err(|_| heed::Error::Decoding)?))
+    /// Returns the names of the vector spaces that have an `hnsw` stored.
+    pub fn vector_hnsw_spaces(&self, rtxn: &RoTxn) -> Result<BTreeSet<String>> {
+        let prefix_len = main_key::VECTOR_HNSW_KEY_PREFIX.len();
+        let mut spaces = BTreeSet::new();
+        for result in self.main.prefix_iter::<_, ByteSlice, DecodeIgnore>(
+            rtxn,
+            main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes(),
+        )? {
+            let (key, ()) = result?;
+            let name = key[prefix_len..].split(|b| *b == 0).next().unwrap_or_default();
+            let name = std::str::from_utf8(name).map_err(|_| heed::Error::Decoding)?;
+            spaces.insert(name.to_string());
         }
+        Ok(spaces)
+    }
+
+    /* vector spaces */
+
+    /// Writes the vector spaces declared by the user.
+    pub(crate) fn put_vector_spaces(
+        &self,
+        wtxn: &mut RwTxn,
+        spaces: &BTreeMap<String, VectorSpace>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::VECTOR_SPACES_KEY, spaces)
+    }
+
+    pub(crate) fn delete_vector_spaces(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VECTOR_SPACES_KEY)
+    }
+
+    /// Returns the vector spaces declared by the user.
+    pub fn vector_spaces(&self, rtxn: &RoTxn) -> heed::Result<BTreeMap<String, VectorSpace>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, VectorSpace>>>(
+                rtxn,
+                main_key::VECTOR_SPACES_KEY,
+            )?
+            .unwrap_or_default())
     }
 
     /* field distribution */
@@ -780,6 +905,74 @@ impl Index {
             .get::<_, Str, SerdeBincode<Vec<_>>>(rtxn, main_key::USER_DEFINED_SEARCHABLE_FIELDS_KEY)
     }
 
+    /* attribute weights */
+
+    /// Writes the weights of the searchable attributes used by the attribute ranking rule.
+    pub(crate) fn put_attribute_weights(
+        &self,
+        wtxn: &mut RwTxn,
+        weights: &BTreeMap<String, u16>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::ATTRIBUTE_WEIGHTS_KEY, weights)
+    }
+
+    pub(crate) fn delete_attribute_weights(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::ATTRIBUTE_WEIGHTS_KEY)
+    }
+
+    /// Returns the weights of the searchable attributes, the higher the weight,
+    /// the more important the attribute. No weights means that the attributes are
+    /// ordered by their position in the searchable attributes.
+    pub fn attribute_weights(&self, rtxn: &RoTxn) -> heed::Result<BTreeMap<String, u16>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, u16>>>(rtxn, main_key::ATTRIBUTE_WEIGHTS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /* localized attributes rules */
+
+    /// Writes the rules pinning the locales of the attributes.
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        wtxn: &mut RwTxn,
+        rules: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(
+            wtxn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES_KEY,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::LOCALIZED_ATTRIBUTES_RULES_KEY)
+    }
+
+    /// Returns the rules pinning the locales of the attributes.
+    pub fn localized_attributes_rules(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Vec<LocalizedAttributesRule>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<LocalizedAttributesRule>>>(
+                rtxn,
+                main_key::LOCALIZED_ATTRIBUTES_RULES_KEY,
+            )?
+            .unwrap_or_default())
+    }
+
+    /// Returns the languages pinned for each field matched by a localized attributes rule.
+    pub fn localized_fields_ids(&self, rtxn: &RoTxn) -> Result<HashMap<FieldId, Vec<Language>>> {
+        let rules = self.localized_attributes_rules(rtxn)?;
+        if rules.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(localized_fields_ids(&rules, &fields_ids_map))
+    }
+
     /* filterable fields */
 
     /// Writes the filterable fields names in the database.
@@ -888,7 +1081,8 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct,
+    /// Asc/Desc, and function score fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
@@ -896,6 +1090,7 @@ impl Index {
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::FunctionScore(function_score) => Some(function_score.field),
                 _otherwise => None,
             });
 
@@ -1058,6 +1253,259 @@ impl Index {
         }
     }
 
+    /* stemming */
+
+    pub(crate) fn put_stemming_locales(
+        &self,
+        wtxn: &mut RwTxn,
+        locales: &[String],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::STEMMING_LOCALES_KEY, &locales)
+    }
+
+    pub(crate) fn delete_stemming_locales(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::STEMMING_LOCALES_KEY)
+    }
+
+    /// Returns the locales whose stemmers are used to match the words sharing the same stem,
+    /// the stemming is disabled when empty.
+    pub fn stemming_locales(&self, rtxn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::STEMMING_LOCALES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Writes the FST associating the stems with the words of the engine.
+    pub(crate) fn put_stems_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(wtxn, main_key::STEMS_FST_KEY, fst.as_fst().as_bytes())
+    }
+
+    pub(crate) fn delete_stems_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::STEMS_FST_KEY)
+    }
+
+    /// Returns the FST associating the stems with the words of the engine,
+    /// its keys are the stems followed by a nul byte and a word having this stem.
+    pub fn stems_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Set<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::STEMS_FST_KEY)? {
+            Some(bytes) => Ok(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Set::default().map_data(Cow::Owned)?),
+        }
+    }
+
+    /* phonetic */
+
+    pub(crate) fn put_phonetic_attributes(
+        &self,
+        wtxn: &mut RwTxn,
+        attributes: &[String],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::PHONETIC_ATTRIBUTES_KEY, &attributes)
+    }
+
+    pub(crate) fn delete_phonetic_attributes(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::PHONETIC_ATTRIBUTES_KEY)
+    }
+
+    /// Returns the attributes whose words are also matched by their phonetic codes.
+    pub fn phonetic_attributes(&self, rtxn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::PHONETIC_ATTRIBUTES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the phonetic attributes.
+    pub fn phonetic_attributes_ids(&self, rtxn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attributes = self.phonetic_attributes(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(attributes.iter().filter_map(|attribute| fields_ids_map.id(attribute)).collect())
+    }
+
+    /// Writes the FST associating the phonetic codes with the words of the phonetic attributes.
+    pub(crate) fn put_phonetic_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::PHONETIC_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_phonetic_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::PHONETIC_FST_KEY)
+    }
+
+    /// Returns the FST associating the phonetic codes with the words of the phonetic attributes,
+    /// its keys are the phonetic codes followed by a nul byte and a word having this code.
+    pub fn phonetic_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Set<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::PHONETIC_FST_KEY)? {
+            Some(bytes) => Ok(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Set::default().map_data(Cow::Owned)?),
+        }
+    }
+
+    /* code attributes */
+
+    pub(crate) fn put_code_attributes(
+        &self,
+        wtxn: &mut RwTxn,
+        attributes: &[String],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::CODE_ATTRIBUTES_KEY, &attributes)
+    }
+
+    pub(crate) fn delete_code_attributes(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::CODE_ATTRIBUTES_KEY)
+    }
+
+    /// Returns the attributes tokenized in code mode, keeping their identifiers whole.
+    pub fn code_attributes(&self, rtxn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::CODE_ATTRIBUTES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the code attributes.
+    pub fn code_attributes_ids(&self, rtxn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attributes = self.code_attributes(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(attributes.iter().filter_map(|attribute| fields_ids_map.id(attribute)).collect())
+    }
+
+    /* suggestions */
+
+    pub(crate) fn put_suggestion_attributes(
+        &self,
+        wtxn: &mut RwTxn,
+        attributes: &[String],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(
+            wtxn,
+            main_key::SUGGESTION_ATTRIBUTES_KEY,
+            &attributes,
+        )
+    }
+
+    pub(crate) fn delete_suggestion_attributes(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SUGGESTION_ATTRIBUTES_KEY)
+    }
+
+    /// Returns the attributes whose values are suggested as completions of the queries.
+    pub fn suggestion_attributes(&self, rtxn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::SUGGESTION_ATTRIBUTES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the suggestion attributes.
+    pub fn suggestion_attributes_ids(&self, rtxn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attributes = self.suggestion_attributes(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(attributes.iter().filter_map(|attribute| fields_ids_map.id(attribute)).collect())
+    }
+
+    pub(crate) fn put_suggestion_facet_attributes(
+        &self,
+        wtxn: &mut RwTxn,
+        attributes: &[String],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(
+            wtxn,
+            main_key::SUGGESTION_FACET_ATTRIBUTES_KEY,
+            &attributes,
+        )
+    }
+
+    pub(crate) fn delete_suggestion_facet_attributes(
+        &self,
+        wtxn: &mut RwTxn,
+    ) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SUGGESTION_FACET_ATTRIBUTES_KEY)
+    }
+
+    /// Returns the filterable attributes whose most frequent values are suggested
+    /// as completions of the queries.
+    pub fn suggestion_facet_attributes(&self, rtxn: &RoTxn) -> heed::Result<Vec<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<Vec<String>>>(rtxn, main_key::SUGGESTION_FACET_ATTRIBUTES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the suggestion facet attributes.
+    pub fn suggestion_facet_attributes_ids(&self, rtxn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attributes = self.suggestion_facet_attributes(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(attributes.iter().filter_map(|attribute| fields_ids_map.id(attribute)).collect())
+    }
+
+    /// Writes the FST associating the suggested phrases with their frequency.
+    pub(crate) fn put_suggestions_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Map<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::SUGGESTIONS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_suggestions_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SUGGESTIONS_FST_KEY)
+    }
+
+    /// Returns the FST associating the normalized phrases suggested as completions of
+    /// the queries with the number of documents in which they appear.
+    pub fn suggestions_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Map<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::SUGGESTIONS_FST_KEY)? {
+            Some(bytes) => Ok(fst::Map::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Map::default().map_data(Cow::Owned)?),
+        }
+    }
+
+    /// Writes the FST associating the phrases of the suggestion attributes
+    /// with the number of documents in which they appear.
+    pub(crate) fn put_suggestion_phrases_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Map<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::SUGGESTION_PHRASES_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_suggestion_phrases_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SUGGESTION_PHRASES_FST_KEY)
+    }
+
+    /// Returns the FST associating the phrases of the suggestion attributes with the number
+    /// of documents in which they appear, the suggestions are updated from it.
+    pub(crate) fn suggestion_phrases_fst<'t>(
+        &self,
+        rtxn: &'t RoTxn,
+    ) -> Result<fst::Map<Cow<'t, [u8]>>> {
+        match self.main.get::<_, Str, ByteSlice>(rtxn, main_key::SUGGESTION_PHRASES_FST_KEY)? {
+            Some(bytes) => Ok(fst::Map::new(bytes)?.map_data(Cow::Borrowed)?),
+            None => Ok(fst::Map::default().map_data(Cow::Owned)?),
+        }
+    }
+
     /* stop words */
 
     pub(crate) fn put_stop_words<A: AsRef<[u8]>>(
@@ -1162,13 +1610,52 @@ impl Index {
 
     /* synonyms */
 
-    pub(crate) fn put_synonyms(
+    /// Writes the FST storing the normalized synonyms of the engine.
+    pub(crate) fn put_synonyms_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, ByteSlice>(
+            wtxn,
+            main_key::SYNONYMS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_synonyms_fst(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SYNONYMS_FST_KEY)
+    }
+
+    /// Returns the FST storing the normalized synonyms of the engine, its keys are
+    /// the words followed by a nul byte and one of their synonyms.
+    ///
+    /// The indexes created before the synonyms FST only store their normalized synonyms
+    /// under the legacy synonyms key, the FST is built from them until the next update.
+    pub fn synonyms_fst<'t>(&self, rtxn: &'t RoTxn) -> Result<fst::Set<Cow<'t, [u8]>>> {
+        if let Some(bytes) = self.main.get::<_, Str, ByteSlice>(rtxn, main_key::SYNONYMS_FST_KEY)? {
+            return Ok(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?);
+        }
+
+        match self.main.get::<_, Str, SerdeBincode<HashMap<Vec<String>, Vec<Vec<String>>>>>(
+            rtxn,
+            main_key::SYNONYMS_KEY,
+        )? {
+            Some(synonyms) => Ok(synonyms_fst(&synonyms)?.map_data(Cow::Owned)?),
+            None => Ok(fst::Set::default().map_data(Cow::Owned)?),
+        }
+    }
+
+    /// Deletes the normalized synonyms stored under the legacy synonyms key.
+    pub(crate) fn delete_legacy_synonyms(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SYNONYMS_KEY)
+    }
+
+    pub(crate) fn put_user_defined_synonyms(
         &self,
         wtxn: &mut RwTxn,
-        synonyms: &HashMap<Vec<String>, Vec<Vec<String>>>,
         user_defined_synonyms: &BTreeMap<String, Vec<String>>,
     ) -> heed::Result<()> {
-        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SYNONYMS_KEY, synonyms)?;
         self.main.put::<_, Str, SerdeBincode<_>>(
             wtxn,
             main_key::USER_DEFINED_SYNONYMS_KEY,
@@ -1176,8 +1663,7 @@ impl Index {
         )
     }
 
-    pub(crate) fn delete_synonyms(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
-        self.main.delete::<_, Str>(wtxn, main_key::SYNONYMS_KEY)?;
+    pub(crate) fn delete_user_defined_synonyms(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
         self.main.delete::<_, Str>(wtxn, main_key::USER_DEFINED_SYNONYMS_KEY)
     }
 
@@ -1191,20 +1677,67 @@ impl Index {
             .unwrap_or_default())
     }
 
-    pub fn synonyms(&self, rtxn: &RoTxn) -> heed::Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+    pub(crate) fn put_synonym_rule(
+        &self,
+        wtxn: &mut RwTxn,
+        id: &str,
+        rule: &SynonymRule,
+    ) -> heed::Result<()> {
+        self.synonym_rules.put(wtxn, id, rule)
+    }
+
+    pub(crate) fn delete_synonym_rule(&self, wtxn: &mut RwTxn, id: &str) -> heed::Result<bool> {
+        self.synonym_rules.delete(wtxn, id)
+    }
+
+    /// Returns the synonym rule defined by the user with the given id.
+    pub fn synonym_rule(&self, rtxn: &RoTxn, id: &str) -> heed::Result<Option<SynonymRule>> {
+        self.synonym_rules.get(rtxn, id)
+    }
+
+    /// Returns the synonym rules defined by the user, identified by their id.
+    pub fn synonym_rules(&self, rtxn: &RoTxn) -> heed::Result<BTreeMap<String, SynonymRule>> {
+        self.synonym_rules
+            .iter(rtxn)?
+            .map(|result| result.map(|(id, rule)| (id.to_string(), rule)))
+            .collect()
+    }
+
+    /* query rules */
+
+    pub(crate) fn put_query_rules(
+        &self,
+        wtxn: &mut RwTxn,
+        rules: &BTreeMap<String, QueryRule>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(wtxn, main_key::QUERY_RULES_KEY, rules)
+    }
+
+    pub(crate) fn delete_query_rules(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::QUERY_RULES_KEY)
+    }
+
+    /// Returns the query rules defined by the user, identified by their id.
+    pub fn query_rules(&self, rtxn: &RoTxn) -> heed::Result<BTreeMap<String, QueryRule>> {
         Ok(self
             .main
-            .get::<_, Str, SerdeBincode<_>>(rtxn, main_key::SYNONYMS_KEY)?
+            .get::<_, Str, SerdeJson<_>>(rtxn, main_key::QUERY_RULES_KEY)?
             .unwrap_or_default())
     }
 
+    /// Returns all the normalized synonyms, the user-defined synonyms and the synonym rules merged.
+    pub fn synonyms(&self, rtxn: &RoTxn) -> Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+        synonyms_from_fst(&self.synonyms_fst(rtxn)?)
+    }
+
     pub fn words_synonyms<S: AsRef<str>>(
         &self,
         rtxn: &RoTxn,
         words: &[S],
-    ) -> heed::Result<Option<Vec<Vec<String>>>> {
+    ) -> Result<Option<Vec<Vec<String>>>> {
         let words: Vec<_> = words.iter().map(|s| s.as_ref().to_owned()).collect();
-        Ok(self.synonyms(rtxn)?.remove(&words))
+        let synonyms = words_synonyms_from_fst(&self.synonyms_fst(rtxn)?, &words)?;
+        Ok(if synonyms.is_empty() { None } else { Some(synonyms) })
     }
 
     /* words prefixes fst */
@@ -1334,6 +1867,34 @@ impl Index {
         Ok(())
     }
 
+    /// Returns whether the typos are disabled on the words containing digits.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // The absence of a value is false, because by default, we authorize typos on numbers,
+        // unless the index has code attributes, whose identifiers must be matched exactly.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) => Ok(false),
+            Some(_) => Ok(true),
+            None => Ok(!self.code_attributes(txn)?.is_empty()),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn delete_disable_typos_on_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)
+    }
+
     pub fn min_word_len_one_typo(&self, txn: &RoTxn) -> heed::Result<u8> {
         // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
         // identify 0 as being false, and anything else as true. The absence of a value is true,
@@ -1454,6 +2015,36 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::SORT_FACET_VALUES_BY)
     }
 
+    /// Returns the hierarchical facets along with the separator of the levels of their paths.
+    pub fn hierarchical_facets(&self, txn: &RoTxn) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, String>>>(txn, main_key::HIERARCHICAL_FACETS)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the hierarchical facets along with their separator.
+    pub fn hierarchical_facets_ids(&self, txn: &RoTxn) -> Result<HashMap<FieldId, String>> {
+        let facets = self.hierarchical_facets(txn)?;
+        let fields_ids_map = self.fields_ids_map(txn)?;
+        Ok(facets
+            .into_iter()
+            .filter_map(|(name, separator)| Some((fields_ids_map.id(&name)?, separator)))
+            .collect())
+    }
+
+    pub(crate) fn put_hierarchical_facets(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(txn, main_key::HIERARCHICAL_FACETS, val)
+    }
+
+    pub(crate) fn delete_hierarchical_facets(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::HIERARCHICAL_FACETS)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn) -> heed::Result<Option<usize>> {
         self.main.get::<_, Str, OwnedType<usize>>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1514,19 +2105,31 @@ impl Index {
     }
 }
 
+/// Returns the key under which the metadata of the `hnsw` of a vector space is stored.
+fn vector_hnsw_key(space: &str) -> Vec<u8> {
+    let mut key = main_key::VECTOR_HNSW_KEY_PREFIX.as_bytes().to_vec();
+    key.extend_from_slice(space.as_bytes());
+    key.push(0);
+    key
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::collections::HashSet;
     use std::ops::Deref;
 
     use big_s::S;
+    use heed::types::{ByteSlice, OwnedType, Str};
     use heed::{EnvOpenOptions, RwTxn};
     use maplit::hashset;
     use tempfile::TempDir;
 
     use crate::documents::DocumentsBatchReader;
     use crate::error::{Error, InternalError};
-    use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+    use crate::index::{
+        main_key, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
+        VECTOR_LAYOUT_VERSION,
+    };
     use crate::update::{
         self, DeleteDocuments, DeletionStrategy, IndexDocuments, IndexDocumentsConfig,
         IndexDocumentsMethod, IndexerConfig, Settings,
@@ -2717,8 +3320,11 @@ pub(crate) mod tests {
         let SearchResult {
             matching_words: _,
             candidates: _,
+            facet_candidates: _,
             document_scores: _,
             mut documents_ids,
+            did_you_mean: _,
+            auto_corrected: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -2794,4 +3400,39 @@ pub(crate) mod tests {
 
         db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
     }
+
+    #[test]
+    fn refuse_to_open_an_index_with_a_previous_vector_layout() {
+        let tempdir = TempDir::new_in(".").unwrap();
+        let options = || {
+            let mut options = EnvOpenOptions::new();
+            options.map_size(4096 * 100);
+            options
+        };
+
+        let index = Index::new(options(), tempdir.path()).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let version = index
+            .main
+            .get::<_, Str, OwnedType<u32>>(&rtxn, main_key::VECTOR_LAYOUT_VERSION_KEY)
+            .unwrap();
+        assert_eq!(version, Some(VECTOR_LAYOUT_VERSION));
+        drop(rtxn);
+
+        // the indexes written before the layout was versioned stored their HNSW in chunks.
+        let mut wtxn = index.write_txn().unwrap();
+        index.main.delete::<_, Str>(&mut wtxn, main_key::VECTOR_LAYOUT_VERSION_KEY).unwrap();
+        index.main.put::<_, ByteSlice, ByteSlice>(&mut wtxn, b"vector-hnsw\0\0\0\0", b"").unwrap();
+        wtxn.commit().unwrap();
+        index.prepare_for_closing().wait();
+
+        let error = Index::new(options(), tempdir.path()).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::InternalError(InternalError::VectorLayoutMismatch {
+                found: None,
+                expected: VECTOR_LAYOUT_VERSION
+            })
+        ));
+    }
 }
@@ -9,6 +9,7 @@ pub static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 pub mod documents;
 
 mod asc_desc;
+pub mod code_identifiers;
 mod criterion;
 pub mod distance;
 mod error;
@@ -16,11 +17,17 @@ mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
+pub mod hnsw;
 pub mod index;
+pub mod localized_attributes_rules;
+pub mod phonetic;
 pub mod proximity;
-mod readable_slices;
+pub mod query_rules;
+pub mod rules_update;
 pub mod score_details;
 mod search;
+pub mod stemming;
+pub mod synonyms;
 pub mod update;
 
 #[cfg(test)]
@@ -32,19 +39,21 @@ use std::convert::{TryFrom, TryInto};
 use std::hash::BuildHasherDefault;
 
 use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
-pub use distance::dot_product_similarity;
+pub use distance::{dot_product_similarity, Distance, VectorSpace, DEFAULT_VECTOR_SPACE};
 pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
     execute_search, DefaultSearchLogger, GeoSortStrategy, SearchContext, SearchLogger,
-    VisualSearchLogger,
+    VectorSearchStrategy, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use {charabia as tokenizer, heed};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, Criterion, CriterionError, FunctionScore, ScoreFunction,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -57,11 +66,17 @@ pub use self::heed_codec::{
     UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::query_rules::QueryRule;
 pub use self::search::{
-    FacetDistribution, FacetValueHit, Filter, FormatOptions, MatchBounds, MatcherBuilder,
-    MatchingWords, OrderBy, Search, SearchForFacetValues, SearchResult, TermsMatchingStrategy,
-    DEFAULT_VALUES_PER_FACET,
+    correct_query, Aggregation, AggregationBucket, AggregationResult, BucketKey, CalendarInterval,
+    DidYouMean, FacetBucket, FacetBuckets, FacetDistribution, FacetHierarchyNode, FacetRange,
+    FacetStatsOptions, FacetValueHit, FacetValuesStats, Filter, FormatOptions, FusionMethod,
+    MatchBounds, MatcherBuilder, MatchingWords, OrderBy, QuerySyntax, Search, SearchForFacetValues,
+    SearchForSuggestions, SearchResult, Suggestion, TermsMatchingStrategy, TypoToleranceOverride,
+    DEFAULT_DID_YOU_MEAN_MAX_HITS, DEFAULT_SUGGESTIONS_LIMIT, DEFAULT_VALUES_PER_FACET,
 };
+pub use self::synonyms::{SynonymRule, SynonymRuleKind};
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
@@ -289,6 +304,55 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Returns the path of a hierarchical facet value without its empty leading levels.
+///
+/// ```rust
+/// use milli::trim_facet_path;
+///
+/// assert_eq!(trim_facet_path("/Electronics/Phones", "/"), "Electronics/Phones");
+/// assert_eq!(trim_facet_path(" > Electronics", " > "), "Electronics");
+/// ```
+pub fn trim_facet_path<'a>(path: &'a str, separator: &str) -> &'a str {
+    let separator = separator.trim_start();
+    let mut path = path.trim_start();
+    while let Some(rest) = path.strip_prefix(separator).filter(|_| !separator.is_empty()) {
+        path = rest.trim_start();
+    }
+    path
+}
+
+/// Normalizes the path of a hierarchical facet value, without its empty leading levels.
+pub fn normalize_facet_path(path: &str, separator: &str) -> String {
+    normalize_facet(trim_facet_path(path, separator))
+}
+
+/// Returns the ancestors of the path of a hierarchical facet value, from the root level.
+///
+/// The empty leading levels of the path are ignored.
+///
+/// ```rust
+/// use milli::facet_path_ancestors;
+///
+/// let ancestors: Vec<_> = facet_path_ancestors("Electronics > Phones > Android", " > ").collect();
+/// assert_eq!(ancestors, ["Electronics", "Electronics > Phones"]);
+/// ```
+pub fn facet_path_ancestors<'a>(
+    path: &'a str,
+    separator: &'a str,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let path = trim_facet_path(path, separator);
+    path.match_indices(separator)
+        .map(move |(index, _)| &path[..index])
+        .filter(|ancestor| !ancestor.trim().is_empty())
+}
+
+/// Returns the prefix of the normalized values of the descendants of a normalized path
+/// of a hierarchical facet.
+pub fn facet_path_descendants_prefix(normalized_path: &str, separator: &str) -> String {
+    let separator = CompatibilityDecompositionNormalizer.normalize_str(separator).to_lowercase();
+    format!("{normalized_path}{separator}")
+}
+
 /// Represents either a vector or an array of multiple vectors.
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 #[serde(transparent)]
@@ -324,6 +388,25 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_facet_path_ancestors() {
+        let ancestors = |path, separator| facet_path_ancestors(path, separator).collect::<Vec<_>>();
+
+        assert_eq!(
+            ancestors("Electronics > Phones > Android", " > "),
+            ["Electronics", "Electronics > Phones"]
+        );
+        assert_eq!(ancestors("Electronics/Phones", "/"), ["Electronics"]);
+        assert_eq!(ancestors("Electronics", " > "), Vec::<&str>::new());
+        // the empty leading levels are not ancestors.
+        assert_eq!(ancestors("/Electronics/Phones", "/"), ["Electronics"]);
+        assert_eq!(
+            ancestors("//Electronics/Phones/Android", "/"),
+            ["Electronics", "Electronics/Phones"]
+        );
+        assert_eq!(ancestors(" > Electronics > TV", " > "), ["Electronics"]);
+    }
+
     #[test]
     fn json_to_string_object() {
         let value = json!({
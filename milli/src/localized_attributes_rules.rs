@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::{is_faceted_by, FieldId, FieldsIdsMap};
+
+/// Pins the locales of the attributes matching the patterns, the language of their
+/// values is not freely detected anymore but restricted to the given locales.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    /// The attributes the rule applies to, a pattern can start and/or end
+    /// with a `*` to match any prefix and/or suffix, `*` alone matches every attribute.
+    pub attribute_patterns: Vec<String>,
+    /// The ISO 639-3 codes of the locales, e.g. `jpn`, `cmn` or `swe`.
+    pub locales: Vec<String>,
+}
+
+impl LocalizedAttributesRule {
+    /// Returns `true` if the attribute, or one of its parents, matches a pattern of the rule.
+    pub fn match_attribute(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, attribute))
+    }
+
+    /// Returns the languages of the locales of the rule, the invalid locales are ignored.
+    pub fn languages(&self) -> Vec<Language> {
+        self.locales.iter().filter_map(|locale| parse_locale(locale)).collect()
+    }
+}
+
+fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        _ if pattern == "*" => true,
+        (Some(_), Some(_)) => attribute.contains(&pattern[1..pattern.len() - 1]),
+        (Some(suffix), None) => attribute.ends_with(suffix),
+        (None, Some(prefix)) => attribute.starts_with(prefix),
+        (None, None) => is_faceted_by(attribute, pattern),
+    }
+}
+
+/// Returns the language of the given ISO 639-3 code, `None` if it is not supported.
+pub fn parse_locale(code: &str) -> Option<Language> {
+    let language = Language::from_name(code);
+    (language.name() == code).then_some(language)
+}
+
+/// Returns the languages of the fields matched by the rules,
+/// only the first rule matching a field is used.
+pub fn localized_fields_ids(
+    rules: &[LocalizedAttributesRule],
+    fields_ids_map: &FieldsIdsMap,
+) -> HashMap<FieldId, Vec<Language>> {
+    let mut localized_fields = HashMap::new();
+    for (field_id, name) in fields_ids_map.iter() {
+        if let Some(rule) = rules.iter().find(|rule| rule.match_attribute(name)) {
+            localized_fields.insert(field_id, rule.languages());
+        }
+    }
+    localized_fields
+}
+
+/// The scripts the tokenizer can detect.
+const SCRIPTS: &[Script] = &[
+    Script::Arabic,
+    Script::Armenian,
+    Script::Bengali,
+    Script::Cyrillic,
+    Script::Devanagari,
+    Script::Ethiopic,
+    Script::Georgian,
+    Script::Greek,
+    Script::Gujarati,
+    Script::Gurmukhi,
+    Script::Hangul,
+    Script::Hebrew,
+    Script::Kannada,
+    Script::Khmer,
+    Script::Latin,
+    Script::Malayalam,
+    Script::Myanmar,
+    Script::Oriya,
+    Script::Sinhala,
+    Script::Tamil,
+    Script::Telugu,
+    Script::Thai,
+    Script::Cj,
+    Script::Other,
+];
+
+/// Builds the allow list of a tokenizer that restricts the language detection
+/// of every script to the given locales.
+///
+/// It doesn't depend on the tokenized text and can be reused for all the values of a field.
+pub fn locales_allow_list(locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    SCRIPTS.iter().map(|script| (*script, locales.to_vec())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["title".into(), "*_ja".into(), "ja_*".into(), "*jpn*".into()],
+            locales: vec!["jpn".into()],
+        };
+
+        assert!(rule.match_attribute("title"));
+        assert!(rule.match_attribute("title.main"));
+        assert!(rule.match_attribute("description_ja"));
+        assert!(rule.match_attribute("ja_description"));
+        assert!(rule.match_attribute("description_jpn_short"));
+        assert!(!rule.match_attribute("titles"));
+        assert!(!rule.match_attribute("description"));
+
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["*".into()],
+            locales: vec!["swe".into()],
+        };
+        assert!(rule.match_attribute("description"));
+    }
+
+    #[test]
+    fn parse_locales() {
+        assert_eq!(parse_locale("jpn"), Some(Language::Jpn));
+        assert_eq!(parse_locale("cmn"), Some(Language::Cmn));
+        assert_eq!(parse_locale("japanese"), None);
+        assert_eq!(parse_locale("JPN"), None);
+        assert_eq!(parse_locale(""), None);
+    }
+}
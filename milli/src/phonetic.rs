@@ -0,0 +1,720 @@
+//! The Double Metaphone phonetic encoding of the words, used to match the
+//! words that sound alike but are spelled differently, e.g. `Smith` and `Schmidt`.
+
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+
+/// The byte separating the phonetic code from the word in the keys of the phonetic FST.
+const CODE_SEPARATOR: u8 = 0;
+
+/// The maximum length of a phonetic code.
+const MAX_CODE_LENGTH: usize = 4;
+
+/// Returns the distinct phonetic codes of the word, its primary Double Metaphone
+/// code followed by its alternate one when they differ.
+///
+/// Only the words made of latin letters are encoded, no codes are returned for the others.
+pub fn phonetic_codes(word: &str) -> Vec<String> {
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic() || c == 'ç' || c == 'ñ') {
+        return Vec::new();
+    }
+
+    let (primary, alternate) = DoubleMetaphone::new(word).encode();
+    let mut codes = Vec::with_capacity(2);
+    if !primary.is_empty() {
+        codes.push(primary);
+    }
+    if !alternate.is_empty() && !codes.contains(&alternate) {
+        codes.push(alternate);
+    }
+    codes
+}
+
+/// Returns the key of the phonetic FST associating the word with its phonetic code.
+pub fn phonetic_key(code: &str, word: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(code.len() + 1 + word.len());
+    key.extend_from_slice(code.as_bytes());
+    key.push(CODE_SEPARATOR);
+    key.extend_from_slice(word.as_bytes());
+    key
+}
+
+/// Calls `visit` with every word of the phonetic FST that has the given phonetic code.
+pub fn words_with_phonetic_code<A: AsRef<[u8]>>(
+    phonetic_fst: &fst::Set<A>,
+    code: &str,
+    mut visit: impl FnMut(&str) -> crate::Result<std::ops::ControlFlow<()>>,
+) -> crate::Result<()> {
+    let mut prefix = String::with_capacity(code.len() + 1);
+    prefix.push_str(code);
+    prefix.push(CODE_SEPARATOR as char);
+
+    let mut stream = phonetic_fst.search(Str::new(&prefix).starts_with()).into_stream();
+    while let Some(key) = stream.next() {
+        let word = std::str::from_utf8(&key[prefix.len()..])?;
+        if visit(word)?.is_break() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// An implementation of the Double Metaphone algorithm by Lawrence Philips.
+struct DoubleMetaphone {
+    value: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphone {
+    fn new(word: &str) -> DoubleMetaphone {
+        let value: Vec<char> = word.chars().flat_map(char::to_uppercase).collect();
+        let upper: String = value.iter().collect();
+        let slavo_germanic = upper.contains('W')
+            || upper.contains('K')
+            || upper.contains("CZ")
+            || upper.contains("WITZ");
+        DoubleMetaphone { value, slavo_germanic, primary: String::new(), alternate: String::new() }
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut index = if self.is_silent_start() { 1 } else { 0 };
+        while !self.is_complete() && index < self.len() {
+            index = match self.at(index as isize) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.push('A');
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.push('P');
+                    self.skip_double(index, 'B')
+                }
+                'Ç' => {
+                    self.push('S');
+                    index + 1
+                }
+                'C' => self.handle_c(index),
+                'D' => self.handle_d(index),
+                'F' => {
+                    self.push('F');
+                    self.skip_double(index, 'F')
+                }
+                'G' => self.handle_g(index),
+                'H' => self.handle_h(index),
+                'J' => self.handle_j(index),
+                'K' => {
+                    self.push('K');
+                    self.skip_double(index, 'K')
+                }
+                'L' => self.handle_l(index),
+                'M' => {
+                    self.push('M');
+                    if self.condition_m0(index) {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.push('N');
+                    self.skip_double(index, 'N')
+                }
+                'Ñ' => {
+                    self.push('N');
+                    index + 1
+                }
+                'P' => self.handle_p(index),
+                'Q' => {
+                    self.push('K');
+                    self.skip_double(index, 'Q')
+                }
+                'R' => self.handle_r(index),
+                'S' => self.handle_s(index),
+                'T' => self.handle_t(index),
+                'V' => {
+                    self.push('F');
+                    self.skip_double(index, 'V')
+                }
+                'W' => self.handle_w(index),
+                'X' => self.handle_x(index),
+                'Z' => self.handle_z(index),
+                _ => index + 1,
+            };
+        }
+        (self.primary, self.alternate)
+    }
+
+    fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Returns the char at the given index, a nul char if the index is out of bounds.
+    fn at(&self, index: isize) -> char {
+        usize::try_from(index).ok().and_then(|i| self.value.get(i)).copied().unwrap_or('\0')
+    }
+
+    /// Returns `true` if the substring of the given length starting at the
+    /// given index is one of the given strings.
+    fn contains(&self, start: isize, length: usize, criteria: &[&str]) -> bool {
+        let Ok(start) = usize::try_from(start) else { return false };
+        if start + length > self.len() {
+            return false;
+        }
+        let target: String = self.value[start..start + length].iter().collect();
+        criteria.iter().any(|c| *c == target)
+    }
+
+    fn is_vowel(c: char) -> bool {
+        matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn is_silent_start(&self) -> bool {
+        self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"])
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= MAX_CODE_LENGTH && self.alternate.len() >= MAX_CODE_LENGTH
+    }
+
+    fn push_primary(&mut self, s: &str) {
+        for c in s.chars() {
+            if self.primary.len() < MAX_CODE_LENGTH {
+                self.primary.push(c);
+            }
+        }
+    }
+
+    fn push_alternate(&mut self, s: &str) {
+        for c in s.chars() {
+            if self.alternate.len() < MAX_CODE_LENGTH {
+                self.alternate.push(c);
+            }
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.push_both(c.encode_utf8(&mut [0; 4]), c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn push_both(&mut self, primary: &str, alternate: &str) {
+        self.push_primary(primary);
+        self.push_alternate(alternate);
+    }
+
+    fn skip_double(&self, index: usize, c: char) -> usize {
+        if self.at(index as isize + 1) == c {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_c(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.condition_c0(i) {
+            self.push('K');
+            index + 2
+        } else if index == 0 && self.contains(i, 6, &["CAESAR"]) {
+            self.push('S');
+            index + 2
+        } else if self.contains(i, 2, &["CH"]) {
+            self.handle_ch(index)
+        } else if self.contains(i, 2, &["CZ"]) && !self.contains(i - 2, 4, &["WICZ"]) {
+            self.push_both("S", "X");
+            index + 2
+        } else if self.contains(i + 1, 3, &["CIA"]) {
+            self.push('X');
+            index + 3
+        } else if self.contains(i, 2, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            self.handle_cc(index)
+        } else if self.contains(i, 2, &["CK", "CG", "CQ"]) {
+            self.push('K');
+            index + 2
+        } else if self.contains(i, 2, &["CI", "CE", "CY"]) {
+            if self.contains(i, 3, &["CIO", "CIE", "CIA"]) {
+                self.push_both("S", "X");
+            } else {
+                self.push('S');
+            }
+            index + 2
+        } else {
+            self.push('K');
+            if self.contains(i + 1, 2, &[" C", " Q", " G"]) {
+                index + 3
+            } else if self.contains(i + 1, 1, &["C", "K", "Q"])
+                && !self.contains(i + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn condition_c0(&self, i: isize) -> bool {
+        if self.contains(i, 4, &["CHIA"]) {
+            true
+        } else if i <= 1 || Self::is_vowel(self.at(i - 2)) || !self.contains(i - 1, 3, &["ACH"]) {
+            false
+        } else {
+            let c = self.at(i + 2);
+            (c != 'I' && c != 'E') || self.contains(i - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn handle_cc(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i + 2, 1, &["I", "E", "H"]) && !self.contains(i + 2, 2, &["HU"]) {
+            if (index == 1 && self.at(i - 1) == 'A') || self.contains(i - 1, 5, &["UCCEE", "UCCES"])
+            {
+                self.push_both("KS", "KS");
+            } else {
+                self.push('X');
+            }
+            index + 3
+        } else {
+            self.push('K');
+            index + 2
+        }
+    }
+
+    fn handle_ch(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index > 0 && self.contains(i, 4, &["CHAE"]) {
+            self.push_both("K", "X");
+        } else if self.condition_ch0(i) || self.condition_ch1(i) {
+            self.push('K');
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.push('K');
+            } else {
+                self.push_both("X", "K");
+            }
+        } else {
+            self.push('X');
+        }
+        index + 2
+    }
+
+    fn condition_ch0(&self, i: isize) -> bool {
+        i == 0
+            && (self.contains(i + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(i + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, i: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(i - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(i + 2, 1, &["T", "S"])
+            || ((self.contains(i - 1, 1, &["A", "O", "U", "E"]) || i == 0)
+                && (self.contains(i + 2, 1, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "])
+                    || i + 1 == self.len() as isize - 1))
+    }
+
+    fn handle_d(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 2, &["DG"]) {
+            if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+                self.push('J');
+                index + 3
+            } else {
+                self.push_both("TK", "TK");
+                index + 2
+            }
+        } else if self.contains(i, 2, &["DT", "DD"]) {
+            self.push('T');
+            index + 2
+        } else {
+            self.push('T');
+            index + 1
+        }
+    }
+
+    fn handle_g(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        let slavo_germanic = self.slavo_germanic;
+        if self.at(i + 1) == 'H' {
+            self.handle_gh(index)
+        } else if self.at(i + 1) == 'N' {
+            if index == 1 && Self::is_vowel(self.at(0)) && !slavo_germanic {
+                self.push_both("KN", "N");
+            } else if !self.contains(i + 2, 2, &["EY"]) && self.at(i + 1) != 'Y' && !slavo_germanic
+            {
+                self.push_both("N", "KN");
+            } else {
+                self.push_both("KN", "KN");
+            }
+            index + 2
+        } else if self.contains(i + 1, 2, &["LI"]) && !slavo_germanic {
+            self.push_both("KL", "L");
+            index + 2
+        } else if index == 0
+            && (self.at(i + 1) == 'Y'
+                || self.contains(
+                    i + 1,
+                    2,
+                    &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"],
+                ))
+        {
+            self.push_both("K", "J");
+            index + 2
+        } else if (self.contains(i + 1, 2, &["ER"]) || self.at(i + 1) == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(i - 1, 1, &["E", "I"])
+            && !self.contains(i - 1, 3, &["RGY", "OGY"])
+        {
+            self.push_both("K", "J");
+            index + 2
+        } else if self.contains(i + 1, 1, &["E", "I", "Y"])
+            || self.contains(i - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(i + 1, 2, &["ET"])
+            {
+                self.push('K');
+            } else if self.contains(i + 1, 3, &["IER"]) {
+                self.push('J');
+            } else {
+                self.push_both("J", "K");
+            }
+            index + 2
+        } else if self.at(i + 1) == 'G' {
+            self.push('K');
+            index + 2
+        } else {
+            self.push('K');
+            index + 1
+        }
+    }
+
+    fn handle_gh(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index > 0 && !Self::is_vowel(self.at(i - 1)) {
+            self.push('K');
+        } else if index == 0 {
+            if self.at(i + 2) == 'I' {
+                self.push('J');
+            } else {
+                self.push('K');
+            }
+        } else if (index > 1 && self.contains(i - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(i - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(i - 4, 1, &["B", "H"]))
+        {
+            // the `GH` is silent, e.g. `bough`
+        } else if index > 2
+            && self.at(i - 1) == 'U'
+            && self.contains(i - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            self.push('F');
+        } else if self.at(i - 1) != 'I' {
+            self.push('K');
+        }
+        index + 2
+    }
+
+    fn handle_h(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if (index == 0 || Self::is_vowel(self.at(i - 1))) && Self::is_vowel(self.at(i + 1)) {
+            self.push('H');
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            if (index == 0 && (self.at(i + 4) == ' ' || self.len() == 4))
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.push('H');
+            } else {
+                self.push_both("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            self.push_both("J", "A");
+        } else if Self::is_vowel(self.at(i - 1))
+            && !self.slavo_germanic
+            && (self.at(i + 1) == 'A' || self.at(i + 1) == 'O')
+        {
+            self.push_both("J", "H");
+        } else if index == self.len() - 1 {
+            self.push_primary("J");
+        } else if !self.contains(i + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(i - 1, 1, &["S", "K", "L"])
+        {
+            self.push('J');
+        }
+        self.skip_double(index, 'J')
+    }
+
+    fn handle_l(&mut self, index: usize) -> usize {
+        if self.at(index as isize + 1) == 'L' {
+            if self.condition_l0(index as isize) {
+                self.push_primary("L");
+            } else {
+                self.push('L');
+            }
+            index + 2
+        } else {
+            self.push('L');
+            index + 1
+        }
+    }
+
+    fn condition_l0(&self, i: isize) -> bool {
+        let len = self.len() as isize;
+        (i == len - 3 && self.contains(i - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(len - 2, 2, &["AS", "OS"])
+                || self.contains(len - 1, 1, &["A", "O"]))
+                && self.contains(i - 1, 4, &["ALLE"]))
+    }
+
+    fn condition_m0(&self, index: usize) -> bool {
+        let i = index as isize;
+        self.at(i + 1) == 'M'
+            || (self.contains(i - 1, 3, &["UMB"])
+                && (index + 1 == self.len() - 1 || self.contains(i + 2, 2, &["ER"])))
+    }
+
+    fn handle_p(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.at(i + 1) == 'H' {
+            self.push('F');
+            index + 2
+        } else {
+            self.push('P');
+            if self.contains(i + 1, 1, &["P", "B"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_r(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index == self.len() - 1
+            && !self.slavo_germanic
+            && self.contains(i - 2, 2, &["IE"])
+            && !self.contains(i - 4, 2, &["ME", "MA"])
+        {
+            self.push_alternate("R");
+        } else {
+            self.push('R');
+        }
+        self.skip_double(index, 'R')
+    }
+
+    fn handle_s(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i - 1, 3, &["ISL", "YSL"]) {
+            // the `S` is silent, e.g. `island`
+            index + 1
+        } else if index == 0 && self.contains(i, 5, &["SUGAR"]) {
+            self.push_both("X", "S");
+            index + 1
+        } else if self.contains(i, 2, &["SH"]) {
+            if self.contains(i + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.push('S');
+            } else {
+                self.push('X');
+            }
+            index + 2
+        } else if self.contains(i, 3, &["SIO", "SIA"]) || self.contains(i, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.push('S');
+            } else {
+                self.push_both("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(i + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(i + 1, 1, &["Z"])
+        {
+            self.push_both("S", "X");
+            if self.contains(i + 1, 1, &["Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        } else if self.contains(i, 2, &["SC"]) {
+            self.handle_sc(index)
+        } else {
+            if index == self.len() - 1 && self.contains(i - 2, 2, &["AI", "OI"]) {
+                self.push_alternate("S");
+            } else {
+                self.push('S');
+            }
+            if self.contains(i + 1, 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.at(i + 2) == 'H' {
+            if self.contains(i + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(i + 3, 2, &["ER", "EN"]) {
+                    self.push_both("X", "SK");
+                } else {
+                    self.push_both("SK", "SK");
+                }
+            } else if index == 0 && !Self::is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.push_both("X", "S");
+            } else {
+                self.push('X');
+            }
+        } else if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+            self.push('S');
+        } else {
+            self.push_both("SK", "SK");
+        }
+        index + 3
+    }
+
+    fn handle_t(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 4, &["TION"]) || self.contains(i, 3, &["TIA", "TCH"]) {
+            self.push('X');
+            index + 3
+        } else if self.contains(i, 2, &["TH"]) || self.contains(i, 3, &["TTH"]) {
+            if self.contains(i + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                self.push('T');
+            } else {
+                self.push_both("0", "T");
+            }
+            index + 2
+        } else {
+            self.push('T');
+            if self.contains(i + 1, 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 2, &["WR"]) {
+            self.push('R');
+            index + 2
+        } else if index == 0 && (Self::is_vowel(self.at(i + 1)) || self.contains(i, 2, &["WH"])) {
+            if Self::is_vowel(self.at(i + 1)) {
+                self.push_both("A", "F");
+            } else {
+                self.push('A');
+            }
+            index + 1
+        } else if (index == self.len() - 1 && Self::is_vowel(self.at(i - 1)))
+            || self.contains(i - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            self.push_alternate("F");
+            index + 1
+        } else if self.contains(i, 4, &["WICZ", "WITZ"]) {
+            self.push_both("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index == 0 {
+            self.push('S');
+            return index + 1;
+        }
+        // the final `X` is silent in french words, e.g. `breaux`
+        if !(index == self.len() - 1
+            && (self.contains(i - 3, 3, &["IAU", "EAU"]) || self.contains(i - 2, 2, &["AU", "OU"])))
+        {
+            self.push_both("KS", "KS");
+        }
+        if self.contains(i + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_z(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.at(i + 1) == 'H' {
+            self.push('J');
+            index + 2
+        } else {
+            if self.contains(i + 1, 2, &["ZO", "ZI", "ZA"])
+                || (self.slavo_germanic && index > 0 && self.at(i - 1) != 'T')
+            {
+                self.push_both("S", "TS");
+            } else {
+                self.push('S');
+            }
+            self.skip_double(index, 'Z')
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::ops::ControlFlow;
+
+    use super::*;
+
+    #[test]
+    fn double_metaphone_codes() {
+        assert_eq!(phonetic_codes("smith"), vec!["SM0", "XMT"]);
+        assert_eq!(phonetic_codes("schmidt"), vec!["XMT", "SMT"]);
+        assert_eq!(phonetic_codes("katherine"), vec!["K0RN", "KTRN"]);
+        assert_eq!(phonetic_codes("catherine"), vec!["K0RN", "KTRN"]);
+        assert_eq!(phonetic_codes("knight"), vec!["NT"]);
+        assert_eq!(phonetic_codes("philip"), vec!["FLP"]);
+    }
+
+    #[test]
+    fn non_latin_words_are_not_encoded() {
+        assert!(phonetic_codes("").is_empty());
+        assert!(phonetic_codes("東京").is_empty());
+        assert!(phonetic_codes("r2d2").is_empty());
+    }
+
+    #[test]
+    fn words_of_phonetic_code() {
+        let mut keys = BTreeSet::new();
+        for word in ["smith", "schmidt", "smyth", "catherine"] {
+            for code in phonetic_codes(word) {
+                keys.insert(phonetic_key(&code, word));
+            }
+        }
+        let fst = fst::Set::from_iter(keys).unwrap();
+
+        let mut words = Vec::new();
+        words_with_phonetic_code(&fst, "XMT", |word| {
+            words.push(word.to_owned());
+            Ok(ControlFlow::Continue(()))
+        })
+        .unwrap();
+        assert_eq!(words, vec!["schmidt", "smith", "smyth"]);
+    }
+}
@@ -0,0 +1,227 @@
+//! The query rules of an index, promoting, hiding or filtering documents
+//! when the query contains some words or when some filters are active.
+
+use charabia::TokenizerBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::rules_update::RulesUpdate;
+use crate::{Filter, FilterCondition};
+
+/// An update of the query rules of an index.
+pub type QueryRulesUpdate = RulesUpdate<QueryRule>;
+
+/// A query rule defined by the user, it is identified by a user-defined id.
+///
+/// The rule is applied when one of its conditions holds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRule {
+    pub conditions: Vec<QueryRuleCondition>,
+    pub consequence: QueryRuleConsequence,
+}
+
+/// A condition of a query rule, it holds when both its pattern
+/// matches the query and its filter is active, if defined.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRuleCondition {
+    /// The words the query must contain, they are normalized like the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub anchoring: Anchoring,
+    /// A filter that must be one of the filters of the search,
+    /// i.e. the whole filter or one of the filters it combines with `AND`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+/// Where the words of the pattern must be found in the query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Anchoring {
+    /// The query is made of the words of the pattern only.
+    Is,
+    /// The query starts with the words of the pattern.
+    StartsWith,
+    /// The query ends with the words of the pattern.
+    EndsWith,
+    /// The words of the pattern follow each other somewhere in the query.
+    #[default]
+    Contains,
+}
+
+/// The consequence of a query rule on the results of the search.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRuleConsequence {
+    /// The documents placed at a given position of the results, whether they match the query or not.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pin: Vec<PinnedDocument>,
+    /// The external ids of the documents removed from the results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hide: Vec<String>,
+    /// The filters whose documents are ranked before the other documents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boost: Vec<String>,
+    /// A filter added to the filter of the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+/// A document pinned at a position of the results, starting at zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedDocument {
+    pub id: String,
+    pub position: usize,
+}
+
+impl QueryRule {
+    /// Returns the reason why the rule is invalid, if any.
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.conditions.is_empty() {
+            return Err("a rule must have at least one condition".to_string());
+        }
+        for condition in &self.conditions {
+            match (&condition.pattern, &condition.filter) {
+                (None, None) => {
+                    return Err("a condition must have a `pattern` or a `filter`".to_string())
+                }
+                (Some(pattern), _) if normalize_words(pattern).is_empty() => {
+                    return Err(format!("the pattern `{pattern}` doesn't contain any word"))
+                }
+                _ => (),
+            }
+            if let Some(filter) = &condition.filter {
+                validate_filter(filter)?;
+            }
+        }
+
+        let QueryRuleConsequence { pin, hide, boost, filter } = &self.consequence;
+        if pin.is_empty() && hide.is_empty() && boost.is_empty() && filter.is_none() {
+            return Err("a consequence must pin, hide, boost or filter documents".to_string());
+        }
+        for filter in boost.iter().chain(filter) {
+            validate_filter(filter)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the rule is applied to a search with the given normalized
+    /// query words and active filters.
+    pub fn applies(&self, query_words: &[String], active_filters: &[String]) -> bool {
+        self.conditions.iter().any(|condition| condition.holds(query_words, active_filters))
+    }
+}
+
+impl QueryRuleCondition {
+    fn holds(&self, query_words: &[String], active_filters: &[String]) -> bool {
+        let pattern_matches = match &self.pattern {
+            Some(pattern) => {
+                let pattern = normalize_words(pattern);
+                match self.anchoring {
+                    Anchoring::Is => query_words == pattern,
+                    Anchoring::StartsWith => query_words.starts_with(&pattern),
+                    Anchoring::EndsWith => query_words.ends_with(&pattern),
+                    Anchoring::Contains => {
+                        query_words.windows(pattern.len()).any(|words| words == pattern)
+                    }
+                }
+            }
+            None => true,
+        };
+
+        let filter_is_active = match self.filter.as_deref().map(normalize_filter) {
+            Some(Some(filter)) => active_filters.contains(&filter),
+            // an invalid filter is never active
+            Some(None) => false,
+            None => true,
+        };
+
+        pattern_matches && filter_is_active
+    }
+}
+
+/// Returns the normalized words of the text, the query and the patterns are compared with them.
+pub fn normalize_words(text: &str) -> Vec<String> {
+    let tokenizer = TokenizerBuilder::default().into_tokenizer();
+    tokenizer
+        .tokenize(text)
+        .filter(|token| token.is_word())
+        .map(|token| token.lemma().to_string())
+        .collect()
+}
+
+/// Returns the filter written in a canonical way, `None` if it is invalid or empty.
+pub fn normalize_filter(filter: &str) -> Option<String> {
+    Filter::from_str(filter).ok().flatten().map(|filter| FilterCondition::from(filter).to_string())
+}
+
+fn validate_filter(filter: &str) -> std::result::Result<(), String> {
+    match Filter::from_str(filter) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(format!("the filter `{filter}` is empty")),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+
+    fn rule(pattern: &str, anchoring: Anchoring) -> QueryRule {
+        QueryRule {
+            conditions: vec![QueryRuleCondition {
+                pattern: Some(pattern.to_string()),
+                anchoring,
+                filter: None,
+            }],
+            consequence: QueryRuleConsequence { hide: vec![S("1")], ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn validate_rules() {
+        assert!(rule("black friday", Anchoring::Contains).validate().is_ok());
+        assert!(rule("  ", Anchoring::Contains).validate().is_err());
+
+        let mut invalid = rule("black friday", Anchoring::Contains);
+        invalid.consequence = QueryRuleConsequence::default();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = rule("black friday", Anchoring::Contains);
+        invalid.consequence.boost = vec![S("brand = ")];
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = rule("black friday", Anchoring::Contains);
+        invalid.conditions = vec![QueryRuleCondition::default()];
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn anchoring() {
+        let query = normalize_words("Black-Friday TV deals");
+        assert!(rule("black friday", Anchoring::Contains).applies(&query, &[]));
+        assert!(rule("black friday", Anchoring::StartsWith).applies(&query, &[]));
+        assert!(!rule("black friday", Anchoring::EndsWith).applies(&query, &[]));
+        assert!(!rule("black friday", Anchoring::Is).applies(&query, &[]));
+        assert!(rule("tv deals", Anchoring::EndsWith).applies(&query, &[]));
+        assert!(!rule("friday deals", Anchoring::Contains).applies(&query, &[]));
+    }
+
+    #[test]
+    fn active_filters() {
+        let mut rule = rule("tv", Anchoring::Contains);
+        rule.conditions[0].filter = Some(S("brand = samsung"));
+
+        let query = normalize_words("tv");
+        assert!(!rule.applies(&query, &[]));
+        let active_filters = [normalize_filter("brand   =   samsung").unwrap()];
+        assert!(rule.applies(&query, &active_filters));
+        assert!(!rule.applies(&normalize_words("radio"), &active_filters));
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+/// An update of the rules of an index identified by a user-defined id,
+/// e.g. the synonym rules or the query rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesUpdate<R> {
+    /// Replaces all the rules.
+    Replace(BTreeMap<String, R>),
+    /// Adds or replaces the rules with a value and removes the rules without,
+    /// the other rules are kept.
+    Patch(BTreeMap<String, Option<R>>),
+}
+
+impl<R: Clone> RulesUpdate<R> {
+    /// Applies the update to the given rules.
+    pub fn apply(&self, mut rules: BTreeMap<String, R>) -> BTreeMap<String, R> {
+        match self {
+            RulesUpdate::Replace(new_rules) => new_rules.clone(),
+            RulesUpdate::Patch(patch) => {
+                for (id, rule) in patch {
+                    match rule {
+                        Some(rule) => rules.insert(id.clone(), rule.clone()),
+                        None => rules.remove(id),
+                    };
+                }
+                rules
+            }
+        }
+    }
+
+    /// Merges the given patch after this update.
+    pub fn merge_patch(&mut self, patch: BTreeMap<String, Option<R>>) {
+        match self {
+            RulesUpdate::Replace(rules) => {
+                *rules = RulesUpdate::Patch(patch).apply(std::mem::take(rules));
+            }
+            RulesUpdate::Patch(previous) => previous.extend(patch),
+        }
+    }
+}
@@ -1,18 +1,26 @@
+use std::collections::BTreeMap;
+
 use serde::Serialize;
 
 use crate::distance_between_two_points;
+use crate::search::FusionMethod;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScoreDetails {
     Words(Words),
     Typo(Typo),
     Proximity(Rank),
-    Fid(Rank),
+    Fid(Fid),
     Position(Rank),
     ExactAttribute(ExactAttribute),
     ExactWords(ExactWords),
     Sort(Sort),
+    FunctionScore(FunctionScore),
+    QueryRule(QueryRule),
     GeoSort(GeoSort),
+    VectorSort(VectorSort),
+    Vector(Vector),
+    Hybrid(Hybrid),
 }
 
 impl ScoreDetails {
@@ -25,17 +33,31 @@ impl ScoreDetails {
             ScoreDetails::Words(details) => Some(details.rank()),
             ScoreDetails::Typo(details) => Some(details.rank()),
             ScoreDetails::Proximity(details) => Some(*details),
-            ScoreDetails::Fid(details) => Some(*details),
+            ScoreDetails::Fid(details) => Some(details.rank),
             ScoreDetails::Position(details) => Some(*details),
             ScoreDetails::ExactAttribute(details) => Some(details.rank()),
             ScoreDetails::ExactWords(details) => Some(details.rank()),
             ScoreDetails::Sort(_) => None,
+            ScoreDetails::FunctionScore(details) => Some(details.rank),
+            ScoreDetails::QueryRule(details) => details.rank(),
             ScoreDetails::GeoSort(_) => None,
+            ScoreDetails::VectorSort(_) => None,
+            ScoreDetails::Vector(_) => None,
+            ScoreDetails::Hybrid(_) => None,
         }
     }
 
+    /// The score of a hybrid search is the one computed by its fusion method,
+    /// otherwise it is the combination of the ranks of the ranking rules.
     pub fn global_score<'a>(details: impl Iterator<Item = &'a Self>) -> f64 {
-        Rank::global_score(details.filter_map(Self::rank))
+        let mut ranks = Vec::new();
+        for details in details {
+            match details {
+                ScoreDetails::Hybrid(hybrid) => return hybrid.score,
+                details => ranks.extend(details.rank()),
+            }
+        }
+        Rank::global_score(ranks.into_iter())
     }
 
     /// Panics
@@ -61,12 +83,15 @@ impl ScoreDetails {
                     order += 1;
                 }
                 ScoreDetails::Typo(typo) => {
-                    let typo_details = serde_json::json!({
+                    let mut typo_details = serde_json::json!({
                         "order": order,
                         "typoCount": typo.typo_count,
                         "maxTypoCount": typo.max_typo_count,
                         "score": typo.rank().local_score(),
                     });
+                    if typo.phonetic_count > 0 {
+                        typo_details["phoneticCount"] = typo.phonetic_count.into();
+                    }
                     details_map.insert("typo".into(), typo_details);
                     order += 1;
                 }
@@ -80,12 +105,15 @@ impl ScoreDetails {
                 }
                 ScoreDetails::Fid(fid) => {
                     // copy the rank for future use in Position.
-                    fid_details = Some(*fid);
+                    fid_details = Some(fid.rank);
                     // For now, fid is a virtual rule always followed by the "position" rule
-                    let fid_details = serde_json::json!({
+                    let mut fid_details = serde_json::json!({
                         "order": order,
-                        "attributeRankingOrderScore": fid.local_score(),
+                        "attributeRankingOrderScore": fid.rank.local_score(),
                     });
+                    if let Some(weights) = &fid.attribute_weights {
+                        fid_details["attributeWeights"] = serde_json::json!(weights);
+                    }
                     details_map.insert("attribute".into(), fid_details);
                     order += 1;
                 }
@@ -161,6 +189,36 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::FunctionScore(details) => {
+                    let rule = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        details.function_score.to_string()
+                    };
+                    let function_score_details = serde_json::json!({
+                        "order": order,
+                        "bucket": details.rank.rank,
+                        "maxBucket": details.rank.max_rank,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(rule, function_score_details);
+                    order += 1;
+                }
+                ScoreDetails::QueryRule(details) => {
+                    let query_rule_details = match details {
+                        QueryRule::Pinned => serde_json::json!({
+                            "order": order,
+                            "pinned": true,
+                        }),
+                        QueryRule::Boost { boosted } => serde_json::json!({
+                            "order": order,
+                            "boosted": boosted,
+                            "score": details.rank().map(Rank::local_score),
+                        }),
+                    };
+                    details_map.insert("queryRules".into(), query_rule_details);
+                    order += 1;
+                }
                 ScoreDetails::GeoSort(details) => {
                     let sort = format!(
                         "_geoPoint({}, {}):{}",
@@ -181,6 +239,40 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::VectorSort(details) => {
+                    let target_vector =
+                        details.target_vector.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+                    let sort = format!(
+                        "_vectorDistance({}, [{}]):{}",
+                        details.space,
+                        target_vector.join(", "),
+                        if details.ascending { "asc" } else { "desc" }
+                    );
+                    let sort_details = serde_json::json!({
+                        "order": order,
+                        "similarity": details.similarity,
+                    });
+                    details_map.insert(sort, sort_details);
+                    order += 1;
+                }
+                ScoreDetails::Vector(details) => {
+                    let vector_details = serde_json::json!({
+                        "order": order,
+                        "similarity": details.similarity,
+                    });
+                    details_map.insert("vector".into(), vector_details);
+                    order += 1;
+                }
+                ScoreDetails::Hybrid(details) => {
+                    let hybrid_details = serde_json::json!({
+                        "order": order,
+                        "fusion": details.fusion,
+                        "semanticRatio": details.semantic_ratio,
+                        "score": details.score,
+                    });
+                    details_map.insert("hybrid".into(), hybrid_details);
+                    order += 1;
+                }
             }
         }
         details_map
@@ -247,31 +339,56 @@ impl ExactWords {
     }
 }
 
+/// The rank of the attribute ranking rule along with the attribute weights it was computed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fid {
+    pub rank: Rank,
+    pub attribute_weights: Option<BTreeMap<String, u16>>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Typo {
     pub typo_count: u32,
     pub max_typo_count: u32,
+    /// The number of query words matched by words sounding like them.
+    pub phonetic_count: u32,
 }
 
 impl Typo {
+    /// The cost of a typo in the typo ranking rule, a word sounding like a query word costs
+    /// one more than a typo so that it is ranked between the one typo and the two typos words.
+    ///
+    /// It is higher than the maximum number of words of a query, the number of typos and
+    /// of words sounding alike can then be retrieved from the cost of a path.
+    pub(crate) const TYPO_COST: u32 = crate::search::new::limits::MAX_TOKEN_COUNT as u32 + 1;
+
     pub fn rank(&self) -> Rank {
+        // a word sounding alike is scored like a one typo word.
         Rank {
-            rank: (self.max_typo_count + 1).saturating_sub(self.typo_count),
+            rank: (self.max_typo_count + 1).saturating_sub(self.typo_count + self.phonetic_count),
             max_rank: (self.max_typo_count + 1),
         }
     }
 
-    // max_rank = max_typo + 1
-    // max_typo = max_rank - 1
+    /// Returns the cost of the given number of typos and words sounding alike.
+    pub(crate) fn cost(typo_count: u32, phonetic_count: u32) -> u32 {
+        (typo_count + phonetic_count) * Self::TYPO_COST + phonetic_count
+    }
+
+    // max_rank = max_cost + 1
+    // rank = max_rank - cost
+    // cost = (typo + phonetic) * TYPO_COST + phonetic
     //
-    // rank = max_typo - typo + 1
-    // rank = max_rank - 1 - typo + 1
-    // rank + typo = max_rank
-    // typo = max_rank - rank
+    // A word sounding alike in the max cost is counted as one typo,
+    // as they are only derived from the words allowing typos.
     pub fn from_rank(rank: Rank) -> Typo {
+        let cost = rank.max_rank.saturating_sub(rank.rank);
+        let max_cost = rank.max_rank.saturating_sub(1);
+        let phonetic_count = cost % Self::TYPO_COST;
         Typo {
-            typo_count: rank.max_rank.saturating_sub(rank.rank),
-            max_typo_count: rank.max_rank.saturating_sub(1),
+            typo_count: (cost / Self::TYPO_COST).saturating_sub(phonetic_count),
+            max_typo_count: max_cost / Self::TYPO_COST,
+            phonetic_count,
         }
     }
 }
@@ -335,6 +452,33 @@ pub struct Sort {
     pub value: serde_json::Value,
 }
 
+/// The bucket of a document for a function score ranking rule,
+/// the rank of the best bucket is the number of buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionScore {
+    pub function_score: crate::FunctionScore,
+    pub redacted: bool,
+    pub rank: Rank,
+}
+
+/// The promotion of a document by the query rules of the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryRule {
+    /// The document is pinned at a position, it is not ranked by the ranking rules.
+    Pinned,
+    /// Whether the document matches one of the boost filters of the query rules.
+    Boost { boosted: bool },
+}
+
+impl QueryRule {
+    pub fn rank(&self) -> Option<Rank> {
+        match self {
+            QueryRule::Pinned => None,
+            QueryRule::Boost { boosted } => Some(Rank { rank: *boosted as u32 + 1, max_rank: 2 }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct GeoSort {
     pub target_point: [f64; 2],
@@ -347,3 +491,27 @@ impl GeoSort {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
     }
 }
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct VectorSort {
+    pub space: String,
+    pub target_vector: Vec<f32>,
+    pub ascending: bool,
+    /// The similarity between the target vector and the nearest vector of the document,
+    /// `None` if the document has no vector in this space.
+    pub similarity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Vector {
+    /// The similarity between the target vector and the nearest vector of the document.
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hybrid {
+    pub semantic_ratio: f32,
+    pub fusion: FusionMethod,
+    /// The score computed by the fusion method, between `0.0` and `1.0`.
+    pub score: f64,
+}
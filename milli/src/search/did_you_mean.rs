@@ -0,0 +1,239 @@
+//! "Did you mean": the spelling correction of the queries matching few documents.
+//!
+//! The unknown words of the query are replaced by the most frequent words of the index
+//! at the smallest edit distance from them.
+
+use std::cmp::Reverse;
+
+use charabia::TokenizerBuilder;
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+use heed::RoTxn;
+
+use super::{build_dfa, TypoTolerance, TypoToleranceOverride};
+use crate::code_identifiers::contains_digit;
+use crate::{CboRoaringBitmapLenCodec, FieldId, Index, Result, RoaringBitmapLenCodec};
+
+/// The default maximum number of hits of a query for which a correction is suggested.
+pub const DEFAULT_DID_YOU_MEAN_MAX_HITS: u64 = 3;
+
+/// How the queries matching few documents are corrected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DidYouMean {
+    /// The maximum number of hits of a query for which a correction is suggested.
+    pub max_hits: u64,
+    /// Whether the search is executed again with the correction, its results
+    /// are returned when they contain more hits than the original query.
+    pub auto_correct: bool,
+}
+
+impl Default for DidYouMean {
+    fn default() -> Self {
+        DidYouMean { max_hits: DEFAULT_DID_YOU_MEAN_MAX_HITS, auto_correct: false }
+    }
+}
+
+/// Returns the query where the words which are not in the index are replaced by their
+/// correction, `None` if no word of the query could be corrected.
+///
+/// The words are corrected with the typos allowed by the typo tolerance of the index,
+/// overridden by the one of the search. The stop words, the words containing digits and
+/// the last word of the query when it is the prefix of words of the index are kept as they are.
+pub fn correct_query(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &str,
+    typo_override: Option<&TypoToleranceOverride>,
+) -> Result<Option<String>> {
+    let typo_tolerance = TypoTolerance::new(index, rtxn, typo_override)?;
+    if !typo_tolerance.authorize_typos {
+        return Ok(None);
+    }
+
+    // the corrections are only searched in the attributes on which the typos are allowed.
+    let typo_fields = match &typo_tolerance.exact_attributes {
+        Some(exact_attributes) => {
+            let fields: Vec<_> = match index.searchable_fields_ids(rtxn)? {
+                Some(fields) => fields,
+                None => index.fields_ids_map(rtxn)?.ids().collect(),
+            };
+            let fields: Vec<_> =
+                fields.into_iter().filter(|fid| !exact_attributes.contains(fid)).collect();
+            if fields.is_empty() {
+                return Ok(None);
+            }
+            Some(fields)
+        }
+        None => None,
+    };
+
+    let words_fst = index.words_fst(rtxn)?;
+    let stop_words = index.stop_words(rtxn)?;
+
+    let tokenizer = TokenizerBuilder::default().into_tokenizer();
+    let mut corrected = String::with_capacity(query.len());
+    let mut last_byte_end = 0;
+    let mut is_corrected = false;
+    let mut tokens = tokenizer.tokenize(query).peekable();
+    while let Some(token) = tokens.next() {
+        if !token.is_word() {
+            continue;
+        }
+
+        let word = token.lemma();
+        let is_known = words_fst.contains(word)
+            || stop_words.as_ref().map_or(false, |stop_words| stop_words.contains(word));
+        if is_known || contains_digit(word) {
+            continue;
+        }
+
+        // like in the search, the last word of the query is a prefix.
+        let is_last = tokens.peek().is_none();
+        if is_last {
+            let mut prefixed = words_fst.search(Str::new(word).starts_with()).into_stream();
+            if prefixed.next().is_some() {
+                continue;
+            }
+        }
+
+        let typos = typo_tolerance.number_of_typos_allowed(word);
+        if typos == 0 {
+            continue;
+        }
+
+        if let Some(correction) =
+            correct_word(index, rtxn, &words_fst, word, typos, typo_fields.as_deref())?
+        {
+            corrected.push_str(&query[last_byte_end..token.byte_start]);
+            corrected.push_str(&correction);
+            last_byte_end = token.byte_end;
+            is_corrected = true;
+        }
+    }
+    corrected.push_str(&query[last_byte_end..]);
+
+    Ok(is_corrected.then_some(corrected))
+}
+
+/// Returns the word of the index with the fewest typos from the given word,
+/// the most frequent one when several words have the same number of typos.
+///
+/// The words are only looked for in the given fields, when the exact attributes of the
+/// index are overridden, otherwise in the attributes on which the index allows typos.
+fn correct_word<A: AsRef<[u8]>>(
+    index: &Index,
+    rtxn: &RoTxn,
+    words_fst: &fst::Set<A>,
+    word: &str,
+    typos: u8,
+    typo_fields: Option<&[FieldId]>,
+) -> Result<Option<String>> {
+    let word_docids = index.word_docids.remap_data_type::<RoaringBitmapLenCodec>();
+    let word_fid_docids = index.word_fid_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+
+    let dfa = build_dfa(word, typos, false);
+    let mut stream = words_fst.search(&dfa).into_stream();
+    let mut best = None;
+    while let Some(candidate) = stream.next() {
+        let candidate = std::str::from_utf8(candidate)?;
+        let frequency = match typo_fields {
+            Some(fields) => {
+                let mut frequency = 0;
+                for &fid in fields {
+                    frequency += word_fid_docids.get(rtxn, &(candidate, fid))?.unwrap_or(0);
+                }
+                frequency
+            }
+            // the words of the exact attributes of the index are not in this database.
+            None => word_docids.get(rtxn, candidate)?.unwrap_or(0),
+        };
+        if frequency == 0 {
+            continue;
+        }
+
+        let distance = dfa.eval(candidate).to_u8();
+        let key = (distance, Reverse(frequency));
+        if best.as_ref().map_or(true, |(best_key, _)| key < *best_key) {
+            best = Some((key, candidate.to_string()));
+        }
+    }
+
+    Ok(best.map(|(_, candidate)| candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::btreeset;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn correct_queries() {
+        let index = TempIndex::new();
+        index.update_settings(|s| s.set_stop_words(btreeset! { S("the") })).unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "The television of the house" },
+                { "id": 1, "title": "A television in the kitchen" },
+                { "id": 2, "title": "A telescope on the roof, not a telephone" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let correct = |query| correct_query(&index, &rtxn, query, None).unwrap();
+
+        assert_eq!(correct("the televsion"), Some(S("the television")));
+        // the most frequent word is chosen among the words with the fewest typos
+        assert_eq!(
+            correct("Telephone and Telecision, Kitchen!"),
+            Some(S("Telephone and television, Kitchen!"))
+        );
+        assert_eq!(correct("telescope roof"), None);
+        assert_eq!(correct("tv 4k kichen"), Some(S("tv 4k kitchen")));
+        assert_eq!(correct("zzzzzz"), None);
+        // the words shorter than the minimum word length for typos are not corrected
+        assert_eq!(correct("the hose"), None);
+        // the last word is kept when it is the prefix of words of the index
+        assert_eq!(correct("the telev"), None);
+    }
+
+    #[test]
+    fn correct_queries_with_typo_tolerance_override() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "The television of the house", "tag": "kitchen" },
+                { "id": 1, "title": "A television in the kitchen", "tag": "roof" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let correct = |query, typo_override: TypoToleranceOverride| {
+            correct_query(&index, &rtxn, query, Some(&typo_override)).unwrap()
+        };
+
+        assert_eq!(correct("hose", TypoToleranceOverride::default()), None);
+        let one_typo =
+            TypoToleranceOverride { min_word_len_one_typo: Some(4), ..Default::default() };
+        assert_eq!(correct("hose", one_typo), Some(S("house")));
+
+        let disabled = TypoToleranceOverride { enabled: Some(false), ..Default::default() };
+        assert_eq!(correct("televsion", disabled), None);
+
+        let disable_on_words = TypoToleranceOverride {
+            disable_on_words: Some(btreeset! { S("Televsion") }),
+            ..Default::default()
+        };
+        assert_eq!(correct("televsion", disable_on_words), None);
+
+        // the corrections are only looked for in the attributes on which the typos are allowed
+        let disable_on_title = TypoToleranceOverride {
+            disable_on_attributes: Some(btreeset! { S("title") }),
+            ..Default::default()
+        };
+        assert_eq!(correct("televsion", disable_on_title.clone()), None);
+        assert_eq!(correct("kitchem", disable_on_title), Some(S("kitchen")));
+    }
+}
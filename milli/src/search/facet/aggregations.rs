@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::ops::ControlFlow;
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::facet_buckets::{docids_within_bounds, FacetRange};
+use super::facet_distribution_iter::count_iterate_over_facet_distribution;
+use super::facet_stats::{FacetStatsOptions, FacetValuesStats};
+use crate::error::{InternalError, UserError};
+use crate::heed_codec::facet::{
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
+};
+use crate::heed_codec::{ByteSliceRefCodec, StrRefCodec};
+use crate::index::db_name;
+use crate::{DocumentId, FieldId, FieldsIdsMap, Index, Result};
+
+/// The maximum number of levels of nested aggregations.
+pub const MAX_AGGREGATIONS_DEPTH: usize = 5;
+
+/// The maximum number of buckets returned by all the aggregations of a search.
+pub const MAX_AGGREGATION_BUCKETS: usize = 10_000;
+
+/// An aggregation of the values of a facet, the buckets of the terms and ranges
+/// aggregations being aggregated again by their sub-aggregations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aggregation {
+    /// A bucket for each of the most frequent values, at most `size` of them.
+    Terms { facet: String, size: Option<usize>, aggregations: BTreeMap<String, Aggregation> },
+    /// A bucket for each of the given ranges.
+    Ranges { facet: String, ranges: Vec<FacetRange>, aggregations: BTreeMap<String, Aggregation> },
+    /// The statistics of the values.
+    Stats { facet: String, options: FacetStatsOptions },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregationResult {
+    Buckets(Vec<AggregationBucket>),
+    Stats(FacetValuesStats),
+}
+
+/// The candidates falling in a bucket of a terms or ranges aggregation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregationBucket {
+    pub key: BucketKey,
+    pub count: u64,
+    pub aggregations: BTreeMap<String, AggregationResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BucketKey {
+    /// The original value of a string, or a number written as a string.
+    Term(String),
+    Range(FacetRange),
+}
+
+/// The depth of the aggregation being computed and the number of buckets
+/// returned so far by the aggregations of a search.
+#[derive(Debug, Default)]
+pub(crate) struct AggregationsLimits {
+    depth: usize,
+    buckets: usize,
+}
+
+impl Aggregation {
+    pub fn facet(&self) -> &str {
+        match self {
+            Aggregation::Terms { facet, .. }
+            | Aggregation::Ranges { facet, .. }
+            | Aggregation::Stats { facet, .. } => facet,
+        }
+    }
+
+    /// Returns the number of levels of this aggregation and of its nested sub-aggregations.
+    pub fn depth(&self) -> usize {
+        match self {
+            Aggregation::Terms { aggregations, .. } | Aggregation::Ranges { aggregations, .. } => {
+                1 + aggregations.values().map(Aggregation::depth).max().unwrap_or_default()
+            }
+            Aggregation::Stats { .. } => 1,
+        }
+    }
+
+    /// Pushes the facets of this aggregation and of all its sub-aggregations.
+    pub(crate) fn collect_facets<'a>(&'a self, facets: &mut Vec<&'a str>) {
+        facets.push(self.facet());
+        match self {
+            Aggregation::Terms { aggregations, .. } | Aggregation::Ranges { aggregations, .. } => {
+                aggregations.values().for_each(|aggregation| aggregation.collect_facets(facets))
+            }
+            Aggregation::Stats { .. } => (),
+        }
+    }
+
+    /// Aggregates the values of the candidates, a field that was never
+    /// indexed having no values but its ranges still being returned.
+    ///
+    /// Returns an error when the aggregations are nested too deeply
+    /// or when all of them return too many buckets.
+    pub(crate) fn compute(
+        &self,
+        index: &Index,
+        rtxn: &heed::RoTxn,
+        fields_ids_map: &FieldsIdsMap,
+        candidates: &RoaringBitmap,
+        max_values_per_facet: usize,
+        limits: &mut AggregationsLimits,
+    ) -> Result<AggregationResult> {
+        if limits.depth + self.depth() > MAX_AGGREGATIONS_DEPTH {
+            return Err(UserError::AggregationsTooDeep(MAX_AGGREGATIONS_DEPTH).into());
+        }
+
+        let field_id = fields_ids_map.id(self.facet());
+        let buckets = match self {
+            Aggregation::Terms { size, aggregations, .. } => {
+                let Some(field_id) = field_id else {
+                    return Ok(AggregationResult::Buckets(Vec::new()));
+                };
+                let size = size.unwrap_or(max_values_per_facet);
+                let terms = facet_terms(index, rtxn, field_id, candidates, size)?;
+                terms
+                    .into_iter()
+                    .map(|(term, docids)| (BucketKey::Term(term), docids, aggregations))
+                    .collect::<Vec<_>>()
+            }
+            Aggregation::Ranges { ranges, aggregations, .. } => {
+                let mut buckets = Vec::with_capacity(ranges.len());
+                for range in ranges {
+                    let docids = match field_id {
+                        Some(field_id) => {
+                            let left = range.from.map_or(Unbounded, Included);
+                            let right = range.to.map_or(Unbounded, Excluded);
+                            docids_within_bounds(index, rtxn, field_id, left, right)? & candidates
+                        }
+                        None => RoaringBitmap::new(),
+                    };
+                    buckets.push((BucketKey::Range(*range), docids, aggregations));
+                }
+                buckets
+            }
+            Aggregation::Stats { options, .. } => {
+                let stats = match field_id {
+                    Some(field_id) => {
+                        FacetValuesStats::compute(index, rtxn, field_id, candidates, options)?
+                    }
+                    None => FacetValuesStats::default(),
+                };
+                return Ok(AggregationResult::Stats(stats));
+            }
+        };
+
+        limits.buckets += buckets.len();
+        if limits.buckets > MAX_AGGREGATION_BUCKETS {
+            return Err(UserError::TooManyAggregationBuckets(MAX_AGGREGATION_BUCKETS).into());
+        }
+
+        limits.depth += 1;
+        let buckets = buckets
+            .into_iter()
+            .map(|(key, docids, aggregations)| {
+                let aggregations = aggregations
+                    .iter()
+                    .map(|(name, aggregation)| {
+                        let result = aggregation.compute(
+                            index,
+                            rtxn,
+                            fields_ids_map,
+                            &docids,
+                            max_values_per_facet,
+                            limits,
+                        )?;
+                        Ok((name.clone(), result))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(AggregationBucket { key, count: docids.len(), aggregations })
+            })
+            .collect::<Result<_>>()?;
+        limits.depth -= 1;
+
+        Ok(AggregationResult::Buckets(buckets))
+    }
+}
+
+/// Returns the most frequent values of the candidates along with the candidates having them,
+/// the numbers and strings being sorted together by decreasing number of candidates.
+fn facet_terms(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    size: usize,
+) -> Result<Vec<(String, RoaringBitmap)>> {
+    let mut terms = most_frequent_values(
+        rtxn,
+        index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+        field_id,
+        candidates,
+        size,
+        |facet_key, _| {
+            let value = OrderedF64Codec::bytes_decode(facet_key).ok_or(heed::Error::Decoding)?;
+            Ok(value.to_string())
+        },
+    )?;
+    terms.extend(most_frequent_values(
+        rtxn,
+        index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+        field_id,
+        candidates,
+        size,
+        |facet_key, any_docid| {
+            let facet_key = StrRefCodec::bytes_decode(facet_key).ok_or(heed::Error::Decoding)?;
+            let key: (FieldId, _, &str) = (field_id, any_docid, facet_key);
+            let original = index.field_id_docid_facet_strings.get(rtxn, &key)?.ok_or(
+                InternalError::DatabaseMissingEntry {
+                    db_name: db_name::FIELD_ID_DOCID_FACET_STRINGS,
+                    key: None,
+                },
+            )?;
+            Ok(original.to_owned())
+        },
+    )?);
+
+    terms.sort_by(|(_, a), (_, b)| b.len().cmp(&a.len()));
+    terms.truncate(size);
+    Ok(terms)
+}
+
+/// Returns at most `size` values of a facet database in decreasing number of candidates,
+/// along with the candidates of their entry in the first level.
+fn most_frequent_values(
+    rtxn: &heed::RoTxn,
+    db: heed::Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    size: usize,
+    term: impl Fn(&[u8], DocumentId) -> Result<String>,
+) -> Result<Vec<(String, RoaringBitmap)>> {
+    let mut values = Vec::new();
+    if size == 0 {
+        return Ok(values);
+    }
+
+    count_iterate_over_facet_distribution(
+        rtxn,
+        db,
+        field_id,
+        candidates,
+        |facet_key, _, any_docid| {
+            let key = FacetGroupKey { field_id, level: 0, left_bound: facet_key };
+            let docids = db.get(rtxn, &key)?.map(|value| value.bitmap).unwrap_or_default();
+            values.push((term(facet_key, any_docid)?, docids & candidates));
+            if values.len() == size {
+                Ok(ControlFlow::Break(()))
+            } else {
+                Ok(ControlFlow::Continue(()))
+            }
+        },
+    )?;
+
+    Ok(values)
+}
@@ -0,0 +1,291 @@
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+use roaring::RoaringBitmap;
+use serde::Serialize;
+use time::{Date, Month, OffsetDateTime};
+
+use super::facet_range_search::find_docids_of_facet_within_bounds;
+use super::{facet_max_value, facet_min_value, get_highest_level};
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::ByteSliceRefCodec;
+use crate::{FieldId, Index, Result};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const WEEK: f64 = 7.0 * DAY;
+
+/// How the values of a numeric facet are grouped into buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetBuckets {
+    /// The buckets of a fixed interval, starting at the multiples of the interval.
+    Histogram { interval: f64 },
+    /// The given ranges, whatever the number of values they contain.
+    Ranges(Vec<FacetRange>),
+    /// The buckets of a calendar interval, the values being UTC timestamps in seconds.
+    DateHistogram { interval: CalendarInterval },
+}
+
+/// A range of facet values, including its start and excluding its end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarInterval {
+    Minute,
+    Hour,
+    Day,
+    /// The weeks start on mondays.
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// The number of candidates having a value in a bucket of a facet.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FacetBucket {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+impl FacetBuckets {
+    /// Returns the buckets of the values of the candidates for the given field.
+    ///
+    /// The ranges are all returned, while the histograms only return their non-empty
+    /// buckets, at most `max_buckets` of them.
+    pub(crate) fn compute(
+        &self,
+        index: &Index,
+        rtxn: &heed::RoTxn,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        max_buckets: usize,
+    ) -> Result<Vec<FacetBucket>> {
+        match self {
+            FacetBuckets::Histogram { interval } => {
+                let interval = *interval;
+                if !interval.is_finite() || interval <= 0.0 {
+                    return Ok(Vec::new());
+                }
+                interval_buckets(index, rtxn, field_id, candidates, max_buckets, |value| {
+                    let from = (value / interval).floor() * interval;
+                    let to = from + interval;
+                    // the interval is lost in the precision of the huge values.
+                    (to > value).then_some((from, to))
+                })
+            }
+            FacetBuckets::DateHistogram { interval } => {
+                interval_buckets(index, rtxn, field_id, candidates, max_buckets, |value| {
+                    interval.bucket(value)
+                })
+            }
+            FacetBuckets::Ranges(ranges) => ranges
+                .iter()
+                .map(|&FacetRange { from, to }| {
+                    let left = from.map_or(Unbounded, Included);
+                    let right = to.map_or(Unbounded, Excluded);
+                    let count =
+                        count_within_bounds(index, rtxn, field_id, candidates, left, right)?;
+                    Ok(FacetBucket { from, to, count })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CalendarInterval {
+    /// Returns the bounds of the bucket containing the given timestamp.
+    fn bucket(self, timestamp: f64) -> Option<(f64, f64)> {
+        let fixed = |length: f64| {
+            let from = (timestamp / length).floor() * length;
+            Some((from, from + length))
+        };
+
+        let months = match self {
+            CalendarInterval::Minute => return fixed(MINUTE),
+            CalendarInterval::Hour => return fixed(HOUR),
+            CalendarInterval::Day => return fixed(DAY),
+            CalendarInterval::Week => {
+                // the epoch is a thursday, three days after a monday.
+                let day = (timestamp / DAY).floor();
+                let from = (day - (day + 3.0).rem_euclid(7.0)) * DAY;
+                return Some((from, from + WEEK));
+            }
+            CalendarInterval::Month => 1,
+            CalendarInterval::Quarter => 3,
+            CalendarInterval::Year => 12,
+        };
+
+        let date = OffsetDateTime::from_unix_timestamp(timestamp.floor() as i64).ok()?.date();
+        let month = (date.month() as i32 - 1) / months * months;
+        let from = date.year() * 12 + month;
+        Some((first_day_of_month(from)?, first_day_of_month(from + months)?))
+    }
+}
+
+/// Returns the timestamp of the first day of the given month, counted from the year zero.
+fn first_day_of_month(months: i32) -> Option<f64> {
+    let month = Month::try_from(months.rem_euclid(12) as u8 + 1).ok()?;
+    let date = Date::from_calendar_date(months.div_euclid(12), month, 1).ok()?;
+    Some(date.midnight().assume_utc().unix_timestamp() as f64)
+}
+
+/// Returns the non-empty buckets of the values of the candidates in ascending order,
+/// `bucket_bounds` giving the bounds of the bucket containing a value.
+///
+/// The buckets are counted over the levels of the facet database and the empty ones are
+/// skipped by looking for the first value of the candidates in the next buckets, each
+/// iteration counting a non-empty bucket.
+fn interval_buckets(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    max_buckets: usize,
+    bucket_bounds: impl Fn(f64) -> Option<(f64, f64)>,
+) -> Result<Vec<FacetBucket>> {
+    let mut buckets = Vec::new();
+    let min = facet_min_value(index, rtxn, field_id, candidates.clone())?;
+    let max = facet_max_value(index, rtxn, field_id, candidates.clone())?;
+    let (Some(mut value), Some(max)) = (min, max) else {
+        return Ok(buckets);
+    };
+
+    while buckets.len() < max_buckets {
+        let Some((from, to)) = bucket_bounds(value) else {
+            break;
+        };
+        let count =
+            count_within_bounds(index, rtxn, field_id, candidates, Included(from), Excluded(to))?;
+        if count != 0 {
+            buckets.push(FacetBucket { from: Some(from), to: Some(to), count });
+        }
+
+        if to > max {
+            break;
+        }
+        match first_candidate_value_from(index, rtxn, field_id, candidates, to)? {
+            Some(next_value) => value = next_value,
+            None => break,
+        }
+    }
+
+    Ok(buckets)
+}
+
+/// Returns the number of candidates having a value within the bounds.
+fn count_within_bounds(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    left: Bound<f64>,
+    right: Bound<f64>,
+) -> Result<u64> {
+    let docids = docids_within_bounds(index, rtxn, field_id, left, right)?;
+    Ok(docids.intersection_len(candidates))
+}
+
+/// Returns the documents having a value within the bounds.
+pub(crate) fn docids_within_bounds(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    left: Bound<f64>,
+    right: Bound<f64>,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+    if let (Included(left), Excluded(right)) = (left, right) {
+        if left >= right {
+            return Ok(docids);
+        }
+    }
+
+    find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+        rtxn,
+        index.facet_id_f64_docids,
+        field_id,
+        &left,
+        &right,
+        &mut docids,
+    )?;
+    Ok(docids)
+}
+
+/// Returns the smallest value of the candidates greater than or equal to the given one.
+fn first_candidate_value_from(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    from: f64,
+) -> Result<Option<f64>> {
+    let db = index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+    let highest_level = get_highest_level(rtxn, db, field_id)?;
+    first_candidate_value_in_groups(
+        index,
+        rtxn,
+        field_id,
+        candidates,
+        from,
+        highest_level,
+        f64::NEG_INFINITY,
+        usize::MAX,
+    )
+}
+
+/// Returns the smallest value of the candidates greater than or equal to `from` among the
+/// `group_size` groups of the level starting at `left_bound`.
+///
+/// The groups without candidates or only containing lower values are skipped, the
+/// work is bounded by the number of levels times the size of the groups.
+#[allow(clippy::too_many_arguments)]
+fn first_candidate_value_in_groups(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    from: f64,
+    level: u8,
+    left_bound: f64,
+    group_size: usize,
+) -> Result<Option<f64>> {
+    let start = FacetGroupKey { field_id, level, left_bound };
+    let end = FacetGroupKey { field_id, level, left_bound: f64::INFINITY };
+    let mut groups =
+        index.facet_id_f64_docids.range(rtxn, &(start..=end))?.take(group_size).peekable();
+    while let Some(result) = groups.next() {
+        let (key, value) = result?;
+        let ends_before = match groups.peek() {
+            Some(Ok((next, _))) => next.left_bound <= from,
+            _ => false,
+        };
+        if ends_before || value.bitmap.is_disjoint(candidates) {
+            continue;
+        }
+
+        if level == 0 {
+            if key.left_bound >= from {
+                return Ok(Some(key.left_bound));
+            }
+        } else if let Some(value) = first_candidate_value_in_groups(
+            index,
+            rtxn,
+            field_id,
+            candidates,
+            from,
+            level - 1,
+            key.left_bound,
+            value.size as usize,
+        )? {
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(None)
+}
@@ -8,16 +8,22 @@ use indexmap::IndexMap;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
-use crate::error::UserError;
+use crate::error::{InternalError, UserError};
 use crate::facet::FacetType;
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{ByteSliceRefCodec, StrRefCodec};
+use crate::index::db_name;
+use crate::search::facet::aggregations::{Aggregation, AggregationResult, AggregationsLimits};
+use crate::search::facet::facet_buckets::{FacetBucket, FacetBuckets};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
-use crate::{FieldId, Index, Result};
+use crate::search::facet::facet_hierarchy::{build_hierarchy, FacetHierarchyNode};
+use crate::search::facet::facet_stats::{FacetStatsOptions, FacetValuesStats};
+use crate::{facet_path_ancestors, trim_facet_path, FieldId, Index, Result};
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
@@ -40,6 +46,8 @@ pub enum OrderBy {
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
+    /// The candidates of the facets whose distribution ignores some filters of the search.
+    facet_candidates: BTreeMap<String, RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
     rtxn: &'a heed::RoTxn<'a>,
@@ -51,6 +59,7 @@ impl<'a> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
             candidates: None,
+            facet_candidates: BTreeMap::new(),
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
             rtxn,
@@ -86,6 +95,30 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Sets the candidates of the given facets, they are used instead of the candidates
+    /// to compute the distribution and the stats of the fields of these facets.
+    ///
+    /// This is how the distribution of a facet ignores the filters applied on this facet.
+    pub fn facet_candidates(
+        &mut self,
+        facet_candidates: BTreeMap<String, RoaringBitmap>,
+    ) -> &mut Self {
+        self.facet_candidates = facet_candidates;
+        self
+    }
+
+    /// Returns the candidates used to compute the distribution of the given field,
+    /// those of its most specific facet if any.
+    fn field_candidates(&self, name: &str) -> Option<&RoaringBitmap> {
+        // the nested facets are sorted after their parent facet.
+        self.facet_candidates
+            .iter()
+            .rev()
+            .find(|(facet, _)| crate::is_faceted_by(name, facet))
+            .map(|(_, candidates)| candidates)
+            .or(self.candidates.as_ref())
+    }
+
     /// There is a small amount of candidates OR we ask for facet string values so we
     /// decide to iterate over the facet values of each one of them, one by one.
     fn facet_distribution_from_documents(
@@ -233,11 +266,12 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         order_by: OrderBy,
+        candidates: Option<&RoaringBitmap>,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
         let mut distribution = IndexMap::new();
-        match (order_by, &self.candidates) {
+        match (order_by, candidates) {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
@@ -246,7 +280,7 @@ impl<'a> FacetDistribution<'a> {
             }
             _ => {
                 let universe;
-                let candidates = match &self.candidates {
+                let candidates = match candidates {
                     Some(cnd) => cnd,
                     None => {
                         universe = self.index.documents_ids(self.rtxn)?;
@@ -275,11 +309,9 @@ impl<'a> FacetDistribution<'a> {
     pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
-        let candidates = if let Some(candidates) = self.candidates.clone() {
-            candidates
-        } else {
+        if self.candidates.is_none() {
             return Ok(Default::default());
-        };
+        }
 
         let fields = match &self.facets {
             Some(facets) => {
@@ -304,6 +336,7 @@ impl<'a> FacetDistribution<'a> {
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
+                let candidates = self.field_candidates(name).cloned().unwrap_or_default();
                 let min_value = if let Some(min_value) = crate::search::facet::facet_min_value(
                     self.index,
                     self.rtxn,
@@ -332,6 +365,202 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Returns the buckets of the given numeric facets, the number of candidates
+    /// having a value in each of them.
+    ///
+    /// The histograms return at most `max_values_per_facet` buckets.
+    pub fn compute_buckets(
+        &self,
+        facet_buckets: &BTreeMap<String, FacetBuckets>,
+    ) -> Result<BTreeMap<String, Vec<FacetBucket>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+
+        let invalid_fields: HashSet<_> = facet_buckets
+            .keys()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .collect();
+        if !invalid_fields.is_empty() {
+            return Err(UserError::InvalidFacetsDistribution {
+                invalid_facets_name: invalid_fields.into_iter().cloned().collect(),
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let mut distribution = BTreeMap::new();
+        for (name, buckets) in facet_buckets {
+            let Some(fid) = fields_ids_map.id(name) else {
+                continue;
+            };
+            let candidates = match self.field_candidates(name) {
+                Some(candidates) => candidates.clone(),
+                None => self.index.documents_ids(self.rtxn)?,
+            };
+            let buckets = buckets.compute(
+                self.index,
+                self.rtxn,
+                fid,
+                &candidates,
+                self.max_values_per_facet,
+            )?;
+            distribution.insert(name.to_string(), buckets);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the statistics of the values of the given facets: their bounds, count, sum,
+    /// number of distinct values and the requested percentiles.
+    pub fn compute_values_stats(
+        &self,
+        facet_stats: &BTreeMap<String, FacetStatsOptions>,
+    ) -> Result<BTreeMap<String, FacetValuesStats>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+
+        let invalid_fields: HashSet<_> = facet_stats
+            .keys()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .collect();
+        if !invalid_fields.is_empty() {
+            return Err(UserError::InvalidFacetsDistribution {
+                invalid_facets_name: invalid_fields.into_iter().cloned().collect(),
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let mut stats = BTreeMap::new();
+        for (name, options) in facet_stats {
+            let Some(fid) = fields_ids_map.id(name) else {
+                continue;
+            };
+            let candidates = match self.field_candidates(name) {
+                Some(candidates) => candidates.clone(),
+                None => self.index.documents_ids(self.rtxn)?,
+            };
+            let values_stats =
+                FacetValuesStats::compute(self.index, self.rtxn, fid, &candidates, options)?;
+            stats.insert(name.to_string(), values_stats);
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the results of the given aggregations, each of them aggregating
+    /// the candidates of its facet and its sub-aggregations the ones of their bucket.
+    pub fn compute_aggregations(
+        &self,
+        aggregations: &BTreeMap<String, Aggregation>,
+    ) -> Result<BTreeMap<String, AggregationResult>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+
+        let mut facets = Vec::new();
+        aggregations.values().for_each(|aggregation| aggregation.collect_facets(&mut facets));
+        let invalid_fields: HashSet<_> = facets
+            .into_iter()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .collect();
+        if !invalid_fields.is_empty() {
+            return Err(UserError::InvalidFacetsDistribution {
+                invalid_facets_name: invalid_fields.into_iter().map(String::from).collect(),
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let mut results = BTreeMap::new();
+        let mut limits = AggregationsLimits::default();
+        for (name, aggregation) in aggregations {
+            let candidates = match self.field_candidates(aggregation.facet()) {
+                Some(candidates) => candidates.clone(),
+                None => self.index.documents_ids(self.rtxn)?,
+            };
+            let result = aggregation.compute(
+                self.index,
+                self.rtxn,
+                &fields_ids_map,
+                &candidates,
+                self.max_values_per_facet,
+                &mut limits,
+            )?;
+            results.insert(name.to_string(), result);
+        }
+
+        Ok(results)
+    }
+
+    /// Returns the trees of the values of the hierarchical facets among the requested ones.
+    ///
+    /// The count of a level includes the candidates of its descendants
+    /// and each level has at most `max_values_per_facet` children.
+    pub fn compute_hierarchies(&self) -> Result<BTreeMap<String, Vec<FacetHierarchyNode>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let hierarchical_facets = self.index.hierarchical_facets(self.rtxn)?;
+
+        let mut hierarchies = BTreeMap::new();
+        for (name, separator) in hierarchical_facets {
+            let requested = match &self.facets {
+                Some(facets) => facets.contains_key(&name),
+                None => crate::is_faceted(&name, &filterable_fields),
+            };
+            let Some(fid) = fields_ids_map.id(&name).filter(|_| requested) else {
+                continue;
+            };
+
+            let candidates = match self.field_candidates(&name) {
+                Some(candidates) => candidates.clone(),
+                None => self.index.documents_ids(self.rtxn)?,
+            };
+            let order_by = self
+                .facets
+                .as_ref()
+                .and_then(|facets| facets.get(&name).copied())
+                .unwrap_or(self.default_order_by);
+
+            // all the values are needed to find the levels under the limit of each node,
+            // a level gathers the candidates of its own value and of its descendants.
+            let mut levels: BTreeMap<String, (String, RoaringBitmap)> = BTreeMap::new();
+            let prefix = FacetGroupKey { field_id: fid, level: 0, left_bound: "" };
+            for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &prefix)? {
+                let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+                let docids = bitmap & &candidates;
+                let Some(any_docid) = docids.min() else { continue };
+
+                let key: (FieldId, _, &str) = (fid, any_docid, left_bound);
+                let original =
+                    self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?.ok_or(
+                        InternalError::DatabaseMissingEntry {
+                            db_name: db_name::FIELD_ID_DOCID_FACET_STRINGS,
+                            key: None,
+                        },
+                    )?;
+
+                for ancestor in facet_path_ancestors(original, &separator) {
+                    let (_, ancestor_docids) = levels
+                        .entry(crate::normalize_facet(ancestor))
+                        .or_insert_with(|| (ancestor.to_string(), RoaringBitmap::new()));
+                    *ancestor_docids |= &docids;
+                }
+                let (_, level_docids) = levels.entry(left_bound.to_string()).or_insert_with(|| {
+                    (trim_facet_path(original, &separator).to_string(), RoaringBitmap::new())
+                });
+                *level_docids |= docids;
+            }
+            let values =
+                levels.into_values().map(|(value, docids)| (value, docids.len())).collect();
+
+            let hierarchy =
+                build_hierarchy(values, &separator, order_by, self.max_values_per_facet);
+            hierarchies.insert(name, hierarchy);
+        }
+
+        Ok(hierarchies)
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
@@ -364,7 +593,7 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let values = self.facet_values(fid, order_by, self.field_candidates(name))?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -378,6 +607,7 @@ impl fmt::Debug for FacetDistribution<'_> {
         let FacetDistribution {
             facets,
             candidates,
+            facet_candidates,
             max_values_per_facet,
             default_order_by,
             rtxn: _,
@@ -387,6 +617,7 @@ impl fmt::Debug for FacetDistribution<'_> {
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
             .field("candidates", candidates)
+            .field("facet_candidates", facet_candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
             .finish()
@@ -395,14 +626,21 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
-    use maplit::hashset;
+    use maplit::{btreemap, hashset};
+    use roaring::RoaringBitmap;
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::search::facet::aggregations::{MAX_AGGREGATIONS_DEPTH, MAX_AGGREGATION_BUCKETS};
+    use crate::{
+        milli_snap, Aggregation, AggregationResult, BucketKey, CalendarInterval, Error,
+        FacetBucket, FacetBuckets, FacetDistribution, FacetHierarchyNode, FacetRange,
+        FacetStatsOptions, FacetValuesStats, Filter, OrderBy, UserError,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -781,6 +1019,403 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_candidates() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("colour"), S("size") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": "blue", "size": 1 },
+            { "colour": "blue", "size": 2 },
+            { "colour": "red", "size": 3 },
+            { "colour": "red", "size": 4 },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // the colour distribution ignores the `colour = blue` filter,
+        // while the other facets use the candidates.
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([("colour", OrderBy::default()), ("size", OrderBy::default())])
+            .candidates([0, 1].iter().copied().collect())
+            .facet_candidates(btreemap! { S("colour") => (0..4).collect() })
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"blue": 2, "red": 2}, "size": {"1": 1, "2": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([("colour", OrderBy::default()), ("size", OrderBy::default())])
+            .candidates([0, 1].iter().copied().collect())
+            .facet_candidates(btreemap! { S("size") => (0..4).collect() })
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"size": (1.0, 4.0)}"###);
+    }
+
+    #[test]
+    fn facet_buckets() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("price"), S("date") })
+            })
+            .unwrap();
+
+        // 2023-01-15, 2023-02-20, 2023-02-28 and 2023-07-04
+        let documents = documents!([
+            { "price": 5, "date": 1673740800 },
+            { "price": 20, "date": 1676851200 },
+            { "price": 45, "date": 1677542400 },
+            { "price": 60, "date": 1688428800 },
+            { "price": 150 },
+            { "price": 151 },
+            { "price": [10, 12] },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let buckets = |facet: &str, buckets: FacetBuckets, candidates: Option<RoaringBitmap>| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            if let Some(candidates) = candidates {
+                distribution.candidates(candidates);
+            }
+            let map = distribution.compute_buckets(&btreemap! { S(facet) => buckets }).unwrap();
+            let buckets: Vec<_> = map[facet]
+                .iter()
+                .map(|FacetBucket { from, to, count }| (*from, *to, *count))
+                .collect();
+            format!("{buckets:?}")
+        };
+
+        // the empty buckets are skipped and the documents counted once per bucket.
+        let histogram = FacetBuckets::Histogram { interval: 50.0 };
+        milli_snap!(buckets("price", histogram.clone(), None), @"[(Some(0.0), Some(50.0), 4), (Some(50.0), Some(100.0), 1), (Some(150.0), Some(200.0), 2)]");
+        milli_snap!(buckets("price", histogram, Some((3..6).collect())), @"[(Some(50.0), Some(100.0), 1), (Some(150.0), Some(200.0), 2)]");
+
+        let ranges = FacetBuckets::Ranges(vec![
+            FacetRange { from: None, to: Some(50.0) },
+            FacetRange { from: Some(50.0), to: Some(100.0) },
+            FacetRange { from: Some(100.0), to: None },
+            FacetRange { from: Some(1000.0), to: None },
+        ]);
+        milli_snap!(buckets("price", ranges, None), @"[(None, Some(50.0), 4), (Some(50.0), Some(100.0), 1), (Some(100.0), None, 2), (Some(1000.0), None, 0)]");
+
+        let months = FacetBuckets::DateHistogram { interval: CalendarInterval::Month };
+        milli_snap!(buckets("date", months, None), @"[(Some(1672531200.0), Some(1675209600.0), 1), (Some(1675209600.0), Some(1677628800.0), 2), (Some(1688169600.0), Some(1690848000.0), 1)]");
+        let quarters = FacetBuckets::DateHistogram { interval: CalendarInterval::Quarter };
+        milli_snap!(buckets("date", quarters, None), @"[(Some(1672531200.0), Some(1680307200.0), 3), (Some(1688169600.0), Some(1696118400.0), 1)]");
+
+        // the next bucket is found among the values of the candidates, whatever the
+        // number of values of the other documents in between.
+        let documents: Vec<_> = (0..1000)
+            .map(|price| match serde_json::json!({ "price": 1000 + price }) {
+                serde_json::Value::Object(object) => object,
+                _ => unreachable!(),
+            })
+            .collect();
+        drop(txn);
+        index
+            .add_documents(crate::documents::documents_batch_reader_from_objects(documents))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let buckets = |facet: &str, buckets: FacetBuckets, candidates: RoaringBitmap| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            distribution.candidates(candidates);
+            let map = distribution.compute_buckets(&btreemap! { S(facet) => buckets }).unwrap();
+            let buckets: Vec<_> = map[facet]
+                .iter()
+                .map(|FacetBucket { from, to, count }| (*from, *to, *count))
+                .collect();
+            format!("{buckets:?}")
+        };
+        let histogram = FacetBuckets::Histogram { interval: 1.0 };
+        let candidates: RoaringBitmap = [0, 7, 500, 1006].into_iter().collect();
+        milli_snap!(buckets("price", histogram, candidates), @"[(Some(5.0), Some(6.0), 1), (Some(1000.0), Some(1001.0), 1), (Some(1493.0), Some(1494.0), 1), (Some(1999.0), Some(2000.0), 1)]");
+    }
+
+    #[test]
+    fn facet_hierarchies() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("categories"), S("colour") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "categories": "Electronics > Phones > Android" },
+            { "categories": "Electronics > Phones > iOS" },
+            { "categories": ["Electronics > TV", "Garden > Tools"] },
+            { "categories": "Electronics" },
+            { "categories": "Garden", "colour": "Green > Dark" },
+            { "categories": " > Garden > Tools" },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        // the documents are reindexed without the empty leading levels of their paths.
+        index
+            .update_settings(|settings| {
+                settings.set_hierarchical_facets(btreemap! { S("categories") => S(" > ") })
+            })
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        fn render(nodes: &[FacetHierarchyNode], depth: usize, output: &mut String) {
+            for FacetHierarchyNode { value, count, children } in nodes {
+                output.push_str(&format!("{}{value} ({count})\n", "  ".repeat(depth)));
+                render(children, depth + 1, output);
+            }
+        }
+        let hierarchies = |candidates: Option<RoaringBitmap>| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            distribution
+                .facets([("categories", OrderBy::default()), ("colour", OrderBy::default())]);
+            if let Some(candidates) = candidates {
+                distribution.candidates(candidates);
+            }
+            let map = distribution.compute_hierarchies().unwrap();
+            let mut output = format!("{:?}\n", map.keys().collect::<Vec<_>>());
+            render(&map["categories"], 0, &mut output);
+            output
+        };
+
+        milli_snap!(hierarchies(None), @r###"
+        ["categories"]
+        Electronics (4)
+          Electronics > Phones (2)
+            Electronics > Phones > Android (1)
+            Electronics > Phones > iOS (1)
+          Electronics > TV (1)
+        Garden (3)
+          Garden > Tools (2)
+        "###);
+        milli_snap!(hierarchies(Some([0, 2, 4].into_iter().collect())), @r###"
+        ["categories"]
+        Electronics (2)
+          Electronics > Phones (1)
+            Electronics > Phones > Android (1)
+          Electronics > TV (1)
+        Garden (2)
+          Garden > Tools (1)
+        "###);
+
+        // the ancestors are not values of the flat distribution.
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("categories", OrderBy::default())))
+            .execute()
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Electronics": 1, "Electronics > Phones > Android": 1, "Electronics > Phones > iOS": 1, "Electronics > TV": 1, "Garden": 1, "Garden > Tools": 2}}"###);
+
+        // filtering on a level matches its descendants.
+        let docids = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            format!("{:?}", filter.evaluate(&txn, &index).unwrap())
+        };
+        milli_snap!(docids("categories = 'Electronics > Phones'"), @"RoaringBitmap<[0, 1]>");
+        milli_snap!(docids("categories = garden"), @"RoaringBitmap<[2, 4, 5]>");
+        milli_snap!(docids("categories = '/Electronics'"), @"RoaringBitmap<[]>");
+        milli_snap!(docids("categories = ' > Electronics > TV'"), @"RoaringBitmap<[2]>");
+        milli_snap!(docids("colour = Green"), @"RoaringBitmap<[]>");
+    }
+
+    #[test]
+    fn facet_values_stats() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("price"), S("brand") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "price": 5, "brand": "Acme" },
+            { "price": 20, "brand": "acme" },
+            { "price": 45, "brand": "Globex" },
+            { "price": [10, 20], "brand": "Initech" },
+            { "price": "unknown" },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let stats = |facet: &str, candidates: Option<RoaringBitmap>| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            if let Some(candidates) = candidates {
+                distribution.candidates(candidates);
+            }
+            let options = FacetStatsOptions { percentiles: vec![50.0, 90.0] };
+            let map =
+                distribution.compute_values_stats(&btreemap! { S(facet) => options }).unwrap();
+            let stats = &map[facet];
+            let FacetValuesStats { min, max, count, sum, distinct, percentiles } = stats;
+            format!("{min:?} {max:?} {count} {sum} {:?} {distinct} {percentiles:?}", stats.avg())
+        };
+
+        // the documents are counted once per value and the strings are only distinct values.
+        milli_snap!(stats("price", None), @"Some(5.0) Some(45.0) 5 100 Some(20.0) 5 [(50.0, 20.0), (90.0, 45.0)]");
+        milli_snap!(stats("price", Some((0..2).collect())), @"Some(5.0) Some(20.0) 2 25 Some(12.5) 2 [(50.0, 5.0), (90.0, 20.0)]");
+        milli_snap!(stats("brand", None), @"None None 0 0 None 3 []");
+    }
+
+    #[test]
+    fn facet_aggregations() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("genre"), S("price") })
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "genre": "rock", "price": 5 },
+            { "genre": "rock", "price": 20 },
+            { "genre": "jazz", "price": 45 },
+            { "genre": "pop", "price": 12 },
+            { "genre": "rock", "price": 30 },
+            { "genre": "pop", "price": 50 },
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        fn render(results: &BTreeMap<String, AggregationResult>, depth: usize, out: &mut String) {
+            let indent = "  ".repeat(depth);
+            for (name, result) in results {
+                match result {
+                    AggregationResult::Buckets(buckets) => {
+                        for bucket in buckets {
+                            let key = match &bucket.key {
+                                BucketKey::Term(term) => term.clone(),
+                                BucketKey::Range(FacetRange { from, to }) => {
+                                    format!("{from:?}..{to:?}")
+                                }
+                            };
+                            out.push_str(&format!("{indent}{name} {key} {}\n", bucket.count));
+                            render(&bucket.aggregations, depth + 1, out);
+                        }
+                    }
+                    AggregationResult::Stats(FacetValuesStats { min, max, count, sum, .. }) => {
+                        out.push_str(&format!("{indent}{name} {min:?} {max:?} {count} {sum}\n"));
+                    }
+                }
+            }
+        }
+
+        let txn = index.read_txn().unwrap();
+        let aggregate = |candidates: Option<RoaringBitmap>| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            if let Some(candidates) = candidates {
+                distribution.candidates(candidates);
+            }
+            let stats =
+                Aggregation::Stats { facet: S("price"), options: FacetStatsOptions::default() };
+            let ranges = Aggregation::Ranges {
+                facet: S("price"),
+                ranges: vec![
+                    FacetRange { from: None, to: Some(10.0) },
+                    FacetRange { from: Some(10.0), to: None },
+                ],
+                aggregations: btreemap! { S("stats") => stats },
+            };
+            let terms = Aggregation::Terms {
+                facet: S("genre"),
+                size: Some(2),
+                aggregations: btreemap! { S("prices") => ranges },
+            };
+            let results =
+                distribution.compute_aggregations(&btreemap! { S("genres") => terms }).unwrap();
+            let mut out = String::new();
+            render(&results, 0, &mut out);
+            out
+        };
+
+        insta::assert_snapshot!(aggregate(None), @r###"
+        genres rock 3
+          prices None..Some(10.0) 1
+            stats Some(5.0) Some(5.0) 1 5
+          prices Some(10.0)..None 2
+            stats Some(20.0) Some(30.0) 2 50
+        genres pop 2
+          prices None..Some(10.0) 0
+            stats None None 0 0
+          prices Some(10.0)..None 2
+            stats Some(12.0) Some(50.0) 2 62
+        "###);
+
+        insta::assert_snapshot!(aggregate(Some((0..3).collect())), @r###"
+        genres rock 2
+          prices None..Some(10.0) 1
+            stats Some(5.0) Some(5.0) 1 5
+          prices Some(10.0)..None 1
+            stats Some(20.0) Some(20.0) 1 20
+        genres jazz 1
+          prices None..Some(10.0) 0
+            stats None None 0 0
+          prices Some(10.0)..None 1
+            stats Some(45.0) Some(45.0) 1 45
+        "###);
+
+        let terms =
+            Aggregation::Terms { facet: S("unknown"), size: None, aggregations: btreemap! {} };
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_aggregations(&btreemap! { S("genres") => terms })
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown"));
+
+        // the aggregations are limited in depth and in number of buckets.
+        let mut aggregation =
+            Aggregation::Stats { facet: S("price"), options: FacetStatsOptions::default() };
+        for _ in 0..MAX_AGGREGATIONS_DEPTH {
+            aggregation = Aggregation::Terms {
+                facet: S("genre"),
+                size: None,
+                aggregations: btreemap! { S("nested") => aggregation },
+            };
+        }
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_aggregations(&btreemap! { S("genres") => aggregation })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::AggregationsTooDeep(MAX_AGGREGATIONS_DEPTH))
+        ));
+
+        let ranges = Aggregation::Ranges {
+            facet: S("price"),
+            ranges: (0..=MAX_AGGREGATION_BUCKETS)
+                .map(|i| FacetRange { from: Some(i as f64), to: Some(i as f64 + 1.0) })
+                .collect(),
+            aggregations: btreemap! {},
+        };
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_aggregations(&btreemap! { S("prices") => ranges })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::TooManyAggregationBuckets(MAX_AGGREGATION_BUCKETS))
+        ));
+    }
+
     #[test]
     fn facet_mixed_values() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
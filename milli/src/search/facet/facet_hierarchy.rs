@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::OrderBy;
+use crate::{facet_path_ancestors, normalize_facet};
+
+/// A level of the path of a hierarchical facet along with the number of candidates
+/// having this path or any of its descendants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FacetHierarchyNode {
+    /// The whole path of this level, that can be used to filter on it.
+    pub value: String,
+    pub count: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<FacetHierarchyNode>,
+}
+
+/// Builds the tree of the values of a hierarchical facet.
+///
+/// The values must contain every ancestor of their paths, the parent of a path being
+/// its last ancestor.
+/// Each node keeps at most `max_children` children, sorted by `order_by`.
+pub(crate) fn build_hierarchy(
+    values: Vec<(String, u64)>,
+    separator: &str,
+    order_by: OrderBy,
+    max_children: usize,
+) -> Vec<FacetHierarchyNode> {
+    let positions: HashMap<_, _> =
+        values.iter().enumerate().map(|(i, (value, _))| (normalize_facet(value), i)).collect();
+
+    let mut roots = Vec::new();
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, (value, _)) in values.iter().enumerate() {
+        let parent = facet_path_ancestors(value, separator)
+            .last()
+            .and_then(|parent| positions.get(&normalize_facet(parent)))
+            .filter(|&&parent| parent != i);
+        match parent {
+            Some(&parent) => children.entry(parent).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    fn build(
+        mut nodes: Vec<usize>,
+        values: &[(String, u64)],
+        children: &mut HashMap<usize, Vec<usize>>,
+        order_by: OrderBy,
+        max_children: usize,
+    ) -> Vec<FacetHierarchyNode> {
+        if order_by == OrderBy::Count {
+            nodes.sort_by(|a, b| values[*b].1.cmp(&values[*a].1));
+        }
+        nodes.truncate(max_children);
+
+        nodes
+            .into_iter()
+            .map(|i| {
+                let (value, count) = &values[i];
+                let node_children = children.remove(&i).unwrap_or_default();
+                FacetHierarchyNode {
+                    value: value.clone(),
+                    count: *count,
+                    children: build(node_children, values, children, order_by, max_children),
+                }
+            })
+            .collect()
+    }
+
+    build(roots, &values, &mut children, order_by, max_children)
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+
+    #[test]
+    fn build_lexicographic_hierarchy() {
+        let values = vec![
+            (S("Electronics"), 4),
+            (S("Electronics > Phones"), 3),
+            (S("Electronics > Phones > Android"), 2),
+            (S("Electronics > Phones > iOS"), 1),
+            (S("Electronics > TV"), 1),
+            (S("Garden"), 1),
+        ];
+        let hierarchy = build_hierarchy(values, " > ", OrderBy::Lexicographic, 10);
+        insta::assert_json_snapshot!(hierarchy, @r###"
+        [
+          {
+            "value": "Electronics",
+            "count": 4,
+            "children": [
+              {
+                "value": "Electronics > Phones",
+                "count": 3,
+                "children": [
+                  {
+                    "value": "Electronics > Phones > Android",
+                    "count": 2
+                  },
+                  {
+                    "value": "Electronics > Phones > iOS",
+                    "count": 1
+                  }
+                ]
+              },
+              {
+                "value": "Electronics > TV",
+                "count": 1
+              }
+            ]
+          },
+          {
+            "value": "Garden",
+            "count": 1
+          }
+        ]
+        "###);
+    }
+
+    #[test]
+    fn build_count_hierarchy() {
+        let values = vec![
+            (S("electronics"), 4),
+            (S("Electronics > Phones"), 1),
+            (S("Electronics > TV"), 3),
+            (S("Electronics > Video"), 2),
+        ];
+        let hierarchy = build_hierarchy(values, " > ", OrderBy::Count, 2);
+        insta::assert_json_snapshot!(hierarchy, @r###"
+        [
+          {
+            "value": "electronics",
+            "count": 4,
+            "children": [
+              {
+                "value": "Electronics > TV",
+                "count": 3
+              },
+              {
+                "value": "Electronics > Video",
+                "count": 2
+              }
+            ]
+          }
+        ]
+        "###);
+    }
+}
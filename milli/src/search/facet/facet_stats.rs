@@ -0,0 +1,192 @@
+use std::ops::ControlFlow;
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::facet_distribution_iter::lexicographically_iterate_over_facet_distribution;
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::ByteSliceRefCodec;
+use crate::{FieldId, Index, Result};
+
+/// The maximum number of centroids kept to approximate the percentiles.
+const MAX_PERCENTILES_CENTROIDS: usize = 1024;
+
+/// The optional aggregations of the statistics of a facet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FacetStatsOptions {
+    /// The percentiles to compute, between 0 and 100.
+    pub percentiles: Vec<f64>,
+}
+
+/// The statistics of the values of a facet over the candidates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FacetValuesStats {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The number of numeric values, a document being counted once per value of its field.
+    pub count: u64,
+    pub sum: f64,
+    /// The number of distinct numeric and string values.
+    pub distinct: u64,
+    /// The requested percentiles along with their approximated value.
+    pub percentiles: Vec<(f64, f64)>,
+}
+
+impl FacetValuesStats {
+    /// Returns the average of the numeric values, if there is any.
+    pub fn avg(&self) -> Option<f64> {
+        (self.count != 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Computes the statistics of the values of the candidates for the given field.
+    ///
+    /// The values are read in ascending order over the levels of the facet databases,
+    /// the percentiles are then approximated by the nearest-rank values of a bounded
+    /// number of centroids of the numeric values.
+    pub(crate) fn compute(
+        index: &Index,
+        rtxn: &heed::RoTxn,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        options: &FacetStatsOptions,
+    ) -> Result<Self> {
+        let mut stats = FacetValuesStats::default();
+        let mut centroids = PercentilesCentroids::default();
+        lexicographically_iterate_over_facet_distribution(
+            rtxn,
+            index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+            field_id,
+            candidates,
+            |facet_key, nbr_docids, _| {
+                let value =
+                    OrderedF64Codec::bytes_decode(facet_key).ok_or(heed::Error::Decoding)?;
+                stats.min.get_or_insert(value);
+                stats.max = Some(value);
+                stats.count += nbr_docids;
+                stats.sum += value * nbr_docids as f64;
+                stats.distinct += 1;
+                if !options.percentiles.is_empty() {
+                    centroids.insert(value, nbr_docids);
+                }
+                Ok(ControlFlow::Continue(()))
+            },
+        )?;
+
+        lexicographically_iterate_over_facet_distribution(
+            rtxn,
+            index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+            field_id,
+            candidates,
+            |_, _, _| {
+                stats.distinct += 1;
+                Ok(ControlFlow::Continue(()))
+            },
+        )?;
+
+        stats.percentiles = options
+            .percentiles
+            .iter()
+            .filter_map(|&percentile| {
+                Some((percentile, nearest_rank(&centroids.centroids, stats.count, percentile)?))
+            })
+            .collect();
+
+        Ok(stats)
+    }
+}
+
+/// The numeric values, inserted in ascending order, gathered into centroids
+/// along with their number of occurrences.
+///
+/// The adjacent values are merged into their weighted mean while a centroid has less than
+/// `max_occurrences` occurrences, this limit grows with the number of values so that there are
+/// never more than `MAX_PERCENTILES_CENTROIDS` centroids. The nearest-rank of a percentile is
+/// then off by at most `max_occurrences`, a small fraction of the number of values.
+#[derive(Debug, Default)]
+struct PercentilesCentroids {
+    centroids: Vec<(f64, u64)>,
+    max_occurrences: u64,
+}
+
+impl PercentilesCentroids {
+    fn insert(&mut self, value: f64, occurrences: u64) {
+        match self.centroids.last_mut() {
+            Some(last) if last.1 + occurrences <= self.max_occurrences => {
+                *last = merge_centroids(*last, (value, occurrences));
+                return;
+            }
+            _ => (),
+        }
+
+        if self.centroids.len() == MAX_PERCENTILES_CENTROIDS {
+            // two adjacent centroids having more than the limit once merged,
+            // there are at most half as many centroids afterwards.
+            let total: u64 = self.centroids.iter().map(|(_, occurrences)| occurrences).sum();
+            let limit = total * 4 / MAX_PERCENTILES_CENTROIDS as u64;
+            self.max_occurrences = self.max_occurrences.max(limit);
+
+            let mut centroids: Vec<(f64, u64)> = Vec::with_capacity(MAX_PERCENTILES_CENTROIDS);
+            for centroid in self.centroids.drain(..) {
+                match centroids.last_mut() {
+                    Some(last) if last.1 + centroid.1 <= self.max_occurrences => {
+                        *last = merge_centroids(*last, centroid);
+                    }
+                    _ => centroids.push(centroid),
+                }
+            }
+            self.centroids = centroids;
+        }
+
+        self.centroids.push((value, occurrences));
+    }
+}
+
+fn merge_centroids((a, a_occurrences): (f64, u64), (b, b_occurrences): (f64, u64)) -> (f64, u64) {
+    let occurrences = a_occurrences + b_occurrences;
+    let mean = (a * a_occurrences as f64 + b * b_occurrences as f64) / occurrences as f64;
+    (mean, occurrences)
+}
+
+/// Returns the smallest value greater than or equal to the given percentage of the values,
+/// the values being sorted along with their number of occurrences.
+fn nearest_rank(values: &[(f64, u64)], count: u64, percentile: f64) -> Option<f64> {
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * count as f64).ceil().max(1.0) as u64;
+    let mut seen = 0;
+    values.iter().find_map(|(value, occurrences)| {
+        seen += occurrences;
+        (seen >= rank).then_some(*value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let values = [(1.0, 1), (2.0, 2), (5.0, 1), (10.0, 1)];
+        let percentile = |p| nearest_rank(&values, 5, p);
+
+        assert_eq!(percentile(0.0), Some(1.0));
+        assert_eq!(percentile(20.0), Some(1.0));
+        assert_eq!(percentile(50.0), Some(2.0));
+        assert_eq!(percentile(80.0), Some(5.0));
+        assert_eq!(percentile(99.0), Some(10.0));
+        assert_eq!(percentile(100.0), Some(10.0));
+        assert_eq!(nearest_rank(&[], 0, 50.0), None);
+    }
+
+    #[test]
+    fn approximated_percentiles() {
+        let mut centroids = PercentilesCentroids::default();
+        for value in 0..100_000 {
+            centroids.insert(value as f64, 1);
+        }
+        assert!(centroids.centroids.len() <= MAX_PERCENTILES_CENTROIDS);
+
+        // the exact percentiles are 49999 and 89999, the error is bounded by 0.4% of the values.
+        let percentile = |p| nearest_rank(&centroids.centroids, 100_000, p).unwrap();
+        assert!((percentile(50.0) - 49_999.0).abs() <= 400.0);
+        assert!((percentile(90.0) - 89_999.0).abs() <= 400.0);
+    }
+}
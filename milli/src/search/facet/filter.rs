@@ -1,18 +1,22 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::{Debug, Display};
 use std::ops::Bound::{self, Excluded, Included};
 
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Span, Token};
+use heed::types::ByteSlice;
 use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::facet_range_search;
+use crate::distance::{Distance, VectorPoint, DEFAULT_VECTOR_SPACE};
 use crate::error::{Error, UserError};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
-use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
+use crate::heed_codec::StrBEU32Codec;
+use crate::hnsw::Hnsw;
+use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result, BEU32};
 
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
@@ -55,6 +59,8 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
+    UnknownVectorSpace { space: &'a str, available: BTreeSet<String> },
+    VectorDimensions { expected: usize, found: usize },
     TooDeep,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
@@ -96,6 +102,20 @@ impl<'a> Display for FilterError<'a> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::UnknownVectorSpace { space, available } => {
+                let available = available.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+                write!(
+                    f,
+                    "The vector space `{}` does not exist. Available vector spaces are: `{}`.",
+                    space,
+                    available.join(", "),
+                )
+            }
+            Self::VectorDimensions { expected, found } => write!(
+                f,
+                "The vector must have {} dimensions but has {} dimensions.",
+                expected, found
+            ),
         }
     }
 }
@@ -221,6 +241,23 @@ impl<'a> Filter<'a> {
 }
 
 impl<'a> Filter<'a> {
+    /// Returns the filters combined with `AND` by this filter, written in a canonical way,
+    /// the filter itself when it doesn't combine filters with `AND`.
+    pub fn active_filters(&self) -> Vec<String> {
+        fn inner(condition: &FilterCondition, output: &mut Vec<String>) {
+            match condition {
+                FilterCondition::And(conditions) => {
+                    conditions.iter().for_each(|condition| inner(condition, output))
+                }
+                condition => output.push(condition.to_string()),
+            }
+        }
+
+        let mut active_filters = Vec::new();
+        inner(&self.condition, &mut active_filters);
+        active_filters
+    }
+
     pub fn evaluate(&self, rtxn: &heed::RoTxn, index: &Index) -> Result<RoaringBitmap> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
         let soft_deleted_documents = index.soft_deleted_documents_ids(rtxn)?;
@@ -271,17 +308,25 @@ impl<'a> Filter<'a> {
                 return Ok(exist);
             }
             Condition::Equal(val) => {
-                let string_docids = strings_db
-                    .get(
-                        rtxn,
-                        &FacetGroupKey {
-                            field_id,
-                            level: 0,
-                            left_bound: &crate::normalize_facet(val.value()),
-                        },
-                    )?
+                let hierarchical_facets = index.hierarchical_facets_ids(rtxn)?;
+                let separator = hierarchical_facets.get(&field_id);
+                let normalized = match separator {
+                    Some(separator) => crate::normalize_facet_path(val.value(), separator),
+                    None => crate::normalize_facet(val.value()),
+                };
+                let mut string_docids = strings_db
+                    .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: &normalized })?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
+                // filtering on a level of a hierarchical facet also matches its descendants.
+                if let Some(separator) = separator.filter(|_| !normalized.is_empty()) {
+                    let prefix = crate::facet_path_descendants_prefix(&normalized, separator);
+                    let prefix = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+                    for result in strings_db.prefix_iter(rtxn, &prefix)? {
+                        let (_, value) = result?;
+                        string_docids |= value.bitmap;
+                    }
+                }
                 let number = val.parse_finite_float().ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
@@ -394,29 +439,36 @@ impl<'a> Filter<'a> {
                 Ok(bitmap)
             }
             FilterCondition::And(subfilters) => {
-                let mut subfilters_iter = subfilters.iter();
-                if let Some(first_subfilter) = subfilters_iter.next() {
-                    let mut bitmap = Self::inner_evaluate(
-                        &(first_subfilter.clone()).into(),
-                        rtxn,
-                        index,
-                        filterable_fields,
-                    )?;
-                    for f in subfilters_iter {
-                        if bitmap.is_empty() {
-                            return Ok(bitmap);
+                // The vector similarities are evaluated last, on the documents
+                // matching the other conditions only.
+                let (vectors, others): (Vec<_>, Vec<_>) = subfilters
+                    .iter()
+                    .partition(|f| matches!(f, FilterCondition::VectorSimilarity { .. }));
+                let mut bitmap: Option<RoaringBitmap> = None;
+                for f in others.into_iter().chain(vectors) {
+                    if bitmap.as_ref().map_or(false, |bitmap| bitmap.is_empty()) {
+                        break;
+                    }
+                    let selected = match f {
+                        FilterCondition::VectorSimilarity { space, vector, op } => {
+                            let universe = bitmap.as_ref();
+                            Self::evaluate_vector_similarity(
+                                rtxn, index, space, vector, op, universe,
+                            )?
                         }
-                        bitmap &= Self::inner_evaluate(
+                        f => Self::inner_evaluate(
                             &(f.clone()).into(),
                             rtxn,
                             index,
                             filterable_fields,
-                        )?;
-                    }
-                    Ok(bitmap)
-                } else {
-                    Ok(RoaringBitmap::new())
+                        )?,
+                    };
+                    bitmap = Some(match bitmap {
+                        Some(bitmap) => bitmap & selected,
+                        None => selected,
+                    });
                 }
+                Ok(bitmap.unwrap_or_default())
             }
             FilterCondition::GeoLowerThan { point, radius } => {
                 if filterable_fields.contains("_geo") {
@@ -583,8 +635,113 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::VectorSimilarity { space, vector, op } => {
+                Self::evaluate_vector_similarity(rtxn, index, space, vector, op, None)
+            }
         }
     }
+
+    /// Evaluates a `_vectorSimilarity` condition, only the vectors of the documents
+    /// of the universe are compared when it is smaller than the vector space.
+    fn evaluate_vector_similarity(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        space_token: &Token,
+        vector: &[Token],
+        op: &Condition,
+        universe: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap> {
+        let space = space_token.value();
+        let vector_spaces = index.vector_spaces(rtxn)?;
+        let declared = vector_spaces.get(space);
+        if declared.is_none() && space != DEFAULT_VECTOR_SPACE {
+            return Err(space_token
+                .as_external_error(FilterError::UnknownVectorSpace {
+                    space,
+                    available: vector_spaces.into_keys().collect(),
+                })
+                .into());
+        }
+
+        let vector = vector
+            .iter()
+            .map(|token| token.parse_finite_float().map(|f| f as f32))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let hnsw = Hnsw::new(index, space);
+        let expected = match declared {
+            Some(declared) => Some(declared.dimensions),
+            None => hnsw.dimensions(rtxn)?,
+        };
+        if let Some(expected) = expected.filter(|expected| *expected != vector.len()) {
+            return Err(space_token
+                .as_external_error(FilterError::VectorDimensions { expected, found: vector.len() })
+                .into());
+        }
+
+        let (threshold, accept): (_, fn(f32, f32) -> bool) = match op {
+            Condition::GreaterThan(val) => (val, |s, t| s > t),
+            Condition::GreaterThanOrEqual(val) => (val, |s, t| s >= t),
+            Condition::LowerThan(val) => (val, |s, t| s < t),
+            Condition::LowerThanOrEqual(val) => (val, |s, t| s <= t),
+            _ => unreachable!("the vector similarity can only be compared"),
+        };
+        let threshold = threshold.parse_finite_float()? as f32;
+
+        let distance = declared.map_or(Distance::default(), |s| s.distance);
+        let query = VectorPoint::new(distance, vector);
+        let accepted = |d| accept(distance.similarity(d), threshold);
+        let near = matches!(op, Condition::GreaterThan(_) | Condition::GreaterThanOrEqual(_));
+
+        let mut docids = RoaringBitmap::new();
+        match universe {
+            Some(universe) if universe.len() < hnsw.len(rtxn)? => {
+                for docid in universe {
+                    let vector_ids =
+                        index.docid_vector_ids.get(rtxn, &BEU32::new(docid))?.unwrap_or_default();
+                    for (_, pid) in vector_ids.into_iter().filter(|(s, _)| s == space) {
+                        if let Some(node) = index.vector_hnsw_nodes.get(rtxn, &(space, pid))? {
+                            if accepted(query.distance_to(&node.point)) {
+                                docids.insert(docid);
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            // the nearest points are found by a range search in the HNSW
+            _ if near => {
+                for (pid, _) in hnsw.search_within(rtxn, &query, accepted)? {
+                    if let Some(docid) = index.vector_id_docid.get(rtxn, &(space, pid))? {
+                        docids.insert(docid.get());
+                    }
+                }
+            }
+            // the furthest points are the ones that are not among the nearest,
+            // only the point ids are iterated, not the vectors.
+            _ => {
+                let nearest: RoaringBitmap = hnsw
+                    .search_within(rtxn, &query, |d| !accepted(d))?
+                    .into_iter()
+                    .map(|(pid, _)| pid)
+                    .collect();
+                let points = index
+                    .vector_id_docid
+                    .remap_key_type::<ByteSlice>()
+                    .prefix_iter(rtxn, space.as_bytes())?
+                    .remap_key_type::<StrBEU32Codec>();
+                for result in points {
+                    let ((point_space, pid), docid) = result?;
+                    // the prefix also matches the spaces whose name starts with this name
+                    if point_space == space && !nearest.contains(pid) {
+                        docids.insert(docid.get());
+                    }
+                }
+            }
+        }
+
+        Ok(docids)
+    }
 }
 
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
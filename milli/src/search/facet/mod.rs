@@ -4,16 +4,24 @@ use heed::types::{ByteSlice, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
+pub use self::aggregations::{Aggregation, AggregationBucket, AggregationResult, BucketKey};
+pub use self::facet_buckets::{CalendarInterval, FacetBucket, FacetBuckets, FacetRange};
 pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_hierarchy::FacetHierarchyNode;
+pub use self::facet_stats::{FacetStatsOptions, FacetValuesStats};
 pub use self::filter::{BadGeoError, Filter};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
 use crate::heed_codec::ByteSliceRefCodec;
 use crate::{Index, Result};
+mod aggregations;
+mod facet_buckets;
 mod facet_distribution;
 mod facet_distribution_iter;
+mod facet_hierarchy;
 mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
+mod facet_stats;
 mod filter;
 
 fn facet_extreme_value<'t>(
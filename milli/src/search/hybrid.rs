@@ -0,0 +1,261 @@
+//! Hybrid search: blends the keyword ranking pipeline with the vector nearest neighbours.
+//!
+//! Both searches are executed independently on the `offset + limit` first results,
+//! then their lists are fused into a single one according to a [`FusionMethod`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use roaring::RoaringBitmap;
+use serde::Serialize;
+
+use super::new::PartialSearchResult;
+use super::{Search, SearchResult};
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::{
+    execute_search, DefaultSearchLogger, DocumentId, MatchingWords, Result, SearchContext,
+};
+
+/// The constant used to dampen the ranks in the reciprocal rank fusion,
+/// the value commonly used in the literature.
+const RRF_RANK_CONSTANT: f64 = 60.0;
+
+/// How the keyword and semantic results are merged together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FusionMethod {
+    /// Only uses the rank of the documents in both lists, the scores are ignored.
+    #[default]
+    ReciprocalRankFusion,
+    /// Blends the keyword ranking score with the semantic similarity of the documents.
+    NormalizedScore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HybridSearch {
+    /// The weight of the semantic results, between `0.0` (keyword only) and `1.0` (semantic only).
+    pub semantic_ratio: f32,
+    pub fusion: FusionMethod,
+}
+
+/// A document found by the keyword search, the semantic search or both.
+struct FusedHit {
+    docid: DocumentId,
+    /// The rank of the document in the keyword results, starting at 1.
+    keyword_rank: Option<usize>,
+    keyword_details: Vec<ScoreDetails>,
+    /// The rank of the document in the semantic results, starting at 1.
+    semantic_rank: Option<usize>,
+    similarity: Option<f32>,
+}
+
+impl FusedHit {
+    fn score(&self, semantic_ratio: f64, fusion: FusionMethod) -> f64 {
+        let keyword_ratio = 1.0 - semantic_ratio;
+        match fusion {
+            FusionMethod::ReciprocalRankFusion => {
+                let rrf = |rank: Option<usize>| match rank {
+                    Some(rank) => 1.0 / (RRF_RANK_CONSTANT + rank as f64),
+                    None => 0.0,
+                };
+                let score = keyword_ratio * rrf(self.keyword_rank)
+                    + semantic_ratio * rrf(self.semantic_rank);
+                // scale the score so that a document ranked first in both lists scores 1.0
+                score * (RRF_RANK_CONSTANT + 1.0)
+            }
+            FusionMethod::NormalizedScore => {
+                let keyword_score = match self.keyword_rank {
+                    Some(_) => ScoreDetails::global_score(self.keyword_details.iter()),
+                    None => 0.0,
+                };
+                let semantic_score = self.similarity.map_or(0.0, |s| s.clamp(0.0, 1.0) as f64);
+                keyword_ratio * keyword_score + semantic_ratio * semantic_score
+            }
+        }
+    }
+}
+
+impl<'a> Search<'a> {
+    pub(super) fn execute_hybrid(&self, hybrid: HybridSearch) -> Result<SearchResult> {
+        let HybridSearch { semantic_ratio, fusion } = hybrid;
+        let window = self.offset + self.limit;
+
+        let mut ctx = SearchContext::new(self.index, self.rtxn);
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.searchable_attributes(searchable_attributes)?;
+        }
+        if let Some(typo_tolerance) = &self.typo_tolerance {
+            ctx.typo_tolerance(typo_tolerance)?;
+        }
+
+        let semantic = if semantic_ratio > 0.0 {
+            Some(execute_search(
+                &mut ctx,
+                &None,
+                &self.vector,
+                self.vector_space.as_deref(),
+                self.terms_matching_strategy,
+                self.query_syntax,
+                self.scoring_strategy,
+                self.exhaustive_number_hits,
+                &self.filter,
+                &self.facet_filters,
+                &self.sort_criteria,
+                &self.ranking_rules,
+                self.locales.as_deref(),
+                self.geo_strategy,
+                self.vector_strategy,
+                0,
+                window,
+                Some(self.words_limit),
+                &mut DefaultSearchLogger,
+                &mut DefaultSearchLogger,
+            )?)
+        } else {
+            None
+        };
+
+        let keyword = if semantic_ratio < 1.0 {
+            // the normalized score fusion needs the ranking scores of the keyword results.
+            let scoring_strategy = match fusion {
+                FusionMethod::ReciprocalRankFusion => self.scoring_strategy,
+                FusionMethod::NormalizedScore => ScoringStrategy::Detailed,
+            };
+            Some(execute_search(
+                &mut ctx,
+                &self.query,
+                &None,
+                None,
+                self.terms_matching_strategy,
+                self.query_syntax,
+                scoring_strategy,
+                self.exhaustive_number_hits,
+                &self.filter,
+                &self.facet_filters,
+                &self.sort_criteria,
+                &self.ranking_rules,
+                self.locales.as_deref(),
+                self.geo_strategy,
+                self.vector_strategy,
+                0,
+                window,
+                Some(self.words_limit),
+                &mut DefaultSearchLogger,
+                &mut DefaultSearchLogger,
+            )?)
+        } else {
+            None
+        };
+
+        let mut candidates = RoaringBitmap::new();
+        let mut facet_candidates: BTreeMap<String, RoaringBitmap> = BTreeMap::new();
+        let mut hits: Vec<FusedHit> = Vec::new();
+        let mut positions: HashMap<DocumentId, usize> = HashMap::new();
+        let mut located_query_terms = None;
+
+        if let Some(keyword) = keyword {
+            let PartialSearchResult {
+                located_query_terms: terms,
+                candidates: keyword_candidates,
+                facet_candidates: keyword_facet_candidates,
+                documents_ids,
+                document_scores,
+            } = keyword;
+            located_query_terms = terms;
+            candidates |= keyword_candidates;
+            for (facet, docids) in keyword_facet_candidates {
+                *facet_candidates.entry(facet).or_default() |= docids;
+            }
+            for (rank, (docid, details)) in
+                documents_ids.into_iter().zip(document_scores).enumerate()
+            {
+                positions.insert(docid, hits.len());
+                hits.push(FusedHit {
+                    docid,
+                    keyword_rank: Some(rank + 1),
+                    keyword_details: details,
+                    semantic_rank: None,
+                    similarity: None,
+                });
+            }
+        }
+
+        if let Some(semantic) = semantic {
+            let PartialSearchResult {
+                candidates: semantic_candidates,
+                facet_candidates: semantic_facet_candidates,
+                documents_ids,
+                document_scores,
+                ..
+            } = semantic;
+            // The semantic candidates are all the filtered documents, they are ranked but never
+            // discarded. Only the returned semantic hits count as candidates, otherwise the
+            // estimated number of hits would always be the number of filtered documents.
+            let semantic_hits: RoaringBitmap = documents_ids.iter().copied().collect();
+            candidates |= semantic_candidates & &semantic_hits;
+            for (facet, docids) in semantic_facet_candidates {
+                *facet_candidates.entry(facet).or_default() |= docids & &semantic_hits;
+            }
+            for (rank, (docid, details)) in
+                documents_ids.into_iter().zip(document_scores).enumerate()
+            {
+                let similarity = details.iter().find_map(|details| match details {
+                    ScoreDetails::Vector(vector) => Some(vector.similarity),
+                    _ => None,
+                });
+                let position = *positions.entry(docid).or_insert_with(|| {
+                    hits.push(FusedHit {
+                        docid,
+                        keyword_rank: None,
+                        keyword_details: Vec::new(),
+                        semantic_rank: None,
+                        similarity: None,
+                    });
+                    hits.len() - 1
+                });
+                let hit = &mut hits[position];
+                hit.semantic_rank = Some(rank + 1);
+                hit.similarity = similarity;
+            }
+        }
+
+        let semantic_ratio = semantic_ratio as f64;
+        let mut scored_hits: Vec<_> =
+            hits.into_iter().map(|hit| (hit.score(semantic_ratio, fusion), hit)).collect();
+        // the sort is stable: on equal scores, the keyword results come first.
+        scored_hits.sort_by(|(left, _), (right, _)| right.total_cmp(left));
+
+        let mut documents_ids = Vec::new();
+        let mut document_scores = Vec::new();
+        for (score, hit) in scored_hits.into_iter().skip(self.offset).take(self.limit) {
+            let FusedHit { docid, keyword_details, similarity, .. } = hit;
+            let mut details = Vec::with_capacity(keyword_details.len() + 2);
+            details.push(ScoreDetails::Hybrid(score_details::Hybrid {
+                semantic_ratio: semantic_ratio as f32,
+                fusion,
+                score,
+            }));
+            details.extend(keyword_details);
+            if let Some(similarity) = similarity {
+                details.push(ScoreDetails::Vector(score_details::Vector { similarity }));
+            }
+            documents_ids.push(docid);
+            document_scores.push(details);
+        }
+
+        // consume context and located_query_terms to build MatchingWords.
+        let matching_words = match located_query_terms {
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+            None => MatchingWords::default(),
+        };
+
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            facet_candidates,
+            documents_ids,
+            document_scores,
+            did_you_mean: None,
+            auto_corrected: false,
+        })
+    }
+}
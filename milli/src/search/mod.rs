@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::ops::ControlFlow;
 
@@ -10,14 +11,23 @@ use log::error;
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::did_you_mean::{correct_query, DidYouMean, DEFAULT_DID_YOU_MEAN_MAX_HITS};
+pub use self::facet::{
+    Aggregation, AggregationBucket, AggregationResult, BucketKey, CalendarInterval, FacetBucket,
+    FacetBuckets, FacetDistribution, FacetHierarchyNode, FacetRange, FacetStatsOptions,
+    FacetValuesStats, Filter, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
+pub use self::hybrid::{FusionMethod, HybridSearch};
 pub use self::new::matches::{FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWords};
 use self::new::PartialSearchResult;
+pub use self::suggestions::{SearchForSuggestions, Suggestion, DEFAULT_SUGGESTIONS_LIMIT};
+pub(crate) use self::typo_tolerance::TypoTolerance;
+pub use self::typo_tolerance::TypoToleranceOverride;
 use crate::error::UserError;
 use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::{
-    execute_search, AscDesc, DefaultSearchLogger, DocumentId, FieldId, Index, Result,
+    execute_search, AscDesc, Criterion, DefaultSearchLogger, DocumentId, FieldId, Index, Result,
     SearchContext, BEU16,
 };
 
@@ -29,21 +39,36 @@ static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 /// The maximum number of facets returned by the facet search route.
 const MAX_NUMBER_OF_FACETS: usize = 100;
 
+mod did_you_mean;
 pub mod facet;
 mod fst_utils;
+mod hybrid;
 pub mod new;
+pub mod suggestions;
+mod typo_tolerance;
 
+#[derive(Clone)]
 pub struct Search<'a> {
     query: Option<String>,
     vector: Option<Vec<f32>>,
+    vector_space: Option<String>,
+    hybrid: Option<HybridSearch>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    /// The filters scoped to a facet, ignored by the candidates of this facet.
+    facet_filters: Vec<(String, Filter<'a>)>,
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    ranking_rules: Option<Vec<Criterion>>,
+    locales: Option<Vec<String>>,
     searchable_attributes: Option<&'a [String]>,
+    typo_tolerance: Option<TypoToleranceOverride>,
+    did_you_mean: Option<DidYouMean>,
     geo_strategy: new::GeoSortStrategy,
+    vector_strategy: new::VectorSearchStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
@@ -56,13 +81,22 @@ impl<'a> Search<'a> {
         Search {
             query: None,
             vector: None,
+            vector_space: None,
+            hybrid: None,
             filter: None,
+            facet_filters: Vec::new(),
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            ranking_rules: None,
+            locales: None,
             searchable_attributes: None,
+            typo_tolerance: None,
+            did_you_mean: None,
             geo_strategy: new::GeoSortStrategy::default(),
+            vector_strategy: new::VectorSearchStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
+            query_syntax: QuerySyntax::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
@@ -81,6 +115,22 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// The name of the vector space in which the vector is searched,
+    /// the default vector space is used if none is given.
+    pub fn vector_space(&mut self, name: impl Into<String>) -> &mut Search<'a> {
+        self.vector_space = Some(name.into());
+        self
+    }
+
+    /// Blends the keyword search of the query with the vector search, the `semantic_ratio`
+    /// being the weight of the vector results, between `0.0` and `1.0`.
+    ///
+    /// Has no effect if no vector is given.
+    pub fn hybrid(&mut self, semantic_ratio: f32, fusion: FusionMethod) -> &mut Search<'a> {
+        self.hybrid = Some(HybridSearch { semantic_ratio, fusion });
+        self
+    }
+
     pub fn offset(&mut self, offset: usize) -> &mut Search<'a> {
         self.offset = offset;
         self
@@ -96,16 +146,46 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Overrides the ranking rules of the index for this search only.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> &mut Search<'a> {
+        self.ranking_rules = Some(ranking_rules);
+        self
+    }
+
+    /// Forces the language detection of the query to the given locales,
+    /// the ISO 639-3 codes of the languages, e.g. `jpn`, `cmn` or `swe`.
+    pub fn locales(&mut self, locales: Vec<String>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
     pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
         self.searchable_attributes = Some(searchable);
         self
     }
 
+    /// Overrides the typo tolerance settings of the index for this search only.
+    pub fn typo_tolerance(&mut self, typo_tolerance: TypoToleranceOverride) -> &mut Search<'a> {
+        self.typo_tolerance = Some(typo_tolerance);
+        self
+    }
+
+    /// Suggests a correction of the query when it matches few documents.
+    pub fn did_you_mean(&mut self, did_you_mean: DidYouMean) -> &mut Search<'a> {
+        self.did_you_mean = Some(did_you_mean);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
     }
 
+    pub fn query_syntax(&mut self, value: QuerySyntax) -> &mut Search<'a> {
+        self.query_syntax = value;
+        self
+    }
+
     pub fn scoring_strategy(&mut self, value: ScoringStrategy) -> &mut Search<'a> {
         self.scoring_strategy = value;
         self
@@ -121,12 +201,32 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Adds a filter scoped to the given facet, it restricts the results like the filter
+    /// but is ignored when computing the candidates of this facet.
+    pub fn facet_filter(
+        &mut self,
+        facet: impl Into<String>,
+        condition: Filter<'a>,
+    ) -> &mut Search<'a> {
+        self.facet_filters.push((facet.into(), condition));
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_strategy = strategy;
         self
     }
 
+    #[cfg(test)]
+    pub fn vector_search_strategy(
+        &mut self,
+        strategy: new::VectorSearchStrategy,
+    ) -> &mut Search<'a> {
+        self.vector_strategy = strategy;
+        self
+    }
+
     /// Forces the search to exhaustively compute the number of candidates,
     /// this will increase the search time but allows finite pagination.
     pub fn exhaustive_number_hits(&mut self, exhaustive_number_hits: bool) -> &mut Search<'a> {
@@ -135,29 +235,72 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        let mut result = self.execute_query()?;
+
+        if let (Some(did_you_mean), Some(query)) = (self.did_you_mean, &self.query) {
+            if result.candidates.len() <= did_you_mean.max_hits {
+                if let Some(correction) =
+                    correct_query(self.index, self.rtxn, query, self.typo_tolerance.as_ref())?
+                {
+                    if did_you_mean.auto_correct {
+                        let mut search = self.clone();
+                        search.query = Some(correction.clone());
+                        let corrected = search.execute_query()?;
+                        if corrected.candidates.len() > result.candidates.len() {
+                            result = SearchResult { auto_corrected: true, ..corrected };
+                        }
+                    }
+                    result.did_you_mean = Some(correction);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn execute_query(&self) -> Result<SearchResult> {
+        if let (Some(hybrid), Some(_)) = (self.hybrid, &self.vector) {
+            return self.execute_hybrid(hybrid);
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn);
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.searchable_attributes(searchable_attributes)?;
         }
 
-        let PartialSearchResult { located_query_terms, candidates, documents_ids, document_scores } =
-            execute_search(
-                &mut ctx,
-                &self.query,
-                &self.vector,
-                self.terms_matching_strategy,
-                self.scoring_strategy,
-                self.exhaustive_number_hits,
-                &self.filter,
-                &self.sort_criteria,
-                self.geo_strategy,
-                self.offset,
-                self.limit,
-                Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
-            )?;
+        if let Some(typo_tolerance) = &self.typo_tolerance {
+            ctx.typo_tolerance(typo_tolerance)?;
+        }
+
+        let PartialSearchResult {
+            located_query_terms,
+            candidates,
+            facet_candidates,
+            documents_ids,
+            document_scores,
+        } = execute_search(
+            &mut ctx,
+            &self.query,
+            &self.vector,
+            self.vector_space.as_deref(),
+            self.terms_matching_strategy,
+            self.query_syntax,
+            self.scoring_strategy,
+            self.exhaustive_number_hits,
+            &self.filter,
+            &self.facet_filters,
+            &self.sort_criteria,
+            &self.ranking_rules,
+            self.locales.as_deref(),
+            self.geo_strategy,
+            self.vector_strategy,
+            self.offset,
+            self.limit,
+            Some(self.words_limit),
+            &mut DefaultSearchLogger,
+            &mut DefaultSearchLogger,
+        )?;
 
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
@@ -165,7 +308,15 @@ impl<'a> Search<'a> {
             None => MatchingWords::default(),
         };
 
-        Ok(SearchResult { matching_words, candidates, document_scores, documents_ids })
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            facet_candidates,
+            document_scores,
+            documents_ids,
+            did_you_mean: None,
+            auto_corrected: false,
+        })
     }
 }
 
@@ -174,13 +325,22 @@ impl fmt::Debug for Search<'_> {
         let Search {
             query,
             vector: _,
+            vector_space,
+            hybrid,
             filter,
+            facet_filters,
             offset,
             limit,
             sort_criteria,
+            ranking_rules,
+            locales,
             searchable_attributes,
+            typo_tolerance,
+            did_you_mean,
             geo_strategy: _,
+            vector_strategy: _,
             terms_matching_strategy,
+            query_syntax,
             scoring_strategy,
             words_limit,
             exhaustive_number_hits,
@@ -190,12 +350,20 @@ impl fmt::Debug for Search<'_> {
         f.debug_struct("Search")
             .field("query", query)
             .field("vector", &"[...]")
+            .field("vector_space", vector_space)
+            .field("hybrid", hybrid)
             .field("filter", filter)
+            .field("facet_filters", facet_filters)
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("ranking_rules", ranking_rules)
+            .field("locales", locales)
             .field("searchable_attributes", searchable_attributes)
+            .field("typo_tolerance", typo_tolerance)
+            .field("did_you_mean", did_you_mean)
             .field("terms_matching_strategy", terms_matching_strategy)
+            .field("query_syntax", query_syntax)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
@@ -207,8 +375,14 @@ impl fmt::Debug for Search<'_> {
 pub struct SearchResult {
     pub matching_words: MatchingWords,
     pub candidates: RoaringBitmap,
+    /// The candidates of the facets of the facet-scoped filters, ignoring their own filters.
+    pub facet_candidates: BTreeMap<String, RoaringBitmap>,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The correction of the query, when it matched few documents and could be corrected.
+    pub did_you_mean: Option<String>,
+    /// Whether the results are those of the corrected query.
+    pub auto_corrected: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -225,6 +399,15 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QuerySyntax {
+    // words and quoted phrases
+    #[default]
+    Plain,
+    // words and phrases required with `+`, excluded with `-`, joined with `OR` and grouped in parentheses
+    Boolean,
+}
+
 fn get_first(s: &str) -> &str {
     match s.chars().next() {
         Some(c) => &s[..c.len_utf8()],
@@ -312,13 +495,16 @@ impl<'a> SearchForFacetValues<'a> {
                 let query = query.normalize(&options);
                 let query = query.as_ref();
 
-                let authorize_typos = self.search_query.index.authorize_typos(rtxn)?;
-                let field_authorizes_typos =
-                    !self.search_query.index.exact_attributes_ids(rtxn)?.contains(&fid);
+                let typo_tolerance =
+                    TypoTolerance::new(index, rtxn, self.search_query.typo_tolerance.as_ref())?;
+                let field_authorizes_typos = match &typo_tolerance.exact_attributes {
+                    Some(exact_attributes) => !exact_attributes.contains(&fid),
+                    None => !index.exact_attributes_ids(rtxn)?.contains(&fid),
+                };
 
-                if authorize_typos && field_authorizes_typos {
-                    let exact_words_fst = self.search_query.index.exact_words(rtxn)?;
-                    if exact_words_fst.map_or(false, |fst| fst.contains(query)) {
+                if typo_tolerance.authorize_typos && field_authorizes_typos {
+                    let exact_words_fst = &typo_tolerance.exact_words;
+                    if exact_words_fst.as_ref().map_or(false, |fst| fst.contains(query)) {
                         let mut results = vec![];
                         if fst.contains(query) {
                             self.fetch_original_facets_using_normalized(
@@ -331,8 +517,8 @@ impl<'a> SearchForFacetValues<'a> {
                         }
                         Ok(results)
                     } else {
-                        let one_typo = self.search_query.index.min_word_len_one_typo(rtxn)?;
-                        let two_typos = self.search_query.index.min_word_len_two_typos(rtxn)?;
+                        let one_typo = typo_tolerance.min_word_len_one_typo;
+                        let two_typos = typo_tolerance.min_word_len_two_typos;
 
                         let is_prefix = true;
                         let automaton = if query.len() < one_typo as usize {
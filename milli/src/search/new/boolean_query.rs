@@ -0,0 +1,377 @@
+//! The boolean syntax of the search queries.
+//!
+//! The words, quoted phrases and groups in parentheses of a boolean query can be:
+//! - required with a leading `+`, they are never removed by the terms matching strategy,
+//! - excluded with a leading `-`, the documents containing them are removed from the results,
+//! - joined with the `OR` operator, any of them can match at this location of the query.
+//!
+//! Inside a group, the `OR` operator separates the alternatives of the group,
+//! e.g. `(red car OR truck)`. The groups cannot be nested.
+
+use std::ops::Range;
+
+use charabia::Tokenizer;
+use roaring::RoaringBitmap;
+
+use super::query_term::{
+    located_query_terms_from_fragment, ExactTerm, QueryClause, QueryTermSubset,
+};
+use super::{SearchContext, Word};
+use crate::Result;
+
+/// The operator joining the alternatives of a query.
+const OR_OPERATOR: &str = "OR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+    Required,
+    Excluded,
+}
+
+/// A word, quoted phrase or group of the query.
+struct Unit {
+    modifier: Option<Modifier>,
+    /// The byte ranges of the alternatives of the unit in the query,
+    /// a single one unless the unit is a group containing the `OR` operator.
+    alternatives: Vec<Range<usize>>,
+    /// `true` if the unit is a single word or phrase.
+    is_plain: bool,
+}
+
+enum Lexeme {
+    Unit(Unit),
+    Or,
+}
+
+/// A clause of the query, the byte ranges of its alternatives in the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BooleanClause {
+    alternatives: Vec<Range<usize>>,
+    required: bool,
+}
+
+/// A search query written with the boolean syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BooleanQuery<'q> {
+    query: &'q str,
+    clauses: Vec<BooleanClause>,
+    /// The byte ranges in the query of the excluded words, phrases and groups.
+    excluded: Vec<Range<usize>>,
+}
+
+impl<'q> BooleanQuery<'q> {
+    pub fn parse(query: &'q str) -> BooleanQuery<'q> {
+        let mut units: Vec<Unit> = Vec::new();
+        let mut join_next = false;
+        for lexeme in lex(query) {
+            match lexeme {
+                Lexeme::Or => join_next = !units.is_empty(),
+                Lexeme::Unit(unit) => {
+                    match units.last_mut() {
+                        Some(last) if join_next => {
+                            last.alternatives.extend(unit.alternatives);
+                            last.is_plain = false;
+                        }
+                        _ => units.push(unit),
+                    }
+                    join_next = false;
+                }
+            }
+        }
+
+        let mut clauses: Vec<BooleanClause> = Vec::new();
+        let mut excluded = Vec::new();
+        // the consecutive plain words and phrases form a single clause, like in a plain query.
+        let mut extend_last_clause = false;
+        for Unit { modifier, alternatives, is_plain } in units {
+            match modifier {
+                Some(Modifier::Excluded) => {
+                    excluded.extend(alternatives);
+                    extend_last_clause = false;
+                }
+                Some(Modifier::Required) => {
+                    clauses.push(BooleanClause { alternatives, required: true });
+                    extend_last_clause = false;
+                }
+                None => {
+                    match clauses.last_mut() {
+                        Some(last) if extend_last_clause && is_plain => {
+                            last.alternatives[0].end = alternatives[0].end;
+                        }
+                        _ => clauses.push(BooleanClause { alternatives, required: false }),
+                    }
+                    extend_last_clause = is_plain;
+                }
+            }
+        }
+
+        BooleanQuery { query, clauses, excluded }
+    }
+
+    /// Returns the located query terms of the clauses of the query.
+    ///
+    /// The positions of the terms of a clause follow the ones of the previous clause,
+    /// the alternatives of a clause start at the same position.
+    pub fn located_query_clauses(
+        &self,
+        ctx: &mut SearchContext,
+        tokenizer: &Tokenizer,
+        words_limit: Option<usize>,
+    ) -> Result<Vec<QueryClause>> {
+        let mut clauses = Vec::new();
+        let mut words_count = 0;
+        let mut position = 0u16;
+        for clause in &self.clauses {
+            let words_limit = words_limit.map(|limit| limit.saturating_sub(words_count));
+            if words_limit == Some(0) {
+                break;
+            }
+
+            let mut alternatives = Vec::new();
+            let mut next_position = position;
+            for range in &clause.alternatives {
+                let tokens = tokenizer.tokenize(&self.query[range.clone()]);
+                let is_prefix = range.end == self.query.len();
+                let mut terms =
+                    located_query_terms_from_fragment(ctx, tokens, words_limit, is_prefix)?;
+                for term in &mut terms {
+                    let start = term.positions.start().saturating_add(position);
+                    let end = term.positions.end().saturating_add(position);
+                    term.positions = start..=end;
+                    next_position = next_position.max(end.saturating_add(1));
+                }
+                if !terms.is_empty() {
+                    alternatives.push(terms);
+                }
+            }
+
+            if let Some(len) = alternatives.iter().map(Vec::len).max() {
+                words_count += len;
+                clauses.push(QueryClause { alternatives, required: clause.required });
+            }
+            position = next_position;
+        }
+
+        Ok(clauses)
+    }
+
+    /// Returns the documents containing any of the excluded words, phrases or groups,
+    /// a group being contained when all its words are.
+    ///
+    /// The excluded words are matched without typos.
+    pub fn excluded_docids(
+        &self,
+        ctx: &mut SearchContext,
+        tokenizer: &Tokenizer,
+    ) -> Result<RoaringBitmap> {
+        let mut excluded = RoaringBitmap::new();
+        for range in &self.excluded {
+            let tokens = tokenizer.tokenize(&self.query[range.clone()]);
+            let terms = located_query_terms_from_fragment(ctx, tokens, None, false)?;
+
+            let mut docids: Option<RoaringBitmap> = None;
+            for term in terms {
+                let term_docids = match QueryTermSubset::full(term.value).exact_term(ctx) {
+                    Some(ExactTerm::Word(word)) => {
+                        ctx.word_docids(Word::Original(word))?.unwrap_or_default()
+                    }
+                    Some(ExactTerm::Phrase(phrase)) => ctx.get_phrase_docids(phrase)?.clone(),
+                    None => RoaringBitmap::new(),
+                };
+                docids = Some(match docids {
+                    Some(docids) => docids & term_docids,
+                    None => term_docids,
+                });
+            }
+            excluded |= docids.unwrap_or_default();
+        }
+
+        Ok(excluded)
+    }
+}
+
+/// Splits the query into its operators and units, the syntax characters being ASCII
+/// the byte ranges of the units are always on char boundaries.
+fn lex(query: &str) -> Vec<Lexeme> {
+    let bytes = query.as_bytes();
+    let find_from = |from: usize, predicate: &dyn Fn(u8) -> bool| {
+        bytes[from..].iter().position(|&b| predicate(b)).map_or(bytes.len(), |i| from + i)
+    };
+
+    let mut lexemes = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() || bytes[i] == b')' {
+            i += 1;
+            continue;
+        }
+
+        // a modifier must be directly followed by the unit it applies to
+        let modifier = match bytes[i] {
+            b'+' => Some(Modifier::Required),
+            b'-' => Some(Modifier::Excluded),
+            _ => None,
+        };
+        let modifier = modifier
+            .filter(|_| bytes.get(i + 1).map_or(false, |&b| !b.is_ascii_whitespace() && b != b')'));
+        let start = if modifier.is_some() { i + 1 } else { i };
+
+        let unit = match bytes[start] {
+            b'"' => {
+                let end = find_from(start + 1, &|b| b == b'"');
+                i = (end + 1).min(bytes.len());
+                Unit { modifier, alternatives: vec![start..i], is_plain: true }
+            }
+            b'(' => {
+                let end = find_from(start + 1, &|b| b == b')');
+                i = (end + 1).min(bytes.len());
+                let alternatives = split_alternatives(query, start + 1..end);
+                Unit { modifier, alternatives, is_plain: false }
+            }
+            _ => {
+                let end = find_from(start, &|b| {
+                    b.is_ascii_whitespace() || matches!(b, b'(' | b')' | b'"')
+                });
+                i = end;
+                if modifier.is_none() && &query[start..end] == OR_OPERATOR {
+                    lexemes.push(Lexeme::Or);
+                    continue;
+                }
+                Unit { modifier, alternatives: vec![start..end], is_plain: true }
+            }
+        };
+
+        if !unit.alternatives.is_empty() {
+            lexemes.push(Lexeme::Unit(unit));
+        }
+    }
+
+    lexemes
+}
+
+/// Splits the content of a group on the `OR` operators outside of its phrases.
+fn split_alternatives(query: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut alternatives = Vec::new();
+    let mut alternative_start = range.start;
+    let mut word_start = range.start;
+    let mut in_phrase = false;
+    for word in query[range.clone()].split(|c: char| c.is_ascii_whitespace()) {
+        if !in_phrase && word == OR_OPERATOR {
+            alternatives.push(alternative_start..word_start);
+            alternative_start = word_start + word.len();
+        }
+        if word.matches('"').count() % 2 == 1 {
+            in_phrase = !in_phrase;
+        }
+        word_start += word.len() + 1;
+    }
+    alternatives.push(alternative_start..range.end);
+
+    alternatives.retain(|alternative| !query[alternative.clone()].trim().is_empty());
+    alternatives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the texts of the clauses, their alternatives separated by `|`
+    /// and prefixed by `+` when they are required, and the texts of the excluded units.
+    fn parse(query: &str) -> (Vec<String>, Vec<&str>) {
+        let BooleanQuery { query, clauses, excluded } = BooleanQuery::parse(query);
+        let clauses = clauses
+            .into_iter()
+            .map(|BooleanClause { alternatives, required }| {
+                let alternatives: Vec<_> = alternatives.into_iter().map(|r| &query[r]).collect();
+                let prefix = if required { "+" } else { "" };
+                format!("{prefix}{}", alternatives.join("|"))
+            })
+            .collect();
+        let excluded = excluded.into_iter().map(|r| &query[r]).collect();
+        (clauses, excluded)
+    }
+
+    #[test]
+    fn parse_plain_query() {
+        insta::assert_debug_snapshot!(parse("the quick, \"brown fox\" t-shirt"), @r###"
+        (
+            [
+                "the quick, \"brown fox\" t-shirt",
+            ],
+            [],
+        )
+        "###);
+        insta::assert_debug_snapshot!(parse(""), @r###"
+        (
+            [],
+            [],
+        )
+        "###);
+    }
+
+    #[test]
+    fn parse_modifiers() {
+        insta::assert_debug_snapshot!(parse("jaguar -car +\"big cat\" speed - + fast"), @r###"
+        (
+            [
+                "jaguar",
+                "+\"big cat\"",
+                "speed - + fast",
+            ],
+            [
+                "car",
+            ],
+        )
+        "###);
+        insta::assert_debug_snapshot!(parse("jaguar -(car OR suv) +(wild cat)"), @r###"
+        (
+            [
+                "jaguar",
+                "+wild cat",
+            ],
+            [
+                "car ",
+                " suv",
+            ],
+        )
+        "###);
+    }
+
+    #[test]
+    fn parse_or_operator() {
+        insta::assert_debug_snapshot!(parse("jaguar car OR cat speed"), @r###"
+        (
+            [
+                "jaguar",
+                "car|cat",
+                "speed",
+            ],
+            [],
+        )
+        "###);
+        insta::assert_debug_snapshot!(parse("(red car OR \"big OR small\" truck) OR bike"), @r###"
+        (
+            [
+                "red car | \"big OR small\" truck|bike",
+            ],
+            [],
+        )
+        "###);
+        insta::assert_debug_snapshot!(parse("OR car OR OR"), @r###"
+        (
+            [
+                "car",
+            ],
+            [],
+        )
+        "###);
+        insta::assert_debug_snapshot!(parse("(car OR ca"), @r###"
+        (
+            [
+                "car | ca",
+            ],
+            [],
+        )
+        "###);
+    }
+}
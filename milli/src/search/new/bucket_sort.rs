@@ -3,7 +3,7 @@ use roaring::RoaringBitmap;
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
 use crate::Result;
 
@@ -215,6 +215,43 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     Ok(BucketSortOutput { docids: valid_docids, scores: valid_scores, all_candidates })
 }
 
+/// Places the pinned documents at their positions among the ranked documents
+/// and returns the requested page of the results.
+///
+/// The ranked documents must be the first `from + length` documents returned by the bucket sort.
+/// The documents pinned after the last ranked document directly follow it.
+pub fn pin_documents(
+    output: BucketSortOutput,
+    pinned: &[(usize, u32)],
+    from: usize,
+    length: usize,
+) -> BucketSortOutput {
+    let BucketSortOutput { docids, scores, mut all_candidates } = output;
+    all_candidates.extend(pinned.iter().map(|(_, docid)| *docid));
+
+    let pinned_score = || vec![ScoreDetails::QueryRule(score_details::QueryRule::Pinned)];
+    let mut ranked = docids.into_iter().zip(scores);
+    let mut pinned = pinned.iter().peekable();
+
+    let mut valid_docids = Vec::new();
+    let mut valid_scores = Vec::new();
+    for position in 0..from.saturating_add(length) {
+        let next = match pinned.next_if(|(pinned_position, _)| *pinned_position <= position) {
+            Some((_, docid)) => Some((*docid, pinned_score())),
+            None => {
+                ranked.next().or_else(|| pinned.next().map(|(_, docid)| (*docid, pinned_score())))
+            }
+        };
+        let Some((docid, score)) = next else { break };
+        if position >= from {
+            valid_docids.push(docid);
+            valid_scores.push(score);
+        }
+    }
+
+    BucketSortOutput { docids: valid_docids, scores: valid_scores, all_candidates }
+}
+
 /// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
 /// into account and inform the logger.
 #[allow(clippy::too_many_arguments)]
@@ -30,6 +30,7 @@ pub struct DatabaseCache<'ctx> {
         FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
     pub word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub exact_word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
+    pub typo_word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub word_prefix_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub exact_word_prefix_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
 
@@ -148,10 +149,30 @@ impl<'ctx> SearchContext<'ctx> {
                     }
                 })
             }
-            Word::Derived(word) => self.get_db_word_docids(word),
+            Word::Derived(word) => match self.typo_fids {
+                Some(_) => self.get_db_typo_word_docids(word),
+                None => self.get_db_word_docids(word),
+            },
         }
     }
 
+    /// Retrieve or insert the docids of the given word in the fields allowing typos,
+    /// only used when the exact attributes are overridden for this search.
+    fn get_db_typo_word_docids(&mut self, word: Interned<String>) -> Result<Option<RoaringBitmap>> {
+        let typo_fids = self.typo_fids.as_deref().unwrap_or_default();
+        let interned = self.word_interner.get(word).as_str();
+        let keys: Vec<_> = typo_fids.iter().map(|fid| (interned, *fid)).collect();
+
+        DatabaseCache::get_value_from_keys::<_, _, CboRoaringBitmapCodec>(
+            self.txn,
+            word,
+            &keys[..],
+            &mut self.db_cache.typo_word_docids,
+            self.index.word_fid_docids.remap_data_type::<ByteSlice>(),
+            merge_cbo_roaring_bitmaps,
+        )
+    }
+
     /// Retrieve or insert the given value in the `word_docids` database.
     fn get_db_word_docids(&mut self, word: Interned<String>) -> Result<Option<RoaringBitmap>> {
         match &self.restricted_fids {
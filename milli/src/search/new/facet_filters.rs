@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use roaring::RoaringBitmap;
+
+use super::SearchContext;
+use crate::{DocumentId, Filter, Result};
+
+/// The facet-scoped filters of a search, each one evaluated once.
+///
+/// They restrict the results like the filter of the search, but the candidates of a facet,
+/// used to compute its distribution, ignore the filters scoped to this facet.
+#[derive(Debug, Default)]
+pub struct FacetFilters {
+    /// The facets along with the documents matching the filters scoped to them.
+    filters: Vec<(String, RoaringBitmap)>,
+}
+
+impl FacetFilters {
+    pub fn new(ctx: &SearchContext, filters: &[(String, Filter)]) -> Result<Self> {
+        let filters = filters
+            .iter()
+            .map(|(facet, filter)| Ok((facet.clone(), filter.evaluate(ctx.txn, ctx.index)?)))
+            .collect::<Result<_>>()?;
+        Ok(FacetFilters { filters })
+    }
+
+    /// Returns `true` if the document matches all the facet-scoped filters.
+    pub fn contains(&self, docid: DocumentId) -> bool {
+        self.filters.iter().all(|(_, filter)| filter.contains(docid))
+    }
+
+    /// Removes the documents not matching all the facet-scoped filters.
+    pub fn restrict(&self, docids: &mut RoaringBitmap) {
+        for (_, filter) in &self.filters {
+            *docids &= filter;
+        }
+    }
+
+    /// Returns, for each facet, the documents matching the filters scoped to the other facets.
+    pub fn facet_candidates(&self, docids: &RoaringBitmap) -> BTreeMap<String, RoaringBitmap> {
+        let mut candidates = BTreeMap::new();
+        for (facet, _) in &self.filters {
+            if !candidates.contains_key(facet) {
+                let facet_candidates = self
+                    .filters
+                    .iter()
+                    .filter(|(other, _)| other != facet)
+                    .fold(docids.clone(), |docids, (_, filter)| docids & filter);
+                candidates.insert(facet.clone(), facet_candidates);
+            }
+        }
+        candidates
+    }
+}
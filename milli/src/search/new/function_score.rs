@@ -0,0 +1,135 @@
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::ByteSliceRefCodec;
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::search::facet::{ascending_facet_sort, get_last_facet_value};
+use crate::{FieldId, FunctionScore, Index, Result};
+
+/// Ranks the documents by the bucket of the score computed from the numeric values of a field.
+///
+/// The documents with several values are ranked with their best value, the documents
+/// without any numeric value are part of the last bucket.
+pub struct FunctionScoreRule<Query> {
+    function_score: FunctionScore,
+    field_id: Option<FieldId>,
+    must_redact: bool,
+    original_query: Option<Query>,
+    /// The documents of each bucket, the best bucket is the last one.
+    buckets: Vec<RoaringBitmap>,
+}
+
+impl<Query> FunctionScoreRule<Query> {
+    pub fn new(index: &Index, rtxn: &heed::RoTxn, function_score: FunctionScore) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&function_score.field);
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.contains(&function_score.field.as_str()),
+            None => false,
+        };
+
+        Ok(Self {
+            function_score,
+            field_id,
+            must_redact,
+            original_query: None,
+            buckets: Vec::new(),
+        })
+    }
+
+    fn output(
+        &self,
+        query: Query,
+        candidates: RoaringBitmap,
+        rank: u32,
+    ) -> RankingRuleOutput<Query> {
+        RankingRuleOutput {
+            query,
+            candidates,
+            score: ScoreDetails::FunctionScore(score_details::FunctionScore {
+                function_score: self.function_score.clone(),
+                redacted: self.must_redact,
+                rank: Rank { rank, max_rank: self.function_score.buckets },
+            }),
+        }
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for FunctionScoreRule<Query> {
+    fn id(&self) -> String {
+        self.function_score.to_string()
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let mut buckets = vec![RoaringBitmap::new(); self.function_score.buckets as usize];
+
+        if let Some(field_id) = self.field_id {
+            let number_db = ctx
+                .index
+                .facet_id_f64_docids
+                .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+
+            // the scores are relative to the highest value of the whole index and
+            // not of the candidates, so that they do not depend on the query.
+            let max_value = get_last_facet_value::<OrderedF64Codec>(ctx.txn, number_db, field_id)?
+                .unwrap_or_default();
+
+            let iter =
+                ascending_facet_sort(ctx.txn, number_db, field_id, parent_candidates.clone())?;
+            for result in iter {
+                let (docids, bytes) = result?;
+                let value = OrderedF64Codec::bytes_decode(bytes).expect("some number");
+                let bucket = self.function_score.bucket(value, max_value);
+                buckets[bucket as usize] |= docids;
+            }
+        }
+
+        self.original_query = Some(parent_query.clone());
+        self.buckets = buckets;
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+
+        // the documents that were already returned are not part of the universe anymore,
+        // the documents with several values are thus only returned in their best bucket.
+        while let Some(docids) = self.buckets.pop() {
+            let rank = self.buckets.len() as u32 + 1;
+            if self.buckets.is_empty() {
+                // the last bucket also contains the documents without values
+                return Ok(Some(self.output(query, universe.clone(), rank)));
+            }
+
+            let candidates = docids & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(self.output(query, candidates, rank)));
+            }
+        }
+
+        Ok(Some(self.output(query, universe.clone(), 1)))
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = Vec::new();
+    }
+}
@@ -7,6 +7,10 @@ pub const MAX_PREFIX_COUNT: usize = 1_000;
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
 pub const MAX_TWO_TYPOS_COUNT: usize = 50;
+/// Maximum number of words sharing a stem that can be derived from a single word.
+pub const MAX_STEM_COUNT: usize = 50;
+/// Maximum number of words sounding alike that can be derived from a single word.
+pub const MAX_PHONETIC_COUNT: usize = 50;
 
 /// Maximum amount of synonym phrases that can be derived from a single word.
 pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
@@ -507,12 +507,18 @@ mod tests {
                 &mut ctx,
                 &Some(query.to_string()),
                 &None,
+                None,
                 crate::TermsMatchingStrategy::default(),
+                crate::QuerySyntax::default(),
                 crate::score_details::ScoringStrategy::Skip,
                 false,
                 &None,
+                &[],
                 &None,
+                &None,
+                None,
                 crate::search::new::GeoSortStrategy::default(),
+                crate::search::new::VectorSearchStrategy::default(),
                 0,
                 100,
                 Some(10),
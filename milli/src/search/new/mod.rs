@@ -1,39 +1,50 @@
+mod boolean_query;
 mod bucket_sort;
 mod db_cache;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
 mod interner;
-mod limits;
+pub(crate) mod limits;
 mod logger;
 pub mod matches;
 mod query_graph;
+mod query_rules;
 mod query_term;
 mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
+mod vector_search;
+mod vector_sort;
 
 mod exact_attribute;
+mod facet_filters;
+mod function_score;
 mod sort;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use boolean_query::BooleanQuery;
+use bucket_sort::{bucket_sort, pin_documents, BucketSortOutput};
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
+use facet_filters::FacetFilters;
+use function_score::FunctionScoreRule;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
-use instant_distance::Search;
 use interner::{DedupInterner, Interner};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
-use query_term::{located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryTerm};
+use query_rules::AppliedQueryRules;
+use query_term::{
+    located_query_terms_from_tokens, LocatedQueryTerm, Phrase, QueryClause, QueryTerm,
+};
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
@@ -46,12 +57,18 @@ use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
-use crate::distance::NDotProductPoint;
+use self::vector_search::vector_search;
+pub use self::vector_search::Strategy as VectorSearchStrategy;
+use self::vector_sort::VectorSort;
+use crate::code_identifiers::code_identifiers;
 use crate::error::FieldIdMapMissingEntry;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::localized_attributes_rules::{self, parse_locale};
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
+use crate::search::{TypoTolerance, TypoToleranceOverride};
 use crate::{
-    AscDesc, DocumentId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError, BEU32,
+    AscDesc, Criterion, DocumentId, Filter, Index, Member, QuerySyntax, Result,
+    TermsMatchingStrategy, UserError,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -64,6 +81,11 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<Vec<u16>>,
+    /// The typo tolerance settings overridden for this search, if any.
+    pub typo_tolerance: Option<TypoTolerance<'ctx>>,
+    /// The fields in which the words derived from the query are searched,
+    /// only defined when the exact attributes are overridden for this search.
+    pub typo_fids: Option<Vec<u16>>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -77,6 +99,8 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            typo_tolerance: None,
+            typo_fids: None,
         }
     }
 
@@ -130,6 +154,32 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Overrides the typo tolerance settings of the index for this search,
+    /// must be called once the searchable attributes are restricted.
+    pub fn typo_tolerance(&mut self, typo_tolerance: &TypoToleranceOverride) -> Result<()> {
+        let typo_tolerance = TypoTolerance::new(self.index, self.txn, Some(typo_tolerance))?;
+
+        // the exact word databases are computed with the exact attributes of the index,
+        // the derived words are thus only searched in the fields allowing typos.
+        self.typo_fids = if let Some(exact_attributes) = &typo_tolerance.exact_attributes {
+            let searchable_fids = match &self.restricted_fids {
+                Some(restricted_fids) => restricted_fids.clone(),
+                None => match self.index.searchable_fields_ids(self.txn)? {
+                    Some(searchable_fids) => searchable_fids,
+                    None => self.index.fields_ids_map(self.txn)?.ids().collect(),
+                },
+            };
+            let typo_fids =
+                searchable_fids.into_iter().filter(|fid| !exact_attributes.contains(fid)).collect();
+            Some(typo_fids)
+        } else {
+            None
+        };
+        self.typo_tolerance = Some(typo_tolerance);
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -189,17 +239,30 @@ fn resolve_universe(
     )
 }
 
+/// Return the ranking rules given at search time, or the ranking rules of the index otherwise.
+fn ranking_rules_or_settings(
+    ctx: &SearchContext,
+    query_ranking_rules: &Option<Vec<Criterion>>,
+) -> Result<Vec<Criterion>> {
+    match query_ranking_rules {
+        Some(ranking_rules) => Ok(ranking_rules.clone()),
+        None => ctx.index.criteria(ctx.txn),
+    }
+}
+
 /// Return the list of initialised ranking rules to be used for a placeholder search.
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    query_ranking_rules: &Option<Vec<Criterion>>,
     geo_strategy: geo_sort::Strategy,
+    vector_strategy: vector_search::Strategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ranking_rules_or_settings(ctx, query_ranking_rules)?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
@@ -219,6 +282,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                     &mut sorted_fields,
                     &mut geo_sorted,
                     geo_strategy,
+                    vector_strategy,
                 )?;
                 sort = true;
             }
@@ -236,6 +300,13 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::FunctionScore(function_score) => {
+                ranking_rules.push(Box::new(FunctionScoreRule::new(
+                    ctx.index,
+                    ctx.txn,
+                    function_score,
+                )?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -245,7 +316,9 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
 fn get_ranking_rules_for_query_graph_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    query_ranking_rules: &Option<Vec<Criterion>>,
     geo_strategy: geo_sort::Strategy,
+    vector_strategy: vector_search::Strategy,
     terms_matching_strategy: TermsMatchingStrategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
     // query graph search
@@ -264,7 +337,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ranking_rules_or_settings(ctx, query_ranking_rules)?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
@@ -320,6 +393,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                     &mut sorted_fields,
                     &mut geo_sorted,
                     geo_strategy,
+                    vector_strategy,
                 )?;
                 sort = true;
             }
@@ -345,6 +419,13 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::FunctionScore(function_score) => {
+                ranking_rules.push(Box::new(FunctionScoreRule::new(
+                    ctx.index,
+                    ctx.txn,
+                    function_score,
+                )?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -357,6 +438,7 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
     sorted_fields: &mut HashSet<String>,
     geo_sorted: &mut bool,
     geo_strategy: geo_sort::Strategy,
+    vector_strategy: vector_search::Strategy,
 ) -> Result<()> {
     let sort_criteria = sort_criteria.clone().unwrap_or_default();
     ranking_rules.reserve(sort_criteria.len());
@@ -400,22 +482,76 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     false,
                 )?));
             }
+            AscDesc::Asc(Member::Vector { space, vector }) => {
+                ranking_rules.push(Box::new(VectorSort::new(
+                    ctx,
+                    vector_strategy,
+                    space,
+                    vector,
+                    true,
+                )?));
+            }
+            AscDesc::Desc(Member::Vector { space, vector }) => {
+                ranking_rules.push(Box::new(VectorSort::new(
+                    ctx,
+                    vector_strategy,
+                    space,
+                    vector,
+                    false,
+                )?));
+            }
         };
     }
     Ok(())
 }
 
+/// Returns the locales of the query when all the searchable attributes are localized,
+/// an empty list otherwise.
+fn localized_query_languages(ctx: &SearchContext) -> Result<Vec<Language>> {
+    let localized_fields = ctx.index.localized_fields_ids(ctx.txn)?;
+    if localized_fields.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let searchable_fields = match ctx.index.searchable_fields_ids(ctx.txn)? {
+        Some(searchable_fields) => searchable_fields,
+        None => ctx.index.fields_ids_map(ctx.txn)?.ids().collect(),
+    };
+
+    let mut languages = Vec::new();
+    for field_id in searchable_fields {
+        match localized_fields.get(&field_id) {
+            Some(field_languages) => {
+                for language in field_languages {
+                    if !languages.contains(language) {
+                        languages.push(*language);
+                    }
+                }
+            }
+            None => return Ok(Vec::new()),
+        }
+    }
+
+    Ok(languages)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_search(
     ctx: &mut SearchContext,
     query: &Option<String>,
     vector: &Option<Vec<f32>>,
+    vector_space: Option<&str>,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
     scoring_strategy: ScoringStrategy,
     exhaustive_number_hits: bool,
     filters: &Option<Filter>,
+    facet_filters: &[(String, Filter)],
     sort_criteria: &Option<Vec<AscDesc>>,
+    query_ranking_rules: &Option<Vec<Criterion>>,
+    locales: Option<&[String]>,
     geo_strategy: geo_sort::Strategy,
+    vector_strategy: vector_search::Strategy,
     from: usize,
     length: usize,
     words_limit: Option<usize>,
@@ -428,45 +564,112 @@ pub fn execute_search(
         ctx.index.documents_ids(ctx.txn)?
     };
 
-    check_sort_criteria(ctx, sort_criteria.as_ref())?;
+    check_sort_criteria(ctx, sort_criteria.as_ref(), query_ranking_rules)?;
+    check_query_ranking_rules(ctx, query_ranking_rules.as_ref())?;
+
+    // the query rules applied to the search hide documents and add filters to the search.
+    let query_rules = AppliedQueryRules::new(ctx, query.as_deref(), filters.as_ref())?;
+    query_rules.restrict_universe(ctx, &mut universe)?;
+
+    // the facet-scoped filters are applied to the universe once the query is resolved,
+    // the candidates of their facets being computed from the universe ignoring them.
+    let facet_filters = FacetFilters::new(ctx, facet_filters)?;
+
+    // the pinned documents are not ranked but placed among the first ranked documents.
+    let mut pinned = query_rules.pinned_documents(ctx, &universe)?;
+    pinned.retain(|(_, docid)| facet_filters.contains(*docid));
+    for (_, docid) in &pinned {
+        universe.remove(*docid);
+    }
+    let (ranked_from, ranked_length) =
+        if pinned.is_empty() { (from, length) } else { (0, from.saturating_add(length)) };
 
     if let Some(vector) = vector {
-        let mut search = Search::default();
-        let docids = match ctx.index.vector_hnsw(ctx.txn)? {
-            Some(hnsw) => {
-                let vector = NDotProductPoint::new(vector.clone());
-                let neighbors = hnsw.search(&vector, &mut search);
-
-                let mut docids = Vec::new();
-                let mut uniq_docids = RoaringBitmap::new();
-                for instant_distance::Item { distance: _, pid, point: _ } in neighbors {
-                    let index = BEU32::new(pid.into_inner());
-                    let docid = ctx.index.vector_id_docid.get(ctx.txn, &index)?.unwrap().get();
-                    if universe.contains(docid) && uniq_docids.insert(docid) {
-                        docids.push(docid);
-                        if docids.len() == (from + length) {
-                            break;
-                        }
-                    }
-                }
+        let mut facet_candidates = facet_filters.facet_candidates(&universe);
+        facet_filters.restrict(&mut universe);
+
+        let vector_scores = |boosted: Option<bool>, similarity: f32| {
+            let vector = ScoreDetails::Vector(score_details::Vector { similarity });
+            match boosted {
+                Some(boosted) => vec![
+                    ScoreDetails::QueryRule(score_details::QueryRule::Boost { boosted }),
+                    vector,
+                ],
+                None => vec![vector],
+            }
+        };
 
-                // return the nearest documents that are also part of the candidates
-                // along with a dummy list of scores that are useless in this context.
-                docids.into_iter().skip(from).take(length).collect()
+        // the boosted documents are ranked by similarity before the other documents.
+        let ranked: Vec<_> = match query_rules.boosted_documents(ctx)? {
+            Some(boosted) => {
+                let count = ranked_from.saturating_add(ranked_length);
+                let boosted_universe = &universe & &boosted;
+                let other_universe = &universe - &boosted;
+                let mut ranked: Vec<_> = vector_search(
+                    ctx,
+                    vector,
+                    vector_space,
+                    &boosted_universe,
+                    vector_strategy,
+                    0,
+                    count,
+                )?
+                .into_iter()
+                .map(|(docid, similarity)| (docid, vector_scores(Some(true), similarity)))
+                .collect();
+                if ranked.len() < count {
+                    let others = vector_search(
+                        ctx,
+                        vector,
+                        vector_space,
+                        &other_universe,
+                        vector_strategy,
+                        0,
+                        count - ranked.len(),
+                    )?;
+                    ranked.extend(others.into_iter().map(|(docid, similarity)| {
+                        (docid, vector_scores(Some(false), similarity))
+                    }));
+                }
+                ranked.into_iter().skip(ranked_from).take(ranked_length).collect()
             }
-            None => Vec::new(),
+            None => vector_search(
+                ctx,
+                vector,
+                vector_space,
+                &universe,
+                vector_strategy,
+                ranked_from,
+                ranked_length,
+            )?
+            .into_iter()
+            .map(|(docid, similarity)| (docid, vector_scores(None, similarity)))
+            .collect(),
         };
 
+        let (docids, scores) = ranked.into_iter().unzip();
+        let output = BucketSortOutput { docids, scores, all_candidates: universe };
+        let BucketSortOutput { docids, scores, all_candidates } = match pinned.is_empty() {
+            true => output,
+            false => pin_documents(output, &pinned, from, length),
+        };
+        // the pinned documents match all the facet-scoped filters.
+        for candidates in facet_candidates.values_mut() {
+            candidates.extend(pinned.iter().map(|(_, docid)| *docid));
+        }
+
         return Ok(PartialSearchResult {
-            candidates: universe,
-            document_scores: vec![Vec::new(); docids.len()],
+            candidates: all_candidates,
+            facet_candidates,
+            document_scores: scores,
             documents_ids: docids,
             located_query_terms: None,
         });
     }
 
     let mut located_query_terms = None;
-    let query_terms = if let Some(query) = query {
+    let mut facet_candidates;
+    let query_clauses = if let Some(query) = query {
         // We make sure that the analyzer is aware of the stop words
         // this ensures that the query builder is able to properly remove them.
         let mut tokbuilder = TokenizerBuilder::new();
@@ -483,68 +686,126 @@ pub fn execute_search(
         }
 
         let dictionary = ctx.index.dictionary(ctx.txn)?;
-        let dictionary: Option<Vec<_>> =
+        let mut dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        // the identifiers of the query are kept whole to match those of the code attributes.
+        if !ctx.index.code_attributes(ctx.txn)?.is_empty() {
+            let identifiers = code_identifiers(query);
+            if !identifiers.is_empty() {
+                dictionary.get_or_insert_with(Vec::new).extend(identifiers);
+            }
+        }
         if let Some(ref dictionary) = dictionary {
             tokbuilder.words_dict(dictionary);
         }
 
+        let languages = match locales {
+            Some(locales) => locales
+                .iter()
+                .map(|locale| {
+                    parse_locale(locale)
+                        .ok_or_else(|| UserError::InvalidSearchLocale(locale.clone()))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+            None => localized_query_languages(ctx)?,
+        };
+
+        // the forced locales take precedence over the languages detected in the documents.
+        let locales_allow_list;
         let script_lang_map = ctx.index.script_language(ctx.txn)?;
-        if !script_lang_map.is_empty() {
+        if !languages.is_empty() {
+            locales_allow_list = localized_attributes_rules::locales_allow_list(&languages);
+            tokbuilder.allow_list(&locales_allow_list);
+        } else if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
 
         let tokenizer = tokbuilder.build();
-        let tokens = tokenizer.tokenize(query);
-
-        let query_terms = located_query_terms_from_tokens(ctx, tokens, words_limit)?;
-        if query_terms.is_empty() {
+        let query_clauses: Vec<QueryClause> = match query_syntax {
+            QuerySyntax::Plain => {
+                let tokens = tokenizer.tokenize(query);
+                let query_terms = located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+                query_terms.into_iter().map(QueryClause::from).collect()
+            }
+            QuerySyntax::Boolean => {
+                let boolean_query = BooleanQuery::parse(query);
+                universe -= boolean_query.excluded_docids(ctx, &tokenizer)?;
+                boolean_query.located_query_clauses(ctx, &tokenizer, words_limit)?
+            }
+        };
+        if query_clauses.is_empty() {
             // Do a placeholder search instead
             None
         } else {
-            Some(query_terms)
+            Some(query_clauses)
         }
     } else {
         None
     };
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
-        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
+    let bucket_sort_output = if let Some(query_clauses) = query_clauses {
+        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_clauses)?;
         located_query_terms = Some(new_located_query_terms);
 
-        let ranking_rules = get_ranking_rules_for_query_graph_search(
+        let mut ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
+            query_ranking_rules,
             geo_strategy,
+            vector_strategy,
             terms_matching_strategy,
         )?;
+        if let Some(boost) = query_rules.boost_rule::<QueryGraph>(ctx)? {
+            ranking_rules.insert(0, Box::new(boost));
+        }
 
         universe =
             resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
+        facet_candidates = facet_filters.facet_candidates(&universe);
+        facet_filters.restrict(&mut universe);
 
         bucket_sort(
             ctx,
             ranking_rules,
             &graph,
             &universe,
-            from,
-            length,
+            ranked_from,
+            ranked_length,
             scoring_strategy,
             query_graph_logger,
         )?
     } else {
-        let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
+        let mut ranking_rules = get_ranking_rules_for_placeholder_search(
+            ctx,
+            sort_criteria,
+            query_ranking_rules,
+            geo_strategy,
+            vector_strategy,
+        )?;
+        if let Some(boost) = query_rules.boost_rule::<PlaceholderQuery>(ctx)? {
+            ranking_rules.insert(0, Box::new(boost));
+        }
+        facet_candidates = facet_filters.facet_candidates(&universe);
+        facet_filters.restrict(&mut universe);
+
         bucket_sort(
             ctx,
             ranking_rules,
             &PlaceholderQuery,
             &universe,
-            from,
-            length,
+            ranked_from,
+            ranked_length,
             scoring_strategy,
             placeholder_search_logger,
         )?
     };
+    let bucket_sort_output = match pinned.is_empty() {
+        true => bucket_sort_output,
+        false => pin_documents(bucket_sort_output, &pinned, from, length),
+    };
+    // the pinned documents match all the facet-scoped filters.
+    for candidates in facet_candidates.values_mut() {
+        candidates.extend(pinned.iter().map(|(_, docid)| *docid));
+    }
 
     let BucketSortOutput { docids, scores, mut all_candidates } = bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
@@ -561,13 +822,18 @@ pub fn execute_search(
 
     Ok(PartialSearchResult {
         candidates: all_candidates,
+        facet_candidates,
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
     })
 }
 
-fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>) -> Result<()> {
+fn check_sort_criteria(
+    ctx: &SearchContext,
+    sort_criteria: Option<&Vec<AscDesc>>,
+    query_ranking_rules: &Option<Vec<Criterion>>,
+) -> Result<()> {
     let sort_criteria = if let Some(sort_criteria) = sort_criteria {
         sort_criteria
     } else {
@@ -580,7 +846,8 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
 
     // We check that the sort ranking rule exists and throw an
     // error if we try to use it and that it doesn't.
-    let sort_ranking_rule_missing = !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort);
+    let sort_ranking_rule_missing =
+        !ranking_rules_or_settings(ctx, query_ranking_rules)?.contains(&crate::Criterion::Sort);
     if sort_ranking_rule_missing {
         return Err(UserError::SortRankingRuleMissing.into());
     }
@@ -610,6 +877,9 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
                     hidden_fields,
                 })?;
             }
+            Member::Vector { space, vector } => {
+                vector_search::check_vector_space(ctx, space, vector.len())?;
+            }
             _ => (),
         }
     }
@@ -617,9 +887,55 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
     Ok(())
 }
 
+/// Checks that the attributes of the ranking rules given at search time have their values indexed,
+/// the sortable attributes and those of the ranking rules of the settings for the `asc`/`desc`
+/// rules, and any faceted attribute for the function scores.
+fn check_query_ranking_rules(
+    ctx: &SearchContext,
+    query_ranking_rules: Option<&Vec<Criterion>>,
+) -> Result<()> {
+    let Some(query_ranking_rules) = query_ranking_rules else {
+        return Ok(());
+    };
+
+    for rule in query_ranking_rules {
+        let (field, valid_fields) = match rule {
+            Criterion::Asc(field) | Criterion::Desc(field) => {
+                let mut sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+                sortable_fields.extend(ctx.index.criteria(ctx.txn)?.into_iter().filter_map(
+                    |criterion| match criterion {
+                        Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                        _ => None,
+                    },
+                ));
+                (field, sortable_fields)
+            }
+            Criterion::FunctionScore(function_score) => {
+                (&function_score.field, ctx.index.user_defined_faceted_fields(ctx.txn)?)
+            }
+            _ => continue,
+        };
+
+        if !crate::is_faceted(field, &valid_fields) {
+            let (valid_fields, hidden_fields) =
+                ctx.index.remove_hidden_fields(ctx.txn, valid_fields)?;
+            return Err(UserError::InvalidSearchRankingRuleAttribute {
+                rule: rule.to_string(),
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
 pub struct PartialSearchResult {
     pub located_query_terms: Option<Vec<LocatedQueryTerm>>,
     pub candidates: RoaringBitmap,
+    /// The candidates of the facets of the facet-scoped filters, ignoring their own filters.
+    pub facet_candidates: BTreeMap<String, RoaringBitmap>,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
 }
@@ -1,12 +1,14 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
 
 use fxhash::{FxHashMap, FxHasher};
 
 use super::interner::{FixedSizeInterner, Interned};
 use super::query_term::{
-    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryTermSubset,
+    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryClause,
+    QueryTermSubset,
 };
 use super::small_bitmap::SmallBitmap;
 use super::SearchContext;
@@ -91,6 +93,21 @@ impl QueryGraph {
     /// Build the query graph from the parsed user search query, return an updated list of the located query terms
     /// which contains ngrams.
     pub fn from_query(
+        ctx: &mut SearchContext,
+        // The clauses here must be consecutive
+        clauses: &[QueryClause],
+    ) -> Result<(QueryGraph, Vec<LocatedQueryTerm>)> {
+        // the queries without boolean operators are made of single terms
+        if clauses.iter().all(QueryClause::is_single_term) {
+            let terms: Vec<_> =
+                clauses.iter().flat_map(|clause| clause.alternatives.concat()).collect();
+            Self::from_query_terms(ctx, &terms)
+        } else {
+            Self::from_query_clauses(ctx, clauses)
+        }
+    }
+
+    fn from_query_terms(
         ctx: &mut SearchContext,
         // The terms here must be consecutive
         terms: &[LocatedQueryTerm],
@@ -176,6 +193,110 @@ impl QueryGraph {
         Ok((graph, new_located_query_terms))
     }
 
+    /// Build the query graph of a boolean query, the alternatives of a clause are parallel
+    /// paths of the graph and the ngrams are only made of the terms of the clauses without
+    /// alternatives.
+    fn from_query_clauses(
+        ctx: &mut SearchContext,
+        // The clauses here must be consecutive
+        clauses: &[QueryClause],
+    ) -> Result<(QueryGraph, Vec<LocatedQueryTerm>)> {
+        let mut new_located_query_terms = vec![];
+        let mut ngrams = vec![];
+
+        let nbr_typos = number_of_typos_allowed(ctx)?;
+
+        let mut nodes_data: Vec<QueryNodeData> = vec![QueryNodeData::Start, QueryNodeData::End];
+        // The clause and alternative of the nodes which are part of an alternative
+        let mut nodes_alternative: Vec<Option<(usize, usize)>> = vec![None, None];
+        let root_node = 0;
+        let end_node = 1;
+
+        // The previous consecutive terms along with their id and whether they are required.
+        // Ee could consider generalizing to 4,5,6,7,etc. ngrams
+        let mut prev_terms: Vec<(LocatedQueryTerm, usize, bool)> = vec![];
+
+        let mut term_idx = 0;
+        for (clause_idx, clause) in clauses.iter().enumerate() {
+            let clause_len = clause.alternatives.iter().map(Vec::len).max().unwrap_or_default();
+            if clause_len == 0 {
+                continue;
+            }
+            let has_alternatives = clause.alternatives.len() > 1;
+            if has_alternatives {
+                prev_terms.clear();
+            }
+
+            for (alternative_idx, alternative) in clause.alternatives.iter().enumerate() {
+                for (i, term) in alternative.iter().enumerate() {
+                    let start_term_idx = term_idx + i;
+                    // the last term of an alternative spans until the end of the clause
+                    let end_term_idx = if i + 1 == alternative.len() {
+                        term_idx + clause_len - 1
+                    } else {
+                        start_term_idx
+                    };
+                    new_located_query_terms.push(term.clone());
+                    nodes_data.push(term_node_data(
+                        term,
+                        start_term_idx..=end_term_idx,
+                        clause.required,
+                    ));
+                    nodes_alternative
+                        .push(has_alternatives.then_some((clause_idx, alternative_idx)));
+
+                    if has_alternatives {
+                        continue;
+                    }
+                    prev_terms.push((term.clone(), start_term_idx, clause.required));
+                    for ngram_len in [2, 3] {
+                        let Some(ngram_start) = prev_terms.len().checked_sub(ngram_len) else {
+                            continue;
+                        };
+                        let ngram_terms = &prev_terms[ngram_start..];
+                        let terms: Vec<_> = ngram_terms.iter().map(|(t, _, _)| t.clone()).collect();
+                        if let Some(ngram) = query_term::make_ngram(ctx, &terms, &nbr_typos)? {
+                            let required = ngram_terms.iter().any(|&(_, _, required)| required);
+                            nodes_data.push(term_node_data(
+                                &ngram,
+                                ngram_terms[0].1..=start_term_idx,
+                                required,
+                            ));
+                            nodes_alternative.push(None);
+                            ngrams.push(ngram);
+                        }
+                    }
+                }
+            }
+
+            if has_alternatives {
+                prev_terms.clear();
+            }
+            term_idx += clause_len;
+        }
+        new_located_query_terms.extend(ngrams);
+
+        let root_node = Interned::from_raw(root_node);
+        let end_node = Interned::from_raw(end_node);
+        let mut nodes = FixedSizeInterner::new(
+            nodes_data.len() as u16,
+            QueryNode {
+                data: QueryNodeData::Deleted,
+                predecessors: SmallBitmap::new(nodes_data.len() as u16),
+                successors: SmallBitmap::new(nodes_data.len() as u16),
+            },
+        );
+        for (node_idx, node_data) in nodes_data.into_iter().enumerate() {
+            let node = nodes.get_mut(Interned::from_raw(node_idx as u16));
+            node.data = node_data;
+        }
+        let mut graph = QueryGraph { root_node, end_node, nodes };
+        graph.build_initial_edges();
+        graph.disconnect_alternatives(&nodes_alternative);
+
+        Ok((graph, new_located_query_terms))
+    }
+
     /// Remove the given nodes, connecting all their predecessors to all their successors.
     pub fn remove_nodes_keep_edges(&mut self, nodes: &[Interned<QueryNode>]) {
         for &node_id in nodes {
@@ -290,6 +411,28 @@ impl QueryGraph {
         }
     }
 
+    /// Remove the edges between the nodes of the different alternatives of a clause,
+    /// which are connected by the initial edges when they have consecutive term ids.
+    fn disconnect_alternatives(&mut self, nodes_alternative: &[Option<(usize, usize)>]) {
+        for node_id in self.nodes.indexes() {
+            let Some((clause, alternative)) = nodes_alternative[node_id.into_raw() as usize] else {
+                continue;
+            };
+            let successors = self.nodes.get(node_id).successors.clone();
+            for successor in successors.iter() {
+                let Some((successor_clause, successor_alternative)) =
+                    nodes_alternative[successor.into_raw() as usize]
+                else {
+                    continue;
+                };
+                if successor_clause == clause && successor_alternative != alternative {
+                    self.nodes.get_mut(node_id).successors.remove(successor);
+                    self.nodes.get_mut(successor).predecessors.remove(node_id);
+                }
+            }
+        }
+    }
+
     pub fn removal_order_for_terms_matching_strategy_last(
         &self,
         ctx: &SearchContext,
@@ -366,6 +509,22 @@ fn add_node(nodes_data: &mut Vec<QueryNodeData>, node_data: QueryNodeData) -> u1
     new_node_idx
 }
 
+fn term_node_data(
+    term: &LocatedQueryTerm,
+    term_ids: RangeInclusive<usize>,
+    required: bool,
+) -> QueryNodeData {
+    let mut term_subset = QueryTermSubset::full(term.value);
+    if required {
+        term_subset.make_mandatory();
+    }
+    QueryNodeData::Term(LocatedQueryTermSubset {
+        term_subset,
+        positions: term.positions.clone(),
+        term_ids: *term_ids.start() as u8..=*term_ids.end() as u8,
+    })
+}
+
 impl QueryGraph {
     /*
     Build a query graph from a list of paths
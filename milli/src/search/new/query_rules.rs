@@ -0,0 +1,173 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::query_rules::normalize_words;
+use crate::score_details::{self, ScoreDetails};
+use crate::{Filter, Result};
+
+/// The consequences of the query rules of the index applied to a search.
+#[derive(Debug, Default)]
+pub struct AppliedQueryRules {
+    /// The external ids of the hidden documents.
+    hide: Vec<String>,
+    /// The filters added to the filter of the search.
+    filters: Vec<String>,
+    /// The filters whose documents are ranked first.
+    boost: Vec<String>,
+    /// The external ids of the pinned documents along with their position.
+    pin: Vec<(usize, String)>,
+}
+
+impl AppliedQueryRules {
+    /// Returns the consequences of the rules applied to a search with the given query and filter.
+    pub fn new(ctx: &SearchContext, query: Option<&str>, filter: Option<&Filter>) -> Result<Self> {
+        let mut applied = AppliedQueryRules::default();
+
+        let rules = ctx.index.query_rules(ctx.txn)?;
+        if rules.is_empty() {
+            return Ok(applied);
+        }
+
+        let query_words = query.map(normalize_words).unwrap_or_default();
+        let active_filters = filter.map(Filter::active_filters).unwrap_or_default();
+        for rule in rules.values().filter(|rule| rule.applies(&query_words, &active_filters)) {
+            let consequence = &rule.consequence;
+            applied.hide.extend(consequence.hide.iter().cloned());
+            applied.filters.extend(consequence.filter.iter().cloned());
+            applied.boost.extend(consequence.boost.iter().cloned());
+            applied.pin.extend(consequence.pin.iter().map(|pin| (pin.position, pin.id.clone())));
+        }
+
+        Ok(applied)
+    }
+
+    /// Removes the hidden documents and the documents not matching the added filters.
+    pub fn restrict_universe(
+        &self,
+        ctx: &SearchContext,
+        universe: &mut RoaringBitmap,
+    ) -> Result<()> {
+        let external_documents_ids = ctx.index.external_documents_ids(ctx.txn)?;
+        for id in &self.hide {
+            if let Some(docid) = external_documents_ids.get(id) {
+                universe.remove(docid);
+            }
+        }
+
+        for filter in &self.filters {
+            if let Some(filter) = Filter::from_str(filter)? {
+                *universe &= filter.evaluate(ctx.txn, ctx.index)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the pinned documents of the universe, sorted by position.
+    ///
+    /// A document pinned several times is only pinned at its first position.
+    pub fn pinned_documents(
+        &self,
+        ctx: &SearchContext,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<(usize, u32)>> {
+        let external_documents_ids = ctx.index.external_documents_ids(ctx.txn)?;
+        let mut pinned_docids = RoaringBitmap::new();
+        let mut pinned = Vec::new();
+        for (position, id) in &self.pin {
+            if let Some(docid) = external_documents_ids.get(id) {
+                if universe.contains(docid) && pinned_docids.insert(docid) {
+                    pinned.push((*position, docid));
+                }
+            }
+        }
+        // the sort is stable, the documents pinned at the same position keep their order.
+        pinned.sort_by_key(|(position, _)| *position);
+        Ok(pinned)
+    }
+
+    /// Returns the documents matching the boost filters, if any.
+    pub fn boosted_documents(&self, ctx: &SearchContext) -> Result<Option<RoaringBitmap>> {
+        if self.boost.is_empty() {
+            return Ok(None);
+        }
+
+        let mut boosted = RoaringBitmap::new();
+        for filter in &self.boost {
+            if let Some(filter) = Filter::from_str(filter)? {
+                boosted |= filter.evaluate(ctx.txn, ctx.index)?;
+            }
+        }
+        Ok(Some(boosted))
+    }
+
+    /// Returns the ranking rule ranking the boosted documents first, if any.
+    pub fn boost_rule<Query>(&self, ctx: &SearchContext) -> Result<Option<QueryRuleBoost<Query>>> {
+        let boost = self.boosted_documents(ctx)?.map(|boosted| QueryRuleBoost {
+            boosted,
+            original_query: None,
+            buckets: Vec::new(),
+        });
+        Ok(boost)
+    }
+}
+
+/// Ranks the documents matching the boost filters of the query rules before the other documents.
+pub struct QueryRuleBoost<Query> {
+    boosted: RoaringBitmap,
+    original_query: Option<Query>,
+    /// The documents of each bucket along with whether they are boosted, the best bucket is the last one.
+    buckets: Vec<(RoaringBitmap, bool)>,
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for QueryRuleBoost<Query> {
+    fn id(&self) -> String {
+        "queryRules".to_string()
+    }
+
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let boosted = parent_candidates & &self.boosted;
+        let not_boosted = parent_candidates - &boosted;
+        self.original_query = Some(parent_query.clone());
+        self.buckets = vec![(not_boosted, false), (boosted, true)];
+        Ok(())
+    }
+
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+
+        while let Some((docids, boosted)) = self.buckets.pop() {
+            let candidates = docids & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: ScoreDetails::QueryRule(score_details::QueryRule::Boost { boosted }),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = Vec::new();
+    }
+}
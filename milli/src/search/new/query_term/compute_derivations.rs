@@ -7,10 +7,13 @@ use fst::{Automaton, IntoStreamer, Streamer};
 use heed::types::DecodeIgnore;
 
 use super::*;
+use crate::code_identifiers::{code_identifier_parts, is_code_identifier};
+use crate::phonetic::{phonetic_codes, words_with_phonetic_code};
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::query_term::TwoTypoTerm;
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
+use crate::stemming::{words_with_stem, Stemmers};
 use crate::{Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -32,9 +35,10 @@ impl Interned<QueryTerm> {
             assert!(s.two_typo.is_uninit());
             // Initialize one_typo subterm even if max_nbr_typo is 0 because of split words
             self.initialize_one_typo_subterm(ctx)?;
+            let phonetic = self.find_phonetic_derivations(ctx)?;
             let s = ctx.term_interner.get_mut(self);
             assert!(s.one_typo.is_init());
-            s.two_typo = Lazy::Init(TwoTypoTerm::default());
+            s.two_typo = Lazy::Init(TwoTypoTerm { two_typos: BTreeSet::new(), phonetic });
         } else if s.max_levenshtein_distance > 1 && s.two_typo.is_uninit() {
             assert!(s.two_typo.is_uninit());
             self.initialize_one_and_two_typo_subterm(ctx)?;
@@ -233,11 +237,9 @@ pub fn partially_initialized_term_from_word(
             },
         )?;
     }
-    let synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = ctx.index.words_synonyms(ctx.txn, &[word])?;
     let mut synonym_word_count = 0;
-    let synonyms = synonyms
-        .get(&vec![word.to_owned()])
-        .cloned()
+    let mut synonyms: BTreeSet<_> = synonyms
         .unwrap_or_default()
         .into_iter()
         .take(limits::MAX_SYNONYM_PHRASE_COUNT)
@@ -250,8 +252,17 @@ pub fn partially_initialized_term_from_word(
             Some(ctx.phrase_interner.insert(Phrase { words }))
         })
         .collect();
+    // the identifiers kept whole in the code attributes are split by the separators
+    // in the other attributes, where they are matched as a phrase of their parts.
+    if is_code_identifier(word) && !ctx.index.code_attributes(ctx.txn)?.is_empty() {
+        let words = code_identifier_parts(word)
+            .map(|part| Some(ctx.word_interner.insert(part.to_owned())))
+            .collect();
+        synonyms.insert(ctx.phrase_interner.insert(Phrase { words }));
+    }
+    let stems = find_stem_derivations(ctx, word_interned)?;
     let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db };
 
     Ok(QueryTerm {
         original: word_interned,
@@ -264,6 +275,87 @@ pub fn partially_initialized_term_from_word(
     })
 }
 
+/// Returns the words of the index sharing a stem with the given word, the word excluded.
+fn find_stem_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+) -> Result<BTreeSet<Interned<String>>> {
+    let mut stems = BTreeSet::new();
+
+    let stemmers = Stemmers::new(&ctx.index.stemming_locales(ctx.txn)?);
+    if stemmers.is_empty() {
+        return Ok(stems);
+    }
+
+    let words_fst = ctx.get_words_fst()?;
+    let stems_fst = ctx.index.stems_fst(ctx.txn)?;
+    let word = ctx.word_interner.get(word_interned).to_owned();
+    for stem in stemmers.stems(&word) {
+        words_with_stem(&stems_fst, &stem, |derived_word| {
+            if stems.len() >= limits::MAX_STEM_COUNT {
+                return Ok(ControlFlow::Break(()));
+            }
+            // the stems FST is not updated when documents are deleted,
+            // it can thus contain words that are no longer in the index.
+            if derived_word != word && words_fst.contains(derived_word) {
+                stems.insert(ctx.word_interner.insert(derived_word.to_owned()));
+            }
+            Ok(ControlFlow::Continue(()))
+        })?;
+    }
+
+    Ok(stems)
+}
+
+impl Interned<QueryTerm> {
+    /// Returns the words of the index sounding like the original word that are not
+    /// already zero or one typo derivations of it.
+    ///
+    /// Words are only derived phonetically when typos are allowed on the term.
+    fn find_phonetic_derivations(
+        self,
+        ctx: &mut SearchContext,
+    ) -> Result<BTreeSet<Interned<String>>> {
+        let mut phonetic = BTreeSet::new();
+
+        let term = ctx.term_interner.get(self);
+        if term.max_levenshtein_distance == 0 || term.zero_typo.phrase.is_some() {
+            return Ok(phonetic);
+        }
+        let mut excluded: BTreeSet<_> = term.zero_typo.exact.iter().copied().collect();
+        excluded.extend(term.zero_typo.prefix_of.iter().copied());
+        excluded.extend(term.zero_typo.stems.iter().copied());
+        if let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &term.one_typo {
+            excluded.extend(one_typo.iter().copied());
+        }
+        let word = ctx.word_interner.get(term.original).to_owned();
+
+        let phonetic_fst = ctx.index.phonetic_fst(ctx.txn)?;
+        if phonetic_fst.is_empty() {
+            return Ok(phonetic);
+        }
+        let words_fst = ctx.get_words_fst()?;
+        for code in phonetic_codes(&word) {
+            words_with_phonetic_code(&phonetic_fst, &code, |derived_word| {
+                if phonetic.len() >= limits::MAX_PHONETIC_COUNT {
+                    return Ok(ControlFlow::Break(()));
+                }
+                // the phonetic FST is not updated when documents are deleted,
+                // it can thus contain words that are no longer in the index.
+                if derived_word != word && words_fst.contains(derived_word) {
+                    let derived_word = ctx.word_interner.insert(derived_word.to_owned());
+                    if !excluded.contains(&derived_word) {
+                        phonetic.insert(derived_word);
+                    }
+                }
+                Ok(ControlFlow::Continue(()))
+            })?;
+        }
+
+        Ok(phonetic)
+    }
+}
+
 fn find_split_words(ctx: &mut SearchContext, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
         Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
@@ -348,7 +440,8 @@ impl Interned<QueryTerm> {
             max_levenshtein_distance: max_nbr_typos,
             ..
         } = self_mut;
-        let original_str = ctx.word_interner.get(*original).to_owned();
+        let original_interned = *original;
+        let original_str = ctx.word_interner.get(original_interned).to_owned();
         if two_typo.is_init() {
             return Ok(());
         }
@@ -390,10 +483,14 @@ impl Interned<QueryTerm> {
         let self_mut = ctx.term_interner.get_mut(self);
 
         let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words };
+        self_mut.one_typo = Lazy::Init(one_typo);
 
-        let two_typo = TwoTypoTerm { two_typos: two_typo_words };
+        let phonetic = self.find_phonetic_derivations(ctx)?;
+        let self_mut = ctx.term_interner.get_mut(self);
+        // the words sounding like the original word are ranked before the two typos words
+        two_typo_words.retain(|word| !phonetic.contains(word));
+        let two_typo = TwoTypoTerm { two_typos: two_typo_words, phonetic };
 
-        self_mut.one_typo = Lazy::Init(one_typo);
         self_mut.two_typo = Lazy::Init(two_typo);
 
         Ok(())
@@ -10,7 +10,10 @@ use std::ops::RangeInclusive;
 use compute_derivations::partially_initialized_term_from_word;
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
-pub use parse_query::{located_query_terms_from_tokens, make_ngram, number_of_typos_allowed};
+pub use parse_query::{
+    located_query_terms_from_fragment, located_query_terms_from_tokens, make_ngram,
+    number_of_typos_allowed,
+};
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
@@ -63,6 +66,8 @@ struct ZeroTypoTerm {
     prefix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
+    /// Words sharing a stem with the original word, they are derived whatever the typo tolerance
+    stems: BTreeSet<Interned<String>>,
     /// A prefix in the prefix databases matching the original word
     use_prefix_db: Option<Interned<String>>,
 }
@@ -77,6 +82,8 @@ struct OneTypoTerm {
 struct TwoTypoTerm {
     /// Words that are 2 typos away from the original word
     two_typos: BTreeSet<Interned<String>>,
+    /// Words sounding like the original word, they are ranked between the one and two typos words
+    phonetic: BTreeSet<Interned<String>>,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -212,6 +219,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 result.extend(zero_typo.iter().copied().map(|w| {
@@ -228,6 +236,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(stems.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let ZeroTypoTerm {
@@ -235,6 +244,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 if let Some(zero_typo) = zero_typo {
@@ -253,6 +263,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(stems.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         }
@@ -263,7 +274,7 @@ impl QueryTermSubset {
                 else {
                     panic!()
                 };
-                result.extend(one_typo.iter().copied().map(Word::Derived))
+                result.extend(one_typo.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
@@ -277,12 +288,18 @@ impl QueryTermSubset {
 
         match &self.two_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
+                let Lazy::Init(TwoTypoTerm { two_typos, phonetic }) = &original.two_typo else {
+                    panic!()
+                };
                 result.extend(two_typos.iter().copied().map(Word::Derived));
+                result.extend(phonetic.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
-                let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
+                let Lazy::Init(TwoTypoTerm { two_typos, phonetic }) = &original.two_typo else {
+                    panic!()
+                };
                 result.extend(two_typos.intersection(words).copied().map(Word::Derived));
+                result.extend(phonetic.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         };
@@ -297,7 +314,7 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, stems: _, use_prefix_db: _ } =
             &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
@@ -389,6 +406,57 @@ impl QueryTermSubset {
             }
         }
     }
+    /// Returns the words sounding like the original word that are part of this subset.
+    pub fn phonetic_derivations(
+        &self,
+        ctx: &mut SearchContext,
+    ) -> Result<BTreeSet<Interned<String>>> {
+        if self.two_typo_subset.is_empty() || ctx.index.phonetic_fst(ctx.txn)?.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(TwoTypoTerm { two_typos: _, phonetic }) = &original.two_typo else {
+            panic!()
+        };
+        Ok(match &self.two_typo_subset {
+            NTypoTermSubset::All => phonetic.clone(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                phonetic.intersection(words).copied().collect()
+            }
+            NTypoTermSubset::Nothing => BTreeSet::new(),
+        })
+    }
+    /// Keeps only the given words in the two typos subset, which must be computed.
+    pub fn keep_only_two_typo_words(&mut self, words: BTreeSet<Interned<String>>) {
+        self.clear_zero_typo_subset();
+        self.clear_one_typo_subset();
+        self.two_typo_subset
+            .intersect(&NTypoTermSubset::Subset { words, phrases: BTreeSet::new() });
+    }
+    /// Removes the given words from the two typos subset, which must be computed.
+    pub fn remove_two_typo_words(
+        &mut self,
+        ctx: &SearchContext,
+        removed: &BTreeSet<Interned<String>>,
+    ) {
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(TwoTypoTerm { two_typos, phonetic }) = &original.two_typo else { panic!() };
+        match &mut self.two_typo_subset {
+            NTypoTermSubset::All => {
+                let words = two_typos.union(phonetic).filter(|w| !removed.contains(w));
+                self.two_typo_subset = NTypoTermSubset::Subset {
+                    words: words.copied().collect(),
+                    phrases: BTreeSet::new(),
+                };
+            }
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                words.retain(|w| !removed.contains(w));
+            }
+            NTypoTermSubset::Nothing => {}
+        }
+    }
     pub fn clear_zero_typo_subset(&mut self) {
         self.zero_typo_subset = NTypoTermSubset::Nothing;
     }
@@ -406,11 +474,13 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db } =
+            self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
             && synonyms.is_empty()
+            && stems.is_empty()
             && use_prefix_db.is_none()
     }
 }
@@ -422,8 +492,8 @@ impl OneTypoTerm {
 }
 impl TwoTypoTerm {
     fn is_empty(&self) -> bool {
-        let TwoTypoTerm { two_typos } = self;
-        two_typos.is_empty()
+        let TwoTypoTerm { two_typos, phonetic } = self;
+        two_typos.is_empty() && phonetic.is_empty()
     }
 }
 
@@ -469,6 +539,30 @@ impl LocatedQueryTerm {
     }
 }
 
+/// The alternative sequences of query terms found at a location of the search query,
+/// any of them can match.
+#[derive(Clone)]
+pub struct QueryClause {
+    pub alternatives: Vec<Vec<LocatedQueryTerm>>,
+    /// `true` if the terms of the clause cannot be deleted through the term matching strategy
+    pub required: bool,
+}
+
+impl QueryClause {
+    /// Returns `true` if the clause is an optional term without alternatives,
+    /// like the terms of the plain queries.
+    pub fn is_single_term(&self) -> bool {
+        !self.required
+            && matches!(self.alternatives.as_slice(), [alternative] if alternative.len() == 1)
+    }
+}
+
+impl From<LocatedQueryTerm> for QueryClause {
+    fn from(term: LocatedQueryTerm) -> Self {
+        QueryClause { alternatives: vec![vec![term]], required: false }
+    }
+}
+
 impl QueryTerm {
     pub fn is_cached_prefix(&self) -> bool {
         self.zero_typo.use_prefix_db.is_some()
@@ -483,10 +577,11 @@ impl QueryTerm {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db: _ } =
             &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(stems.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
@@ -495,8 +590,9 @@ impl QueryTerm {
             phrases.extend(split_words.iter().copied());
         };
 
-        if let Lazy::Init(TwoTypoTerm { two_typos }) = &self.two_typo {
+        if let Lazy::Init(TwoTypoTerm { two_typos, phonetic }) = &self.two_typo {
             words.extend(two_typos.iter().copied());
+            words.extend(phonetic.iter().copied());
         };
 
         (words.into_iter().collect(), phrases.into_iter().collect())
@@ -2,6 +2,7 @@ use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind};
 
 use super::*;
+use crate::search::TypoTolerance;
 use crate::{Result, SearchContext, MAX_WORD_LENGTH};
 
 /// Convert the tokenised search query into a list of located query terms.
@@ -9,6 +10,17 @@ pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext,
     query: NormalizedTokenIter,
     words_limit: Option<usize>,
+) -> Result<Vec<LocatedQueryTerm>> {
+    located_query_terms_from_fragment(ctx, query, words_limit, true)
+}
+
+/// Convert a tokenised fragment of the search query into a list of located query terms,
+/// its last word is a prefix only if `last_word_is_prefix` is `true`.
+pub fn located_query_terms_from_fragment(
+    ctx: &mut SearchContext,
+    query: NormalizedTokenIter,
+    words_limit: Option<usize>,
+    last_word_is_prefix: bool,
 ) -> Result<Vec<LocatedQueryTerm>> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
 
@@ -38,10 +50,11 @@ pub fn located_query_terms_from_tokens(
 
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
-                // 3. if the word is the last token of the query we push it as a prefix word.
+                // 3. if the word is the last token of the query we push it as a prefix word,
+                //    unless the last word of the fragment is not a prefix.
                 if let Some(phrase) = &mut phrase {
                     phrase.push_word(ctx, &token, position)
-                } else if peekable.peek().is_some() {
+                } else if peekable.peek().is_some() || !last_word_is_prefix {
                     match token.kind {
                         TokenKind::Word => {
                             let word = token.lemma();
@@ -135,24 +148,12 @@ pub fn located_query_terms_from_tokens(
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
-    let authorize_typos = ctx.index.authorize_typos(ctx.txn)?;
-    let min_len_one_typo = ctx.index.min_word_len_one_typo(ctx.txn)?;
-    let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
-
-    let exact_words = ctx.index.exact_words(ctx.txn)?;
-
-    Ok(Box::new(move |word: &str| {
-        if !authorize_typos
-            || word.len() < min_len_one_typo as usize
-            || exact_words.as_ref().map_or(false, |fst| fst.contains(word))
-        {
-            0
-        } else if word.len() < min_len_two_typos as usize {
-            1
-        } else {
-            2
-        }
-    }))
+    let typo_tolerance = match &ctx.typo_tolerance {
+        Some(typo_tolerance) => typo_tolerance.clone(),
+        None => TypoTolerance::new(ctx.index, ctx.txn, None)?,
+    };
+
+    Ok(Box::new(move |word: &str| typo_tolerance.number_of_typos_allowed(word)))
 }
 
 pub fn make_ngram(
@@ -199,14 +200,12 @@ pub fn make_ngram(
         partially_initialized_term_from_word(ctx, &ngram_str, max_nbr_typos, is_prefix, true)?;
 
     // Now add the synonyms
-    let index_synonyms = ctx.index.synonyms(ctx.txn)?;
+    let index_synonyms = ctx.index.words_synonyms(ctx.txn, &words)?;
 
-    term.zero_typo.synonyms.extend(
-        index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
-            let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
-        }),
-    );
+    term.zero_typo.synonyms.extend(index_synonyms.unwrap_or_default().into_iter().map(|words| {
+        let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
+        ctx.phrase_interner.insert(Phrase { words })
+    }));
 
     let term = QueryTerm {
         original: ngram_str_interned,
@@ -271,6 +270,7 @@ impl PhraseBuilder {
                         exact: None,
                         prefix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
+                        stems: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
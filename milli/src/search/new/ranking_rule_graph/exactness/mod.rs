@@ -86,7 +86,7 @@ impl RankingRuleGraphTrait for ExactnessGraph {
         Ok(vec![(0, exact_condition), (dest_node.term_ids.len() as u32, skip_condition)])
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::ExactWords(score_details::ExactWords::from_rank(rank))
+    fn rank_to_score(_ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        Ok(ScoreDetails::ExactWords(score_details::ExactWords::from_rank(rank)))
     }
 }
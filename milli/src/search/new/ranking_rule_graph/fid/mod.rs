@@ -1,13 +1,13 @@
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use roaring::RoaringBitmap;
 
 use super::{ComputedCondition, RankingRuleGraphTrait};
-use crate::score_details::{Rank, ScoreDetails};
+use crate::score_details::{self, Rank, ScoreDetails};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::search::new::resolve_query_graph::compute_query_term_subset_docids_within_field_id;
 use crate::search::new::SearchContext;
-use crate::Result;
+use crate::{is_faceted_by, FieldId, Result};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct FidCondition {
@@ -50,49 +50,29 @@ impl RankingRuleGraphTrait for FidGraph {
     ) -> Result<Vec<(u32, Interned<Self::Condition>)>> {
         let term = to_term;
 
-        let mut all_fields = FxHashSet::default();
-        for word in term.term_subset.all_single_words_except_prefix_db(ctx)? {
-            let fields = ctx.get_db_word_fids(word.interned())?;
-            all_fields.extend(fields);
-        }
-
-        for phrase in term.term_subset.all_phrases(ctx)? {
-            for &word in phrase.words(ctx).iter().flatten() {
-                let fields = ctx.get_db_word_fids(word)?;
-                all_fields.extend(fields);
-            }
-        }
-
-        if let Some(word_prefix) = term.term_subset.use_prefix_db(ctx) {
-            let fields = ctx.get_db_word_prefix_fids(word_prefix.interned())?;
-            all_fields.extend(fields);
-        }
+        let all_fields = term_fids(ctx, term)?;
+        let costs = FieldsCosts::new(ctx)?;
+        let term_len = term.term_ids.len() as u32;
 
         let mut edges = vec![];
         for fid in all_fields.iter().copied() {
             edges.push((
-                fid as u32 * term.term_ids.len() as u32,
+                costs.cost(fid) * term_len,
                 conditions_interner.insert(FidCondition { term: term.clone(), fid }),
             ));
         }
 
-        // always lookup the max_fid if we don't already and add an artificial condition for max scoring
-        let max_fid: Option<u16> = {
-            if let Some(max_fid) = ctx
-                .index
-                .searchable_fields_ids(ctx.txn)?
-                .map(|field_ids| field_ids.into_iter().max())
-            {
-                max_fid
-            } else {
-                ctx.index.fields_ids_map(ctx.txn)?.ids().max()
-            }
+        // always lookup the least important fid if we don't already and add an artificial condition for max scoring
+        let searchable_fids: Vec<u16> = match ctx.index.searchable_fields_ids(ctx.txn)? {
+            Some(field_ids) => field_ids,
+            None => ctx.index.fields_ids_map(ctx.txn)?.ids().collect(),
         };
+        let max_fid = searchable_fids.into_iter().max_by_key(|fid| (costs.cost(*fid), *fid));
 
         if let Some(max_fid) = max_fid {
             if !all_fields.contains(&max_fid) {
                 edges.push((
-                    max_fid as u32 * term.term_ids.len() as u32, // TODO improve the fid score i.e. fid^10.
+                    costs.cost(max_fid) * term_len, // TODO improve the fid score i.e. fid^10.
                     conditions_interner.insert(FidCondition {
                         term: term.clone(), // TODO remove this ugly clone
                         fid: max_fid,
@@ -104,7 +84,85 @@ impl RankingRuleGraphTrait for FidGraph {
         Ok(edges)
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Fid(rank)
+    fn rank_to_score(ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        let attribute_weights = ctx.index.attribute_weights(ctx.txn)?;
+        let attribute_weights = (!attribute_weights.is_empty()).then_some(attribute_weights);
+        Ok(ScoreDetails::Fid(score_details::Fid { rank, attribute_weights }))
+    }
+}
+
+/// Returns the fields in which the words of the query term appear.
+pub(super) fn term_fids(
+    ctx: &mut SearchContext,
+    term: &LocatedQueryTermSubset,
+) -> Result<FxHashSet<FieldId>> {
+    let mut all_fields = FxHashSet::default();
+    for word in term.term_subset.all_single_words_except_prefix_db(ctx)? {
+        let fields = ctx.get_db_word_fids(word.interned())?;
+        all_fields.extend(fields);
+    }
+
+    for phrase in term.term_subset.all_phrases(ctx)? {
+        for &word in phrase.words(ctx).iter().flatten() {
+            let fields = ctx.get_db_word_fids(word)?;
+            all_fields.extend(fields);
+        }
+    }
+
+    if let Some(word_prefix) = term.term_subset.use_prefix_db(ctx) {
+        let fields = ctx.get_db_word_prefix_fids(word_prefix.interned())?;
+        all_fields.extend(fields);
+    }
+
+    Ok(all_fields)
+}
+
+/// The cost of matching a query term in each searchable field.
+///
+/// When no attribute weights are defined, the fields are ordered by their position
+/// in the searchable attributes. Otherwise, the cost of a field is the difference between
+/// its weight and the highest weight, the fields without a weight have a weight of zero.
+pub(super) enum FieldsCosts {
+    FieldsOrder,
+    Weights { weights: FxHashMap<FieldId, u16>, max_weight: u16 },
+}
+
+impl FieldsCosts {
+    pub fn new(ctx: &SearchContext) -> Result<Self> {
+        let attribute_weights = ctx.index.attribute_weights(ctx.txn)?;
+        if attribute_weights.is_empty() {
+            return Ok(FieldsCosts::FieldsOrder);
+        }
+
+        let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+        let mut weights = FxHashMap::default();
+        for (fid, name) in fields_ids_map.iter() {
+            // the weight of an attribute also applies to its nested fields
+            let weight = attribute_weights
+                .iter()
+                .filter(|(attribute, _)| is_faceted_by(name, attribute))
+                .map(|(_, weight)| *weight)
+                .max();
+            if let Some(weight) = weight {
+                weights.insert(fid, weight);
+            }
+        }
+        let max_weight = attribute_weights.values().copied().max().unwrap_or_default();
+
+        Ok(FieldsCosts::Weights { weights, max_weight })
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        matches!(self, FieldsCosts::Weights { .. })
+    }
+
+    pub fn cost(&self, fid: FieldId) -> u32 {
+        match self {
+            FieldsCosts::FieldsOrder => fid as u32,
+            FieldsCosts::Weights { weights, max_weight } => {
+                let weight = weights.get(&fid).copied().unwrap_or_default();
+                (max_weight - weight) as u32
+            }
+        }
     }
 }
@@ -1,18 +1,25 @@
 use fxhash::{FxHashMap, FxHashSet};
 use roaring::RoaringBitmap;
 
+use super::fid::{term_fids, FieldsCosts};
 use super::{ComputedCondition, RankingRuleGraphTrait};
 use crate::score_details::{Rank, ScoreDetails};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::LocatedQueryTermSubset;
-use crate::search::new::resolve_query_graph::compute_query_term_subset_docids_within_position;
+use crate::search::new::resolve_query_graph::{
+    compute_query_term_subset_docids_within_field_id,
+    compute_query_term_subset_docids_within_position,
+};
 use crate::search::new::SearchContext;
-use crate::Result;
+use crate::{FieldId, Result};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct PositionCondition {
     term: LocatedQueryTermSubset,
     positions: Vec<u16>,
+    /// The fields of the same weight in which the term must appear,
+    /// only defined when the index has attribute weights.
+    fids: Option<Vec<FieldId>>,
 }
 
 pub enum PositionGraph {}
@@ -25,7 +32,7 @@ impl RankingRuleGraphTrait for PositionGraph {
         condition: &Self::Condition,
         universe: &RoaringBitmap,
     ) -> Result<ComputedCondition> {
-        let PositionCondition { term, positions } = condition;
+        let PositionCondition { term, positions, fids } = condition;
         let mut docids = RoaringBitmap::new();
         for position in positions {
             // maybe compute_query_term_subset_docids_within_position should accept a universe as argument
@@ -36,6 +43,17 @@ impl RankingRuleGraphTrait for PositionGraph {
                     *position,
                 )?;
         }
+
+        // The word positions are not stored by field, so this is an approximation: a document
+        // matching at this position in any field is kept if it also matches in one of these fields.
+        if let Some(fids) = fids {
+            let mut fids_docids = RoaringBitmap::new();
+            for fid in fids {
+                fids_docids |=
+                    compute_query_term_subset_docids_within_field_id(ctx, &term.term_subset, *fid)?;
+            }
+            docids &= fids_docids;
+        }
         Ok(ComputedCondition {
             docids,
             universe_len: universe.len(),
@@ -94,31 +112,91 @@ impl RankingRuleGraphTrait for PositionGraph {
             positions_for_costs.entry(cost).or_default().push(position);
         }
 
-        let max_cost = term.term_ids.len() as u32 * 10;
-        let max_cost_exists = positions_for_costs.contains_key(&max_cost);
+        let max_position_cost = term.term_ids.len() as u32 * 10;
+
+        let costs = FieldsCosts::new(ctx)?;
+        if !costs.is_weighted() {
+            let max_cost_exists = positions_for_costs.contains_key(&max_position_cost);
+
+            let mut edges = vec![];
+            for (cost, positions) in positions_for_costs {
+                edges.push((
+                    cost,
+                    conditions_interner.insert(PositionCondition {
+                        term: term.clone(),
+                        positions,
+                        fids: None,
+                    }),
+                ));
+            }
 
-        let mut edges = vec![];
-        for (cost, positions) in positions_for_costs {
-            edges.push((
-                cost,
-                conditions_interner.insert(PositionCondition { term: term.clone(), positions }),
-            ));
+            if !max_cost_exists {
+                // artificial empty condition for computing max cost
+                edges.push((
+                    max_position_cost,
+                    conditions_interner.insert(PositionCondition {
+                        term: term.clone(),
+                        positions: Vec::default(),
+                        fids: None,
+                    }),
+                ));
+            }
+
+            return Ok(edges);
         }
 
-        if !max_cost_exists {
-            // artificial empty condition for computing max cost
-            edges.push((
-                max_cost,
-                conditions_interner
-                    .insert(PositionCondition { term: term.clone(), positions: Vec::default() }),
-            ));
+        // With attribute weights, the positions in the most important fields come first:
+        // the fields are grouped by weight and the position costs of each group are
+        // shifted after the ones of the more important groups.
+        let searchable_fids: Vec<FieldId> = match ctx.index.searchable_fields_ids(ctx.txn)? {
+            Some(field_ids) => field_ids,
+            None => ctx.index.fields_ids_map(ctx.txn)?.ids().collect(),
+        };
+        let mut weight_groups: Vec<u32> =
+            searchable_fids.iter().map(|fid| costs.cost(*fid)).collect();
+        weight_groups.sort_unstable();
+        weight_groups.dedup();
+
+        let mut fids_for_groups = FxHashMap::<usize, Vec<FieldId>>::default();
+        for fid in term_fids(ctx, term)? {
+            if let Ok(group) = weight_groups.binary_search(&costs.cost(fid)) {
+                fids_for_groups.entry(group).or_default().push(fid);
+            }
+        }
+
+        let group_stride = max_position_cost + 1;
+        let mut edges = vec![];
+        for (group, mut fids) in fids_for_groups {
+            fids.sort_unstable();
+            for (cost, positions) in positions_for_costs.iter() {
+                edges.push((
+                    group as u32 * group_stride + cost,
+                    conditions_interner.insert(PositionCondition {
+                        term: term.clone(),
+                        positions: positions.clone(),
+                        fids: Some(fids.clone()),
+                    }),
+                ));
+            }
         }
 
+        // artificial empty condition for computing max cost
+        let max_cost =
+            weight_groups.len().saturating_sub(1) as u32 * group_stride + max_position_cost;
+        edges.push((
+            max_cost,
+            conditions_interner.insert(PositionCondition {
+                term: term.clone(),
+                positions: Vec::default(),
+                fids: None,
+            }),
+        ));
+
         Ok(edges)
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Position(rank)
+    fn rank_to_score(_ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        Ok(ScoreDetails::Position(rank))
     }
 }
 
@@ -38,7 +38,7 @@ impl RankingRuleGraphTrait for ProximityGraph {
         build::build_edges(ctx, conditions_interner, source_term, dest_term)
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Proximity(rank)
+    fn rank_to_score(_ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        Ok(ScoreDetails::Proximity(rank))
     }
 }
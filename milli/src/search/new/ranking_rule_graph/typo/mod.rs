@@ -1,7 +1,7 @@
 use roaring::RoaringBitmap;
 
 use super::{ComputedCondition, RankingRuleGraphTrait};
-use crate::score_details::{self, Rank, ScoreDetails};
+use crate::score_details::{Rank, ScoreDetails, Typo};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::search::new::resolve_query_graph::compute_query_term_subset_docids;
@@ -12,6 +12,8 @@ use crate::Result;
 pub struct TypoCondition {
     term: LocatedQueryTermSubset,
     nbr_typos: u8,
+    /// Whether the condition matches the words sounding like the term.
+    phonetic: bool,
 }
 
 pub enum TypoGraph {}
@@ -49,7 +51,19 @@ impl RankingRuleGraphTrait for TypoGraph {
         // Ngrams have a base typo cost
         // 2-gram -> equivalent to 1 typo
         // 3-gram -> equivalent to 2 typos
-        let base_cost = if term.term_ids.len() == 1 { 0 } else { term.term_ids.len() as u32 };
+        let base_typos = if term.term_ids.len() == 1 { 0 } else { term.term_ids.len() as u32 };
+
+        // The words sounding like the original word have their own cost,
+        // they are removed from the two typos words.
+        let phonetic = term.term_subset.phonetic_derivations(ctx)?;
+        if !phonetic.is_empty() {
+            let mut term = term.clone();
+            term.term_subset.keep_only_two_typo_words(phonetic.clone());
+            edges.push((
+                Typo::cost(base_typos, 1),
+                conditions_interner.insert(TypoCondition { term, nbr_typos: 0, phonetic: true }),
+            ));
+        }
 
         for nbr_typos in 0..=term.term_subset.max_typo_cost(ctx) {
             let mut term = term.clone();
@@ -65,19 +79,22 @@ impl RankingRuleGraphTrait for TypoGraph {
                 2 => {
                     term.term_subset.clear_zero_typo_subset();
                     term.term_subset.clear_one_typo_subset();
+                    if !phonetic.is_empty() {
+                        term.term_subset.remove_two_typo_words(ctx, &phonetic);
+                    }
                 }
                 _ => panic!(),
             };
 
             edges.push((
-                nbr_typos as u32 + base_cost,
-                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+                Typo::cost(base_typos + nbr_typos as u32, 0),
+                conditions_interner.insert(TypoCondition { term, nbr_typos, phonetic: false }),
             ));
         }
         Ok(edges)
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Typo(score_details::Typo::from_rank(rank))
+    fn rank_to_score(_ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        Ok(ScoreDetails::Typo(Typo::from_rank(rank)))
     }
 }
@@ -45,7 +45,7 @@ impl RankingRuleGraphTrait for WordsGraph {
         Ok(vec![(0, conditions_interner.insert(WordsCondition { term: to_term.clone() }))])
     }
 
-    fn rank_to_score(rank: Rank) -> ScoreDetails {
-        ScoreDetails::Words(score_details::Words::from_rank(rank))
+    fn rank_to_score(_ctx: &mut SearchContext, rank: Rank) -> Result<ScoreDetails> {
+        Ok(ScoreDetails::Words(score_details::Words::from_rank(rank)))
     }
 }
@@ -0,0 +1,126 @@
+/*!
+This module tests the attribute weights used by the attribute ranking rule:
+
+1. without weights, the attributes are ordered by their position in the searchable attributes
+2. with weights, the attributes with the highest weight are the most important ones
+3. attributes can share the same weight, their matches are then ranked equally
+4. the searchable attributes without a weight are the least important ones
+5. the weights are exposed in the score details
+*/
+
+use maplit::btreemap;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec![
+                "title".to_owned(),
+                "description".to_owned(),
+                "plot".to_owned(),
+            ]);
+            s.set_criteria(vec![Criterion::Attribute]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "", "description": "", "plot": "fox" },
+            { "id": 1, "title": "", "description": "fox", "plot": "" },
+            { "id": 2, "title": "fox", "description": "", "plot": "" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+#[test]
+fn test_attribute_weights() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1", "0"]"###);
+    drop(txn);
+
+    index
+        .update_settings(|s| {
+            s.set_attribute_weights(btreemap! { "plot".to_owned() => 3, "title".to_owned() => 1 });
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2", "1"]"###);
+}
+
+#[test]
+fn test_attribute_weights_ties() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_attribute_weights(btreemap! { "plot".to_owned() => 2, "title".to_owned() => 2 });
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2", "1"]"###);
+
+    // the documents matching in the plot and in the title are ranked equally
+    let scores: Vec<_> =
+        document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    assert_eq!(scores[0], scores[1]);
+    assert!(scores[1] > scores[2]);
+}
+
+#[test]
+fn test_attribute_weights_positions() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_attribute_weights(btreemap! { "plot".to_owned() => 1 });
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 3, "title": "", "description": "", "plot": "the quick brown fox" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the documents matching in the weighted plot come first, ranked by the position of the word
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "3", "1", "2"]"###);
+
+    // the weights are exposed in the score details of the attribute ranking rule
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    insta::assert_json_snapshot!(details["attribute"]["attributeWeights"], @r###"
+    {
+      "plot": 1
+    }
+    "###);
+}
@@ -0,0 +1,112 @@
+/*!
+This module tests the boolean syntax of the search queries:
+
+1. the operators are only understood when the boolean syntax is enabled
+2. the documents containing an excluded word or phrase are removed from the results
+3. a required word is never removed by the terms matching strategy
+4. the alternatives joined by `OR` can match at the same location of the query,
+but the words of different alternatives are never mixed
+5. the queries without operators are resolved like with the plain syntax
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, QuerySyntax, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "jaguar car dealer" },
+            { "id": 1, "text": "jaguar cat in the jungle" },
+            { "id": 2, "text": "jaguar speed record" },
+            { "id": 3, "text": "red car for sale" },
+            { "id": 4, "text": "big cat" },
+            { "id": 5, "text": "jaguar" },
+            { "id": 6, "text": "big car" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    query_syntax: QuerySyntax,
+    terms_matching_strategy: TermsMatchingStrategy,
+) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.query_syntax(query_syntax);
+    s.terms_matching_strategy(terms_matching_strategy);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    collect_field_values(index, &txn, "id", &documents_ids)
+}
+
+#[test]
+fn test_excluded_terms() {
+    let index = create_index();
+
+    let ids = search(&index, "jaguar -car", QuerySyntax::Plain, TermsMatchingStrategy::Last);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1", "2", "5"]"###);
+
+    let ids = search(&index, "jaguar -car", QuerySyntax::Boolean, TermsMatchingStrategy::Last);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "2", "5"]"###);
+
+    let ids =
+        search(&index, "jaguar -\"car dealer\"", QuerySyntax::Boolean, TermsMatchingStrategy::Last);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "2", "5"]"###);
+}
+
+#[test]
+fn test_required_terms() {
+    let index = create_index();
+
+    let ids = search(&index, "jaguar cat", QuerySyntax::Boolean, TermsMatchingStrategy::Last);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "0", "2", "5"]"###);
+
+    // "jaguar" can be removed but "cat" can't
+    let ids = search(&index, "jaguar +cat", QuerySyntax::Boolean, TermsMatchingStrategy::Last);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "4"]"###);
+}
+
+#[test]
+fn test_or_operator() {
+    let index = create_index();
+
+    let ids =
+        search(&index, "jaguar (car OR cat)", QuerySyntax::Boolean, TermsMatchingStrategy::All);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    let ids = search(&index, "jaguar car OR cat", QuerySyntax::Boolean, TermsMatchingStrategy::All);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    // "big car" matches neither "red car" nor "big cat"
+    let ids =
+        search(&index, "(red car OR big cat)", QuerySyntax::Boolean, TermsMatchingStrategy::All);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["3", "4"]"###);
+}
+
+#[test]
+fn test_queries_without_operators() {
+    let index = create_index();
+
+    for query in ["jaguar car", "big cat jungle", "bigcat", "red car for sale", "jaguar speed"] {
+        for strategy in [TermsMatchingStrategy::Last, TermsMatchingStrategy::All] {
+            let plain = search(&index, query, QuerySyntax::Plain, strategy);
+            let boolean = search(&index, query, QuerySyntax::Boolean, strategy);
+            assert_eq!(plain, boolean, "{query:?} with the {strategy:?} strategy");
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/*!
+This module tests the "did you mean" correction of the queries:
+
+1. no correction is computed unless it is requested
+2. the correction of a query matching no document is returned along with its empty results
+3. with the auto correction, the results of the corrected query are returned instead
+4. no correction is computed for the queries matching more documents than the maximum number of hits
+5. the correction follows the typo tolerance of the index, overridden by the one of the search
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{DidYouMean, Search, SearchResult, TypoToleranceOverride};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a television in the kitchen" },
+            { "id": 1, "text": "the television of the house" },
+            { "id": 2, "text": "a kitchen table" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    did_you_mean: Option<DidYouMean>,
+) -> (Vec<String>, Option<String>, bool) {
+    search_with_typo_tolerance(index, query, did_you_mean, None)
+}
+
+fn search_with_typo_tolerance(
+    index: &TempIndex,
+    query: &str,
+    did_you_mean: Option<DidYouMean>,
+    typo_tolerance: Option<TypoToleranceOverride>,
+) -> (Vec<String>, Option<String>, bool) {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    if let Some(did_you_mean) = did_you_mean {
+        s.did_you_mean(did_you_mean);
+    }
+    if let Some(typo_tolerance) = typo_tolerance {
+        s.typo_tolerance(typo_tolerance);
+    }
+    let SearchResult { documents_ids, did_you_mean, auto_corrected, .. } = s.execute().unwrap();
+    (collect_field_values(index, &txn, "id", &documents_ids), did_you_mean, auto_corrected)
+}
+
+#[test]
+fn test_did_you_mean() {
+    let index = create_index();
+
+    // a typo on the first letter counts as two typos, "jouse" is too short to be matched with it
+    let result = search(&index, "jouse", None);
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [],
+        None,
+        false,
+    )
+    "###);
+
+    let result = search(&index, "jouse", Some(DidYouMean::default()));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [],
+        Some(
+            "house",
+        ),
+        false,
+    )
+    "###);
+
+    let result =
+        search(&index, "jouse", Some(DidYouMean { auto_correct: true, ..Default::default() }));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [
+            "1",
+        ],
+        Some(
+            "house",
+        ),
+        true,
+    )
+    "###);
+
+    // the query matches two documents thanks to the "kitchen" word
+    let result =
+        search(&index, "kitchen jouse", Some(DidYouMean { max_hits: 1, auto_correct: true }));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [
+            "2",
+            "0",
+        ],
+        None,
+        false,
+    )
+    "###);
+}
+
+#[test]
+fn test_did_you_mean_typo_tolerance() {
+    let index = create_index();
+
+    // "hose" is too short to be corrected with the typo tolerance of the index
+    let result = search(&index, "hose", Some(DidYouMean::default()));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [],
+        None,
+        false,
+    )
+    "###);
+
+    // but not with the one of the search
+    let one_typo = TypoToleranceOverride { min_word_len_one_typo: Some(4), ..Default::default() };
+    let result =
+        search_with_typo_tolerance(&index, "hose", Some(DidYouMean::default()), Some(one_typo));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [
+            "1",
+        ],
+        Some(
+            "house",
+        ),
+        false,
+    )
+    "###);
+
+    let disabled = TypoToleranceOverride { enabled: Some(false), ..Default::default() };
+    let result =
+        search_with_typo_tolerance(&index, "jouse", Some(DidYouMean::default()), Some(disabled));
+    insta::assert_debug_snapshot!(result, @r###"
+    (
+        [],
+        None,
+        false,
+    )
+    "###);
+}
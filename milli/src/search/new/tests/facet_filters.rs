@@ -0,0 +1,98 @@
+/*!
+This module tests the facet-scoped filters of a search:
+
+1. they restrict the results like the filter of the search
+2. the candidates of a facet ignore the filters scoped to this facet but not the others
+3. the facet distribution computed from these candidates ignores the filters of each facet
+*/
+
+use std::collections::BTreeMap;
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{
+    Criterion, FacetDistribution, Filter, OrderBy, Search, SearchResult, TermsMatchingStrategy,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["name".to_owned()]);
+            s.set_filterable_fields(
+                ["brand".to_owned(), "colour".to_owned()].into_iter().collect(),
+            );
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "tv", "brand": "acme", "colour": "red" },
+            { "id": 1, "name": "tv", "brand": "globex", "colour": "blue" },
+            { "id": 2, "name": "tv", "brand": "acme", "colour": "blue" },
+            { "id": 3, "name": "tv", "brand": "initech", "colour": "red" },
+            { "id": 4, "name": "radio", "brand": "globex", "colour": "red" },
+            { "id": 5, "name": "tv", "brand": "globex", "colour": "red" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+#[test]
+fn test_facet_filters() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("tv");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.facet_filter("brand", Filter::from_str("brand IN [acme, globex]").unwrap().unwrap());
+    s.facet_filter("colour", Filter::from_str("colour = red").unwrap().unwrap());
+    let SearchResult { documents_ids, candidates, facet_candidates, .. } = s.execute().unwrap();
+
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "5"]"###);
+    assert_eq!(candidates.len(), 2);
+
+    let facet_ids: BTreeMap<_, _> = facet_candidates
+        .iter()
+        .map(|(facet, docids)| {
+            let docids: Vec<_> = docids.iter().collect();
+            (facet.as_str(), collect_field_values(&index, &txn, "id", &docids))
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{facet_ids:?}"), @r###"{"brand": ["0", "3", "5"], "colour": ["0", "1", "2", "5"]}"###);
+
+    let distribution = FacetDistribution::new(&txn, &index)
+        .facets([("brand", OrderBy::default()), ("colour", OrderBy::default())])
+        .candidates(candidates)
+        .facet_candidates(facet_candidates)
+        .execute()
+        .unwrap();
+    insta::assert_snapshot!(format!("{distribution:?}"), @r###"{"brand": {"acme": 1, "globex": 1, "initech": 1}, "colour": {"blue": 2, "red": 2}}"###);
+}
+
+#[test]
+fn test_facet_filters_with_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the filter of the search applies to the candidates of all the facets.
+    let mut s = Search::new(&txn, &index);
+    s.filter(Filter::from_str("brand != initech").unwrap().unwrap());
+    s.facet_filter("colour", Filter::from_str("colour = red").unwrap().unwrap());
+    let SearchResult { documents_ids, facet_candidates, .. } = s.execute().unwrap();
+
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "4", "5"]"###);
+
+    let colour_docids: Vec<_> = facet_candidates[&S("colour")].iter().collect();
+    let colour_ids = collect_field_values(&index, &txn, "id", &colour_docids);
+    insta::assert_snapshot!(format!("{colour_ids:?}"), @r###"["0", "1", "2", "4", "5"]"###);
+}
@@ -0,0 +1,103 @@
+/*!
+This module tests the function score ranking rule:
+
+1. the documents are bucketed by the score computed from the numeric value of a field
+2. the documents of the same bucket are ranked equally and sorted by the next ranking rules
+3. the documents without a numeric value are part of the last bucket
+4. the previous ranking rules still dominate the function score
+*/
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, FunctionScore, ScoreFunction, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::FunctionScore(FunctionScore {
+                    field: S("popularity"),
+                    function: ScoreFunction::Log,
+                    buckets: 3,
+                }),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "popularity": 1 },
+            { "id": 1, "text": "the quick brown fox", "popularity": 1000 },
+            { "id": 2, "text": "the quick fox", "popularity": 10000 },
+            { "id": 3, "text": "the quick brown fox", "popularity": 900 },
+            { "id": 4, "text": "the quick brown fox" },
+            { "id": 5, "text": "the quick brown fox", "popularity": 50 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+#[test]
+fn test_function_score() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the documents 1 and 3 share the best bucket, the document 2 is less relevant
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "3", "5", "0", "4", "2"]"###);
+
+    let buckets: Vec<_> = document_scores
+        .iter()
+        .map(|scores| match scores.last() {
+            Some(ScoreDetails::FunctionScore(details)) => details.rank.rank,
+            _ => panic!("the last score must be the function score"),
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{buckets:?}"), @"[3, 3, 2, 1, 1, 3]");
+}
+
+#[test]
+fn test_function_score_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "2", "3", "5", "0", "4"]"###);
+}
+
+#[test]
+fn test_function_score_decay() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::FunctionScore(FunctionScore {
+                field: S("popularity"),
+                function: ScoreFunction::Gauss { origin: 1000.0, scale: 100.0 },
+                buckets: 10,
+            })]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the values nearest to 1000 first, the farthest ones are bucketed together
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "3", "0", "2", "4", "5"]"###);
+}
@@ -0,0 +1,122 @@
+/*!
+This module tests the hybrid search, i.e. the fusion of the keyword and semantic results:
+- a semantic ratio of `0.0` returns the keyword results
+- a semantic ratio of `1.0` returns the semantic results
+- the documents found by both searches are ranked first
+- the hybrid score is always displayed first in the score details
+- only the returned semantic hits are added to the keyword candidates
+*/
+
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoreDetails;
+use crate::search::new::tests::collect_field_values;
+use crate::{FusionMethod, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "title": "the quick brown fox",
+            "_vectors": [1.0, 0.0, 0.0],
+        },
+        {
+            "id": 1,
+            "title": "a brown dog",
+            "_vectors": [0.0, 1.0, 0.0],
+        },
+        {
+            "id": 2,
+            "title": "the lazy cat",
+            "_vectors": [0.9, 0.1, 0.0],
+        },
+        {
+            "id": 3,
+            "title": "an orange bird",
+            "_vectors": [0.0, 0.0, 1.0],
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_hybrid_extreme_ratios() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("brown");
+    let SearchResult { documents_ids: keyword_ids, .. } = s.execute().unwrap();
+
+    s.vector([1.0, 0.0, 0.0]);
+    s.hybrid(0.0, FusionMethod::ReciprocalRankFusion);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, keyword_ids);
+
+    let mut s = Search::new(&txn, &index);
+    s.vector([1.0, 0.0, 0.0]);
+    let SearchResult { documents_ids: semantic_ids, .. } = s.execute().unwrap();
+
+    s.query("brown");
+    s.hybrid(1.0, FusionMethod::NormalizedScore);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, semantic_ids);
+}
+
+#[test]
+fn test_hybrid_fusion() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    for fusion in [FusionMethod::ReciprocalRankFusion, FusionMethod::NormalizedScore] {
+        let mut s = Search::new(&txn, &index);
+        s.query("brown");
+        s.vector([1.0, 0.0, 0.0]);
+        s.hybrid(0.5, fusion);
+        let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+        let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+        // `0` is both the best keyword and semantic match, `2` is only a semantic match.
+        assert_eq!(ids[0], "0", "{fusion:?}");
+        assert!(ids.contains(&"2".to_owned()), "{fusion:?}");
+        assert_eq!(ids.len(), 4, "{fusion:?}");
+
+        let scores: Vec<_> = document_scores
+            .iter()
+            .map(|details| ScoreDetails::global_score(details.iter()))
+            .collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]), "{fusion:?}: {scores:?}");
+        assert!(scores.iter().all(|score| (0.0..=1.0).contains(score)), "{fusion:?}: {scores:?}");
+        assert!(document_scores
+            .iter()
+            .all(|details| matches!(details[0], ScoreDetails::Hybrid(_))));
+    }
+}
+
+#[test]
+fn test_hybrid_candidates() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("brown");
+    s.vector([1.0, 0.0, 0.0]);
+    s.hybrid(0.5, FusionMethod::ReciprocalRankFusion);
+    s.limit(1);
+    let SearchResult { candidates, .. } = s.execute().unwrap();
+
+    // the keyword candidates `0` and `1` along with the semantic hit `0`,
+    // not all the documents of the semantic search universe.
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates.len() < index.number_of_documents(&txn).unwrap());
+}
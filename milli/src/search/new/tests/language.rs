@@ -1,5 +1,7 @@
+use charabia::{Language, Script};
+
 use crate::index::tests::TempIndex;
-use crate::{Search, SearchResult};
+use crate::{Error, LocalizedAttributesRule, Search, SearchResult, UserError};
 
 #[test]
 fn test_kanji_language_detection() {
@@ -21,3 +23,78 @@ fn test_kanji_language_detection() {
 
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
 }
+
+#[test]
+fn test_localized_attributes_language() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_localized_attributes_rules(vec![LocalizedAttributesRule {
+                attribute_patterns: vec!["title".to_owned()],
+                locales: vec!["jpn".to_owned()],
+            }]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "東京のお寿司。" },
+            { "id": 1, "title": "東京" },
+        ]))
+        .unwrap();
+
+    // the kanji-only title would be detected as chinese without its locale.
+    let txn = index.read_txn().unwrap();
+    let japanese = index.script_language_documents_ids(&txn, &(Script::Cj, Language::Jpn)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", japanese.unwrap()), @"RoaringBitmap<[0, 1]>");
+    let chinese = index.script_language_documents_ids(&txn, &(Script::Cj, Language::Cmn)).unwrap();
+    assert!(chinese.is_none());
+
+    // every searchable attribute is localized, the query is thus tokenized as japanese.
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    let SearchResult { mut documents_ids, .. } = search.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    search.locales(vec!["jpn".to_owned()]);
+    let SearchResult { mut documents_ids, .. } = search.execute().unwrap();
+    documents_ids.sort_unstable();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // tokenized as chinese, the query is normalized to simplified characters
+    // and doesn't match the japanese documents anymore.
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    search.locales(vec!["cmn".to_owned()]);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    search.locales(vec!["japanese".to_owned()]);
+    let error = search.execute().unwrap_err();
+    assert!(
+        matches!(error, Error::UserError(UserError::InvalidSearchLocale(ref locale)) if locale == "japanese")
+    );
+}
+
+#[test]
+fn test_invalid_localized_attributes_locale() {
+    let index = TempIndex::new();
+
+    let error = index
+        .update_settings(|s| {
+            s.set_localized_attributes_rules(vec![LocalizedAttributesRule {
+                attribute_patterns: vec!["title".to_owned()],
+                locales: vec!["jp".to_owned()],
+            }]);
+        })
+        .unwrap_err();
+    assert!(
+        matches!(error, Error::UserError(UserError::InvalidLocalizedAttributesLocale(ref locale)) if locale == "jp")
+    );
+}
@@ -1,18 +1,31 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod attribute_weights;
+pub mod boolean_query;
+pub mod did_you_mean;
 pub mod distinct;
 pub mod exactness;
+pub mod facet_filters;
+pub mod function_score;
 pub mod geo_sort;
+pub mod hybrid;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
+pub mod numbers;
+pub mod phonetic;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod query_rules;
+pub mod ranking_rules;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
 pub mod typo;
 pub mod typo_proximity;
+pub mod vector_search;
+pub mod vector_sort;
 pub mod words_tms;
 
 fn collect_field_values(
@@ -0,0 +1,120 @@
+/*!
+This module tests the handling of the words containing digits:
+
+1. by default, the typos are allowed on the numbers like on any other word
+2. once the typos are disabled on numbers, the words containing digits are only matched exactly
+3. the typo tolerance of a search can enable them again
+4. by default, the identifiers like `A-1234` are split by the separators
+5. the identifiers of the code attributes are kept whole, and are matched as a phrase
+of their parts in the other attributes
+6. once the index has code attributes, the typos are disabled on numbers unless they are
+explicitly enabled
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy, TypoToleranceOverride};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["sku".to_owned(), "name".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "sku": "A-1234", "name": "charger 123456" },
+            { "id": 1, "sku": "B-1234", "name": "charger 123457" },
+            { "id": 2, "sku": "C-9", "name": "cable compatible with A-1234" },
+            { "id": 3, "sku": "A-5", "name": "cable 1234" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    typo_tolerance: Option<TypoToleranceOverride>,
+) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    if let Some(typo_tolerance) = typo_tolerance {
+        s.typo_tolerance(typo_tolerance);
+    }
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    collect_field_values(index, &txn, "id", &documents_ids)
+}
+
+#[test]
+fn test_disable_typos_on_numbers() {
+    let index = create_index();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    index.update_settings(|s| s.set_disable_typos_on_numbers(true)).unwrap();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0"]"###);
+
+    let ids = search(
+        &index,
+        "charger 123456",
+        Some(TypoToleranceOverride { disable_on_numbers: Some(false), ..Default::default() }),
+    );
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    index.update_settings(|s| s.reset_disable_typos_on_numbers()).unwrap();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+}
+
+#[test]
+fn test_code_attributes() {
+    let index = create_index();
+
+    // "a" and "1234" are matched anywhere in the documents
+    let ids = search(&index, "A-1234", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2", "3"]"###);
+
+    index.update_settings(|s| s.set_code_attributes(vec!["sku".to_owned()])).unwrap();
+
+    // "a-1234" is matched whole in the skus and as the "a 1234" phrase in the names
+    let ids = search(&index, "A-1234", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2"]"###);
+
+    index.update_settings(|s| s.reset_code_attributes()).unwrap();
+
+    let ids = search(&index, "A-1234", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2", "3"]"###);
+}
+
+#[test]
+fn test_disable_typos_on_numbers_of_code_attributes() {
+    let index = create_index();
+
+    index.update_settings(|s| s.set_code_attributes(vec!["sku".to_owned()])).unwrap();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0"]"###);
+
+    index.update_settings(|s| s.set_disable_typos_on_numbers(false)).unwrap();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    index.update_settings(|s| s.reset_disable_typos_on_numbers()).unwrap();
+
+    let ids = search(&index, "charger 123456", None);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0"]"###);
+}
@@ -0,0 +1,107 @@
+/*!
+This module tests the phonetic matching of the query words:
+
+1. the phonetic matching is disabled by default, a query word only matches itself and its typos
+2. once the phonetic attributes are set, a query word also matches the words of these
+attributes sounding like it
+3. the words sounding alike are ranked between the one typo and the two typos words,
+but they are not counted as typos in the score details
+4. the documents added after setting the attributes are also matched phonetically
+5. resetting the attributes disables the phonetic matching
+*/
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["name".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "john schmidt" },
+            { "id": 1, "name": "john smyth" },
+            { "id": 2, "name": "john smith" },
+            { "id": 3, "name": "jane doe" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search_smith(index: &TempIndex) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query("smith");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    collect_field_values(index, &txn, "id", &documents_ids)
+}
+
+#[test]
+fn test_phonetic() {
+    let index = create_index();
+
+    let ids = search_smith(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1"]"###);
+
+    index.update_settings(|s| s.set_phonetic_attributes(vec!["name".to_owned()])).unwrap();
+
+    // "schmidt" sounds like "smith" but is ranked after the one typo "smyth"
+    let ids = search_smith(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1", "0"]"###);
+
+    index.add_documents(documents!([{ "id": 4, "name": "jane schmitt" }])).unwrap();
+
+    let ids = search_smith(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1", "0", "4"]"###);
+
+    index.update_settings(|s| s.reset_phonetic_attributes()).unwrap();
+
+    let ids = search_smith(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1"]"###);
+}
+
+#[test]
+fn test_phonetic_score_details() {
+    let index = create_index();
+    index.update_settings(|s| s.set_phonetic_attributes(vec!["name".to_owned()])).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("smith");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1", "0"]"###);
+
+    // (typo count, phonetic count) of "smith", "smyth" and "schmidt"
+    let counts: Vec<_> = document_scores
+        .iter()
+        .map(|details| {
+            details
+                .iter()
+                .find_map(|detail| match detail {
+                    ScoreDetails::Typo(typo) => Some((typo.typo_count, typo.phonetic_count)),
+                    _ => None,
+                })
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(counts, vec![(0, 0), (1, 0), (0, 1)]);
+
+    // but the words sounding alike are scored like the one typo words
+    let scores: Vec<_> =
+        document_scores.iter().map(|details| ScoreDetails::global_score(details.iter())).collect();
+    assert!(scores[0] > scores[1]);
+    assert_eq!(scores[1], scores[2]);
+}
@@ -76,6 +76,7 @@ fn test_trap_basic() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    phonetic_count: 0,
                 },
             ),
         ],
@@ -90,6 +91,7 @@ fn test_trap_basic() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    phonetic_count: 0,
                 },
             ),
         ],
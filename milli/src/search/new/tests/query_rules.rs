@@ -0,0 +1,213 @@
+/*!
+This module tests the query rules of the index:
+
+1. the rules are only applied when their pattern matches the query or their filter is active
+2. the pinned documents are placed at their position, even when they don't match the query
+3. the pinned documents keep the pagination consistent
+4. the hidden documents are removed from the results
+5. the boosted documents are ranked before the others
+6. the filter of a rule is added to the filter of the search
+7. the pinned and boosted documents are also applied to the vector search
+*/
+
+use std::collections::BTreeMap;
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::query_rules::{Anchoring, PinnedDocument, QueryRuleCondition, QueryRuleConsequence};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Filter, QueryRule, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["name".to_owned()]);
+            s.set_filterable_fields(["id".to_owned(), "brand".to_owned()].into_iter().collect());
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "tv", "brand": "acme" },
+            { "id": 1, "name": "tv", "brand": "globex" },
+            { "id": 2, "name": "tv", "brand": "acme" },
+            { "id": 3, "name": "tv", "brand": "initech" },
+            { "id": 4, "name": "radio", "brand": "globex" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn rule(pattern: &str, consequence: QueryRuleConsequence) -> QueryRule {
+    QueryRule {
+        conditions: vec![QueryRuleCondition {
+            pattern: Some(pattern.to_owned()),
+            anchoring: Anchoring::Contains,
+            filter: None,
+        }],
+        consequence,
+    }
+}
+
+fn set_rules(index: &TempIndex, rules: Vec<(&str, QueryRule)>) {
+    let rules: BTreeMap<_, _> = rules.into_iter().map(|(id, rule)| (id.to_owned(), rule)).collect();
+    index.update_settings(|s| s.set_query_rules(rules.clone())).unwrap();
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    filter: Option<&str>,
+    offset: usize,
+    limit: usize,
+) -> (Vec<String>, u64) {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.offset(offset);
+    s.limit(limit);
+    if let Some(filter) = filter {
+        s.filter(Filter::from_str(filter).unwrap().unwrap());
+    }
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    (collect_field_values(index, &txn, "id", &documents_ids), candidates.len())
+}
+
+#[test]
+fn test_pin() {
+    let index = create_index();
+
+    let pin = |id: &str, position| PinnedDocument { id: id.to_owned(), position };
+    set_rules(
+        &index,
+        vec![(
+            "pin",
+            rule(
+                "tv",
+                QueryRuleConsequence { pin: vec![pin("4", 0), pin("3", 2)], ..Default::default() },
+            ),
+        )],
+    );
+
+    let (ids, total) = search(&index, "tv", None, 0, 10);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["4", "0", "3", "1", "2"] 5"###);
+
+    // the pages are consistent with the whole results
+    let (ids, total) = search(&index, "tv", None, 0, 2);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["4", "0"] 5"###);
+    let (ids, total) = search(&index, "tv", None, 2, 2);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["3", "1"] 5"###);
+    let (ids, total) = search(&index, "tv", None, 4, 2);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["2"] 5"###);
+
+    // the rule is not applied to the other queries
+    let (ids, total) = search(&index, "radio", None, 0, 10);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["4"] 1"###);
+}
+
+#[test]
+fn test_hide_and_filter() {
+    let index = create_index();
+
+    set_rules(
+        &index,
+        vec![
+            ("hide", rule("tv", QueryRuleConsequence { hide: vec![S("0")], ..Default::default() })),
+            (
+                "filter",
+                rule(
+                    "cheap tv",
+                    QueryRuleConsequence { filter: Some(S("brand = acme")), ..Default::default() },
+                ),
+            ),
+        ],
+    );
+
+    let (ids, total) = search(&index, "tv", None, 0, 10);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["1", "2", "3"] 3"###);
+
+    let (ids, total) = search(&index, "cheap tv", None, 0, 10);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["2"] 1"###);
+}
+
+#[test]
+fn test_boost_on_active_filter() {
+    let index = create_index();
+
+    let mut boost = rule(
+        "tv",
+        QueryRuleConsequence { boost: vec![S("brand = initech")], ..Default::default() },
+    );
+    boost.conditions[0].filter = Some(S("brand != globex"));
+    set_rules(&index, vec![("boost", boost)]);
+
+    // the filter of the condition is not active
+    let (ids, _) = search(&index, "tv", None, 0, 10);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1", "2", "3"]"###);
+
+    let (ids, _) = search(&index, "tv", Some("brand != globex"), 0, 10);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["3", "0", "2"]"###);
+
+    // the filter of the condition is one of the filters of the search
+    let (ids, _) = search(&index, "tv", Some("id < 3 AND brand != globex"), 0, 10);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "2"]"###);
+}
+
+#[test]
+fn test_pin_and_boost_vector_search() {
+    let index = create_index();
+
+    // the further the id is from 0 the further the vector is from `[1.0, 0.0]`
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "tv", "brand": "acme", "_vectors": [1.0, 0.0] },
+            { "id": 1, "name": "tv", "brand": "globex", "_vectors": [0.995, 0.0998] },
+            { "id": 2, "name": "tv", "brand": "acme", "_vectors": [0.98, 0.1987] },
+            { "id": 3, "name": "tv", "brand": "initech", "_vectors": [0.9553, 0.2955] },
+            { "id": 4, "name": "radio", "brand": "globex", "_vectors": [0.9211, 0.3894] },
+        ]))
+        .unwrap();
+
+    let pin = PinnedDocument { id: S("4"), position: 0 };
+    set_rules(
+        &index,
+        vec![(
+            "pin-and-boost",
+            rule(
+                "tv",
+                QueryRuleConsequence {
+                    pin: vec![pin],
+                    boost: vec![S("brand = initech")],
+                    ..Default::default()
+                },
+            ),
+        )],
+    );
+
+    let search = |offset, limit| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, &index);
+        s.query("tv");
+        s.vector(vec![1.0, 0.0]);
+        s.offset(offset);
+        s.limit(limit);
+        let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+        (collect_field_values(&index, &txn, "id", &documents_ids), candidates.len())
+    };
+
+    let (ids, total) = search(0, 10);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["4", "3", "0", "1", "2"] 5"###);
+
+    // the pages are consistent with the whole results
+    let (ids, total) = search(0, 2);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["4", "3"] 5"###);
+    let (ids, total) = search(2, 2);
+    insta::assert_snapshot!(format!("{ids:?} {total}"), @r###"["0", "1"] 5"###);
+}
@@ -0,0 +1,111 @@
+/*!
+This module tests the ranking rules given at search time:
+
+1. they replace the ranking rules of the index settings for this search only
+2. the sort ranking rule must be part of them to sort at search time
+3. their attributes must be sortable, or faceted for the function scores
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{AscDesc, Criterion, Error, Member, Search, SearchResult, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Desc(S("rank"))]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "rank": 2 },
+            { "id": 1, "text": "the quick fox", "rank": 0 },
+            { "id": 2, "text": "the fox", "rank": 3 },
+            { "id": 3, "text": "the quick brown", "rank": 1 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+#[test]
+fn test_query_ranking_rules() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // the ranking rules of the settings
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "3", "1"]"###);
+
+    // the ranking rules given at search time
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.ranking_rules(vec![Criterion::Asc(S("rank")), Criterion::Words]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "3", "0"]"###);
+
+    // the ranking rules given at search time are also used by the placeholder search
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Asc(S("rank"))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "3", "0", "2"]"###);
+}
+
+#[test]
+fn test_query_ranking_rules_and_sort() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+
+    // the settings don't have the sort ranking rule
+    let error = s.execute().unwrap_err();
+    assert!(error.to_string().starts_with("You must specify where `sort` is listed"));
+
+    s.ranking_rules(vec![Criterion::Sort, Criterion::Words]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "3", "0"]"###);
+}
+
+#[test]
+fn test_query_ranking_rules_on_unsortable_attributes() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick brown fox");
+    s.ranking_rules(vec![Criterion::Words, Criterion::Asc(S("text"))]);
+    let error = s.execute().unwrap_err();
+    assert!(matches!(
+        error,
+        Error::UserError(UserError::InvalidSearchRankingRuleAttribute { ref field, .. }) if field == "text"
+    ));
+    insta::assert_snapshot!(error, @"Attribute `text` cannot be used by the `text:asc` ranking rule given at search time. Available attributes are: `rank`.");
+
+    s.ranking_rules(vec![Criterion::Words, "text:log".parse().unwrap()]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `text` cannot be used by the `text:log` ranking rule given at search time. Available attributes are: `rank`.");
+
+    // the attributes of the ranking rules of the settings can be used
+    s.ranking_rules(vec![Criterion::Words, Criterion::Asc(S("rank"))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "3", "1"]"###);
+}
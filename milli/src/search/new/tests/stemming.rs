@@ -0,0 +1,106 @@
+/*!
+This module tests the stemming of the query words:
+
+1. the stemming is disabled by default, a query word only matches itself and its typos
+2. once the stemming locales are set, a query word also matches the words sharing its stem
+3. the words sharing a stem are not typos, the exactness rule ranks them below the exact matches
+4. the documents added after setting the locales are also matched by their stems
+5. resetting the locales disables the stemming
+6. the stems are derived for the words too short to have typos and when typos are disabled
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo, Criterion::Exactness]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the fox runs" },
+            { "id": 1, "title": "the fox is running" },
+            { "id": 2, "title": "the fox ran" },
+            { "id": 3, "title": "run, fox, run" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    collect_field_values(index, &txn, "id", &documents_ids)
+}
+
+fn search_running(index: &TempIndex) -> Vec<String> {
+    search(index, "running")
+}
+
+#[test]
+fn test_stemming() {
+    let index = create_index();
+
+    let ids = search_running(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1"]"###);
+
+    index.update_settings(|s| s.set_stemming_locales(vec!["eng".to_owned()])).unwrap();
+
+    // the exact match is ranked first, "ran" is not a stem of "running"
+    let ids = search_running(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "0", "3"]"###);
+
+    index.add_documents(documents!([{ "id": 4, "title": "the fox will run" }])).unwrap();
+
+    let ids = search_running(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "0", "3", "4"]"###);
+
+    index.update_settings(|s| s.reset_stemming_locales()).unwrap();
+
+    let ids = search_running(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1"]"###);
+}
+
+#[test]
+fn test_stemming_short_word() {
+    let index = create_index();
+    index.update_settings(|s| s.set_stemming_locales(vec!["eng".to_owned()])).unwrap();
+
+    // "runs" is too short to have typos but still matches the words sharing its stem
+    let ids = search(&index, "runs");
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1", "3"]"###);
+}
+
+#[test]
+fn test_stemming_without_typos() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_stemming_locales(vec!["eng".to_owned()]);
+            s.set_autorize_typos(false);
+        })
+        .unwrap();
+
+    let ids = search_running(&index);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "0", "3"]"###);
+}
+
+#[test]
+fn test_stemming_invalid_locale() {
+    let index = create_index();
+
+    let error =
+        index.update_settings(|s| s.set_stemming_locales(vec!["jpn".to_owned()])).unwrap_err();
+    insta::assert_snapshot!(error, @"Locale `jpn` does not support stemming. Stemming is supported for the ISO 639-3 codes `ara`, `dan`, `deu`, `ell`, `eng`, `fin`, `fra`, `hun`, `ita`, `nld`, `nob`, `por`, `ron`, `rus`, `spa`, `swe`, `tam` and `tur`.");
+}
@@ -97,6 +97,7 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -106,9 +107,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -150,6 +154,7 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -159,9 +164,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -203,6 +211,7 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -212,9 +221,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -255,6 +267,7 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -264,9 +277,12 @@ fn test_ignore_stop_words() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -314,6 +330,7 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 0,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -323,9 +340,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -355,6 +375,7 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 0,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -364,9 +385,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
@@ -407,6 +431,7 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    phonetic_count: 0,
                 },
             ),
             Proximity(
@@ -416,9 +441,12 @@ fn test_stop_words_in_phrase() {
                 },
             ),
             Fid(
-                Rank {
-                    rank: 1,
-                    max_rank: 1,
+                Fid {
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    attribute_weights: None,
                 },
             ),
             Position(
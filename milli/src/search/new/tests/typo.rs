@@ -16,13 +16,14 @@ if `words` doesn't exist before it.
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. The typo tolerance settings can be overridden for a single search
 */
 
 use std::collections::BTreeMap;
 
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
-use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy, TypoToleranceOverride};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -423,6 +424,76 @@ fn test_typo_exact_attribute() {
     insta::assert_snapshot!(format!("{document_scores:?}"), @"[]");
 }
 
+#[test]
+fn test_typo_tolerance_override() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_searchable_fields(
+                ["text", "tolerant_text"].iter().map(ToString::to_string).collect(),
+            );
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let search = |query: &str, typo_tolerance: TypoToleranceOverride| {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query(query);
+        s.typo_tolerance(typo_tolerance);
+        s.execute().map(|SearchResult { documents_ids, .. }| documents_ids)
+    };
+
+    // no override keeps the settings of the index
+    let documents_ids =
+        search("the quack brown fox jumps over the lazy dog", Default::default()).unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 24]");
+
+    let documents_ids = search(
+        "the quack brown fox jumps over the lazy dog",
+        TypoToleranceOverride { enabled: Some(false), ..Default::default() },
+    )
+    .unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let documents_ids = search(
+        "the quack brown fox jumps over the lazy dog",
+        TypoToleranceOverride { min_word_len_one_typo: Some(6), ..Default::default() },
+    )
+    .unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let documents_ids = search(
+        "the quack brown fox jumps over the lazy dog",
+        TypoToleranceOverride {
+            disable_on_words: Some(["Quack".to_string()].into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    // 1 typo only returns the attribute that is not exact for this search
+    let documents_ids = search(
+        "the quidk brown fox jumps over the lazy dog",
+        TypoToleranceOverride {
+            disable_on_attributes: Some(["text".to_string()].into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[24, 25]");
+
+    let error = search(
+        "the quack brown fox jumps over the lazy dog",
+        TypoToleranceOverride { min_word_len_one_typo: Some(10), ..Default::default() },
+    )
+    .unwrap_err();
+    insta::assert_snapshot!(error, @"`typoTolerance.minWordSizeForTypos` parameter is invalid. `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: 10` and `twoTypos: 9`.");
+}
+
 #[test]
 fn test_ngram_typos() {
     let index = create_index();
@@ -0,0 +1,110 @@
+/*!
+This module tests the vector search with filters:
+- the filter is applied while exploring the HNSW, the limit is reached even with restrictive filters
+- the HNSW and the brute-force scan of the candidates return the same results
+*/
+
+use big_s::S;
+use heed::RoTxn;
+use maplit::hashset;
+
+use crate::documents::documents_batch_reader_from_objects;
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoreDetails;
+use crate::search::new::tests::collect_field_values;
+use crate::{Filter, Search, SearchResult, VectorSearchStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("id"), S("even") });
+        })
+        .unwrap();
+
+    // the further the id is from 0 the further the vector is from `[1.0, 0.0]`
+    let documents = (0..200).map(|i| {
+        let angle = i as f32 * 0.01;
+        let document = serde_json::json!({
+            "id": i,
+            "even": i % 2 == 0,
+            "_vectors": [angle.cos(), angle.sin()],
+        });
+        match document {
+            serde_json::Value::Object(object) => object,
+            _ => unreachable!(),
+        }
+    });
+    index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+    index
+}
+
+#[track_caller]
+fn execute_hnsw_and_brute_force_returns_the_same<'a>(
+    rtxn: &RoTxn<'a>,
+    index: &TempIndex,
+    search: &mut Search<'a>,
+) -> (Vec<usize>, Vec<Vec<ScoreDetails>>) {
+    search.vector_search_strategy(VectorSearchStrategy::AlwaysHnsw);
+    let SearchResult { documents_ids, document_scores: hnsw_scores, .. } =
+        search.execute().unwrap();
+    let hnsw_ids = collect_field_values(index, rtxn, "id", &documents_ids);
+
+    search.vector_search_strategy(VectorSearchStrategy::AlwaysBruteForce);
+    let SearchResult { documents_ids, document_scores: brute_force_scores, .. } =
+        search.execute().unwrap();
+    let brute_force_ids = collect_field_values(index, rtxn, "id", &documents_ids);
+
+    assert_eq!(hnsw_ids, brute_force_ids, "hnsw vs brute force");
+    assert_eq!(hnsw_scores, brute_force_scores, "hnsw vs brute force scores");
+
+    (hnsw_ids.into_iter().map(|id| id.parse().unwrap()).collect(), hnsw_scores)
+}
+
+#[test]
+fn test_vector_search_without_filter() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.vector([1.0, 0.0]);
+    s.limit(5);
+    let (ids, _) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3, 4]");
+
+    s.offset(3);
+    let (ids, _) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 4, 5, 6, 7]");
+}
+
+#[test]
+fn test_vector_search_with_filter() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.vector([1.0, 0.0]);
+    s.limit(5);
+    s.filter(Filter::from_str("even = false").unwrap().unwrap());
+    let (ids, _) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 3, 5, 7, 9]");
+}
+
+#[test]
+fn test_vector_search_with_restrictive_filter() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    // the filtered documents are not part of the nearest neighbours of the query
+    // but they must be returned anyway.
+    let mut s = Search::new(&rtxn, &index);
+    s.vector([1.0, 0.0]);
+    s.limit(5);
+    s.filter(Filter::from_str("id IN [150, 3, 199, 120]").unwrap().unwrap());
+    let (ids, scores) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 120, 150, 199]");
+    assert!(scores.iter().all(|details| matches!(details[..], [ScoreDetails::Vector(_)])));
+}
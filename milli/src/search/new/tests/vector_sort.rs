@@ -0,0 +1,165 @@
+/*!
+This module tests the `_vectorDistance` sort and the `_vectorSimilarity` filter:
+- the HNSW and the brute-force scan of the candidates return the same results
+- the documents without vectors are returned after the documents with vectors
+- the `_vectorSimilarity` filter only compares the vectors of the documents matching the other conditions
+*/
+
+use big_s::S;
+use heed::RoTxn;
+use maplit::hashset;
+
+use crate::documents::documents_batch_reader_from_objects;
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::new::tests::collect_field_values;
+use crate::{AscDesc, Filter, Member, Search, SearchResult, VectorSearchStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(hashset! { S("id") });
+        })
+        .unwrap();
+
+    // the further the id is from 0 the further the vector is from `[1.0, 0.0]`,
+    // the documents 200 and 201 don't have any vector.
+    let documents = (0..202).map(|i| {
+        let angle = i as f32 * 0.01;
+        let document = if i < 200 {
+            serde_json::json!({ "id": i, "_vectors": [angle.cos(), angle.sin()] })
+        } else {
+            serde_json::json!({ "id": i })
+        };
+        match document {
+            serde_json::Value::Object(object) => object,
+            _ => unreachable!(),
+        }
+    });
+    index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+    index
+}
+
+fn vector_distance(vector: Vec<f32>) -> Member {
+    Member::Vector { space: S("default"), vector }
+}
+
+#[track_caller]
+fn execute_hnsw_and_brute_force_returns_the_same<'a>(
+    rtxn: &RoTxn<'a>,
+    index: &TempIndex,
+    search: &mut Search<'a>,
+) -> (Vec<usize>, Vec<Vec<ScoreDetails>>) {
+    search.vector_search_strategy(VectorSearchStrategy::AlwaysHnsw);
+    let SearchResult { documents_ids, document_scores: hnsw_scores, .. } =
+        search.execute().unwrap();
+    let hnsw_ids = collect_field_values(index, rtxn, "id", &documents_ids);
+
+    search.vector_search_strategy(VectorSearchStrategy::AlwaysBruteForce);
+    let SearchResult { documents_ids, document_scores: brute_force_scores, .. } =
+        search.execute().unwrap();
+    let brute_force_ids = collect_field_values(index, rtxn, "id", &documents_ids);
+
+    assert_eq!(hnsw_ids, brute_force_ids, "hnsw vs brute force");
+    assert_eq!(hnsw_scores, brute_force_scores, "hnsw vs brute force scores");
+
+    (hnsw_ids.into_iter().map(|id| id.parse().unwrap()).collect(), hnsw_scores)
+}
+
+#[test]
+fn test_vector_sort() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+    s.sort_criteria(vec![AscDesc::Asc(vector_distance(vec![1.0, 0.0]))]);
+    s.limit(5);
+    let (ids, scores) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3, 4]");
+    match &scores[0][..] {
+        [ScoreDetails::VectorSort(score_details::VectorSort {
+            space,
+            target_vector,
+            ascending: true,
+            similarity: Some(similarity),
+        })] => {
+            assert_eq!(space, "default");
+            assert_eq!(target_vector, &[1.0, 0.0]);
+            assert!((similarity - 1.0).abs() < 1e-6);
+        }
+        details => panic!("unexpected score details {details:?}"),
+    }
+
+    s.offset(196);
+    let (ids, scores) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[196, 197, 198, 199, 200]");
+    assert!(matches!(
+        scores[4][..],
+        [ScoreDetails::VectorSort(score_details::VectorSort { similarity: None, .. })]
+    ));
+
+    s.offset(0);
+    s.sort_criteria(vec![AscDesc::Desc(vector_distance(vec![1.0, 0.0]))]);
+    let (ids, _) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[199, 198, 197, 196, 195]");
+}
+
+#[test]
+fn test_vector_sort_with_filter() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(vector_distance(vec![1.0, 0.0]))]);
+    s.filter(Filter::from_str("id IN [150, 3, 201, 199, 120]").unwrap().unwrap());
+    let (ids, _) = execute_hnsw_and_brute_force_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 120, 150, 199, 201]");
+}
+
+#[test]
+fn test_vector_similarity_filter() {
+    let index = create_index();
+    let rtxn = index.read_txn().unwrap();
+
+    let filter = Filter::from_str("_vectorSimilarity(default, [1, 0]) > 0.9999").unwrap().unwrap();
+    let docids = filter.evaluate(&rtxn, &index).unwrap();
+    let ids = collect_field_values(&index, &rtxn, "id", &docids.into_iter().collect::<Vec<_>>());
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "1"]"###);
+
+    let filter =
+        Filter::from_str("_vectorSimilarity(default, [1, 0]) < 0.5 AND id < 108").unwrap().unwrap();
+    let docids = filter.evaluate(&rtxn, &index).unwrap();
+    let ids = collect_field_values(&index, &rtxn, "id", &docids.into_iter().collect::<Vec<_>>());
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["105", "106", "107"]"###);
+
+    // the range search explores more candidates than a single HNSW search
+    let filter = Filter::from_str("_vectorSimilarity(default, [1, 0]) > 0.5").unwrap().unwrap();
+    let docids = filter.evaluate(&rtxn, &index).unwrap();
+    assert_eq!(docids.len(), 105);
+    let filter = Filter::from_str("_vectorSimilarity(default, [1, 0]) <= 0.5").unwrap().unwrap();
+    let far = filter.evaluate(&rtxn, &index).unwrap();
+    assert_eq!(far.len(), 95);
+    assert!(far.is_disjoint(&docids));
+
+    // the similarity is computed on the documents matching the other conditions
+    let filter =
+        Filter::from_str("id > 102 AND _vectorSimilarity(default, [1, 0]) > 0.5").unwrap().unwrap();
+    let docids = filter.evaluate(&rtxn, &index).unwrap();
+    let ids = collect_field_values(&index, &rtxn, "id", &docids.into_iter().collect::<Vec<_>>());
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["103", "104"]"###);
+
+    let filter = Filter::from_str("_vectorSimilarity(images, [1, 0]) > 0.5").unwrap().unwrap();
+    let error = filter.evaluate(&rtxn, &index).unwrap_err();
+    assert!(error.to_string().starts_with("The vector space `images` does not exist."));
+
+    let filter = Filter::from_str("_vectorSimilarity(default, [1, 0, 0]) > 0.5").unwrap().unwrap();
+    let error = filter.evaluate(&rtxn, &index).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("The vector must have 2 dimensions but has 3 dimensions."));
+}
@@ -0,0 +1,183 @@
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::SearchContext;
+use crate::distance::{Distance, VectorPoint, DEFAULT_VECTOR_SPACE};
+use crate::hnsw::Hnsw;
+use crate::index::db_name::VECTOR_ID_DOCID;
+use crate::{DocumentId, InternalError, Result, UserError, VectorOrArrayOfVectors};
+
+/// Define the strategy used by the vector search.
+/// The parameter of the Dynamic strategy represents the number of candidates from
+/// which we move from an exhaustive scan of the candidates to the HNSW exploration.
+#[derive(Debug, Clone, Copy)]
+pub enum Strategy {
+    AlwaysBruteForce,
+    AlwaysHnsw,
+    Dynamic(usize),
+}
+
+impl Default for Strategy {
+    fn default() -> Self {
+        Strategy::Dynamic(1000)
+    }
+}
+
+impl Strategy {
+    pub fn use_hnsw(&self, candidates: usize) -> bool {
+        match self {
+            Strategy::AlwaysBruteForce => false,
+            Strategy::AlwaysHnsw => true,
+            Strategy::Dynamic(i) => candidates >= *i,
+        }
+    }
+}
+
+/// Returns the `from..from + length` documents of the universe that have the vectors
+/// nearest to the query vector along with their similarity with the query vector.
+pub fn vector_search(
+    ctx: &mut SearchContext,
+    vector: &[f32],
+    vector_space: Option<&str>,
+    universe: &RoaringBitmap,
+    strategy: Strategy,
+    from: usize,
+    length: usize,
+) -> Result<Vec<(DocumentId, f32)>> {
+    let space = vector_space.unwrap_or(DEFAULT_VECTOR_SPACE);
+    let distance = check_vector_space(ctx, space, vector.len())?;
+
+    let hnsw = Hnsw::new(ctx.index, space);
+    if hnsw.is_empty(ctx.txn)? {
+        return Ok(Vec::new());
+    }
+
+    let query = VectorPoint::new(distance, vector.to_vec());
+    let docids = if strategy.use_hnsw(universe.len() as usize) {
+        hnsw_search(ctx, &hnsw, space, &query, universe, from + length)?
+    } else {
+        brute_force_search(ctx, space, &query, universe, from + length)?
+    };
+
+    // return the nearest documents that are also part of the candidates
+    // along with their similarity with the target vector.
+    Ok(docids
+        .into_iter()
+        .skip(from)
+        .take(length)
+        .map(|(docid, d)| (docid, distance.similarity(d)))
+        .collect())
+}
+
+/// Checks that the vector space exists and that its vectors have the same number
+/// of dimensions as the query vector, returns the distance metric of the space.
+pub(super) fn check_vector_space(
+    ctx: &SearchContext,
+    space: &str,
+    dimensions: usize,
+) -> Result<Distance> {
+    let vector_spaces = ctx.index.vector_spaces(ctx.txn)?;
+    let declared = vector_spaces.get(space);
+    if declared.is_none() && space != DEFAULT_VECTOR_SPACE {
+        return Err(UserError::InvalidSearchVectorSpace {
+            space: space.to_string(),
+            available: vector_spaces.into_keys().collect(),
+        }
+        .into());
+    }
+
+    let expected = match declared {
+        Some(declared) => Some(declared.dimensions),
+        None => Hnsw::new(ctx.index, space).dimensions(ctx.txn)?,
+    };
+    if let Some(expected) = expected.filter(|expected| *expected != dimensions) {
+        return Err(UserError::InvalidVectorDimensions { expected, found: dimensions }.into());
+    }
+
+    Ok(declared.map_or(Distance::default(), |s| s.distance))
+}
+
+/// Explores the HNSW of the vector space, only the vectors of the documents
+/// that are part of the universe are returned.
+pub(super) fn hnsw_search(
+    ctx: &mut SearchContext,
+    hnsw: &Hnsw,
+    space: &str,
+    query: &VectorPoint,
+    universe: &RoaringBitmap,
+    count: usize,
+) -> Result<Vec<(DocumentId, f32)>> {
+    let vector_id_docid = ctx.index.vector_id_docid;
+    let txn = ctx.txn;
+    let neighbors = hnsw.search_filtered(txn, query, count, &mut |pid| {
+        let docid = vector_id_docid.get(txn, &(space, pid))?;
+        Ok(docid.map_or(false, |docid| universe.contains(docid.get())))
+    })?;
+
+    let mut docids = Vec::new();
+    let mut uniq_docids = RoaringBitmap::new();
+    for (pid, d) in neighbors {
+        let docid = vector_id_docid
+            .get(txn, &(space, pid))?
+            .ok_or(InternalError::DatabaseMissingEntry { db_name: VECTOR_ID_DOCID, key: None })?
+            .get();
+        if uniq_docids.insert(docid) {
+            docids.push((docid, d));
+            if docids.len() == count {
+                break;
+            }
+        }
+    }
+
+    Ok(docids)
+}
+
+/// Computes the distance between the query and all the vectors of the documents of the universe,
+/// this is faster than exploring the HNSW when the universe is small.
+pub(super) fn brute_force_search(
+    ctx: &mut SearchContext,
+    space: &str,
+    query: &VectorPoint,
+    universe: &RoaringBitmap,
+    count: usize,
+) -> Result<Vec<(DocumentId, f32)>> {
+    let vectors_fid = match ctx.index.fields_ids_map(ctx.txn)?.id("_vectors") {
+        Some(fid) => fid,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut docids = Vec::new();
+    for result in ctx.index.iter_documents(ctx.txn, universe.iter())? {
+        let (docid, obkv) = result?;
+        let vectors = match obkv.get(vectors_fid) {
+            Some(vectors) => vectors,
+            None => continue,
+        };
+
+        let value: Value = serde_json::from_slice(vectors).map_err(InternalError::SerdeJson)?;
+        let vectors = match value {
+            Value::Object(mut spaces) => spaces.remove(space),
+            value if space == DEFAULT_VECTOR_SPACE => Some(value),
+            _ => None,
+        };
+        // the vectors have been validated when the document was indexed
+        let vectors = vectors
+            .and_then(|vectors| serde_json::from_value(vectors).ok())
+            .and_then(VectorOrArrayOfVectors::into_array_of_vectors)
+            .unwrap_or_default();
+
+        let nearest = vectors
+            .into_iter()
+            .map(|vector| query.distance_to(&VectorPoint::new(query.distance_metric(), vector)))
+            .min_by_key(|d| OrderedFloat(*d));
+        if let Some(d) = nearest {
+            docids.push((docid, d));
+        }
+    }
+
+    docids.sort_by_key(|(docid, d)| (OrderedFloat(*d), *docid));
+    docids.truncate(count);
+
+    Ok(docids)
+}
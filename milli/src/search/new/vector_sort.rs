@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use std::iter::FromIterator;
+
+use heed::types::ByteSlice;
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+
+use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
+use super::vector_search::{self, brute_force_search, hnsw_search, Strategy};
+use crate::distance::{Distance, VectorPoint};
+use crate::heed_codec::StrBEU32Codec;
+use crate::hnsw::Hnsw;
+use crate::score_details::{self, ScoreDetails};
+use crate::{Index, Result, SearchContext, SearchLogger};
+
+/// The number of documents retrieved at once when exploring the HNSW.
+const HNSW_CACHE_SIZE: usize = 1000;
+
+/// Returns the ids of the documents that have at least one vector in the given vector space.
+fn vector_space_docids(index: &Index, rtxn: &RoTxn, space: &str) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+    let iter = index
+        .vector_id_docid
+        .remap_key_type::<ByteSlice>()
+        .prefix_iter(rtxn, space.as_bytes())?
+        .remap_key_type::<StrBEU32Codec>();
+    for result in iter {
+        let ((vector_space, _), docid) = result?;
+        // the prefix also matches the spaces whose name starts with the name of this space
+        if vector_space == space {
+            docids.insert(docid.get());
+        }
+    }
+    Ok(docids)
+}
+
+pub struct VectorSort<Q: RankingRuleQueryTrait> {
+    query: Option<Q>,
+
+    strategy: Strategy,
+    ascending: bool,
+    space: String,
+    target: VectorPoint,
+    target_vector: Vec<f32>,
+
+    cached_sorted_docids: VecDeque<(u32, f32)>,
+    vector_candidates: RoaringBitmap,
+}
+
+impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
+    pub fn new(
+        ctx: &SearchContext,
+        strategy: Strategy,
+        space: String,
+        target_vector: Vec<f32>,
+        ascending: bool,
+    ) -> Result<Self> {
+        let distance = vector_search::check_vector_space(ctx, &space, target_vector.len())?;
+        let vector_candidates = vector_space_docids(ctx.index, ctx.txn, &space)?;
+
+        Ok(Self {
+            query: None,
+            strategy,
+            ascending,
+            target: VectorPoint::new(distance, target_vector.clone()),
+            space,
+            target_vector,
+            cached_sorted_docids: VecDeque::new(),
+            vector_candidates,
+        })
+    }
+
+    fn distance_metric(&self) -> Distance {
+        self.target.distance_metric()
+    }
+
+    fn score(&self, similarity: Option<f32>) -> ScoreDetails {
+        ScoreDetails::VectorSort(score_details::VectorSort {
+            space: self.space.clone(),
+            target_vector: self.target_vector.clone(),
+            ascending: self.ascending,
+            similarity,
+        })
+    }
+
+    /// Refill the internal buffer of cached docids based on the strategy.
+    ///
+    /// The HNSW is only able to find the nearest vectors, the descending
+    /// sort always computes the distance with the vectors of all the candidates.
+    fn fill_buffer(&mut self, ctx: &mut SearchContext) -> Result<()> {
+        debug_assert!(self.cached_sorted_docids.is_empty());
+
+        let candidates = self.vector_candidates.len() as usize;
+        let docids = if self.ascending && self.strategy.use_hnsw(candidates) {
+            let hnsw = Hnsw::new(ctx.index, &self.space);
+            hnsw_search(
+                ctx,
+                &hnsw,
+                &self.space,
+                &self.target,
+                &self.vector_candidates,
+                HNSW_CACHE_SIZE,
+            )?
+        } else {
+            brute_force_search(ctx, &self.space, &self.target, &self.vector_candidates, candidates)?
+        };
+
+        self.cached_sorted_docids.extend(docids);
+        Ok(())
+    }
+}
+
+impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
+    fn id(&self) -> String {
+        "vector_sort".to_owned()
+    }
+
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+        query: &Q,
+    ) -> Result<()> {
+        assert!(self.query.is_none());
+
+        self.query = Some(query.clone());
+        self.vector_candidates &= universe;
+
+        if self.vector_candidates.is_empty() {
+            return Ok(());
+        }
+
+        self.fill_buffer(ctx)?;
+        Ok(())
+    }
+
+    #[allow(clippy::only_used_in_recursion)]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        logger: &mut dyn SearchLogger<Q>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Q>>> {
+        let query = self.query.as_ref().unwrap().clone();
+        self.vector_candidates &= universe;
+
+        if self.vector_candidates.is_empty() {
+            return Ok(Some(RankingRuleOutput {
+                query,
+                candidates: universe.clone(),
+                score: self.score(None),
+            }));
+        }
+
+        let ascending = self.ascending;
+        let next = |cache: &mut VecDeque<_>| {
+            if ascending {
+                cache.pop_front()
+            } else {
+                cache.pop_back()
+            }
+        };
+        while let Some((id, distance)) = next(&mut self.cached_sorted_docids) {
+            if self.vector_candidates.contains(id) {
+                let similarity = self.distance_metric().similarity(distance);
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates: RoaringBitmap::from_iter([id]),
+                    score: self.score(Some(similarity)),
+                }));
+            }
+        }
+
+        // if we got out of this loop it means we've exhausted our cache.
+        // we need to refill it and run the function again.
+        self.fill_buffer(ctx)?;
+
+        // the remaining candidates could not be reached by the HNSW exploration
+        // nor by the brute force, we return them as if they had no vectors.
+        if self.cached_sorted_docids.is_empty() {
+            self.vector_candidates.clear();
+        }
+
+        self.next_bucket(ctx, logger, universe)
+    }
+
+    fn end_iteration(&mut self, _ctx: &mut SearchContext<'ctx>, _logger: &mut dyn SearchLogger<Q>) {
+        self.query = None;
+        self.cached_sorted_docids.clear();
+    }
+}
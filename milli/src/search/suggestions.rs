@@ -0,0 +1,301 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::TokenizerBuilder;
+use fst::{IntoStreamer, Streamer};
+use heed::RoTxn;
+use levenshtein_automata::Distance;
+
+use super::{build_dfa, TypoTolerance};
+use crate::{Index, Result};
+
+/// The default number of suggestions returned by a search for suggestions.
+pub const DEFAULT_SUGGESTIONS_LIMIT: usize = 10;
+
+/// The maximum number of words of a suggested phrase, the longer values are truncated.
+pub const MAX_SUGGESTION_WORDS: usize = 8;
+
+/// Returns the phrase as it is stored in the suggestions FST and compared with the queries,
+/// the lemmas of its first words separated by a space.
+pub fn normalize_phrase(text: &str) -> String {
+    let tokenizer = TokenizerBuilder::default().into_tokenizer();
+    let words: Vec<_> = tokenizer
+        .tokenize(text)
+        .filter(|token| token.is_word())
+        .take(MAX_SUGGESTION_WORDS)
+        .map(|token| token.lemma().to_string())
+        .collect();
+    words.join(" ")
+}
+
+/// Searches the phrases of the suggestions FST completing the query,
+/// the typos on the query are allowed like on any query word.
+pub struct SearchForSuggestions<'a> {
+    index: &'a Index,
+    rtxn: &'a RoTxn<'a>,
+    query: Option<String>,
+    limit: usize,
+}
+
+impl<'a> SearchForSuggestions<'a> {
+    pub fn new(index: &'a Index, rtxn: &'a RoTxn<'a>) -> SearchForSuggestions<'a> {
+        SearchForSuggestions { index, rtxn, query: None, limit: DEFAULT_SUGGESTIONS_LIMIT }
+    }
+
+    pub fn query(&mut self, query: impl Into<String>) -> &mut Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Returns the suggestions with the fewest typos first, then the most frequent ones.
+    pub fn execute(&self) -> Result<Vec<Suggestion>> {
+        let fst = self.index.suggestions_fst(self.rtxn)?;
+        let query = self.query.as_deref().map(normalize_phrase).unwrap_or_default();
+
+        let mut suggestions = BestSuggestions::new(self.limit);
+        if query.is_empty() {
+            let mut stream = fst.stream();
+            while let Some((phrase, frequency)) = stream.next() {
+                let phrase = std::str::from_utf8(phrase)?;
+                suggestions.push(phrase, frequency, 0, || 0);
+            }
+        } else {
+            let typo_tolerance = TypoTolerance::new(self.index, self.rtxn, None)?;
+            let typos = typo_tolerance.number_of_typos_allowed(&query);
+            let dfa = build_dfa(&query, typos, true);
+
+            let mut stream = fst.search(&dfa).into_stream();
+            while let Some((phrase, frequency)) = stream.next() {
+                let phrase = std::str::from_utf8(phrase)?;
+                let typos = match dfa.eval(phrase) {
+                    Distance::Exact(typos) => typos,
+                    Distance::AtLeast(_) => continue,
+                };
+                suggestions.push(phrase, frequency, typos, || matched_len(&dfa, phrase, typos));
+            }
+        }
+
+        Ok(suggestions.into_sorted_vec())
+    }
+}
+
+/// Keeps the `limit` best suggestions among the phrases matching the query,
+/// the worst of them being on top of the heap.
+struct BestSuggestions {
+    limit: usize,
+    heap: BinaryHeap<(u8, Reverse<u64>, String, usize)>,
+}
+
+impl BestSuggestions {
+    fn new(limit: usize) -> BestSuggestions {
+        BestSuggestions { limit, heap: BinaryHeap::with_capacity(limit.saturating_add(1)) }
+    }
+
+    /// Keeps the phrase if it is better than the worst of the kept suggestions,
+    /// the length of the matched beginning of the phrase is only computed in this case.
+    fn push(
+        &mut self,
+        phrase: &str,
+        frequency: u64,
+        typos: u8,
+        matched_len: impl FnOnce() -> usize,
+    ) {
+        if self.heap.len() >= self.limit {
+            match self.heap.peek() {
+                Some((worst_typos, Reverse(worst_frequency), worst_phrase, _))
+                    if (typos, Reverse(frequency), phrase)
+                        < (*worst_typos, Reverse(*worst_frequency), worst_phrase.as_str()) =>
+                {
+                    self.heap.pop();
+                }
+                _ => return,
+            }
+        }
+        self.heap.push((typos, Reverse(frequency), phrase.to_string(), matched_len()));
+    }
+
+    fn into_sorted_vec(self) -> Vec<Suggestion> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(typos, Reverse(frequency), phrase, matched_len)| Suggestion {
+                phrase,
+                frequency,
+                typos,
+                matched_len,
+            })
+            .collect()
+    }
+}
+
+/// Returns the length of the shortest beginning of the phrase matching the query
+/// with the given number of typos, it is the part of the phrase typed by the user.
+fn matched_len(dfa: &levenshtein_automata::DFA, phrase: &str, typos: u8) -> usize {
+    phrase
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .find(|&len| dfa.eval(&phrase[..len]) == Distance::Exact(typos))
+        .unwrap_or(phrase.len())
+}
+
+/// A phrase completing the query of a search for suggestions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The normalized phrase.
+    pub phrase: String,
+    /// The number of documents in which the phrase appears.
+    pub frequency: u64,
+    /// The number of typos between the query and the beginning of the phrase.
+    pub typos: u8,
+    /// The length in bytes of the beginning of the phrase matched by the query.
+    pub matched_len: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn suggest(index: &TempIndex, query: &str) -> Vec<(String, u64)> {
+        let rtxn = index.read_txn().unwrap();
+        let suggestions = SearchForSuggestions::new(index, &rtxn).query(query).execute().unwrap();
+        suggestions.into_iter().map(|s| (s.phrase, s.frequency)).collect()
+    }
+
+    #[test]
+    fn normalize_phrases() {
+        assert_eq!(normalize_phrase("  The Lord-of the   RINGS! "), "the lord of the rings");
+        assert_eq!(normalize_phrase("a b c d e f g h i j"), "a b c d e f g h");
+        assert_eq!(normalize_phrase(" ,; "), "");
+    }
+
+    #[test]
+    fn matched_beginning() {
+        let dfa = build_dfa("lord of", 1, true);
+        assert_eq!(matched_len(&dfa, "lord of the rings", 0), "lord of".len());
+        let dfa = build_dfa("lrod", 1, true);
+        assert_eq!(matched_len(&dfa, "lord of the rings", 1), "lord".len());
+    }
+
+    #[test]
+    fn suggestions_of_index() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_filterable_fields(hashset! { S("genre") });
+                s.set_suggestion_attributes(vec![S("title")]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "The Lord of the Rings", "genre": "Fantasy" },
+                { "id": 1, "title": "The Lord of the Rings", "genre": "Fantasy" },
+                { "id": 2, "title": "The Lord of the Flies", "genre": "Novel" },
+                { "id": 3, "title": "Lords of Dogtown", "genre": "Drama" },
+            ]))
+            .unwrap();
+
+        insta::assert_debug_snapshot!(suggest(&index, "the lord of"), @r###"
+        [
+            (
+                "the lord of the rings",
+                2,
+            ),
+            (
+                "the lord of the flies",
+                1,
+            ),
+        ]
+        "###);
+        insta::assert_debug_snapshot!(suggest(&index, "lrods"), @r###"
+        [
+            (
+                "lords of dogtown",
+                1,
+            ),
+        ]
+        "###);
+
+        index.update_settings(|s| s.set_suggestion_facet_attributes(vec![S("genre")])).unwrap();
+        insta::assert_debug_snapshot!(suggest(&index, "fant"), @r###"
+        [
+            (
+                "fantasy",
+                2,
+            ),
+        ]
+        "###);
+
+        index.delete_document("0");
+        insta::assert_debug_snapshot!(suggest(&index, "the lord of the r"), @r###"
+        [
+            (
+                "the lord of the rings",
+                1,
+            ),
+            (
+                "the lord of the flies",
+                1,
+            ),
+        ]
+        "###);
+
+        index
+            .update_settings(|s| {
+                s.reset_suggestion_attributes();
+                s.reset_suggestion_facet_attributes();
+            })
+            .unwrap();
+        assert!(suggest(&index, "the").is_empty());
+    }
+
+    #[test]
+    fn best_suggestions_are_kept() {
+        let mut suggestions = BestSuggestions::new(2);
+        suggestions.push("lord of the flies", 1, 0, || 4);
+        suggestions.push("lords of dogtown", 3, 1, || 5);
+        suggestions.push("lord of the rings", 2, 0, || 4);
+        suggestions.push("lord of war", 1, 0, || 4);
+        let suggestions: Vec<_> =
+            suggestions.into_sorted_vec().into_iter().map(|s| s.phrase).collect();
+        assert_eq!(suggestions, ["lord of the rings", "lord of the flies"]);
+
+        let mut suggestions = BestSuggestions::new(0);
+        suggestions.push("lord of the rings", 2, 0, || unreachable!());
+        assert!(suggestions.into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn suggestions_of_replaced_documents() {
+        let index = TempIndex::new();
+        index.update_settings(|s| s.set_suggestion_attributes(vec![S("title")])).unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "The Lord of the Rings" },
+                { "id": 1, "title": "The Lord of the Rings" },
+            ]))
+            .unwrap();
+        index.add_documents(documents!([{ "id": 1, "title": "The Lord of the Flies" }])).unwrap();
+
+        insta::assert_debug_snapshot!(suggest(&index, "the lord"), @r###"
+        [
+            (
+                "the lord of the flies",
+                1,
+            ),
+            (
+                "the lord of the rings",
+                1,
+            ),
+        ]
+        "###);
+    }
+}
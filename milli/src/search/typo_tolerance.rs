@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+
+use charabia::TokenizerBuilder;
+use heed::RoTxn;
+
+use crate::code_identifiers::contains_digit;
+use crate::error::UserError;
+use crate::{FieldId, Index, Result};
+
+/// Overrides the typo tolerance settings of the index for a single search,
+/// the settings left to `None` keep the values defined in the index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypoToleranceOverride {
+    pub enabled: Option<bool>,
+    pub min_word_len_one_typo: Option<u8>,
+    pub min_word_len_two_typos: Option<u8>,
+    /// The words on which the typos are disabled, they are normalized like the query.
+    pub disable_on_words: Option<BTreeSet<String>>,
+    pub disable_on_attributes: Option<BTreeSet<String>>,
+    pub disable_on_numbers: Option<bool>,
+}
+
+/// The typo tolerance settings used by a search, those of the index
+/// with the overrides of the search applied.
+#[derive(Clone)]
+pub struct TypoTolerance<'t> {
+    pub authorize_typos: bool,
+    pub min_word_len_one_typo: u8,
+    pub min_word_len_two_typos: u8,
+    pub exact_words: Option<fst::Set<Cow<'t, [u8]>>>,
+    /// Whether the words containing digits, e.g. `15` or `a1234`, are matched exactly.
+    pub disable_on_numbers: bool,
+    /// The exact attributes overridden for this search, `None` when those of the index are used.
+    ///
+    /// The exact word databases are computed with the exact attributes of the index
+    /// and can't be relied on when they are overridden.
+    pub exact_attributes: Option<HashSet<FieldId>>,
+}
+
+impl<'t> TypoTolerance<'t> {
+    pub fn new(
+        index: &Index,
+        rtxn: &'t RoTxn,
+        typo_override: Option<&TypoToleranceOverride>,
+    ) -> Result<Self> {
+        let TypoToleranceOverride {
+            enabled,
+            min_word_len_one_typo,
+            min_word_len_two_typos,
+            disable_on_words,
+            disable_on_attributes,
+            disable_on_numbers,
+        } = typo_override.cloned().unwrap_or_default();
+
+        let authorize_typos = match enabled {
+            Some(enabled) => enabled,
+            None => index.authorize_typos(rtxn)?,
+        };
+
+        let min_word_len_one_typo = match min_word_len_one_typo {
+            Some(one) => one,
+            None => index.min_word_len_one_typo(rtxn)?,
+        };
+        let min_word_len_two_typos = match min_word_len_two_typos {
+            Some(two) => two,
+            None => index.min_word_len_two_typos(rtxn)?,
+        };
+        if min_word_len_one_typo > min_word_len_two_typos {
+            return Err(UserError::InvalidSearchMinTypoWordLen(
+                min_word_len_one_typo,
+                min_word_len_two_typos,
+            )
+            .into());
+        }
+
+        let exact_words = match disable_on_words {
+            Some(words) => {
+                let tokenizer = TokenizerBuilder::default().into_tokenizer();
+                let mut words: Vec<String> = words
+                    .iter()
+                    .map(|word| {
+                        tokenizer.tokenize(word).map(|token| token.lemma().to_string()).collect()
+                    })
+                    .collect();
+                // normalization could reorder words
+                words.sort_unstable();
+                words.dedup();
+                Some(fst::Set::from_iter(words)?.map_data(Cow::Owned)?)
+            }
+            None => index.exact_words(rtxn)?,
+        };
+
+        let disable_on_numbers = match disable_on_numbers {
+            Some(disable_on_numbers) => disable_on_numbers,
+            None => index.disable_typos_on_numbers(rtxn)?,
+        };
+
+        let exact_attributes = match disable_on_attributes {
+            Some(attributes) => {
+                let fields_ids_map = index.fields_ids_map(rtxn)?;
+                Some(attributes.iter().filter_map(|name| fields_ids_map.id(name)).collect())
+            }
+            None => None,
+        };
+
+        Ok(TypoTolerance {
+            authorize_typos,
+            min_word_len_one_typo,
+            min_word_len_two_typos,
+            exact_words,
+            disable_on_numbers,
+            exact_attributes,
+        })
+    }
+
+    /// Returns the maximum number of typos allowed on the given word.
+    pub fn number_of_typos_allowed(&self, word: &str) -> u8 {
+        if !self.authorize_typos
+            || word.len() < self.min_word_len_one_typo as usize
+            || self.exact_words.as_ref().map_or(false, |fst| fst.contains(word))
+            || (self.disable_on_numbers && contains_digit(word))
+        {
+            0
+        } else if word.len() < self.min_word_len_two_typos as usize {
+            1
+        } else {
+            2
+        }
+    }
+}
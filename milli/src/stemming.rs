@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+
+use charabia::Language;
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+pub use rust_stemmers::Algorithm;
+use rust_stemmers::Stemmer;
+
+use crate::localized_attributes_rules::parse_locale;
+
+/// The byte separating the stem from the word in the keys of the stems FST.
+const STEM_SEPARATOR: u8 = 0;
+
+/// Returns the stemming algorithm of the given language, `None` if it is not supported.
+pub fn stemming_algorithm(language: Language) -> Option<Algorithm> {
+    match language {
+        Language::Ara => Some(Algorithm::Arabic),
+        Language::Dan => Some(Algorithm::Danish),
+        Language::Nld => Some(Algorithm::Dutch),
+        Language::Eng => Some(Algorithm::English),
+        Language::Fin => Some(Algorithm::Finnish),
+        Language::Fra => Some(Algorithm::French),
+        Language::Deu => Some(Algorithm::German),
+        Language::Ell => Some(Algorithm::Greek),
+        Language::Hun => Some(Algorithm::Hungarian),
+        Language::Ita => Some(Algorithm::Italian),
+        Language::Nob => Some(Algorithm::Norwegian),
+        Language::Por => Some(Algorithm::Portuguese),
+        Language::Ron => Some(Algorithm::Romanian),
+        Language::Rus => Some(Algorithm::Russian),
+        Language::Spa => Some(Algorithm::Spanish),
+        Language::Swe => Some(Algorithm::Swedish),
+        Language::Tam => Some(Algorithm::Tamil),
+        Language::Tur => Some(Algorithm::Turkish),
+        _ => None,
+    }
+}
+
+/// Returns the stemming algorithm of the given ISO 639-3 code,
+/// `None` if the locale is invalid or does not support stemming.
+pub fn parse_stemming_locale(code: &str) -> Option<Algorithm> {
+    parse_locale(code).and_then(stemming_algorithm)
+}
+
+/// The stemmers of the locales of an index.
+pub struct Stemmers {
+    stemmers: Vec<Stemmer>,
+}
+
+impl Stemmers {
+    /// Creates the stemmers of the given locales, the unsupported locales are ignored.
+    pub fn new(locales: &[String]) -> Stemmers {
+        let mut algorithms = Vec::new();
+        for algorithm in locales.iter().filter_map(|locale| parse_stemming_locale(locale)) {
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        Stemmers { stemmers: algorithms.into_iter().map(Stemmer::create).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stemmers.is_empty()
+    }
+
+    /// Returns the distinct stems of the word, one for each stemmer.
+    pub fn stems<'a>(&self, word: &'a str) -> Vec<Cow<'a, str>> {
+        let mut stems: Vec<Cow<str>> = Vec::with_capacity(self.stemmers.len());
+        for stemmer in &self.stemmers {
+            let stem = stemmer.stem(word);
+            if !stems.contains(&stem) {
+                stems.push(stem);
+            }
+        }
+        stems
+    }
+}
+
+/// Returns the key of the stems FST associating the word with its stem.
+pub fn stem_key(stem: &str, word: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(stem.len() + 1 + word.len());
+    key.extend_from_slice(stem.as_bytes());
+    key.push(STEM_SEPARATOR);
+    key.extend_from_slice(word.as_bytes());
+    key
+}
+
+/// Calls `visit` with every word of the stems FST that has the given stem.
+pub fn words_with_stem<A: AsRef<[u8]>>(
+    stems_fst: &fst::Set<A>,
+    stem: &str,
+    mut visit: impl FnMut(&str) -> crate::Result<std::ops::ControlFlow<()>>,
+) -> crate::Result<()> {
+    let mut prefix = String::with_capacity(stem.len() + 1);
+    prefix.push_str(stem);
+    prefix.push(STEM_SEPARATOR as char);
+
+    let mut stream = stems_fst.search(Str::new(&prefix).starts_with()).into_stream();
+    while let Some(key) = stream.next() {
+        let word = std::str::from_utf8(&key[prefix.len()..])?;
+        if visit(word)?.is_break() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::ops::ControlFlow;
+
+    use super::*;
+
+    #[test]
+    fn stems_of_words() {
+        let stemmers = Stemmers::new(&["eng".to_owned(), "jpn".to_owned()]);
+        assert!(!stemmers.is_empty());
+        assert_eq!(stemmers.stems("running"), vec!["run"]);
+        assert_eq!(stemmers.stems("runs"), vec!["run"]);
+
+        assert!(Stemmers::new(&["jpn".to_owned()]).is_empty());
+        assert_eq!(parse_stemming_locale("fra"), Some(Algorithm::French));
+        assert_eq!(parse_stemming_locale("cmn"), None);
+        assert_eq!(parse_stemming_locale("french"), None);
+    }
+
+    #[test]
+    fn find_words_with_stem() {
+        let keys: BTreeSet<_> =
+            [("run", "run"), ("run", "running"), ("run", "runs"), ("runner", "runner")]
+                .iter()
+                .map(|(stem, word)| stem_key(stem, word))
+                .collect();
+        let stems_fst = fst::Set::from_iter(keys).unwrap();
+
+        let mut words = Vec::new();
+        words_with_stem(&stems_fst, "run", |word| {
+            words.push(word.to_owned());
+            Ok(ControlFlow::Continue(()))
+        })
+        .unwrap();
+        assert_eq!(words, vec!["run", "running", "runs"]);
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+use serde::{Deserialize, Serialize};
+
+use crate::rules_update::RulesUpdate;
+use crate::Result;
+
+/// The byte separating the words of a phrase in the keys of the synonyms FST.
+const WORD_SEPARATOR: u8 = 0x1F;
+/// The byte separating the words from one of their synonyms in the keys of the synonyms FST.
+const SYNONYM_SEPARATOR: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SynonymRuleKind {
+    /// The input is rewritten into the synonyms, but the synonyms are not rewritten into the input.
+    OneWay,
+    /// All the synonyms of the group are rewritten into each other.
+    Equivalent,
+}
+
+/// A synonym rule defined by the user, it is identified by a user-defined id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynonymRule {
+    #[serde(rename = "type")]
+    pub kind: SynonymRuleKind,
+    /// The words rewritten into the synonyms, only used by the one-way rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+impl SynonymRule {
+    pub fn one_way(input: impl Into<String>, synonyms: Vec<String>) -> SynonymRule {
+        SynonymRule { kind: SynonymRuleKind::OneWay, input: Some(input.into()), synonyms }
+    }
+
+    pub fn equivalent(synonyms: Vec<String>) -> SynonymRule {
+        SynonymRule { kind: SynonymRuleKind::Equivalent, input: None, synonyms }
+    }
+
+    /// Returns the reason why the rule is invalid, if any.
+    pub fn validate(&self) -> std::result::Result<(), &'static str> {
+        match (self.kind, &self.input) {
+            (SynonymRuleKind::OneWay, None) => Err("a one-way rule must have an `input`"),
+            (SynonymRuleKind::OneWay, Some(_)) if self.synonyms.is_empty() => {
+                Err("a one-way rule must have at least one synonym")
+            }
+            (SynonymRuleKind::Equivalent, Some(_)) => {
+                Err("an equivalent rule cannot have an `input`")
+            }
+            (SynonymRuleKind::Equivalent, None) if self.synonyms.len() < 2 => {
+                Err("an equivalent rule must have at least two synonyms")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the words rewritten by the rule associated with their synonyms.
+    pub fn rewrites(&self) -> Vec<(&str, Vec<&str>)> {
+        match (self.kind, &self.input) {
+            (SynonymRuleKind::OneWay, Some(input)) => {
+                vec![(input.as_str(), self.synonyms.iter().map(String::as_str).collect())]
+            }
+            (SynonymRuleKind::OneWay, None) => Vec::new(),
+            (SynonymRuleKind::Equivalent, _) => self
+                .synonyms
+                .iter()
+                .enumerate()
+                .map(|(i, words)| {
+                    let synonyms = self
+                        .synonyms
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(_, synonym)| synonym.as_str())
+                        .collect();
+                    (words.as_str(), synonyms)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An update of the synonym rules of an index.
+pub type SynonymRulesUpdate = RulesUpdate<SynonymRule>;
+
+fn phrase_key(words: &[String], key: &mut Vec<u8>) {
+    for (i, word) in words.iter().enumerate() {
+        if i != 0 {
+            key.push(WORD_SEPARATOR);
+        }
+        key.extend_from_slice(word.as_bytes());
+    }
+}
+
+fn phrase_from_key(bytes: &[u8]) -> Result<Vec<String>> {
+    bytes
+        .split(|b| *b == WORD_SEPARATOR)
+        .map(|word| Ok(std::str::from_utf8(word)?.to_owned()))
+        .collect()
+}
+
+/// Builds the FST storing the normalized synonyms, its keys are the words
+/// followed by a nul byte and one of their synonyms.
+pub fn synonyms_fst(
+    synonyms: &HashMap<Vec<String>, Vec<Vec<String>>>,
+) -> Result<fst::Set<Vec<u8>>> {
+    let mut keys = Vec::new();
+    for (words, synonyms) in synonyms {
+        for synonym in synonyms {
+            let mut key = Vec::new();
+            phrase_key(words, &mut key);
+            key.push(SYNONYM_SEPARATOR);
+            phrase_key(synonym, &mut key);
+            keys.push(key);
+        }
+    }
+    keys.sort_unstable();
+    keys.dedup();
+
+    Ok(fst::Set::from_iter(keys)?)
+}
+
+/// Returns all the synonyms stored in the FST.
+pub fn synonyms_from_fst<A: AsRef<[u8]>>(
+    fst: &fst::Set<A>,
+) -> Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+    let mut synonyms: HashMap<_, Vec<_>> = HashMap::new();
+    let mut stream = fst.stream();
+    while let Some(key) = stream.next() {
+        let separator = key.iter().position(|b| *b == SYNONYM_SEPARATOR).unwrap_or(key.len());
+        let words = phrase_from_key(&key[..separator])?;
+        let synonym = phrase_from_key(&key[separator + 1..])?;
+        synonyms.entry(words).or_default().push(synonym);
+    }
+    Ok(synonyms)
+}
+
+/// Returns the synonyms of the given words stored in the FST.
+pub fn words_synonyms_from_fst<A: AsRef<[u8]>>(
+    fst: &fst::Set<A>,
+    words: &[String],
+) -> Result<Vec<Vec<String>>> {
+    let mut prefix = Vec::new();
+    phrase_key(words, &mut prefix);
+    prefix.push(SYNONYM_SEPARATOR);
+    let prefix = std::str::from_utf8(&prefix)?;
+
+    let mut synonyms = Vec::new();
+    let mut stream = fst.search(Str::new(prefix).starts_with()).into_stream();
+    while let Some(key) = stream.next() {
+        synonyms.push(phrase_from_key(&key[prefix.len()..])?);
+    }
+    Ok(synonyms)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use big_s::S;
+    use maplit::{btreemap, hashmap};
+
+    use super::*;
+
+    #[test]
+    fn validate_rules() {
+        assert!(SynonymRule::one_way("wolverine", vec![S("logan")]).validate().is_ok());
+        assert!(SynonymRule::one_way("wolverine", vec![]).validate().is_err());
+        assert!(SynonymRule::equivalent(vec![S("phone"), S("mobile")]).validate().is_ok());
+        assert!(SynonymRule::equivalent(vec![S("phone")]).validate().is_err());
+
+        let rule = SynonymRule { kind: SynonymRuleKind::OneWay, input: None, synonyms: vec![] };
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn equivalent_rewrites() {
+        let rule = SynonymRule::equivalent(vec![S("phone"), S("mobile"), S("cell phone")]);
+        assert_eq!(
+            rule.rewrites(),
+            vec![
+                ("phone", vec!["mobile", "cell phone"]),
+                ("mobile", vec!["phone", "cell phone"]),
+                ("cell phone", vec!["phone", "mobile"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn patch_rules() {
+        let rules = btreemap! {
+            S("a") => SynonymRule::equivalent(vec![S("phone"), S("mobile")]),
+            S("b") => SynonymRule::one_way("wolverine", vec![S("logan")]),
+        };
+
+        let mut update = SynonymRulesUpdate::Patch(btreemap! { S("a") => None });
+        update.merge_patch(btreemap! {
+            S("c") => Some(SynonymRule::one_way("hulk", vec![S("banner")])),
+        });
+        let rules = update.apply(rules);
+        insta::assert_debug_snapshot!(rules.keys().collect::<Vec<_>>(), @r###"
+        [
+            "b",
+            "c",
+        ]
+        "###);
+
+        let mut update = SynonymRulesUpdate::Replace(BTreeMap::new());
+        update.merge_patch(btreemap! {
+            S("d") => Some(SynonymRule::one_way("thor", vec![S("odinson")])),
+        });
+        let rules = update.apply(rules);
+        insta::assert_debug_snapshot!(rules.keys().collect::<Vec<_>>(), @r###"
+        [
+            "d",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn synonyms_fst_roundtrip() {
+        let synonyms = hashmap! {
+            vec![S("new"), S("york")] => vec![vec![S("nyc")], vec![S("big"), S("apple")]],
+            vec![S("nyc")] => vec![vec![S("new"), S("york")]],
+        };
+        let fst = synonyms_fst(&synonyms).unwrap();
+
+        let mut from_fst = synonyms_from_fst(&fst).unwrap();
+        from_fst.values_mut().for_each(|synonyms| synonyms.sort_unstable());
+        let mut expected = synonyms.clone();
+        expected.values_mut().for_each(|synonyms| synonyms.sort_unstable());
+        assert_eq!(from_fst, expected);
+
+        let mut new_york = words_synonyms_from_fst(&fst, &[S("new"), S("york")]).unwrap();
+        new_york.sort_unstable();
+        assert_eq!(new_york, vec![vec![S("big"), S("apple")], vec![S("nyc")]]);
+        assert!(words_synonyms_from_fst(&fst, &[S("new")]).unwrap().is_empty());
+    }
+}
@@ -44,6 +44,9 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_id_docid,
+            docid_vector_ids,
+            vector_hnsw_nodes,
+            synonym_rules: _,
             documents,
         } = self.index;
 
@@ -56,13 +59,17 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         // We clean some of the main engine datastructures.
         self.index.put_words_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+        self.index.delete_stems_fst(self.wtxn)?;
+        self.index.delete_phonetic_fst(self.wtxn)?;
+        self.index.delete_suggestions_fst(self.wtxn)?;
+        self.index.delete_suggestion_phrases_fst(self.wtxn)?;
         self.index.put_external_documents_ids(self.wtxn, &ExternalDocumentsIds::default())?;
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_soft_deleted_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
-        self.index.delete_vector_hnsw(self.wtxn)?;
+        self.index.delete_all_vector_hnsw(self.wtxn)?;
 
         // We clean all the faceted documents ids.
         for field_id in faceted_fields {
@@ -104,6 +111,8 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
         vector_id_docid.clear(self.wtxn)?;
+        docid_vector_ids.clear(self.wtxn)?;
+        vector_hnsw_nodes.clear(self.wtxn)?;
         documents.clear(self.wtxn)?;
 
         Ok(number_of_documents)
@@ -155,6 +164,8 @@ mod tests {
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_strings.is_empty(&rtxn).unwrap());
+        assert!(index.docid_vector_ids.is_empty(&rtxn).unwrap());
+        assert!(index.vector_hnsw_nodes.is_empty(&rtxn).unwrap());
         assert!(index.documents.is_empty(&rtxn).unwrap());
     }
 }
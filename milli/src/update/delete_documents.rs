@@ -4,18 +4,17 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use fst::IntoStreamer;
 use heed::types::{ByteSlice, DecodeIgnore, Str, UnalignedSlice};
 use heed::{BytesDecode, BytesEncode, Database, RwIter};
-use instant_distance::PointId;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::facet::delete::FacetsDelete;
-use super::ClearDocuments;
+use super::{ClearDocuments, SuggestionsFst};
 use crate::error::InternalError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FieldDocIdFacetCodec;
 use crate::heed_codec::CboRoaringBitmapCodec;
-use crate::index::Hnsw;
+use crate::hnsw::Hnsw;
 use crate::{
     ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec, BEU32,
 };
@@ -181,6 +180,10 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
 
         self.index.put_field_distribution(self.wtxn, &field_distribution)?;
 
+        // the phrases of the deleted documents are no longer suggested.
+        SuggestionsFst::new(self.wtxn, self.index)
+            .execute_for_deleted_documents(&self.to_delete_docids)?;
+
         soft_deleted_docids |= &self.to_delete_docids;
 
         // We always soft-delete the documents, even if they will be permanently
@@ -249,6 +252,9 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
             vector_id_docid,
+            docid_vector_ids,
+            vector_hnsw_nodes: _,
+            synonym_rules: _,
             documents,
         } = self.index;
         // Remove from the documents database
@@ -438,28 +444,16 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             &self.to_delete_docids,
         )?;
 
-        // An ugly and slow way to remove the vectors from the HNSW
-        // It basically reconstructs the HNSW from scratch without editing the current one.
-        if let Some(current_hnsw) = self.index.vector_hnsw(self.wtxn)? {
-            let mut points = Vec::new();
-            let mut docids = Vec::new();
-            for result in vector_id_docid.iter(self.wtxn)? {
-                let (vector_id, docid) = result?;
-                if !self.to_delete_docids.contains(docid.get()) {
-                    let pid = PointId::from(vector_id.get());
-                    let vector = current_hnsw[pid].clone();
-                    points.push(vector);
-                    docids.push(docid);
-                }
-            }
-
-            let (new_hnsw, pids) = Hnsw::builder().build_hnsw(points);
-
-            vector_id_docid.clear(self.wtxn)?;
-            for (pid, docid) in pids.into_iter().zip(docids) {
-                vector_id_docid.put(self.wtxn, &BEU32::new(pid.into_inner()), &docid)?;
+        // We remove the vectors of the deleted documents from the HNSWs one
+        // by one, the graphs are repaired around the removed points.
+        for docid in &self.to_delete_docids {
+            let docid = BEU32::new(docid);
+            let vector_ids = docid_vector_ids.get(self.wtxn, &docid)?.unwrap_or_default();
+            for (space, pid) in vector_ids {
+                Hnsw::new(self.index, &space).delete(self.wtxn, pid)?;
+                vector_id_docid.delete(self.wtxn, &(space.as_str(), pid))?;
             }
-            self.index.put_vector_hnsw(self.wtxn, &new_hnsw)?;
+            docid_vector_ids.delete(self.wtxn, &docid)?;
         }
 
         self.index.put_soft_deleted_documents_ids(self.wtxn, &RoaringBitmap::new())?;
@@ -1,8 +1,10 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::BufReader;
-use std::{io, mem, str};
+use std::ops::Range;
+use std::{io, iter, mem, str};
 
 use charabia::{Language, Script, SeparatorKind, Token, TokenKind, Tokenizer, TokenizerBuilder};
 use obkv::KvReader;
@@ -10,7 +12,9 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, GrenadParameters};
+use crate::code_identifiers::code_identifier_spans;
 use crate::error::{InternalError, SerializationError};
+use crate::localized_attributes_rules::locales_allow_list;
 use crate::update::index_documents::MergeFn;
 use crate::{
     absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
@@ -24,10 +28,13 @@ pub type ScriptLanguageDocidsMap = HashMap<(Script, Language), RoaringBitmap>;
 /// Returns the generated internal documents ids and a grenad reader
 /// with the list of extracted words from the given chunk of documents.
 #[logging_timer::time]
+#[allow(clippy::too_many_arguments)]
 pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
+    localized_fields: &HashMap<FieldId, Vec<Language>>,
+    code_fields: &HashSet<FieldId>,
     stop_words: Option<&fst::Set<&[u8]>>,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
@@ -51,18 +58,29 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     );
 
     let mut buffers = Buffers::default();
-    let mut tokenizer_builder = TokenizerBuilder::new();
-    if let Some(stop_words) = stop_words {
-        tokenizer_builder.stop_words(stop_words);
-    }
-    if let Some(dictionary) = dictionary {
-        tokenizer_builder.words_dict(dictionary);
-    }
-    if let Some(separators) = allowed_separators {
-        tokenizer_builder.separators(separators);
-    }
+    let tokenizer_settings = TokenizerSettings { stop_words, allowed_separators, dictionary };
+    let mut tokenizer_builder = tokenizer_settings.builder();
     let tokenizer = tokenizer_builder.build();
 
+    // the language detection of the localized fields is restricted to their locales,
+    // their tokenizers are built once for the whole extraction.
+    let localized_allow_lists: HashMap<_, _> = localized_fields
+        .iter()
+        .map(|(field_id, languages)| (*field_id, locales_allow_list(languages)))
+        .collect();
+    let mut localized_tokenizer_builders: HashMap<_, _> = localized_allow_lists
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let mut builder = tokenizer_settings.builder();
+            builder.allow_list(allow_list);
+            (*field_id, builder)
+        })
+        .collect();
+    let localized_tokenizers: HashMap<_, _> = localized_tokenizer_builders
+        .iter_mut()
+        .map(|(field_id, builder)| (*field_id, builder.build()))
+        .collect();
+
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
         let document_id = key
@@ -80,6 +98,8 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         extract_tokens_from_document(
             &obkv,
             searchable_fields,
+            &localized_tokenizers,
+            code_fields,
             &tokenizer,
             max_positions_per_attributes,
             &mut buffers,
@@ -116,6 +136,8 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                 extract_tokens_from_document(
                     &obkv,
                     searchable_fields,
+                    &localized_tokenizers,
+                    code_fields,
                     &tokenizer,
                     max_positions_per_attributes,
                     &mut buffers,
@@ -139,9 +161,35 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         .map(|reader| (documents_ids, reader, script_language_docids))
 }
 
+/// The settings used to build the tokenizers of the documents.
+struct TokenizerSettings<'a> {
+    stop_words: Option<&'a fst::Set<&'a [u8]>>,
+    allowed_separators: Option<&'a [&'a str]>,
+    dictionary: Option<&'a [&'a str]>,
+}
+
+impl<'a> TokenizerSettings<'a> {
+    fn builder(&self) -> TokenizerBuilder<'a, &'a [u8]> {
+        let mut tokenizer_builder = TokenizerBuilder::new();
+        if let Some(stop_words) = self.stop_words {
+            tokenizer_builder.stop_words(stop_words);
+        }
+        if let Some(dictionary) = self.dictionary {
+            tokenizer_builder.words_dict(dictionary);
+        }
+        if let Some(separators) = self.allowed_separators {
+            tokenizer_builder.separators(separators);
+        }
+        tokenizer_builder
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn extract_tokens_from_document(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
+    code_fields: &HashSet<FieldId>,
     tokenizer: &Tokenizer,
     max_positions_per_attributes: u32,
     buffers: &mut Buffers,
@@ -153,7 +201,15 @@ fn extract_tokens_from_document(
             let value = serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
             buffers.field_buffer.clear();
             if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
-                let tokens = process_tokens(tokenizer.tokenize(field))
+                let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(tokenizer);
+                // the identifiers of the code fields are kept whole.
+                let identifiers = match code_fields.contains(&field_id) {
+                    true => code_identifier_spans(field).collect(),
+                    false => Vec::new(),
+                };
+                let tokens = merge_code_identifiers(tokenizer.tokenize(field), identifiers);
+
+                let tokens = process_tokens(tokens)
                     .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                 for (index, token) in tokens {
@@ -219,6 +275,37 @@ fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a st
     }
 }
 
+/// Merges the tokens of the identifiers of a code attribute, e.g. `a`, `-` and `1234`,
+/// into the single words the search keeps whole by adding them to the words dictionary.
+///
+/// The identifiers are given by their ordered byte ranges in the tokenized text.
+fn merge_code_identifiers<'a>(
+    tokens: impl Iterator<Item = Token<'a>>,
+    identifiers: Vec<Range<usize>>,
+) -> impl Iterator<Item = Token<'a>> {
+    let mut spans = identifiers.into_iter().peekable();
+    let mut tokens = tokens.peekable();
+    iter::from_fn(move || {
+        let mut token = tokens.next()?;
+        // the identifiers ending before the token can't be merged anymore.
+        while spans.next_if(|span| span.end <= token.byte_start).is_some() {}
+        if let Some(span) =
+            spans.next_if(|span| span.start == token.byte_start && !token.is_separator())
+        {
+            let mut lemma = mem::take(&mut token.lemma).into_owned();
+            while let Some(next) = tokens.next_if(|next| next.byte_end <= span.end) {
+                lemma.push_str(next.lemma());
+                token.char_end = next.char_end;
+                token.byte_end = next.byte_end;
+            }
+            token.lemma = Cow::Owned(lemma);
+            // the identifiers starting with a stop word, e.g. `a-1234`, are not stop words.
+            token.kind = TokenKind::Word;
+        }
+        Some(token)
+    })
+}
+
 /// take an iterator on tokens and compute their relative position depending on separator kinds
 /// if it's an `Hard` separator we add an additional relative proximity of 8 between words,
 /// else we keep the standard proximity of 1 between words.
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::{self, BufReader};
@@ -29,11 +29,13 @@ pub struct ExtractedFacetValues {
 /// Returns the generated grenad reader containing the docid the fid and the orginal value as key
 /// and the normalized value as value extracted from the given chunk of documents.
 /// We need the fid of the geofields to correctly parse them as numbers if they were sent as strings initially.
+/// The string values of the hierarchical fields are normalized without the empty leading levels of their path.
 #[logging_timer::time]
 pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     faceted_fields: &HashSet<FieldId>,
+    hierarchical_fields: &HashMap<FieldId, String>,
     geo_fields_ids: Option<(FieldId, FieldId)>,
 ) -> Result<ExtractedFacetValues> {
     puffin::profile_function!();
@@ -96,7 +98,14 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                     FilterableValues::Empty => {
                         facet_is_empty_docids.entry(field_id).or_default().insert(document);
                     }
-                    FilterableValues::Values { numbers, strings } => {
+                    FilterableValues::Values { numbers, mut strings } => {
+                        // the empty leading levels of the paths are ignored.
+                        if let Some(separator) = hierarchical_fields.get(&field_id) {
+                            for (normalized, original) in strings.iter_mut() {
+                                *normalized = crate::normalize_facet_path(original, separator);
+                            }
+                        }
+
                         // insert facet numbers in sorter
                         for number in numbers {
                             key_buffer.truncate(size_of::<FieldId>() + size_of::<DocumentId>());
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{self, BufReader};
@@ -6,18 +7,24 @@ use bytemuck::cast_slice;
 use serde_json::{from_slice, Value};
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::distance::DEFAULT_VECTOR_SPACE;
 use crate::error::UserError;
-use crate::{FieldId, InternalError, Result, VectorOrArrayOfVectors};
+use crate::{FieldId, InternalError, Result, VectorOrArrayOfVectors, VectorSpace};
 
-/// Extracts the embedding vector contained in each document under the `_vectors` field.
+/// Extracts the embedding vectors contained in each document under the `_vectors` field.
 ///
-/// Returns the generated grenad reader containing the docid as key associated to the Vec<f32>
+/// The `_vectors` field is either directly the vectors of the default vector space
+/// or an object associating the name of the vector spaces with their vectors.
+///
+/// Returns the generated grenad reader containing the docid and the vector space name
+/// as key associated to the Vec<f32>
 #[logging_timer::time]
 pub fn extract_vector_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     primary_key_id: FieldId,
     vectors_fid: FieldId,
+    vector_spaces: &BTreeMap<String, VectorSpace>,
 ) -> Result<grenad::Reader<BufReader<File>>> {
     puffin::profile_function!();
 
@@ -40,25 +47,59 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
 
         // first we retrieve the _vectors field
         if let Some(vectors) = obkv.get(vectors_fid) {
-            // extract the vectors
-            let vectors = match from_slice(vectors) {
-                Ok(vectors) => VectorOrArrayOfVectors::into_array_of_vectors(vectors),
-                Err(_) => {
-                    return Err(UserError::InvalidVectorsType {
+            let value: Value = from_slice(vectors).map_err(InternalError::SerdeJson)?;
+            let mut spaces: Vec<_> = match value {
+                Value::Object(spaces) => spaces.into_iter().collect(),
+                value => vec![(DEFAULT_VECTOR_SPACE.to_string(), value)],
+            };
+            // the keys must be inserted in order in the grenad writer
+            spaces.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+            for (space, vectors) in spaces {
+                let declared = vector_spaces.get(&space);
+                if declared.is_none() && space != DEFAULT_VECTOR_SPACE {
+                    return Err(UserError::UnknownVectorSpace {
                         document_id: document_id(),
-                        value: from_slice(vectors).map_err(InternalError::SerdeJson)?,
+                        space,
+                        available: vector_spaces.keys().cloned().collect(),
                     }
-                    .into())
+                    .into());
                 }
-            };
 
-            if let Some(vectors) = vectors {
-                for (i, vector) in vectors.into_iter().enumerate().take(u16::MAX as usize) {
-                    let index = u16::try_from(i).unwrap();
-                    let mut key = docid_bytes.to_vec();
-                    key.extend_from_slice(&index.to_be_bytes());
-                    let bytes = cast_slice(&vector);
-                    writer.insert(key, bytes)?;
+                // extract the vectors
+                let vectors = match serde_json::from_value(vectors.clone()) {
+                    Ok(vectors) => VectorOrArrayOfVectors::into_array_of_vectors(vectors),
+                    Err(_) => {
+                        return Err(UserError::InvalidVectorsType {
+                            document_id: document_id(),
+                            value: vectors,
+                        }
+                        .into())
+                    }
+                };
+
+                if let Some(vectors) = vectors {
+                    for (i, vector) in vectors.into_iter().enumerate().take(u16::MAX as usize) {
+                        if let Some(VectorSpace { dimensions, .. }) = declared {
+                            if vector.len() != *dimensions {
+                                return Err(UserError::InvalidVectorSpaceDimensions {
+                                    document_id: document_id(),
+                                    space,
+                                    expected: *dimensions,
+                                    found: vector.len(),
+                                }
+                                .into());
+                            }
+                        }
+
+                        let index = u16::try_from(i).unwrap();
+                        let mut key = docid_bytes.to_vec();
+                        key.extend_from_slice(space.as_bytes());
+                        key.push(0);
+                        key.extend_from_slice(&index.to_be_bytes());
+                        let bytes = cast_slice(&vector);
+                        writer.insert(key, bytes)?;
+                    }
                 }
             }
         }
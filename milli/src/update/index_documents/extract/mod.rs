@@ -10,10 +10,11 @@ mod extract_word_fid_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 
+use charabia::Language;
 use crossbeam_channel::Sender;
 use log::debug;
 use rayon::prelude::*;
@@ -34,7 +35,7 @@ use super::helpers::{
     GrenadParameters, MergeFn, MergeableReader,
 };
 use super::{helpers, TypedChunk};
-use crate::{FieldId, Result};
+use crate::{FieldId, Result, VectorSpace};
 
 /// Extract data for each databases from obkv documents in parallel.
 /// Send data in grenad file over provided Sender.
@@ -45,10 +46,14 @@ pub(crate) fn data_from_obkv_documents(
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: Option<HashSet<FieldId>>,
+    localized_fields: HashMap<FieldId, Vec<Language>>,
+    code_fields: HashSet<FieldId>,
     faceted_fields: HashSet<FieldId>,
+    hierarchical_fields: HashMap<FieldId, String>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     vectors_field_id: Option<FieldId>,
+    vector_spaces: BTreeMap<String, VectorSpace>,
     stop_words: Option<fst::Set<&[u8]>>,
     allowed_separators: Option<&[&str]>,
     dictionary: Option<&[&str]>,
@@ -65,6 +70,7 @@ pub(crate) fn data_from_obkv_documents(
                 indexer,
                 lmdb_writer_sx.clone(),
                 vectors_field_id,
+                &vector_spaces,
                 primary_key_id,
             )
         })
@@ -80,7 +86,10 @@ pub(crate) fn data_from_obkv_documents(
                     indexer,
                     lmdb_writer_sx.clone(),
                     &searchable_fields,
+                    &localized_fields,
+                    &code_fields,
                     &faceted_fields,
+                    &hierarchical_fields,
                     primary_key_id,
                     geo_fields_ids,
                     &stop_words,
@@ -278,6 +287,7 @@ fn send_original_documents_data(
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     vectors_field_id: Option<FieldId>,
+    vector_spaces: &BTreeMap<String, VectorSpace>,
     primary_key_id: FieldId,
 ) -> Result<()> {
     let original_documents_chunk =
@@ -286,12 +296,14 @@ fn send_original_documents_data(
     if let Some(vectors_field_id) = vectors_field_id {
         let documents_chunk_cloned = original_documents_chunk.clone();
         let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        let vector_spaces = vector_spaces.clone();
         rayon::spawn(move || {
             let result = extract_vector_points(
                 documents_chunk_cloned,
                 indexer,
                 primary_key_id,
                 vectors_field_id,
+                &vector_spaces,
             );
             let _ = match result {
                 Ok(vector_points) => {
@@ -320,7 +332,10 @@ fn send_and_extract_flattened_documents_data(
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: &Option<HashSet<FieldId>>,
+    localized_fields: &HashMap<FieldId, Vec<Language>>,
+    code_fields: &HashSet<FieldId>,
     faceted_fields: &HashSet<FieldId>,
+    hierarchical_fields: &HashMap<FieldId, String>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: &Option<fst::Set<&[u8]>>,
@@ -364,6 +379,8 @@ fn send_and_extract_flattened_documents_data(
                         flattened_documents_chunk.clone(),
                         indexer,
                         searchable_fields,
+                        localized_fields,
+                        code_fields,
                         stop_words.as_ref(),
                         *allowed_separators,
                         *dictionary,
@@ -393,6 +410,7 @@ fn send_and_extract_flattened_documents_data(
                     flattened_documents_chunk.clone(),
                     indexer,
                     faceted_fields,
+                    hierarchical_fields,
                     geo_fields_ids,
                 )?;
 
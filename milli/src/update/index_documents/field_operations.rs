@@ -0,0 +1,334 @@
+//! Field-level operations that can be sent in place of plain values when editing documents
+//! with the [`IndexDocumentsMethod::EditDocuments`](super::IndexDocumentsMethod::EditDocuments)
+//! method.
+//!
+//! An operation is a JSON object with a single key starting with a `$`, e.g. `{ "$inc": 1 }`.
+//! Once parsed by the transform, the operations are stored in the obkvs behind a
+//! [`FIELD_OPERATIONS_PREFIX`] byte, which can never start a valid JSON value. They stay in
+//! this pending state until they are merged with a plain value of the field, or until the
+//! end of the transform where they are resolved against a missing field.
+//!
+//! The edited documents also carry an [`Edition`] under the reserved [`EDITION_FIELD_ID`],
+//! keeping track of the additions they come from and of the editions that were rejected
+//! when merged with a conflicting version of the document from the same batch.
+
+use obkv::{KvReaderU16, KvWriter};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+use crate::error::InternalError;
+use crate::{FieldId, Result};
+
+/// The byte prepended to the JSON array of pending operations stored in an obkv value.
+pub const FIELD_OPERATIONS_PREFIX: u8 = 0;
+
+/// The field of the obkvs of the edited documents storing their [`Edition`], it is
+/// the last one so that it is always written after the fields of the document.
+pub const EDITION_FIELD_ID: FieldId = FieldId::MAX;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldOperation {
+    /// `$inc`: adds the number to the current value, a missing value counts as zero.
+    Increment(Number),
+    /// `$push`: appends the value to the current array, a missing value counts as an empty array.
+    Push(Value),
+    /// `$pull`: removes every occurrence of the value from the current array.
+    Pull(Value),
+    /// `$unset`: removes the field from the document.
+    Unset,
+    /// `$setIfMissing`: sets the value only if the document doesn't already have this field.
+    SetIfMissing(Value),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FieldOperationError {
+    #[error("Unknown operator `{0}`. Available operators are `$inc`, `$push`, `$pull`, `$unset` and `$setIfMissing`.")]
+    UnknownOperator(String),
+    #[error("The `$inc` operator expects a number but received `{0}`.")]
+    InvalidIncrement(Value),
+    #[error("The `{operator}` operator cannot be applied on the existing value `{value}`.")]
+    IncompatibleValue { operator: &'static str, value: Value },
+}
+
+impl FieldOperation {
+    /// Interprets the value as an operation if it is an object with a single `$`-prefixed key.
+    ///
+    /// Returns `Ok(None)` if the value must be considered as a plain value.
+    pub fn from_value(value: &Value) -> std::result::Result<Option<Self>, FieldOperationError> {
+        let (operator, argument) = match value {
+            Value::Object(object) if object.len() == 1 => object.iter().next().unwrap(),
+            _ => return Ok(None),
+        };
+
+        if !operator.starts_with('$') {
+            return Ok(None);
+        }
+
+        let operation = match operator.as_str() {
+            "$inc" => match argument {
+                Value::Number(number) => FieldOperation::Increment(number.clone()),
+                otherwise => return Err(FieldOperationError::InvalidIncrement(otherwise.clone())),
+            },
+            "$push" => FieldOperation::Push(argument.clone()),
+            "$pull" => FieldOperation::Pull(argument.clone()),
+            "$unset" => FieldOperation::Unset,
+            "$setIfMissing" => FieldOperation::SetIfMissing(argument.clone()),
+            otherwise => return Err(FieldOperationError::UnknownOperator(otherwise.to_string())),
+        };
+
+        Ok(Some(operation))
+    }
+
+    pub fn operator(&self) -> &'static str {
+        match self {
+            FieldOperation::Increment(_) => "$inc",
+            FieldOperation::Push(_) => "$push",
+            FieldOperation::Pull(_) => "$pull",
+            FieldOperation::Unset => "$unset",
+            FieldOperation::SetIfMissing(_) => "$setIfMissing",
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        let argument = match self {
+            FieldOperation::Increment(number) => Value::Number(number.clone()),
+            FieldOperation::Push(value)
+            | FieldOperation::Pull(value)
+            | FieldOperation::SetIfMissing(value) => value.clone(),
+            FieldOperation::Unset => Value::Bool(true),
+        };
+
+        let mut object = Map::new();
+        object.insert(self.operator().to_string(), argument);
+        Value::Object(object)
+    }
+
+    /// Applies the operation on the current value of a field, `None` meaning that the field
+    /// is missing from the document.
+    ///
+    /// Returns the new value of the field, `None` meaning that it must be removed.
+    pub fn apply(
+        &self,
+        current: Option<Value>,
+    ) -> std::result::Result<Option<Value>, FieldOperationError> {
+        let incompatible =
+            |value| FieldOperationError::IncompatibleValue { operator: self.operator(), value };
+
+        match (self, current) {
+            (FieldOperation::Increment(number), None | Some(Value::Null)) => {
+                Ok(Some(Value::Number(number.clone())))
+            }
+            (FieldOperation::Increment(number), Some(Value::Number(current))) => {
+                match add_numbers(&current, number) {
+                    Some(sum) => Ok(Some(Value::Number(sum))),
+                    None => Err(incompatible(Value::Number(current))),
+                }
+            }
+            (FieldOperation::Push(value), None | Some(Value::Null)) => {
+                Ok(Some(Value::Array(vec![value.clone()])))
+            }
+            (FieldOperation::Push(value), Some(Value::Array(mut array))) => {
+                array.push(value.clone());
+                Ok(Some(Value::Array(array)))
+            }
+            (FieldOperation::Pull(_), None) => Ok(None),
+            (FieldOperation::Pull(_), Some(Value::Null)) => Ok(Some(Value::Null)),
+            (FieldOperation::Pull(value), Some(Value::Array(mut array))) => {
+                array.retain(|v| v != value);
+                Ok(Some(Value::Array(array)))
+            }
+            (FieldOperation::Unset, _) => Ok(None),
+            (FieldOperation::SetIfMissing(value), None) => Ok(Some(value.clone())),
+            (FieldOperation::SetIfMissing(_), Some(current)) => Ok(Some(current)),
+            (
+                FieldOperation::Increment(_) | FieldOperation::Push(_) | FieldOperation::Pull(_),
+                Some(current),
+            ) => Err(incompatible(current)),
+        }
+    }
+
+    /// Applies all the operations one after the other on the current value of a field.
+    pub fn apply_all<'o>(
+        operations: impl IntoIterator<Item = &'o FieldOperation>,
+        current: Option<Value>,
+    ) -> std::result::Result<Option<Value>, FieldOperationError> {
+        operations.into_iter().try_fold(current, |current, operation| operation.apply(current))
+    }
+}
+
+/// Adds two JSON numbers, staying in the integer space as long as it doesn't overflow.
+fn add_numbers(left: &Number, right: &Number) -> Option<Number> {
+    match (left.as_i64(), right.as_i64()) {
+        (Some(left), Some(right)) => match left.checked_add(right) {
+            Some(sum) => Some(Number::from(sum)),
+            None => Number::from_f64(left as f64 + right as f64),
+        },
+        _ => Number::from_f64(left.as_f64()? + right.as_f64()?),
+    }
+}
+
+/// The additions an edited document comes from and the editions of
+/// this document that were rejected while merging its versions.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Edition {
+    /// The numbers of the additions of the transform merged in this version of the document.
+    pub additions: Vec<usize>,
+    pub rejections: Vec<RejectedEdition>,
+}
+
+/// An edition whose operations couldn't be applied on the version of the document
+/// written by a previous edition of the same batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectedEdition {
+    /// The numbers of the additions of the rejected version, all of them are rejected together.
+    pub additions: Vec<usize>,
+    pub field_id: FieldId,
+    pub operation: Value,
+    pub value: Value,
+}
+
+impl Edition {
+    pub fn new(addition: usize) -> Self {
+        Edition { additions: vec![addition], rejections: Vec::new() }
+    }
+
+    /// Returns the edition stored in the obkv, if any.
+    pub fn from_obkv(obkv: KvReaderU16) -> Option<Self> {
+        obkv.get(EDITION_FIELD_ID).and_then(|edition| serde_json::from_slice(edition).ok())
+    }
+
+    pub fn encode(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.clear();
+        serde_json::to_writer(&mut *buffer, self).map_err(InternalError::SerdeJson)?;
+        Ok(())
+    }
+}
+
+impl RejectedEdition {
+    /// Returns the error of the operation that couldn't be applied.
+    pub fn error(&self) -> FieldOperationError {
+        // the operation was encoded by the merge function, it is always valid.
+        let operator = FieldOperation::from_value(&self.operation)
+            .ok()
+            .flatten()
+            .map_or("", |operation| operation.operator());
+        FieldOperationError::IncompatibleValue { operator, value: self.value.clone() }
+    }
+}
+
+/// Encodes the pending operations of a field into the given buffer.
+pub fn encode_field_operations(operations: &[FieldOperation], buffer: &mut Vec<u8>) -> Result<()> {
+    let operations: Vec<_> = operations.iter().map(FieldOperation::to_value).collect();
+    buffer.clear();
+    buffer.push(FIELD_OPERATIONS_PREFIX);
+    serde_json::to_writer(&mut *buffer, &operations).map_err(InternalError::SerdeJson)?;
+    Ok(())
+}
+
+/// Decodes the pending operations of a field, returns `None` if the obkv value is a plain JSON value.
+pub fn decode_field_operations(value: &[u8]) -> Option<Vec<FieldOperation>> {
+    match value.split_first() {
+        Some((&FIELD_OPERATIONS_PREFIX, operations)) => {
+            let operations: Vec<Value> = serde_json::from_slice(operations).ok()?;
+            operations.iter().map(|op| FieldOperation::from_value(op).ok().flatten()).collect()
+        }
+        _ => None,
+    }
+}
+
+/// Writes the obkv into the buffer after having applied all the pending operations
+/// against missing fields, i.e. the fields that never got a plain value.
+///
+/// The edition of the document is removed from the obkv and returned.
+pub fn resolve_field_operations(obkv: KvReaderU16, buffer: &mut Vec<u8>) -> Result<Edition> {
+    buffer.clear();
+    let mut edition = Edition::default();
+    let mut writer = KvWriter::new(buffer);
+    for (field_id, value) in obkv.iter() {
+        if field_id == EDITION_FIELD_ID {
+            edition = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            continue;
+        }
+        match decode_field_operations(value) {
+            Some(operations) => {
+                // applying operations on a missing field can't fail.
+                if let Ok(Some(value)) = FieldOperation::apply_all(&operations, None) {
+                    let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
+                    writer.insert(field_id, value)?;
+                }
+            }
+            None => writer.insert(field_id, value)?,
+        }
+    }
+    writer.finish()?;
+    Ok(edition)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply(operation: Value, current: Option<Value>) -> Option<Value> {
+        let operation = FieldOperation::from_value(&operation).unwrap().unwrap();
+        operation.apply(current).unwrap()
+    }
+
+    #[test]
+    fn parse_operations() {
+        assert_eq!(FieldOperation::from_value(&json!(1)), Ok(None));
+        assert_eq!(FieldOperation::from_value(&json!({ "inc": 1 })), Ok(None));
+        assert_eq!(FieldOperation::from_value(&json!({ "$inc": 1, "$push": 2 })), Ok(None));
+        assert_eq!(
+            FieldOperation::from_value(&json!({ "$inc": 1 })),
+            Ok(Some(FieldOperation::Increment(1.into())))
+        );
+        assert_eq!(
+            FieldOperation::from_value(&json!({ "$inc": "1" })),
+            Err(FieldOperationError::InvalidIncrement(json!("1")))
+        );
+        assert_eq!(
+            FieldOperation::from_value(&json!({ "$mul": 2 })),
+            Err(FieldOperationError::UnknownOperator(String::from("$mul")))
+        );
+    }
+
+    #[test]
+    fn apply_operations() {
+        assert_eq!(apply(json!({ "$inc": 2 }), None), Some(json!(2)));
+        assert_eq!(apply(json!({ "$inc": 2 }), Some(json!(40))), Some(json!(42)));
+        assert_eq!(apply(json!({ "$inc": 0.5 }), Some(json!(1))), Some(json!(1.5)));
+        assert_eq!(apply(json!({ "$push": "b" }), None), Some(json!(["b"])));
+        assert_eq!(apply(json!({ "$push": "b" }), Some(json!(["a"]))), Some(json!(["a", "b"])));
+        assert_eq!(
+            apply(json!({ "$pull": "a" }), Some(json!(["a", "b", "a"]))),
+            Some(json!(["b"]))
+        );
+        assert_eq!(apply(json!({ "$pull": "a" }), None), None);
+        assert_eq!(apply(json!({ "$unset": true }), Some(json!("hello"))), None);
+        assert_eq!(apply(json!({ "$setIfMissing": 1 }), None), Some(json!(1)));
+        assert_eq!(apply(json!({ "$setIfMissing": 1 }), Some(json!(2))), Some(json!(2)));
+
+        let operation = FieldOperation::Increment(1.into());
+        assert_eq!(
+            operation.apply(Some(json!("hello"))),
+            Err(FieldOperationError::IncompatibleValue { operator: "$inc", value: json!("hello") })
+        );
+    }
+
+    #[test]
+    fn encode_decode_operations() {
+        let operations = vec![
+            FieldOperation::Increment(1.into()),
+            FieldOperation::Push(json!({ "$inc": 1 })),
+            FieldOperation::Unset,
+        ];
+        let mut buffer = Vec::new();
+        encode_field_operations(&operations, &mut buffer).unwrap();
+        assert_eq!(buffer[0], FIELD_OPERATIONS_PREFIX);
+        assert_eq!(decode_field_operations(&buffer), Some(operations));
+        assert_eq!(decode_field_operations(br#"{"$inc":1}"#), None);
+    }
+}
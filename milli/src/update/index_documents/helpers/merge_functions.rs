@@ -4,10 +4,15 @@ use std::io;
 use std::result::Result as StdResult;
 
 use roaring::RoaringBitmap;
+use serde_json::Value;
 
 use crate::heed_codec::CboRoaringBitmapCodec;
+use crate::update::index_documents::field_operations::{
+    decode_field_operations, encode_field_operations, Edition, FieldOperation, FieldOperationError,
+    RejectedEdition, EDITION_FIELD_ID,
+};
 use crate::update::index_documents::transform::Operation;
-use crate::Result;
+use crate::{FieldId, Result};
 
 pub type MergeFn = for<'a> fn(&[u8], &[Cow<'a, [u8]>]) -> Result<Cow<'a, [u8]>>;
 
@@ -91,10 +96,139 @@ pub fn merge_two_obkvs(base: obkv::KvReaderU16, update: obkv::KvReaderU16, buffe
     writer.finish().unwrap();
 }
 
+/// Merge two obkvs, applying the field operations of the update on the values of the base.
+///
+/// The operations of a field that isn't in the base are kept pending and are concatenated
+/// with the ones of the following updates, they will be resolved at the end of the transform.
+///
+/// The operations were validated against the version of the document stored in the database,
+/// they can only fail if a previous update of the same batch changed the type of the field.
+/// The whole update is rejected in this case and recorded in the edition of the document.
+pub fn merge_two_obkvs_and_field_operations(
+    base: obkv::KvReaderU16,
+    update: obkv::KvReaderU16,
+    buffer: &mut Vec<u8>,
+) {
+    let mut edition = Edition::from_obkv(base).unwrap_or_default();
+    let update_edition = Edition::from_obkv(update).unwrap_or_default();
+    edition.rejections.extend(update_edition.rejections);
+
+    let fields = match apply_field_operations(base, update) {
+        Ok(fields) => {
+            edition.additions.extend(update_edition.additions);
+            fields
+        }
+        Err((field_id, operation, error)) => {
+            let value = match error {
+                FieldOperationError::IncompatibleValue { value, .. } => value,
+                _ => Value::Null,
+            };
+            edition.rejections.push(RejectedEdition {
+                additions: update_edition.additions,
+                field_id,
+                operation: operation.to_value(),
+                value,
+            });
+            base.iter()
+                .filter(|(key, _)| *key != EDITION_FIELD_ID)
+                .map(|(key, value)| (key, Cow::from(value)))
+                .collect()
+        }
+    };
+
+    buffer.clear();
+    let mut writer = obkv::KvWriter::new(buffer);
+    for (key, value) in fields {
+        writer.insert(key, value).unwrap();
+    }
+    if edition != Edition::default() {
+        let mut edition_buffer = Vec::new();
+        edition.encode(&mut edition_buffer).unwrap();
+        writer.insert(EDITION_FIELD_ID, edition_buffer).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+/// Returns the fields of the base updated with the values and operations of the update,
+/// or the first operation that couldn't be applied on the value of the base.
+#[allow(clippy::type_complexity)]
+fn apply_field_operations<'a>(
+    base: obkv::KvReaderU16<'a>,
+    update: obkv::KvReaderU16<'a>,
+) -> StdResult<Vec<(FieldId, Cow<'a, [u8]>)>, (FieldId, FieldOperation, FieldOperationError)> {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    let base = base.iter().filter(|(key, _)| *key != EDITION_FIELD_ID);
+    let update = update.iter().filter(|(key, _)| *key != EDITION_FIELD_ID);
+
+    let mut fields = Vec::new();
+    for eob in merge_join_by(base, update, |(b, _), (u, _)| b.cmp(u)) {
+        let (key, base, update) = match eob {
+            Left((k, v)) => {
+                fields.push((k, Cow::from(v)));
+                continue;
+            }
+            Right((k, update)) => (k, None, update),
+            Both((k, base), (_, update)) => (k, Some(base), update),
+        };
+
+        let operations = match decode_field_operations(update) {
+            Some(operations) => operations,
+            // a plain value always replaces the previous one
+            None => {
+                fields.push((key, Cow::from(update)));
+                continue;
+            }
+        };
+
+        match base.map(|base| (base, decode_field_operations(base))) {
+            // there is no known value for this field yet, the operations stay pending
+            None => fields.push((key, Cow::from(update))),
+            Some((_, Some(mut pending))) => {
+                pending.extend(operations);
+                let mut value_buffer = Vec::new();
+                encode_field_operations(&pending, &mut value_buffer).unwrap();
+                fields.push((key, Cow::from(value_buffer)));
+            }
+            Some((base, None)) => {
+                let mut current = serde_json::from_slice(base).ok();
+                for operation in operations {
+                    match operation.apply(current) {
+                        Ok(value) => current = value,
+                        Err(error) => return Err((key, operation, error)),
+                    }
+                }
+                // the operations may have removed the field
+                if let Some(value) = current {
+                    fields.push((key, Cow::from(serde_json::to_vec(&value).unwrap())));
+                }
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
 /// Merge all the obks in the order we see them.
 pub fn merge_obkvs_and_operations<'a>(
     _key: &[u8],
     obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    inner_merge_obkvs_and_operations(obkvs, merge_two_obkvs)
+}
+
+/// Merge all the obks in the order we see them, applying the field operations of the edited documents.
+pub fn merge_obkvs_and_field_operations<'a>(
+    _key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    inner_merge_obkvs_and_operations(obkvs, merge_two_obkvs_and_field_operations)
+}
+
+fn inner_merge_obkvs_and_operations<'a>(
+    obkvs: &[Cow<'a, [u8]>],
+    merge_two_obkvs: fn(obkv::KvReaderU16, obkv::KvReaderU16, &mut Vec<u8>),
 ) -> Result<Cow<'a, [u8]>> {
     // [add, add, delete, add, add]
     // we can ignore everything that happened before the last delete.
@@ -14,8 +14,8 @@ pub use grenad_helpers::{
 };
 pub use merge_functions::{
     concat_u32s_array, keep_first, keep_latest_obkv, merge_btreeset_string,
-    merge_cbo_roaring_bitmaps, merge_obkvs_and_operations, merge_roaring_bitmaps, merge_two_obkvs,
-    serialize_roaring_bitmap, MergeFn,
+    merge_cbo_roaring_bitmaps, merge_obkvs_and_field_operations, merge_obkvs_and_operations,
+    merge_roaring_bitmaps, merge_two_obkvs, serialize_roaring_bitmap, MergeFn,
 };
 
 use crate::MAX_WORD_LENGTH;
@@ -1,5 +1,6 @@
 mod enrich;
 mod extract;
+mod field_operations;
 mod helpers;
 mod transform;
 mod typed_chunk;
@@ -17,26 +18,28 @@ use log::debug;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use slice_group_by::GroupBy;
-use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
+use typed_chunk::{merge_word_docids_reader_into_fst, write_typed_chunk_into_index, TypedChunk};
 
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{
     extract_finite_float_from_value, validate_document_id, validate_document_id_value,
     validate_geo_from_json, DocumentId,
 };
+pub use self::field_operations::{FieldOperation, FieldOperationError};
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
     fst_stream_into_vec, merge_btreeset_string, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps,
     sorter_into_lmdb_database, valid_lmdb_key, writer_into_reader, ClonableMmap, MergeFn,
 };
 use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
-pub use self::transform::{Transform, TransformOutput};
+pub use self::transform::{RejectedDocument, Transform, TransformOutput, MAX_REJECTED_DOCUMENTS};
 use crate::documents::{obkv_to_object, DocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
-    self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
-    WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
+    self, DeletionStrategy, IndexerConfig, PhoneticFst, PrefixWordPairsProximityDocids, StemsFst,
+    SuggestionsFst, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids,
+    WordsPrefixesFst,
 };
 use crate::{Index, Result, RoaringBitmapCodec};
 
@@ -62,6 +65,10 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Merge the previous version of the document with the new version like `UpdateDocuments`
+    /// but apply the field operations (e.g. `{ "$inc": 1 }`) on the old attributes values.
+    EditDocuments,
 }
 
 impl Default for IndexDocumentsMethod {
@@ -80,6 +87,8 @@ pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     should_abort: FA,
     added_documents: u64,
     deleted_documents: u64,
+    /// The number of calls to `add_documents` and `edit_documents`, used to number the editions.
+    additions: usize,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -124,6 +133,7 @@ where
             index,
             added_documents: 0,
             deleted_documents: 0,
+            additions: 0,
         })
     }
 
@@ -134,11 +144,42 @@ where
     ///
     /// Returns the number of documents added to the builder.
     pub fn add_documents<R: Read + Seek>(
+        self,
+        reader: DocumentsBatchReader<R>,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
+        self.inner_add_documents(reader, false)
+    }
+
+    /// Adds a batch of documents to the current builder, interpreting the objects with a
+    /// single `$`-prefixed key as field operations, e.g. `{ "views": { "$inc": 1 } }`.
+    ///
+    /// The builder must have been created with the `EditDocuments` method. The documents
+    /// whose operations can't be applied are skipped and can be retrieved with the
+    /// `take_rejected_documents` method.
+    ///
+    /// Returns the number of documents added to the builder.
+    pub fn edit_documents<R: Read + Seek>(
+        self,
+        reader: DocumentsBatchReader<R>,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
+        assert_eq!(
+            self.config.update_method,
+            IndexDocumentsMethod::EditDocuments,
+            "Invalid document edition method"
+        );
+        self.inner_add_documents(reader, true)
+    }
+
+    fn inner_add_documents<R: Read + Seek>(
         mut self,
         reader: DocumentsBatchReader<R>,
+        field_operations: bool,
     ) -> Result<(Self, StdResult<u64, UserError>)> {
         puffin::profile_function!();
 
+        let addition = self.additions;
+        self.additions += 1;
+
         // Early return when there is no document to add
         if reader.is_empty() {
             return Ok((self, Ok(0)));
@@ -161,6 +202,7 @@ where
             self.transform.as_mut().expect("Invalid document addition state").read_documents(
                 enriched_documents_reader,
                 self.wtxn,
+                field_operations.then_some(addition),
                 &self.progress,
                 &self.should_abort,
             )? as u64;
@@ -170,6 +212,14 @@ where
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the documents of the previous additions that were rejected because
+    /// their field operations couldn't be applied.
+    ///
+    /// Only the `EditDocuments` method can reject documents.
+    pub fn take_rejected_documents(&mut self) -> Vec<RejectedDocument> {
+        self.transform.as_mut().map(Transform::take_rejected_documents).unwrap_or_default()
+    }
+
     /// Remove a batch of documents from the current builder.
     ///
     /// Returns the number of documents deleted from the builder.
@@ -197,14 +247,25 @@ where
     }
 
     #[logging_timer::time("IndexDocuments::{}")]
-    pub fn execute(mut self) -> Result<DocumentAdditionResult> {
+    pub fn execute(self) -> Result<DocumentAdditionResult> {
+        self.execute_editions().map(|(addition, _)| addition)
+    }
+
+    /// Same as `execute`, but also returns the editions that were rejected because their
+    /// field operations conflicted with a previous edition of the same document in this batch,
+    /// along with the number of the call to `add_documents` or `edit_documents` they come from.
+    #[logging_timer::time("IndexDocuments::{}")]
+    pub fn execute_editions(
+        mut self,
+    ) -> Result<(DocumentAdditionResult, Vec<(usize, RejectedDocument)>)> {
         puffin::profile_function!();
 
         if self.added_documents == 0 {
             let number_of_documents = self.index.number_of_documents(self.wtxn)?;
-            return Ok(DocumentAdditionResult { indexed_documents: 0, number_of_documents });
+            let addition = DocumentAdditionResult { indexed_documents: 0, number_of_documents };
+            return Ok((addition, Vec::new()));
         }
-        let output = self
+        let mut output = self
             .transform
             .take()
             .expect("Invalid document addition state")
@@ -226,9 +287,10 @@ where
         }
 
         let indexed_documents = output.documents_count as u64;
+        let rejected_editions = std::mem::take(&mut output.rejected_editions);
         let number_of_documents = self.execute_raw(output)?;
 
-        Ok(DocumentAdditionResult { indexed_documents, number_of_documents })
+        Ok((DocumentAdditionResult { indexed_documents, number_of_documents }, rejected_editions))
     }
 
     /// Returns the total number of documents in the index after the update.
@@ -250,6 +312,7 @@ where
             documents_count,
             original_documents,
             flattened_documents,
+            rejected_editions: _,
         } = output;
 
         // The fields_ids_map is put back to the store now so the rest of the transaction sees an
@@ -290,8 +353,14 @@ where
         // get searchable fields for word databases
         let searchable_fields =
             self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+        // get the locales pinned by the localized attributes rules
+        let localized_fields = self.index.localized_fields_ids(self.wtxn)?;
+        // get the fields tokenized in code mode
+        let code_fields = self.index.code_attributes_ids(self.wtxn)?;
         // get filterable fields for facet databases
         let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
+        // get the separators of the hierarchical facets
+        let hierarchical_fields = self.index.hierarchical_facets_ids(self.wtxn)?;
         // get the fid of the `_geo.lat` and `_geo.lng` fields.
         let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
             Some(gfid) => {
@@ -314,6 +383,7 @@ where
         };
         // get the fid of the `_vectors` field.
         let vectors_field_id = self.index.fields_ids_map(self.wtxn)?.id("_vectors");
+        let vector_spaces = self.index.vector_spaces(self.wtxn)?;
 
         let stop_words = self.index.stop_words(self.wtxn)?;
         let separators = self.index.allowed_separators(self.wtxn)?;
@@ -354,10 +424,14 @@ where
                     pool_params,
                     lmdb_writer_sx.clone(),
                     searchable_fields,
+                    localized_fields,
+                    code_fields,
                     faceted_fields,
+                    hierarchical_fields,
                     primary_key_id,
                     geo_fields_ids,
                     vectors_field_id,
+                    vector_spaces,
                     stop_words,
                     separators.as_deref(),
                     dictionary.as_deref(),
@@ -467,10 +541,11 @@ where
         let external_documents_ids = external_documents_ids.into_static();
         self.index.put_external_documents_ids(self.wtxn, &external_documents_ids)?;
 
-        let all_documents_ids = index_documents_ids | new_documents_ids;
+        let all_documents_ids = index_documents_ids | &new_documents_ids;
         self.index.put_documents_ids(self.wtxn, &all_documents_ids)?;
 
         self.execute_prefix_databases(
+            &new_documents_ids,
             word_docids,
             exact_word_docids,
             word_pair_proximity_docids,
@@ -484,6 +559,7 @@ where
     #[logging_timer::time("IndexDocuments::{}")]
     pub fn execute_prefix_databases(
         self,
+        new_documents_ids: &RoaringBitmap,
         word_docids: Option<grenad::Reader<CursorClonableMmap>>,
         exact_word_docids: Option<grenad::Reader<CursorClonableMmap>>,
         word_pair_proximity_docids: Option<grenad::Reader<CursorClonableMmap>>,
@@ -526,6 +602,15 @@ where
         }
         builder.execute()?;
 
+        // Run the words derivations update operations with the words of this batch.
+        let batch_words_fst = match (&word_docids, &exact_word_docids) {
+            (Some(word_docids), Some(exact_word_docids)) => {
+                merge_word_docids_reader_into_fst(word_docids.clone(), exact_word_docids.clone())?
+            }
+            _ => fst::Set::default(),
+        };
+        execute_words_derivations(self.wtxn, self.index, &batch_words_fst, new_documents_ids)?;
+
         if (self.should_abort)() {
             return Err(Error::InternalError(InternalError::AbortedIndexation));
         }
@@ -685,6 +770,24 @@ where
     }
 }
 
+/// Run the update operations of the structures derived from the words of the index,
+/// used at search time to find the words sharing a stem, sounding alike or completing
+/// a query. Only the words and the documents of the batch are processed, none of these
+/// operations requires to reindex the documents.
+fn execute_words_derivations<'i, A: AsRef<[u8]>>(
+    wtxn: &mut heed::RwTxn<'i, '_>,
+    index: &'i Index,
+    batch_words: &fst::Set<A>,
+    batch_documents_ids: &RoaringBitmap,
+) -> Result<()> {
+    puffin::profile_function!();
+
+    StemsFst::new(wtxn, index).execute_for_words(batch_words)?;
+    PhoneticFst::new(wtxn, index).execute_for_words(batch_words)?;
+    SuggestionsFst::new(wtxn, index).execute_for_new_documents(batch_documents_ids)?;
+    Ok(())
+}
+
 /// Run the word prefix docids update operation.
 #[allow(clippy::too_many_arguments)]
 fn execute_word_prefix_docids(
@@ -712,14 +815,14 @@ fn execute_word_prefix_docids(
 #[cfg(test)]
 mod tests {
     use big_s::S;
-    use maplit::hashset;
+    use maplit::{btreemap, btreeset, hashset};
 
     use super::*;
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
     use crate::search::TermsMatchingStrategy;
     use crate::update::DeleteDocuments;
-    use crate::{db_snap, BEU16};
+    use crate::{db_snap, Distance, VectorSpace, BEU16};
 
     #[test]
     fn simple_document_replacement() {
@@ -2170,6 +2273,40 @@ mod tests {
         assert_eq!(cj_cmn_docs, expected_cj_cmn_docids);
     }
 
+    #[cfg(feature = "default")]
+    #[test]
+    fn store_pinned_language_of_localized_attributes_during_indexing() {
+        use charabia::{Language, Script};
+
+        use crate::LocalizedAttributesRule;
+
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_localized_attributes_rules(vec![LocalizedAttributesRule {
+                    attribute_patterns: vec![S("*_ja")],
+                    locales: vec![S("jpn")],
+                }]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "title_ja": "人人生而自由﹐在尊嚴和權利上一律平等。" },
+                { "id": 2, "title": "人人生而自由﹐在尊嚴和權利上一律平等。" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let key_jpn = (Script::Cj, Language::Jpn);
+        let key_cmn = (Script::Cj, Language::Cmn);
+        let cj_jpn_docs = index.script_language_documents_ids(&rtxn, &key_jpn).unwrap().unwrap();
+        let cj_cmn_docs = index.script_language_documents_ids(&rtxn, &key_cmn).unwrap().unwrap();
+        let expected_cj_jpn_docids = [0].iter().collect();
+        assert_eq!(cj_jpn_docs, expected_cj_jpn_docids);
+        let expected_cj_cmn_docids = [1].iter().collect();
+        assert_eq!(cj_cmn_docs, expected_cj_cmn_docids);
+    }
+
     #[test]
     fn add_and_delete_documents_in_single_transform() {
         let mut index = TempIndex::new();
@@ -2212,6 +2349,146 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn edit_documents_with_field_operations() {
+        let mut index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 1, "views": 10, "tags": ["a", "b"], "draft": true },
+                { "id": 2, "views": "many" },
+            ]))
+            .unwrap();
+
+        index.index_documents_config.update_method = IndexDocumentsMethod::EditDocuments;
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        let documents = documents!([
+            { "id": 1, "views": { "$inc": 2 }, "tags": { "$push": "c" }, "draft": { "$unset": true } },
+            { "id": 2, "views": { "$inc": 1 } },
+            { "id": 3, "views": { "$inc": 1 }, "tags": { "$pull": "a" }, "title": { "$setIfMissing": "hello" } },
+        ]);
+        let (mut builder, added) = builder.edit_documents(documents).unwrap();
+        insta::assert_display_snapshot!(added.unwrap(), @"2");
+        let rejected = builder.take_rejected_documents();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].document_id, "2");
+
+        // the operations of the same transform are applied one after the other
+        let documents = documents!([
+            { "id": 1, "views": { "$inc": 3 }, "tags": { "$pull": "a" } },
+            { "id": 3, "views": { "$inc": 1 }, "title": { "$setIfMissing": "world" } },
+        ]);
+        let (builder, added) = builder.edit_documents(documents).unwrap();
+        insta::assert_display_snapshot!(added.unwrap(), @"2");
+
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"id":2,"views":"many"}
+        {"id":1,"views":15,"tags":["b","c"]}
+        {"id":3,"views":2,"title":"hello"}
+        "###);
+    }
+
+    #[test]
+    fn reject_conflicting_editions_of_the_same_transform() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::EditDocuments;
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        let documents = documents!([
+            { "id": 1, "views": "many", "title": "hello" },
+            { "id": 2, "views": 1 },
+        ]);
+        let (mut builder, added) = builder.edit_documents(documents).unwrap();
+        insta::assert_display_snapshot!(added.unwrap(), @"2");
+        assert!(builder.take_rejected_documents().is_empty());
+
+        // the views of the first document can only be known to not be a number
+        // once both versions are merged, the whole edition must then be rejected.
+        let documents = documents!([
+            { "id": 1, "views": { "$inc": 1 }, "title": "world" },
+            { "id": 2, "views": { "$inc": 1 }, "title": "world" },
+        ]);
+        let (mut builder, added) = builder.edit_documents(documents).unwrap();
+        insta::assert_display_snapshot!(added.unwrap(), @"2");
+        assert!(builder.take_rejected_documents().is_empty());
+
+        let (_, rejected) = builder.execute_editions().unwrap();
+        wtxn.commit().unwrap();
+
+        assert_eq!(rejected.len(), 1);
+        let (addition, rejected) = &rejected[0];
+        assert_eq!(*addition, 1);
+        assert_eq!(rejected.document_id, "1");
+        assert!(matches!(rejected.error, UserError::InvalidDocumentFieldOperation { .. }));
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"views":"many","title":"hello"}
+        {"id":2,"views":2,"title":"world"}
+        "###);
+    }
+
+    #[test]
+    fn cap_the_rejected_editions_of_each_addition() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::EditDocuments;
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        let documents = |objects: Vec<serde_json::Value>| {
+            documents_batch_reader_from_objects(objects.into_iter().filter_map(|json| match json {
+                serde_json::Value::Object(object) => Some(object),
+                _ => None,
+            }))
+        };
+        let count = MAX_REJECTED_DOCUMENTS as i32 + 10;
+
+        let views = (0..=count).map(|i| serde_json::json!({ "id": i, "views": "many" })).collect();
+        let (builder, _) = builder.edit_documents(documents(views)).unwrap();
+
+        // the first edition is rejected more often than the cap, the second one only once.
+        let increments =
+            (0..count).map(|i| serde_json::json!({ "id": i, "views": { "$inc": 1 } })).collect();
+        let (builder, _) = builder.edit_documents(documents(increments)).unwrap();
+        let increment = vec![serde_json::json!({ "id": count, "views": { "$inc": 1 } })];
+        let (builder, _) = builder.edit_documents(documents(increment)).unwrap();
+
+        let (_, rejected) = builder.execute_editions().unwrap();
+        wtxn.commit().unwrap();
+
+        let rejected_of = |addition| rejected.iter().filter(|(a, _)| *a == addition).count();
+        assert_eq!(rejected_of(1), MAX_REJECTED_DOCUMENTS);
+        assert_eq!(rejected_of(2), 1);
+    }
+
     #[test]
     fn add_update_and_delete_documents_in_single_transform() {
         let mut index = TempIndex::new();
@@ -2569,6 +2846,104 @@ mod tests {
         assert_eq!(res.documents_ids.len(), 3);
     }
 
+    /// Index vectors in different named vector spaces, each with its own dimensions.
+    #[test]
+    fn test_named_vector_spaces() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_vector_spaces(btreemap! {
+                    S("title") => VectorSpace { dimensions: 3, distance: Distance::Cosine },
+                    S("image") => VectorSpace { dimensions: 2, distance: Distance::Euclidean },
+                });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "_vectors": { "title": [0, 1, 2], "image": [[0, 0], [5, 5]] } },
+                { "id": 1, "_vectors": { "title": [3, 4, 5] } },
+                { "id": 2, "_vectors": { "image": [1, 1] } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let spaces = index.vector_hnsw_spaces(&rtxn).unwrap();
+        assert_eq!(spaces, btreeset! { S("image"), S("title") });
+
+        let res =
+            index.search(&rtxn).vector([0.0, 1.0, 2.0]).vector_space("title").execute().unwrap();
+        assert_eq!(res.documents_ids, vec![0, 1]);
+
+        let res = index.search(&rtxn).vector([1.0, 1.0]).vector_space("image").execute().unwrap();
+        assert_eq!(res.documents_ids, vec![2, 0]);
+
+        let error =
+            index.search(&rtxn).vector([1.0, 1.0]).vector_space("sound").execute().unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            "Vector space `sound` is not declared. Available vector spaces are: `image, title`."
+        );
+        drop(rtxn);
+
+        // the vectors of the deleted documents are removed from every vector space
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = DeleteDocuments::new(&mut wtxn, &index).unwrap();
+        builder.strategy(DeletionStrategy::AlwaysHard);
+        builder.delete_external_id("0");
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let res =
+            index.search(&rtxn).vector([0.0, 1.0, 2.0]).vector_space("title").execute().unwrap();
+        assert_eq!(res.documents_ids, vec![1]);
+        let res = index.search(&rtxn).vector([1.0, 1.0]).vector_space("image").execute().unwrap();
+        assert_eq!(res.documents_ids, vec![2]);
+        assert!(index.docid_vector_ids.get(&rtxn, &BEU32::new(0)).unwrap().is_none());
+        assert_eq!(index.docid_vector_ids.len(&rtxn).unwrap(), 2);
+        assert_eq!(index.vector_id_docid.len(&rtxn).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_invalid_named_vector_spaces() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_vector_spaces(btreemap! {
+                    S("title") => VectorSpace { dimensions: 3, distance: Distance::Dot },
+                });
+            })
+            .unwrap();
+
+        let error = index
+            .add_documents(documents!([{ "id": 0, "_vectors": { "title": [0, 1] } }]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            "Invalid vector dimensions in the `title` vector space of the document with the id: `0`: expected: `3`, found: `2`."
+        );
+
+        let error = index
+            .add_documents(documents!([{ "id": 0, "_vectors": { "image": [0, 1] } }]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            "The `_vectors` field in the document with the id: `0` references the `image` vector space which is not declared. Available vector spaces are: `title`."
+        );
+
+        let error = index
+            .update_settings(|settings| {
+                settings.set_vector_spaces(btreemap! {
+                    S("title\0") => VectorSpace { dimensions: 3, distance: Distance::Dot },
+                });
+            })
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidVectorSpaceName(_))));
+    }
+
     #[test]
     fn reproduce_the_bug() {
         /*
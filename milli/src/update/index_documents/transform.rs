@@ -12,8 +12,13 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 use smartstring::SmartString;
 
+use super::field_operations::{
+    encode_field_operations, resolve_field_operations, Edition, FieldOperation,
+    FieldOperationError, EDITION_FIELD_ID,
+};
 use super::helpers::{
-    create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_operations, MergeFn,
+    create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_field_operations,
+    merge_obkvs_and_operations, MergeFn,
 };
 use super::{IndexDocumentsMethod, IndexerConfig};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
@@ -21,7 +26,8 @@ use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
 use crate::update::{AvailableDocumentsIds, ClearDocuments, UpdateIndexingStep};
 use crate::{
-    FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result, BEU32,
+    ExternalDocumentsIds, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index,
+    Result, BEU32,
 };
 
 pub struct TransformOutput {
@@ -34,6 +40,21 @@ pub struct TransformOutput {
     pub documents_count: usize,
     pub original_documents: File,
     pub flattened_documents: File,
+    /// The editions rejected while merging the versions of the documents,
+    /// along with the number of the addition they come from.
+    pub rejected_editions: Vec<(usize, RejectedDocument)>,
+}
+
+/// The maximum number of rejected documents kept for each addition of a transform,
+/// the following ones are still rejected but not reported.
+pub const MAX_REJECTED_DOCUMENTS: usize = 100;
+
+/// A document of an edition that was not indexed because its
+/// field operations couldn't be applied on the stored version.
+#[derive(Debug)]
+pub struct RejectedDocument {
+    pub document_id: String,
+    pub error: UserError,
 }
 
 /// Extract the external ids, deduplicate and compute the new internal documents ids
@@ -62,6 +83,7 @@ pub struct Transform<'a, 'i> {
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
     documents_count: usize,
+    rejected_documents: Vec<RejectedDocument>,
 }
 
 /// This enum is specific to the grenad sorter stored in the transform.
@@ -108,6 +130,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         let merge_function = match index_documents_method {
             IndexDocumentsMethod::ReplaceDocuments => keep_latest_obkv,
             IndexDocumentsMethod::UpdateDocuments => merge_obkvs_and_operations,
+            IndexDocumentsMethod::EditDocuments => merge_obkvs_and_field_operations,
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -148,13 +171,22 @@ impl<'a, 'i> Transform<'a, 'i> {
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
             documents_count: 0,
+            rejected_documents: Vec::new(),
         })
     }
 
+    /// Returns the documents of an edition that were rejected since the last call.
+    pub fn take_rejected_documents(&mut self) -> Vec<RejectedDocument> {
+        std::mem::take(&mut self.rejected_documents)
+    }
+
+    /// Reads the documents of an addition, `edition` being the number of the addition
+    /// when its values can be field operations.
     pub fn read_documents<R, FP, FA>(
         &mut self,
         reader: EnrichedDocumentsBatchReader<R>,
         wtxn: &mut heed::RwTxn,
+        edition: Option<usize>,
         progress_callback: FP,
         should_abort: FA,
     ) -> Result<usize>
@@ -171,11 +203,20 @@ impl<'a, 'i> Transform<'a, 'i> {
         let primary_key_id =
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
 
+        let edit_documents = self.index_documents_method == IndexDocumentsMethod::EditDocuments;
+        if edit_documents && mapping.values().any(|&field_id| field_id == EDITION_FIELD_ID) {
+            return Err(UserError::AttributeLimitReached.into());
+        }
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_buffer = Vec::new();
         let mut documents_count = 0;
         let mut docid_buffer: Vec<u8> = Vec::new();
         let mut field_buffer: Vec<(u16, Cow<[u8]>)> = Vec::new();
+        let mut operations_buffers: Vec<Vec<u8>> = Vec::new();
+        let mut edition_buffer = Vec::new();
+        if let Some(addition) = edition {
+            Edition::new(addition).encode(&mut edition_buffer)?;
+        }
         while let Some(enriched_document) = cursor.next_enriched_document()? {
             let EnrichedDocument { document, document_id } = enriched_document;
 
@@ -201,12 +242,68 @@ impl<'a, 'i> Transform<'a, 'i> {
                 field_buffer_cache.push((primary_key_id, Cow::from(&docid_buffer)));
             }
 
+            // The operations of an edition are kept aside to be validated before being encoded.
+            let mut document_operations = Vec::new();
+            let mut rejection = None;
             for (k, v) in document.iter() {
                 let mapped_id =
                     *mapping.get(&k).ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
+                if edition.is_some() {
+                    let value: Value =
+                        serde_json::from_slice(v).map_err(InternalError::SerdeJson)?;
+                    match FieldOperation::from_value(&value) {
+                        Ok(Some(operation)) => {
+                            document_operations.push((mapped_id, operation));
+                            continue;
+                        }
+                        Ok(None) => (),
+                        Err(error) => {
+                            rejection = Some((mapped_id, error));
+                            break;
+                        }
+                    }
+                }
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
             }
 
+            if rejection.is_none() && !document_operations.is_empty() {
+                rejection = self.validate_field_operations(
+                    wtxn,
+                    &external_documents_ids,
+                    external_id,
+                    &document_operations,
+                )?;
+            }
+
+            if let Some((field_id, error)) = rejection {
+                if self.rejected_documents.len() < MAX_REJECTED_DOCUMENTS {
+                    let field = self.fields_ids_map.name(field_id).unwrap_or_default().to_string();
+                    self.rejected_documents.push(RejectedDocument {
+                        document_id: external_id.to_string(),
+                        error: UserError::InvalidDocumentFieldOperation {
+                            document_id: Value::from(external_id),
+                            field,
+                            error,
+                        },
+                    });
+                }
+                field_buffer = drop_and_reuse(field_buffer_cache);
+                docid_buffer.clear();
+                continue;
+            }
+
+            operations_buffers.resize_with(document_operations.len(), Vec::new);
+            for ((field_id, operation), buffer) in
+                document_operations.iter().zip(operations_buffers.iter_mut())
+            {
+                encode_field_operations(std::slice::from_ref(operation), buffer)?;
+                field_buffer_cache.push((*field_id, Cow::from(buffer.as_slice())));
+            }
+            // The edition is needed to report the conflicts with the other versions of the batch.
+            if !document_operations.is_empty() {
+                field_buffer_cache.push((EDITION_FIELD_ID, Cow::from(edition_buffer.as_slice())));
+            }
+
             // Insertion in a obkv need to be done with keys ordered. For now they are ordered
             // according to the document addition key order, so we sort it according to the
             // fieldids map keys order.
@@ -267,18 +364,25 @@ impl<'a, 'i> Transform<'a, 'i> {
                     document_sorter_buffer.push(Operation::Addition as u8);
                     document_sorter_buffer.extend_from_slice(base_obkv);
                     self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
-                    match self.flatten_from_fields_ids_map(KvReader::new(base_obkv))? {
-                        Some(flattened_obkv) => {
-                            // we recreate our buffer with the flattened documents
-                            document_sorter_buffer.clear();
-                            document_sorter_buffer.push(Operation::Addition as u8);
-                            document_sorter_buffer.extend_from_slice(&flattened_obkv);
-                            self.flattened_sorter
-                                .insert(docid.to_be_bytes(), &document_sorter_buffer)?
+                    // The edited documents are only flattened once their
+                    // operations are resolved, in the `output_from_sorter` method.
+                    if !edit_documents {
+                        match Self::flatten_from_fields_ids_map(
+                            &mut self.fields_ids_map,
+                            KvReader::new(base_obkv),
+                        )? {
+                            Some(flattened_obkv) => {
+                                // we recreate our buffer with the flattened documents
+                                document_sorter_buffer.clear();
+                                document_sorter_buffer.push(Operation::Addition as u8);
+                                document_sorter_buffer.extend_from_slice(&flattened_obkv);
+                                self.flattened_sorter
+                                    .insert(docid.to_be_bytes(), &document_sorter_buffer)?
+                            }
+                            None => self
+                                .flattened_sorter
+                                .insert(docid.to_be_bytes(), &document_sorter_buffer)?,
                         }
-                        None => self
-                            .flattened_sorter
-                            .insert(docid.to_be_bytes(), &document_sorter_buffer)?,
                     }
                 }
             }
@@ -292,17 +396,22 @@ impl<'a, 'i> Transform<'a, 'i> {
                 // We use the extracted/generated user id as the key for this document.
                 self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
 
-                match self.flatten_from_fields_ids_map(KvReader::new(&obkv_buffer))? {
-                    Some(flattened_obkv) => {
-                        document_sorter_buffer.clear();
-                        document_sorter_buffer.push(Operation::Addition as u8);
-                        document_sorter_buffer.extend_from_slice(&flattened_obkv);
-                        self.flattened_sorter
-                            .insert(docid.to_be_bytes(), &document_sorter_buffer)?
+                if !edit_documents {
+                    match Self::flatten_from_fields_ids_map(
+                        &mut self.fields_ids_map,
+                        KvReader::new(&obkv_buffer),
+                    )? {
+                        Some(flattened_obkv) => {
+                            document_sorter_buffer.clear();
+                            document_sorter_buffer.push(Operation::Addition as u8);
+                            document_sorter_buffer.extend_from_slice(&flattened_obkv);
+                            self.flattened_sorter
+                                .insert(docid.to_be_bytes(), &document_sorter_buffer)?
+                        }
+                        None => self
+                            .flattened_sorter
+                            .insert(docid.to_be_bytes(), &document_sorter_buffer)?,
                     }
-                    None => self
-                        .flattened_sorter
-                        .insert(docid.to_be_bytes(), &document_sorter_buffer)?,
                 }
             }
             documents_count += 1;
@@ -395,9 +504,52 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_deleted)
     }
 
-    // Flatten a document from the fields ids map contained in self and insert the new
+    /// Checks that the field operations of an edited document can be applied on the version
+    /// stored in the database, returns the first field on which an operation failed.
+    ///
+    /// The documents that were already edited in this transform are not checked, their
+    /// operations are applied leniently when the sorters are merged.
+    fn validate_field_operations(
+        &self,
+        rtxn: &RoTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+        operations: &[(FieldId, FieldOperation)],
+    ) -> Result<Option<(FieldId, FieldOperationError)>> {
+        if self.new_external_documents_ids_builder.contains_key(external_id) {
+            return Ok(None);
+        }
+
+        let docid = match external_documents_ids.get(external_id) {
+            Some(docid) if !self.replaced_documents_ids.contains(docid) => docid,
+            _ => return Ok(None),
+        };
+
+        let obkv = self.index.documents.get(rtxn, &BEU32::new(docid))?.ok_or(
+            InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
+        )?;
+
+        for (field_id, operation) in operations {
+            let current = match obkv.get(*field_id) {
+                Some(value) => {
+                    Some(serde_json::from_slice(value).map_err(InternalError::SerdeJson)?)
+                }
+                None => None,
+            };
+            if let Err(error) = operation.apply(current) {
+                return Ok(Some((*field_id, error)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Flatten a document from the fields ids map and insert the new
     // created fields. Returns `None` if the document doesn't need to be flattened.
-    fn flatten_from_fields_ids_map(&mut self, obkv: KvReader<FieldId>) -> Result<Option<Vec<u8>>> {
+    fn flatten_from_fields_ids_map(
+        fields_ids_map: &mut FieldsIdsMap,
+        obkv: KvReader<FieldId>,
+    ) -> Result<Option<Vec<u8>>> {
         if obkv
             .iter()
             .all(|(_, value)| !json_depth_checker::should_flatten_from_unchecked_slice(value))
@@ -418,7 +570,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // all the raw values get inserted directly in the `key_value` vec.
         for (key, value) in obkv.iter() {
             if json_depth_checker::should_flatten_from_unchecked_slice(value) {
-                let key = self.fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
+                let key = fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
                     field_id: key,
                     process: "Flatten from fields ids map.",
                 })?;
@@ -436,7 +588,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // Once we have the flattened version we insert all the new generated fields_ids
         // (if any) in the fields ids map and serialize the value.
         for (key, value) in flattened.into_iter() {
-            let fid = self.fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
+            let fid = fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
             let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
             key_value.push((fid, value.into()));
         }
@@ -583,6 +735,20 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         self.remove_deleted_documents_from_field_distribution(wtxn, &mut field_distribution)?;
 
+        // We create a final writer to write the new documents in order from the sorter.
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let edit_documents = self.index_documents_method == IndexDocumentsMethod::EditDocuments;
+        let mut fields_ids_map = self.fields_ids_map;
+        let primary_key_id = fields_ids_map.id(&primary_key);
+        let mut resolved_buffer = Vec::new();
+        let mut rejected_editions = Vec::new();
+        let mut rejected_by_addition: HashMap<usize, usize> = HashMap::new();
+
         // Here we are going to do the document count + field distribution + `write_into_stream_writer`
         let mut iter = self.original_sorter.into_stream_merger_iter()?;
         // used only for the callback
@@ -592,7 +758,43 @@ impl<'a, 'i> Transform<'a, 'i> {
             if val[0] == Operation::Deletion as u8 {
                 continue;
             }
-            let val = &val[1..];
+            let mut val = &val[1..];
+
+            // The operations on fields that were missing from the previous version
+            // of an edited document are still pending, we apply them now.
+            if edit_documents {
+                let edition = resolve_field_operations(KvReader::new(val), &mut resolved_buffer)?;
+                val = resolved_buffer.as_slice();
+
+                for rejection in edition.rejections {
+                    let document_id = match primary_key_id.and_then(|id| KvReader::new(val).get(id))
+                    {
+                        Some(value) => {
+                            match serde_json::from_slice(value).map_err(InternalError::SerdeJson)? {
+                                Value::String(document_id) => document_id,
+                                document_id => document_id.to_string(),
+                            }
+                        }
+                        None => continue,
+                    };
+                    let field =
+                        fields_ids_map.name(rejection.field_id).unwrap_or_default().to_string();
+                    for &addition in &rejection.additions {
+                        let rejected = rejected_by_addition.entry(addition).or_default();
+                        if *rejected == MAX_REJECTED_DOCUMENTS {
+                            continue;
+                        }
+                        *rejected += 1;
+                        let error = UserError::InvalidDocumentFieldOperation {
+                            document_id: Value::from(document_id.as_str()),
+                            field: field.clone(),
+                            error: rejection.error(),
+                        };
+                        let document_id = document_id.clone();
+                        rejected_editions.push((addition, RejectedDocument { document_id, error }));
+                    }
+                }
+            }
 
             // send a callback to show at which step we are
             documents_count += 1;
@@ -605,27 +807,27 @@ impl<'a, 'i> Transform<'a, 'i> {
             let obkv = KvReader::new(val);
 
             for (key, _) in obkv.iter() {
-                let name =
-                    self.fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
-                        field_id: key,
-                        process: "Computing field distribution in transform.",
-                    })?;
+                let name = fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
+                    field_id: key,
+                    process: "Computing field distribution in transform.",
+                })?;
                 *field_distribution.entry(name.to_string()).or_insert(0) += 1;
             }
             writer.insert(key, val)?;
+
+            // The edited documents were not flattened in the `read_documents` method.
+            if edit_documents {
+                match Self::flatten_from_fields_ids_map(&mut fields_ids_map, obkv)? {
+                    Some(flattened_obkv) => flattened_writer.insert(key, flattened_obkv)?,
+                    None => flattened_writer.insert(key, val)?,
+                }
+            }
         }
 
         let mut original_documents = writer.into_inner()?;
         // We then extract the file and reset the seek to be able to read it again.
         original_documents.rewind()?;
 
-        // We create a final writer to write the new documents in order from the sorter.
-        let mut writer = create_writer(
-            self.indexer_settings.chunk_compression_type,
-            self.indexer_settings.chunk_compression_level,
-            tempfile::tempfile()?,
-        );
-
         // Once we have written all the documents into the final sorter, we write the nested documents
         // into this writer.
         // We get rids of the `Operation` byte and skip the deleted documents as well.
@@ -635,9 +837,9 @@ impl<'a, 'i> Transform<'a, 'i> {
                 continue;
             }
             let val = &val[1..];
-            writer.insert(key, val)?;
+            flattened_writer.insert(key, val)?;
         }
-        let mut flattened_documents = writer.into_inner()?;
+        let mut flattened_documents = flattened_writer.into_inner()?;
         flattened_documents.rewind()?;
 
         let mut new_external_documents_ids_builder: Vec<_> =
@@ -653,7 +855,7 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         Ok(TransformOutput {
             primary_key,
-            fields_ids_map: self.fields_ids_map,
+            fields_ids_map,
             field_distribution,
             new_external_documents_ids: new_external_documents_ids.map_data(Cow::Owned).unwrap(),
             new_documents_ids: self.new_documents_ids,
@@ -663,6 +865,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             flattened_documents: flattened_documents
                 .into_inner()
                 .map_err(|err| err.into_error())?,
+            rejected_editions,
         })
     }
 
@@ -785,6 +988,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             flattened_documents: flattened_documents
                 .into_inner()
                 .map_err(|err| err.into_error())?,
+            rejected_editions: Vec::new(),
         };
 
         let new_facets = output.compute_real_facets(wtxn, self.index)?;
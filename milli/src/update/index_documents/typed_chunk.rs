@@ -15,10 +15,10 @@ use super::helpers::{
     self, merge_ignore_values, serialize_roaring_bitmap, valid_lmdb_key, CursorClonableMmap,
 };
 use super::{ClonableMmap, MergeFn};
-use crate::distance::NDotProductPoint;
+use crate::distance::{Distance, VectorPoint};
 use crate::error::UserError;
 use crate::facet::FacetType;
-use crate::index::Hnsw;
+use crate::hnsw::Hnsw;
 use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::{as_cloneable_grenad, try_split_array_at};
 use crate::{lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, GeoPoint, Index, Result, BEU32};
@@ -289,55 +289,49 @@ pub(crate) fn write_typed_chunk_into_index(
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
         TypedChunk::VectorPoints(vector_points) => {
-            let (pids, mut points): (Vec<_>, Vec<_>) = match index.vector_hnsw(wtxn)? {
-                Some(hnsw) => hnsw.iter().map(|(pid, point)| (pid, point.clone())).unzip(),
-                None => Default::default(),
-            };
-
-            // Convert the PointIds into DocumentIds
-            let mut docids = Vec::new();
-            for pid in pids {
-                let docid =
-                    index.vector_id_docid.get(wtxn, &BEU32::new(pid.into_inner()))?.unwrap();
-                docids.push(docid.get());
-            }
+            let vector_spaces = index.vector_spaces(wtxn)?;
+            // the number of dimensions expected for each vector space, computed lazily
+            let mut expected_dimensions: HashMap<String, Option<usize>> = HashMap::new();
+            let mut inserted_points = 0;
 
-            let mut expected_dimensions = points.get(0).map(|p| p.len());
             let mut cursor = vector_points.into_cursor()?;
             while let Some((key, value)) = cursor.move_on_next()? {
-                // convert the key back to a u32 (4 bytes)
-                let (left, _index) = try_split_array_at(key).unwrap();
+                // convert the key back to a u32 (4 bytes) and the vector space name
+                let (left, space) = try_split_array_at(key).unwrap();
                 let docid = DocumentId::from_be_bytes(left);
+                let space = extract_vector_space_name(space).unwrap();
                 // convert the vector back to a Vec<f32>
                 let vector: Vec<f32> = pod_collect_to_vec(value);
 
+                let declared = vector_spaces.get(space);
+                let distance = declared.map_or(Distance::default(), |s| s.distance);
+                let hnsw = Hnsw::new(index, space);
+
+                if !expected_dimensions.contains_key(space) {
+                    let dimensions = match declared {
+                        Some(declared) => Some(declared.dimensions),
+                        None => hnsw.dimensions(wtxn)?,
+                    };
+                    expected_dimensions.insert(space.to_string(), dimensions);
+                }
+
                 // TODO Inform the user about the document that has a wrong `_vectors`
                 let found = vector.len();
-                let expected = *expected_dimensions.get_or_insert(found);
+                let expected = *expected_dimensions.get_mut(space).unwrap().get_or_insert(found);
                 if expected != found {
                     return Err(UserError::InvalidVectorDimensions { expected, found })?;
                 }
 
-                points.push(NDotProductPoint::new(vector));
-                docids.push(docid);
+                let pid = hnsw.insert(wtxn, VectorPoint::new(distance, vector))?;
+                let docid = BEU32::new(docid);
+                index.vector_id_docid.put(wtxn, &(space, pid), &docid)?;
+                let mut vector_ids = index.docid_vector_ids.get(wtxn, &docid)?.unwrap_or_default();
+                vector_ids.push((space.to_string(), pid));
+                index.docid_vector_ids.put(wtxn, &docid, &vector_ids)?;
+                inserted_points += 1;
             }
 
-            assert_eq!(docids.len(), points.len());
-
-            let hnsw_length = points.len();
-            let (new_hnsw, pids) = Hnsw::builder().build_hnsw(points);
-
-            index.vector_id_docid.clear(wtxn)?;
-            for (docid, pid) in docids.into_iter().zip(pids) {
-                index.vector_id_docid.put(
-                    wtxn,
-                    &BEU32::new(pid.into_inner()),
-                    &BEU32::new(docid),
-                )?;
-            }
-
-            log::debug!("There are {} entries in the HNSW so far", hnsw_length);
-            index.put_vector_hnsw(wtxn, &new_hnsw)?;
+            log::debug!("Inserted {} points in the HNSW structures", inserted_points);
         }
         TypedChunk::ScriptLanguageDocids(hash_pair) => {
             let mut buffer = Vec::new();
@@ -362,7 +356,15 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-fn merge_word_docids_reader_into_fst(
+/// Extracts the vector space name from the end of a vector point key,
+/// it is followed by a nul byte and the big-endian index of the vector in the document.
+fn extract_vector_space_name(bytes: &[u8]) -> Option<&str> {
+    let (space_plus_nul_byte, _index) = bytes.split_at(bytes.len().checked_sub(2)?);
+    let (_, space) = space_plus_nul_byte.split_last()?;
+    std::str::from_utf8(space).ok()
+}
+
+pub(crate) fn merge_word_docids_reader_into_fst(
     word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
     exact_word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
 ) -> Result<fst::Set<Vec<u8>>> {
@@ -5,15 +5,18 @@ pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
 pub use self::index_documents::{
     merge_btreeset_string, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps,
-    DocumentAdditionResult, DocumentId, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod,
-    MergeFn,
+    DocumentAdditionResult, DocumentId, FieldOperation, FieldOperationError, IndexDocuments,
+    IndexDocumentsConfig, IndexDocumentsMethod, MergeFn, RejectedDocument, MAX_REJECTED_DOCUMENTS,
 };
 pub use self::indexer_config::IndexerConfig;
+pub use self::phonetic_fst::PhoneticFst;
 pub use self::prefix_word_pairs::{
     PrefixWordPairsProximityDocids, MAX_LENGTH_FOR_PREFIX_PROXIMITY_DB,
     MAX_PROXIMITY_FOR_PREFIX_PROXIMITY_DB,
 };
 pub use self::settings::{Setting, Settings};
+pub use self::stems_fst::StemsFst;
+pub use self::suggestions_fst::SuggestionsFst;
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
@@ -25,8 +28,11 @@ mod delete_documents;
 pub(crate) mod facet;
 mod index_documents;
 mod indexer_config;
+mod phonetic_fst;
 mod prefix_word_pairs;
 mod settings;
+mod stems_fst;
+mod suggestions_fst;
 mod update_step;
 mod word_prefix_docids;
 mod words_prefix_integer_docids;
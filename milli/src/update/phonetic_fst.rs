@@ -0,0 +1,91 @@
+use std::collections::{BTreeSet, HashSet};
+use std::str;
+
+use fst::{Set, Streamer};
+use heed::types::DecodeIgnore;
+
+use crate::phonetic::{phonetic_codes, phonetic_key};
+use crate::{FieldId, Index, Result};
+
+/// Computes the phonetic codes of the words appearing in the phonetic attributes of the index,
+/// the phonetic FST is used at search time to retrieve the words sounding like a query word.
+pub struct PhoneticFst<'t, 'u, 'i> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+}
+
+impl<'t, 'u, 'i> PhoneticFst<'t, 'u, 'i> {
+    pub fn new(wtxn: &'t mut heed::RwTxn<'i, 'u>, index: &'i Index) -> PhoneticFst<'t, 'u, 'i> {
+        PhoneticFst { wtxn, index }
+    }
+
+    /// Computes the phonetic codes of all the words of the index.
+    #[logging_timer::time("PhoneticFst::{}")]
+    pub fn execute(self) -> Result<()> {
+        puffin::profile_function!();
+
+        let phonetic_fids = self.index.phonetic_attributes_ids(self.wtxn)?;
+        if phonetic_fids.is_empty() {
+            self.index.delete_phonetic_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let words_fst = self.index.words_fst(self.wtxn)?;
+        let phonetic_fst =
+            phonetic_fst_of_words(self.index, self.wtxn, &phonetic_fids, &words_fst)?;
+        self.index.put_phonetic_fst(self.wtxn, &phonetic_fst)?;
+
+        Ok(())
+    }
+
+    /// Only computes the phonetic codes of the given words, the new words of an indexing batch,
+    /// and adds them to the phonetic codes of the index.
+    #[logging_timer::time("PhoneticFst::{}")]
+    pub fn execute_for_words<A: AsRef<[u8]>>(self, words: &Set<A>) -> Result<()> {
+        puffin::profile_function!();
+
+        let phonetic_fids = self.index.phonetic_attributes_ids(self.wtxn)?;
+        if phonetic_fids.is_empty() {
+            self.index.delete_phonetic_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let new_phonetic_fst = phonetic_fst_of_words(self.index, self.wtxn, &phonetic_fids, words)?;
+        let phonetic_fst = self.index.phonetic_fst(self.wtxn)?;
+        let mut builder = fst::SetBuilder::memory();
+        builder.extend_stream(phonetic_fst.op().add(&new_phonetic_fst).union())?;
+        let phonetic_fst = builder.into_set();
+        self.index.put_phonetic_fst(self.wtxn, &phonetic_fst)?;
+
+        Ok(())
+    }
+}
+
+fn phonetic_fst_of_words<A: AsRef<[u8]>>(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    phonetic_fids: &HashSet<FieldId>,
+    words: &Set<A>,
+) -> Result<Set<Vec<u8>>> {
+    let word_fid_docids = index.word_fid_docids.remap_data_type::<DecodeIgnore>();
+    let mut keys = BTreeSet::new();
+    let mut stream = words.stream();
+    while let Some(bytes) = stream.next() {
+        let word = str::from_utf8(bytes)?;
+        // only the words appearing in a phonetic attribute are encoded.
+        let mut in_phonetic_attribute = false;
+        for &fid in phonetic_fids {
+            if word_fid_docids.get(rtxn, &(word, fid))?.is_some() {
+                in_phonetic_attribute = true;
+                break;
+            }
+        }
+        if in_phonetic_attribute {
+            for code in phonetic_codes(word) {
+                keys.insert(phonetic_key(&code, word));
+            }
+        }
+    }
+
+    Ok(Set::from_iter(keys)?)
+}
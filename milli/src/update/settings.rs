@@ -12,9 +12,16 @@ use super::IndexerConfig;
 use crate::criterion::Criterion;
 use crate::error::UserError;
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+use crate::localized_attributes_rules::parse_locale;
+use crate::query_rules::{QueryRule, QueryRulesUpdate};
+use crate::rules_update::RulesUpdate;
+use crate::stemming::parse_stemming_locale;
+use crate::synonyms::{synonyms_fst, SynonymRule, SynonymRulesUpdate};
 use crate::update::index_documents::IndexDocumentsMethod;
-use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, OrderBy, Result};
+use crate::update::{IndexDocuments, PhoneticFst, StemsFst, SuggestionsFst, UpdateIndexingStep};
+use crate::{
+    is_faceted_by, FieldsIdsMap, Index, LocalizedAttributesRule, OrderBy, Result, VectorSpace,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -107,6 +114,7 @@ pub struct Settings<'a, 't, 'u, 'i> {
     indexer_config: &'a IndexerConfig,
 
     searchable_fields: Setting<Vec<String>>,
+    attribute_weights: Setting<BTreeMap<String, u16>>,
     displayed_fields: Setting<Vec<String>>,
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
@@ -117,16 +125,27 @@ pub struct Settings<'a, 't, 'u, 'i> {
     dictionary: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
     synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    synonym_rules: Setting<SynonymRulesUpdate>,
+    query_rules: Setting<QueryRulesUpdate>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
     min_word_len_one_typo: Setting<u8>,
     exact_words: Setting<BTreeSet<String>>,
+    disable_typos_on_numbers: Setting<bool>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
+    hierarchical_facets: Setting<BTreeMap<String, String>>,
     pagination_max_total_hits: Setting<usize>,
+    vector_spaces: Setting<BTreeMap<String, VectorSpace>>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    stemming_locales: Setting<Vec<String>>,
+    phonetic_attributes: Setting<Vec<String>>,
+    code_attributes: Setting<Vec<String>>,
+    suggestion_attributes: Setting<Vec<String>>,
+    suggestion_facet_attributes: Setting<Vec<String>>,
 }
 
 impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
@@ -139,6 +158,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             wtxn,
             index,
             searchable_fields: Setting::NotSet,
+            attribute_weights: Setting::NotSet,
             displayed_fields: Setting::NotSet,
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
@@ -149,15 +169,26 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             dictionary: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            query_rules: Setting::NotSet,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             exact_words: Setting::NotSet,
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
             exact_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
+            hierarchical_facets: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
+            vector_spaces: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            stemming_locales: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
+            code_attributes: Setting::NotSet,
+            suggestion_attributes: Setting::NotSet,
+            suggestion_facet_attributes: Setting::NotSet,
             indexer_config,
         }
     }
@@ -260,6 +291,36 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
     }
 
+    pub fn reset_synonym_rules(&mut self) {
+        self.synonym_rules = Setting::Reset;
+    }
+
+    /// Replaces all the synonym rules of the index.
+    pub fn set_synonym_rules(&mut self, rules: BTreeMap<String, SynonymRule>) {
+        self.synonym_rules = Setting::Set(SynonymRulesUpdate::Replace(rules));
+    }
+
+    /// Adds or replaces the synonym rules with a value and removes the ones without,
+    /// the other synonym rules of the index are kept.
+    pub fn patch_synonym_rules(&mut self, patch: BTreeMap<String, Option<SynonymRule>>) {
+        patch_rules(&mut self.synonym_rules, patch);
+    }
+
+    pub fn reset_query_rules(&mut self) {
+        self.query_rules = Setting::Reset;
+    }
+
+    /// Replaces all the query rules of the index.
+    pub fn set_query_rules(&mut self, rules: BTreeMap<String, QueryRule>) {
+        self.query_rules = Setting::Set(QueryRulesUpdate::Replace(rules));
+    }
+
+    /// Adds or replaces the query rules with a value and removes the ones without,
+    /// the other query rules of the index are kept.
+    pub fn patch_query_rules(&mut self, patch: BTreeMap<String, Option<QueryRule>>) {
+        patch_rules(&mut self.query_rules, patch);
+    }
+
     pub fn reset_primary_key(&mut self) {
         self.primary_key = Setting::Reset;
     }
@@ -300,6 +361,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.exact_words = Setting::Reset;
     }
 
+    pub fn set_disable_typos_on_numbers(&mut self, val: bool) {
+        self.disable_typos_on_numbers = Setting::Set(val);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
     pub fn set_exact_attributes(&mut self, attrs: HashSet<String>) {
         self.exact_attributes = Setting::Set(attrs);
     }
@@ -324,6 +393,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.sort_facet_values_by = Setting::Reset;
     }
 
+    pub fn set_hierarchical_facets(&mut self, value: BTreeMap<String, String>) {
+        self.hierarchical_facets = Setting::Set(value);
+    }
+
+    pub fn reset_hierarchical_facets(&mut self) {
+        self.hierarchical_facets = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -332,6 +409,70 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.pagination_max_total_hits = Setting::Reset;
     }
 
+    pub fn set_vector_spaces(&mut self, value: BTreeMap<String, VectorSpace>) {
+        self.vector_spaces = Setting::Set(value);
+    }
+
+    pub fn reset_vector_spaces(&mut self) {
+        self.vector_spaces = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_stemming_locales(&mut self, value: Vec<String>) {
+        self.stemming_locales = Setting::Set(value);
+    }
+
+    pub fn reset_stemming_locales(&mut self) {
+        self.stemming_locales = Setting::Reset;
+    }
+
+    pub fn set_phonetic_attributes(&mut self, value: Vec<String>) {
+        self.phonetic_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_phonetic_attributes(&mut self) {
+        self.phonetic_attributes = Setting::Reset;
+    }
+
+    pub fn set_code_attributes(&mut self, value: Vec<String>) {
+        self.code_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_code_attributes(&mut self) {
+        self.code_attributes = Setting::Reset;
+    }
+
+    pub fn set_suggestion_attributes(&mut self, value: Vec<String>) {
+        self.suggestion_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_suggestion_attributes(&mut self) {
+        self.suggestion_attributes = Setting::Reset;
+    }
+
+    pub fn set_suggestion_facet_attributes(&mut self, value: Vec<String>) {
+        self.suggestion_facet_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_suggestion_facet_attributes(&mut self) {
+        self.suggestion_facet_attributes = Setting::Reset;
+    }
+
+    pub fn set_attribute_weights(&mut self, value: BTreeMap<String, u16>) {
+        self.attribute_weights = Setting::Set(value);
+    }
+
+    pub fn reset_attribute_weights(&mut self) {
+        self.attribute_weights = Setting::Reset;
+    }
+
     fn reindex<FP, FA>(
         &mut self,
         progress_callback: &FP,
@@ -567,79 +708,127 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     }
 
     fn update_synonyms(&mut self) -> Result<bool> {
-        match self.synonyms {
-            Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
-                        .filter_map(|token| {
-                            if token.is_word() && !token.lemma().is_empty() {
-                                Some(token.lemma().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                }
-
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
+        let user_synonyms = match self.synonyms {
+            Setting::Set(ref user_synonyms) => Some(user_synonyms.clone()),
+            Setting::Reset => Some(BTreeMap::new()),
+            Setting::NotSet => None,
+        };
+        let rules = match self.synonym_rules {
+            Setting::Set(ref update) => Some(update.apply(self.index.synonym_rules(self.wtxn)?)),
+            Setting::Reset => Some(BTreeMap::new()),
+            Setting::NotSet => None,
+        };
+        if user_synonyms.is_none() && rules.is_none() {
+            return Ok(false);
+        }
 
-                let separators = self.index.allowed_separators(self.wtxn)?;
-                let separators: Option<Vec<_>> =
-                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref separators) = separators {
-                    builder.separators(separators);
-                }
+        // the user-defined synonyms and the synonym rules are merged, so the one
+        // which is not updated must be read from the index.
+        let user_synonyms = match user_synonyms {
+            Some(user_synonyms) => user_synonyms,
+            None => self.index.user_defined_synonyms(self.wtxn)?,
+        };
+        let rules = match rules {
+            Some(rules) => rules,
+            None => self.index.synonym_rules(self.wtxn)?,
+        };
+        for (id, rule) in &rules {
+            rule.validate()
+                .map_err(|reason| UserError::InvalidSynonymRule { id: id.clone(), reason })?;
+        }
 
-                let dictionary = self.index.dictionary(self.wtxn)?;
-                let dictionary: Option<Vec<_>> =
-                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref dictionary) = dictionary {
-                    builder.words_dict(dictionary);
-                }
+        fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
+            tokenizer
+                .tokenize(text)
+                .filter_map(|token| {
+                    if token.is_word() && !token.lemma().is_empty() {
+                        Some(token.lemma().to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
 
-                let tokenizer = builder.build();
+        let mut builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.wtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            builder.stop_words(stop_words);
+        }
 
-                let mut new_synonyms = HashMap::new();
-                for (word, synonyms) in user_synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms: Vec<_> = synonyms
-                        .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
-                        .filter(|synonym| !synonym.is_empty())
-                        .collect();
-
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
-                        let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                        entry.extend(normalized_synonyms.into_iter());
-                    }
-                }
+        let separators = self.index.allowed_separators(self.wtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            builder.separators(separators);
+        }
 
-                // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+        let dictionary = self.index.dictionary(self.wtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            builder.words_dict(dictionary);
+        }
 
-                let old_synonyms = self.index.synonyms(self.wtxn)?;
+        let tokenizer = builder.build();
 
-                if new_synonyms != old_synonyms {
-                    self.index.put_synonyms(self.wtxn, &new_synonyms, user_synonyms)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+        let user_rewrites = user_synonyms.iter().map(|(word, synonyms)| {
+            (word.as_str(), synonyms.iter().map(String::as_str).collect::<Vec<_>>())
+        });
+        let rules_rewrites = rules.values().flat_map(SynonymRule::rewrites);
+
+        let mut new_synonyms = HashMap::new();
+        for (word, synonyms) in user_rewrites.chain(rules_rewrites) {
+            // Normalize both the word and associated synonyms.
+            let normalized_word = normalize(&tokenizer, word);
+            let normalized_synonyms: Vec<_> = synonyms
+                .iter()
+                .map(|synonym| normalize(&tokenizer, synonym))
+                .filter(|synonym| !synonym.is_empty())
+                .collect();
+
+            // Store the normalized synonyms under the normalized word,
+            // merging the possible duplicate words.
+            if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
+                let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+                entry.extend(normalized_synonyms.into_iter());
             }
-            Setting::Reset => Ok(self.index.delete_synonyms(self.wtxn)?),
-            Setting::NotSet => Ok(false),
         }
+
+        // Make sure that we don't have duplicate synonyms.
+        new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+            synonyms.sort_unstable();
+            synonyms.dedup();
+        });
+
+        if user_synonyms.is_empty() {
+            self.index.delete_user_defined_synonyms(self.wtxn)?;
+        } else {
+            self.index.put_user_defined_synonyms(self.wtxn, &user_synonyms)?;
+        }
+        let old_rules = self.index.synonym_rules(self.wtxn)?;
+        for id in old_rules.keys().filter(|id| !rules.contains_key(*id)) {
+            self.index.delete_synonym_rule(self.wtxn, id)?;
+        }
+        for (id, rule) in rules.iter().filter(|(id, rule)| old_rules.get(*id) != Some(rule)) {
+            self.index.put_synonym_rule(self.wtxn, id, rule)?;
+        }
+
+        let mut old_synonyms = self.index.synonyms(self.wtxn)?;
+        old_synonyms.values_mut().for_each(|synonyms| synonyms.sort_unstable());
+        // the synonyms still stored under the legacy key are migrated to the FST
+        // even if they did not change.
+        let legacy_synonyms_deleted = self.index.delete_legacy_synonyms(self.wtxn)?;
+        if new_synonyms == old_synonyms && !legacy_synonyms_deleted {
+            return Ok(false);
+        }
+
+        if new_synonyms.is_empty() {
+            self.index.delete_synonyms_fst(self.wtxn)?;
+        } else {
+            self.index.put_synonyms_fst(self.wtxn, &synonyms_fst(&new_synonyms)?)?;
+        }
+        Ok(new_synonyms != old_synonyms)
     }
 
     fn update_exact_attributes(&mut self) -> Result<bool> {
@@ -661,6 +850,43 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    /// The query rules are only used at search time, updating them doesn't require a reindexation.
+    fn update_query_rules(&mut self) -> Result<()> {
+        let rules = match self.query_rules {
+            Setting::Set(ref update) => update.apply(self.index.query_rules(self.wtxn)?),
+            Setting::Reset => BTreeMap::new(),
+            Setting::NotSet => return Ok(()),
+        };
+
+        for (id, rule) in &rules {
+            rule.validate()
+                .map_err(|reason| UserError::InvalidQueryRule { id: id.clone(), reason })?;
+        }
+
+        if rules.is_empty() {
+            self.index.delete_query_rules(self.wtxn)?;
+        } else {
+            self.index.put_query_rules(self.wtxn, &rules)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_code_attributes(&mut self) -> Result<bool> {
+        match self.code_attributes {
+            Setting::Set(ref attributes) => {
+                if &self.index.code_attributes(self.wtxn)? != attributes {
+                    self.index.put_code_attributes(self.wtxn, attributes)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_code_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -753,6 +979,20 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => {
+                self.index.put_disable_typos_on_numbers(self.wtxn, flag)?;
+                Ok(())
+            }
+            Setting::Reset => {
+                self.index.delete_disable_typos_on_numbers(self.wtxn)?;
+                Ok(())
+            }
+            Setting::NotSet => Ok(()),
+        }
+    }
+
     fn update_min_typo_word_len(&mut self) -> Result<()> {
         let one = self.min_word_len_one_typo.or_reset(DEFAULT_MIN_WORD_LEN_ONE_TYPO);
         let two = self.min_word_len_two_typos.or_reset(DEFAULT_MIN_WORD_LEN_TWO_TYPOS);
@@ -847,6 +1087,25 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_hierarchical_facets(&mut self) -> Result<bool> {
+        match self.hierarchical_facets {
+            Setting::Set(ref facets) => {
+                if let Some((name, _)) = facets.iter().find(|(_, sep)| sep.trim().is_empty()) {
+                    return Err(UserError::InvalidHierarchicalFacetSeparator(name.clone()).into());
+                }
+
+                if &self.index.hierarchical_facets(self.wtxn)? != facets {
+                    self.index.put_hierarchical_facets(self.wtxn, facets)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_hierarchical_facets(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -861,6 +1120,193 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_vector_spaces(&mut self) -> Result<bool> {
+        match self.vector_spaces {
+            Setting::Set(ref spaces) => {
+                for (name, space) in spaces {
+                    let valid_name = !name.is_empty()
+                        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                    if !valid_name {
+                        return Err(UserError::InvalidVectorSpaceName(name.clone()).into());
+                    }
+                    if space.dimensions == 0 {
+                        return Err(
+                            UserError::InvalidVectorSpaceDimensionsSetting(name.clone()).into()
+                        );
+                    }
+                }
+
+                // Do the new vector spaces differ from the previous ones?
+                if &self.index.vector_spaces(self.wtxn)? != spaces {
+                    self.index.put_vector_spaces(self.wtxn, spaces)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_vector_spaces(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                for locale in rules.iter().flat_map(|rule| &rule.locales) {
+                    if parse_locale(locale).is_none() {
+                        return Err(
+                            UserError::InvalidLocalizedAttributesLocale(locale.clone()).into()
+                        );
+                    }
+                }
+
+                // Do the new rules differ from the previous ones?
+                if &self.index.localized_attributes_rules(self.wtxn)? != rules {
+                    self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    /// The weights are only used at search time, updating them doesn't require a reindexation.
+    fn update_attribute_weights(&mut self) -> Result<()> {
+        // the weights must only be given to searchable attributes, we check them against
+        // the searchable attributes once this settings update is applied.
+        if !self.attribute_weights.is_not_set() || !self.searchable_fields.is_not_set() {
+            let weights = match self.attribute_weights {
+                Setting::Set(ref weights) => weights.clone(),
+                Setting::Reset => BTreeMap::new(),
+                Setting::NotSet => self.index.attribute_weights(self.wtxn)?,
+            };
+            let searchable_fields: Option<Vec<String>> = match self.searchable_fields {
+                Setting::Set(ref fields) => Some(fields.clone()),
+                Setting::Reset => None,
+                Setting::NotSet => self
+                    .index
+                    .searchable_fields(self.wtxn)?
+                    .map(|fields| fields.into_iter().map(String::from).collect()),
+            };
+
+            if let Some(searchable_fields) = searchable_fields {
+                let invalid = weights.keys().find(|attribute| {
+                    !searchable_fields.iter().any(|field| {
+                        is_faceted_by(attribute, field) || is_faceted_by(field, attribute)
+                    })
+                });
+                if let Some(attribute) = invalid {
+                    return Err(UserError::InvalidAttributeWeight {
+                        attribute: attribute.clone(),
+                        searchable_attributes: searchable_fields,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        match self.attribute_weights {
+            Setting::Set(ref weights) => {
+                self.index.put_attribute_weights(self.wtxn, weights)?;
+            }
+            Setting::Reset => {
+                self.index.delete_attribute_weights(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    /// The stems are only computed from the words dictionary, updating the locales
+    /// doesn't require a reindexation.
+    fn update_stemming_locales(&mut self) -> Result<()> {
+        let updated = match self.stemming_locales {
+            Setting::Set(ref locales) => {
+                for locale in locales {
+                    if parse_stemming_locale(locale).is_none() {
+                        return Err(UserError::InvalidStemmingLocale(locale.clone()).into());
+                    }
+                }
+
+                if &self.index.stemming_locales(self.wtxn)? != locales {
+                    self.index.put_stemming_locales(self.wtxn, locales)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_stemming_locales(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        if updated {
+            StemsFst::new(self.wtxn, self.index).execute()?;
+        }
+
+        Ok(())
+    }
+
+    /// Like the stems, the phonetic codes are computed from the words dictionary.
+    fn update_phonetic_attributes(&mut self) -> Result<()> {
+        let updated = match self.phonetic_attributes {
+            Setting::Set(ref attributes) => {
+                if &self.index.phonetic_attributes(self.wtxn)? != attributes {
+                    self.index.put_phonetic_attributes(self.wtxn, attributes)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_phonetic_attributes(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        if updated {
+            PhoneticFst::new(self.wtxn, self.index).execute()?;
+        }
+
+        Ok(())
+    }
+
+    /// Like the stems, the suggestions are computed without reindexing the documents.
+    fn update_suggestions(&mut self) -> Result<()> {
+        let attributes_updated = match self.suggestion_attributes {
+            Setting::Set(ref attributes) => {
+                if &self.index.suggestion_attributes(self.wtxn)? != attributes {
+                    self.index.put_suggestion_attributes(self.wtxn, attributes)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_suggestion_attributes(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        let facet_attributes_updated = match self.suggestion_facet_attributes {
+            Setting::Set(ref attributes) => {
+                if &self.index.suggestion_facet_attributes(self.wtxn)? != attributes {
+                    self.index.put_suggestion_facet_attributes(self.wtxn, attributes)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_suggestion_facet_attributes(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        if attributes_updated || facet_attributes_updated {
+            SuggestionsFst::new(self.wtxn, self.index).execute()?;
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -879,10 +1325,16 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
+        self.update_disable_typos_on_numbers()?;
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
+        self.update_attribute_weights()?;
+        self.update_stemming_locales()?;
+        self.update_phonetic_attributes()?;
+        self.update_query_rules()?;
+        self.update_suggestions()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
@@ -897,6 +1349,10 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
+        let code_attributes_updated = self.update_code_attributes()?;
+        let vector_spaces_updated = self.update_vector_spaces()?;
+        let localized_attributes_rules_updated = self.update_localized_attributes_rules()?;
+        let hierarchical_facets_updated = self.update_hierarchical_facets()?;
 
         if stop_words_updated
             || non_separator_tokens_updated
@@ -906,6 +1362,10 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             || synonyms_updated
             || searchable_updated
             || exact_attributes_updated
+            || code_attributes_updated
+            || vector_spaces_updated
+            || localized_attributes_rules_updated
+            || hierarchical_facets_updated
         {
             self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
         }
@@ -914,14 +1374,31 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     }
 }
 
+/// Merges the patch after the update of the rules, the rules are replaced
+/// by the ones with a value of the patch if they were reset.
+fn patch_rules<R: Clone>(
+    setting: &mut Setting<RulesUpdate<R>>,
+    patch: BTreeMap<String, Option<R>>,
+) {
+    match setting {
+        Setting::Set(update) => update.merge_patch(patch),
+        Setting::Reset => {
+            let rules = patch.into_iter().filter_map(|(id, rule)| Some((id, rule?))).collect();
+            *setting = Setting::Set(RulesUpdate::Replace(rules));
+        }
+        Setting::NotSet => *setting = Setting::Set(RulesUpdate::Patch(patch)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use big_s::S;
-    use heed::types::ByteSlice;
+    use heed::types::{ByteSlice, SerdeBincode, Str};
     use maplit::{btreemap, btreeset, hashset};
 
     use super::*;
     use crate::error::Error;
+    use crate::index::main_key;
     use crate::index::tests::TempIndex;
     use crate::update::{ClearDocuments, DeleteDocuments};
     use crate::{Criterion, Filter, SearchResult};
@@ -1464,6 +1941,220 @@ mod tests {
         assert_eq!(result.documents_ids.len(), 2);
     }
 
+    #[test]
+    fn set_patch_and_reset_synonym_rules() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .add_documents(documents!([
+                { "name": "logan" },
+                { "name": "wolverine" },
+                { "name": "mobile" },
+                { "name": "phone" },
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_synonym_rules(btreemap! {
+                    S("wolverine") => SynonymRule::one_way("wolverine", vec![S("logan")]),
+                    S("phones") => SynonymRule::equivalent(vec![S("phone"), S("mobile")]),
+                });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.synonym_rules(&rtxn).unwrap().len(), 2);
+
+        // the one-way rule only rewrites its input
+        let result = index.search(&rtxn).query("wolverine").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        let result = index.search(&rtxn).query("logan").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 1);
+
+        // the equivalent rule rewrites all its synonyms
+        let result = index.search(&rtxn).query("phone").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        let result = index.search(&rtxn).query("mobile").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        drop(rtxn);
+
+        // patching the rules only modifies the given rules
+        index
+            .update_settings(|settings| {
+                settings.patch_synonym_rules(btreemap! { S("phones") => None });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let rules = index.synonym_rules(&rtxn).unwrap();
+        assert_eq!(rules.keys().collect::<Vec<_>>(), vec!["wolverine"]);
+        let result = index.search(&rtxn).query("phone").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 1);
+        let result = index.search(&rtxn).query("wolverine").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        drop(rtxn);
+
+        // an invalid rule is rejected
+        let error = index
+            .update_settings(|settings| {
+                settings.patch_synonym_rules(btreemap! {
+                    S("invalid") => Some(SynonymRule::equivalent(vec![S("phone")])),
+                });
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidSynonymRule { ref id, .. }) if id == "invalid"
+        ));
+
+        index
+            .update_settings(|settings| {
+                settings.reset_synonym_rules();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.synonym_rules(&rtxn).unwrap().is_empty());
+        assert!(index.synonyms(&rtxn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn legacy_synonyms_are_migrated() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .add_documents(documents!([
+                { "name": "logan" },
+                { "name": "wolverine" },
+                { "name": "phone" },
+            ]))
+            .unwrap();
+
+        // An index created before the synonyms FST only stores the normalized synonyms.
+        let mut wtxn = index.write_txn().unwrap();
+        let user_synonyms = btreemap! { S("wolverine") => vec![S("logan")] };
+        index.put_user_defined_synonyms(&mut wtxn, &user_synonyms).unwrap();
+        let synonyms: HashMap<_, _> =
+            [(vec![S("wolverine")], vec![vec![S("logan")]])].into_iter().collect();
+        index
+            .main
+            .put::<_, Str, SerdeBincode<HashMap<Vec<String>, Vec<Vec<String>>>>>(
+                &mut wtxn,
+                main_key::SYNONYMS_KEY,
+                &synonyms,
+            )
+            .unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.synonyms(&rtxn).unwrap(), synonyms);
+        let result = index.search(&rtxn).query("wolverine").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_synonym_rules(btreemap! {
+                    S("phones") => SynonymRule::equivalent(vec![S("phone"), S("mobile")]),
+                });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index
+            .main
+            .get::<_, Str, ByteSlice>(&rtxn, main_key::SYNONYMS_KEY)
+            .unwrap()
+            .is_none());
+        let result = index.search(&rtxn).query("wolverine").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 2);
+        let result = index.search(&rtxn).query("mobile").execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 1);
+    }
+
+    #[test]
+    fn set_and_reset_hierarchical_facets() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("categories") });
+                settings.set_hierarchical_facets(btreemap! { S("categories") => S("/") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let facets = index.hierarchical_facets(&rtxn).unwrap();
+        assert_eq!(facets, btreemap! { S("categories") => S("/") });
+        drop(rtxn);
+
+        // a blank separator is rejected
+        let error = index
+            .update_settings(|settings| {
+                settings.set_hierarchical_facets(btreemap! { S("categories") => S(" ") });
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidHierarchicalFacetSeparator(ref name)) if name == "categories"
+        ));
+
+        index
+            .update_settings(|settings| {
+                settings.reset_hierarchical_facets();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.hierarchical_facets(&rtxn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn attribute_weights_must_be_searchable() {
+        let index = TempIndex::new();
+
+        // all the attributes are searchable by default
+        index
+            .update_settings(|settings| {
+                settings.set_attribute_weights(btreemap! { S("title") => 2, S("plot") => 1 });
+            })
+            .unwrap();
+
+        // the weights are checked against the new searchable attributes
+        let error = index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title")]);
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidAttributeWeight { ref attribute, .. }) if attribute == "plot"
+        ));
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title"), S("plot")]);
+            })
+            .unwrap();
+
+        let error = index
+            .update_settings(|settings| {
+                settings.set_attribute_weights(btreemap! { S("title") => 2, S("author") => 1 });
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidAttributeWeight { ref attribute, .. }) if attribute == "author"
+        ));
+
+        let rtxn = index.read_txn().unwrap();
+        let weights = index.attribute_weights(&rtxn).unwrap();
+        assert_eq!(weights, btreemap! { S("title") => 2, S("plot") => 1 });
+    }
+
     #[test]
     fn setting_searchable_recomputes_other_settings() {
         let index = TempIndex::new();
@@ -1712,6 +2403,7 @@ mod tests {
                     index: _,
                     indexer_config: _,
                     searchable_fields,
+                    attribute_weights,
                     displayed_fields,
                     filterable_fields,
                     sortable_fields,
@@ -1722,17 +2414,29 @@ mod tests {
                     dictionary,
                     distinct_field,
                     synonyms,
+                    synonym_rules,
+                    query_rules,
                     primary_key,
                     authorize_typos,
                     min_word_len_two_typos,
                     min_word_len_one_typo,
                     exact_words,
+                    disable_typos_on_numbers,
                     exact_attributes,
                     max_values_per_facet,
                     sort_facet_values_by,
+                    hierarchical_facets,
                     pagination_max_total_hits,
+                    vector_spaces,
+                    localized_attributes_rules,
+                    stemming_locales,
+                    phonetic_attributes,
+                    code_attributes,
+                    suggestion_attributes,
+                    suggestion_facet_attributes,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
+                assert!(matches!(attribute_weights, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
@@ -1743,15 +2447,26 @@ mod tests {
                 assert!(matches!(dictionary, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
+                assert!(matches!(synonym_rules, Setting::NotSet));
+                assert!(matches!(query_rules, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_two_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
+                assert!(matches!(hierarchical_facets, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
+                assert!(matches!(vector_spaces, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(stemming_locales, Setting::NotSet));
+                assert!(matches!(phonetic_attributes, Setting::NotSet));
+                assert!(matches!(code_attributes, Setting::NotSet));
+                assert!(matches!(suggestion_attributes, Setting::NotSet));
+                assert!(matches!(suggestion_facet_attributes, Setting::NotSet));
             })
             .unwrap();
     }
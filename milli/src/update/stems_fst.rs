@@ -0,0 +1,73 @@
+use std::collections::BTreeSet;
+use std::str;
+
+use fst::{Set, Streamer};
+
+use crate::stemming::{stem_key, Stemmers};
+use crate::{Index, Result};
+
+/// Computes the stems of the words of the index with the stemming locales of the index,
+/// the stems FST is used at search time to retrieve the words sharing the stem of a query word.
+pub struct StemsFst<'t, 'u, 'i> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+}
+
+impl<'t, 'u, 'i> StemsFst<'t, 'u, 'i> {
+    pub fn new(wtxn: &'t mut heed::RwTxn<'i, 'u>, index: &'i Index) -> StemsFst<'t, 'u, 'i> {
+        StemsFst { wtxn, index }
+    }
+
+    /// Computes the stems of all the words of the index.
+    #[logging_timer::time("StemsFst::{}")]
+    pub fn execute(self) -> Result<()> {
+        puffin::profile_function!();
+
+        let stemmers = Stemmers::new(&self.index.stemming_locales(self.wtxn)?);
+        if stemmers.is_empty() {
+            self.index.delete_stems_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let words_fst = self.index.words_fst(self.wtxn)?;
+        let stems_fst = stems_fst_of_words(&stemmers, &words_fst)?;
+        self.index.put_stems_fst(self.wtxn, &stems_fst)?;
+
+        Ok(())
+    }
+
+    /// Only computes the stems of the given words, the new words of an indexing batch,
+    /// and adds them to the stems of the index.
+    #[logging_timer::time("StemsFst::{}")]
+    pub fn execute_for_words<A: AsRef<[u8]>>(self, words: &Set<A>) -> Result<()> {
+        puffin::profile_function!();
+
+        let stemmers = Stemmers::new(&self.index.stemming_locales(self.wtxn)?);
+        if stemmers.is_empty() {
+            self.index.delete_stems_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let new_stems_fst = stems_fst_of_words(&stemmers, words)?;
+        let stems_fst = self.index.stems_fst(self.wtxn)?;
+        let mut builder = fst::SetBuilder::memory();
+        builder.extend_stream(stems_fst.op().add(&new_stems_fst).union())?;
+        let stems_fst = builder.into_set();
+        self.index.put_stems_fst(self.wtxn, &stems_fst)?;
+
+        Ok(())
+    }
+}
+
+fn stems_fst_of_words<A: AsRef<[u8]>>(stemmers: &Stemmers, words: &Set<A>) -> Result<Set<Vec<u8>>> {
+    let mut keys = BTreeSet::new();
+    let mut stream = words.stream();
+    while let Some(bytes) = stream.next() {
+        let word = str::from_utf8(bytes)?;
+        for stem in stemmers.stems(word) {
+            keys.insert(stem_key(&stem, word));
+        }
+    }
+
+    Ok(Set::from_iter(keys)?)
+}
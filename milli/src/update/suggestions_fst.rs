@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use fst::Streamer;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use crate::error::InternalError;
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
+use crate::index::db_name;
+use crate::search::suggestions::normalize_phrase;
+use crate::{FieldId, Index, Result, BEU32};
+
+/// The maximum number of values of a suggestion facet attribute that are suggested,
+/// the most frequent ones are kept.
+pub const MAX_FACET_SUGGESTIONS: usize = 100;
+
+/// Computes the phrases suggested as completions of the queries along with their frequency,
+/// from the values of the suggestion attributes and the most frequent values of the
+/// suggestion facet attributes.
+///
+/// The number of documents in which the phrases of the suggestion attributes appear
+/// is stored apart, it is updated with the documents of each batch.
+pub struct SuggestionsFst<'t, 'u, 'i> {
+    wtxn: &'t mut heed::RwTxn<'i, 'u>,
+    index: &'i Index,
+}
+
+impl<'t, 'u, 'i> SuggestionsFst<'t, 'u, 'i> {
+    pub fn new(wtxn: &'t mut heed::RwTxn<'i, 'u>, index: &'i Index) -> SuggestionsFst<'t, 'u, 'i> {
+        SuggestionsFst { wtxn, index }
+    }
+
+    /// Computes the suggestions from all the documents of the index.
+    #[logging_timer::time("SuggestionsFst::{}")]
+    pub fn execute(self) -> Result<()> {
+        puffin::profile_function!();
+
+        let attributes = self.index.suggestion_attributes_ids(self.wtxn)?;
+        let facet_attributes = self.index.suggestion_facet_attributes_ids(self.wtxn)?;
+        if attributes.is_empty() && facet_attributes.is_empty() {
+            self.index.delete_suggestion_phrases_fst(self.wtxn)?;
+            self.index.delete_suggestions_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let documents_ids = self.index.documents_ids(self.wtxn)?;
+        let phrases = documents_phrases(self.index, self.wtxn, &attributes, &documents_ids)?;
+        let phrases_fst = fst::Map::from_iter(phrases)?;
+        self.write_suggestions(&facet_attributes, phrases_fst)
+    }
+
+    /// Only counts the phrases of the given documents, the new documents of an indexing batch,
+    /// and adds them to the phrases of the index.
+    #[logging_timer::time("SuggestionsFst::{}")]
+    pub fn execute_for_new_documents(self, docids: &RoaringBitmap) -> Result<()> {
+        puffin::profile_function!();
+        self.execute_for_documents(docids, true)
+    }
+
+    /// Only counts the phrases of the given documents, which are being deleted,
+    /// and removes them from the phrases of the index.
+    #[logging_timer::time("SuggestionsFst::{}")]
+    pub fn execute_for_deleted_documents(self, docids: &RoaringBitmap) -> Result<()> {
+        puffin::profile_function!();
+        self.execute_for_documents(docids, false)
+    }
+
+    fn execute_for_documents(self, docids: &RoaringBitmap, added: bool) -> Result<()> {
+        let attributes = self.index.suggestion_attributes_ids(self.wtxn)?;
+        let facet_attributes = self.index.suggestion_facet_attributes_ids(self.wtxn)?;
+        if attributes.is_empty() && facet_attributes.is_empty() {
+            self.index.delete_suggestion_phrases_fst(self.wtxn)?;
+            self.index.delete_suggestions_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let batch_phrases = documents_phrases(self.index, self.wtxn, &attributes, docids)?;
+        let batch_phrases_fst = fst::Map::from_iter(batch_phrases)?;
+
+        let phrases_fst = {
+            let phrases_fst = self.index.suggestion_phrases_fst(self.wtxn)?;
+            let mut builder = fst::MapBuilder::memory();
+            let mut stream = phrases_fst.op().add(&batch_phrases_fst).union();
+            while let Some((phrase, values)) = stream.next() {
+                let count_of =
+                    |index| values.iter().find(|v| v.index == index).map_or(0, |v| v.value);
+                let (count, batch_count) = (count_of(0), count_of(1));
+                let count = match added {
+                    true => count + batch_count,
+                    false => count.saturating_sub(batch_count),
+                };
+                if count != 0 {
+                    builder.insert(phrase, count)?;
+                }
+            }
+            builder.into_map()
+        };
+
+        self.write_suggestions(&facet_attributes, phrases_fst)
+    }
+
+    /// Writes the phrases of the suggestion attributes and the suggestions,
+    /// the phrases along with the most frequent values of the suggestion facet attributes.
+    fn write_suggestions(
+        self,
+        facet_attributes: &HashSet<FieldId>,
+        phrases_fst: fst::Map<Vec<u8>>,
+    ) -> Result<()> {
+        let documents_ids = self.index.documents_ids(self.wtxn)?;
+        let mut facet_frequencies: BTreeMap<String, u64> = BTreeMap::new();
+        for &fid in facet_attributes {
+            let mut values = Vec::new();
+            let prefix = FacetGroupKey { field_id: fid, level: 0, left_bound: "" };
+            for result in self.index.facet_id_string_docids.prefix_iter(self.wtxn, &prefix)? {
+                let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+                let count = bitmap.intersection_len(&documents_ids);
+                if count != 0 {
+                    values.push((count, left_bound.to_string()));
+                }
+            }
+            values.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+            for (count, value) in values.into_iter().take(MAX_FACET_SUGGESTIONS) {
+                let phrase = normalize_phrase(&value);
+                if !phrase.is_empty() {
+                    let frequency = facet_frequencies.entry(phrase).or_default();
+                    *frequency = (*frequency).max(count);
+                }
+            }
+        }
+        let facet_frequencies = fst::Map::from_iter(facet_frequencies)?;
+
+        // the documents having a facet value may already be counted by the attributes.
+        let mut builder = fst::MapBuilder::memory();
+        let mut stream = phrases_fst.op().add(&facet_frequencies).union();
+        while let Some((phrase, values)) = stream.next() {
+            let frequency = values.iter().map(|v| v.value).max().unwrap_or_default();
+            builder.insert(phrase, frequency)?;
+        }
+        let suggestions_fst = builder.into_map();
+
+        self.index.put_suggestion_phrases_fst(self.wtxn, &phrases_fst)?;
+        self.index.put_suggestions_fst(self.wtxn, &suggestions_fst)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the phrases of the suggestion attributes of the documents
+/// along with the number of documents in which they appear.
+fn documents_phrases(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    attributes: &HashSet<FieldId>,
+    docids: &RoaringBitmap,
+) -> Result<BTreeMap<String, u64>> {
+    let mut frequencies: BTreeMap<String, u64> = BTreeMap::new();
+    if attributes.is_empty() {
+        return Ok(frequencies);
+    }
+
+    for docid in docids {
+        let obkv = index.documents.get(rtxn, &BEU32::new(docid))?.ok_or(
+            InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
+        )?;
+        // a phrase is only counted once per document.
+        let mut phrases = BTreeSet::new();
+        for &fid in attributes {
+            if let Some(value) = obkv.get(fid) {
+                let value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                collect_phrases(&value, &mut phrases);
+            }
+        }
+        for phrase in phrases {
+            *frequencies.entry(phrase).or_default() += 1;
+        }
+    }
+
+    Ok(frequencies)
+}
+
+/// Collects the normalized phrases of the strings of the value, including those of its arrays.
+fn collect_phrases(value: &Value, phrases: &mut BTreeSet<String>) {
+    match value {
+        Value::String(string) => {
+            let phrase = normalize_phrase(string);
+            if !phrase.is_empty() {
+                phrases.insert(phrase);
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_phrases(value, phrases)),
+        _ => (),
+    }
+}